@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::path::Path;
+use std::vec::IntoIter;
 
 use serde::{Deserialize, Serialize};
 
@@ -390,6 +391,20 @@ pub fn get_sound(id: &str) -> &Sound {
     try_get_sound(id).unwrap()
 }
 
+/// This returns the ids of all loaded sounds of the specified kind, in alphabetical order
+pub fn iter_sound_ids_of_kind(kind: AudioKind) -> IntoIter<String> {
+    let sounds = unsafe { AUDIO.get_or_insert_with(HashMap::new) };
+
+    let mut res = sounds
+        .iter()
+        .filter(|(_, sound)| *sound.kind() == kind)
+        .map(|(id, _)| id.clone())
+        .collect::<Vec<_>>();
+
+    res.sort_unstable();
+    res.into_iter()
+}
+
 #[derive(Serialize, Deserialize)]
 struct SoundMetadata {
     id: String,
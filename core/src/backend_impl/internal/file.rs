@@ -49,6 +49,17 @@ pub async fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, Error> {
     Ok(res)
 }
 
+/// This runs `f` and resolves to its result. This backend does not have a frame loop to yield to
+/// yet, so `f` is run on the calling thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn run_in_background<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    f()
+}
+
 #[cfg(target_arch = "wasm32")]
 async fn write_to_file_wasm<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
     unimplemented!("wasm file handling is unimplemented!")
@@ -77,3 +88,14 @@ pub async fn write_to_file<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), E
 
     Ok(())
 }
+
+pub async fn write_to_file_atomic<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
+    #[cfg(target_arch = "wasm32")]
+    write_to_file_wasm(path, data).await?;
+    #[cfg(target_os = "android")]
+    write_to_file_android(path, data).await?;
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    crate::file::write_to_file_atomic_sync(path, data)?;
+
+    Ok(())
+}
@@ -16,7 +16,7 @@ use crate::camera::{main_camera, Camera};
 use crate::color::{colors, Color};
 use crate::config::Config;
 use crate::context::destroy_context;
-use crate::event::{Event, EventHandler};
+use crate::event::{handle_quit_request, Event, EventHandler, Transition};
 use crate::gl::init_gl_context;
 use crate::gui::SidePanel;
 use crate::gui::{build_gui, gui_context};
@@ -29,7 +29,9 @@ use crate::math::Vec2;
 use crate::physics::{fixed_delta_time, physics_world};
 use crate::prelude::renderer::renderer;
 use crate::prelude::{input_event_handler, DefaultEventHandler};
-use crate::render::{apply_video_config, begin_frame, clear_screen, end_frame, set_clear_color};
+use crate::render::{
+    apply_video_config, begin_frame, clear_screen, draw_rectangle, end_frame, set_clear_color,
+};
 use crate::result::Result;
 use crate::window::{
     apply_window_config, context_wrapper, create_window, window, window_size, WindowMode,
@@ -60,9 +62,18 @@ pub fn draw_delta_time() -> Duration {
     unsafe { DRAW_DELTA_TIME }
 }
 
+// This holds a transition that is in progress. `next_state` is taken when the transition reaches
+// its midpoint, which is when the state is actually changed.
+struct ActiveTransition {
+    transition: Transition,
+    elapsed: f32,
+    next_state: Option<Rc<RefCell<dyn GameState>>>,
+}
+
 pub struct Game<E: 'static + Debug> {
     config: Config,
     state: Rc<RefCell<dyn GameState>>,
+    transition: Option<ActiveTransition>,
     event_loop: Option<EventLoop<Event<E>>>,
     event_handler: Option<Box<dyn EventHandler<E>>>,
     clear_color: Option<Color>,
@@ -78,6 +89,7 @@ impl<E: 'static + Debug> Game<E> {
         Game {
             config: Config::default(),
             state: Rc::new(RefCell::new(state)),
+            transition: None,
             event_loop: None,
             event_handler: None,
             clear_color: None,
@@ -128,6 +140,65 @@ impl<E: 'static + Debug> Game<E> {
         Ok(())
     }
 
+    /// This will change state using the specified transition. Instant transitions will change the
+    /// state immediately, while any other transition will change it at its midpoint.
+    /// A transition started while another is in progress replaces it.
+    pub fn change_state_with_transition(
+        &mut self,
+        state: Rc<RefCell<dyn GameState>>,
+        transition: Transition,
+    ) -> Result<()> {
+        if transition == Transition::Instant || transition.duration() <= 0.0 {
+            self.transition = None;
+            return self.change_state(state);
+        }
+
+        self.transition = Some(ActiveTransition {
+            transition,
+            elapsed: 0.0,
+            next_state: Some(state),
+        });
+
+        Ok(())
+    }
+
+    fn update_transition(&mut self, delta_time: f32) -> Result<()> {
+        let mut next_state = None;
+        let mut is_done = false;
+
+        if let Some(active) = &mut self.transition {
+            active.elapsed += delta_time;
+
+            let duration = active.transition.duration();
+
+            if active.elapsed >= duration / 2.0 {
+                next_state = active.next_state.take();
+            }
+
+            is_done = active.elapsed >= duration;
+        }
+
+        if let Some(state) = next_state {
+            self.change_state(state)?;
+        }
+
+        if is_done {
+            self.transition = None;
+        }
+
+        Ok(())
+    }
+
+    fn draw_transition(&self) {
+        if let Some(active) = &self.transition {
+            if let Some(color) = active.transition.get_overlay_color(active.elapsed) {
+                let size = viewport_size();
+
+                draw_rectangle(0.0, 0.0, size.width, size.height, color);
+            }
+        }
+    }
+
     fn apply_current_config(&mut self) {
         self.fixed_draw_delta_time = self
             .config
@@ -194,7 +265,16 @@ impl<E: 'static + Debug> Game<E> {
                     glutin::event::Event::WindowEvent { event, .. } => {
                         if !gui_context().handle(event) {
                             match event {
-                                WindowEvent::CloseRequested | WindowEvent::Destroyed => {
+                                WindowEvent::CloseRequested => {
+                                    if handle_quit_request() {
+                                        *control_flow = ControlFlow::Exit;
+
+                                        game.state().end();
+
+                                        return;
+                                    }
+                                }
+                                WindowEvent::Destroyed => {
                                     *control_flow = ControlFlow::Exit;
                                 }
                                 WindowEvent::Resized(physical_size) => {
@@ -212,9 +292,18 @@ impl<E: 'static + Debug> Game<E> {
                     glutin::event::Event::UserEvent(event) => match event {
                         Event::Custom(event) => event_handler.handle_custom(event, control_flow),
                         Event::ConfigChanged(config) => game.apply_config(config.clone()),
-                        Event::StateTransition(state) => game
-                            .change_state(state.clone())
+                        Event::StateTransition(state, transition) => game
+                            .change_state_with_transition(state.clone(), *transition)
                             .unwrap_or_else(|err| panic!("Error when changing state: {}", err)),
+                        Event::QuitRequested => {
+                            if handle_quit_request() {
+                                *control_flow = ControlFlow::Exit;
+
+                                game.state().end();
+
+                                return;
+                            }
+                        }
                         Event::Quit => {
                             *control_flow = ControlFlow::Exit;
 
@@ -240,6 +329,9 @@ impl<E: 'static + Debug> Game<E> {
 
                 let delta_time_secs = delta_time.as_secs_f32();
 
+                game.update_transition(delta_time_secs)
+                    .unwrap_or_else(|err| panic!("Error in state transition: {}", err));
+
                 game.state()
                     .update(delta_time_secs)
                     .unwrap_or_else(|err| panic!("Error in game state update: {}", err));
@@ -286,6 +378,8 @@ impl<E: 'static + Debug> Game<E> {
                             .draw(draw_delta_time.as_secs_f32())
                             .unwrap_or_else(|err| panic!("Error in game state draw: {}", err));
 
+                        game.draw_transition();
+
                         build_gui(|ctx| {
                             SidePanel::left("my_side_panel").show(ctx, |ui| {
                                 ui.heading("Hello World!");
@@ -139,7 +139,7 @@ impl From<egui::Vec2> for Size<f32> {
 impl From<Rect> for egui::Rect {
     fn from(rect: Rect) -> Self {
         let min = rect.point();
-        let max = min + rect.size();
+        let max = min + rect.size().as_vec2();
         egui::Rect::from_min_max(min.to_egui_pos2(), max.to_egui_pos2())
     }
 }
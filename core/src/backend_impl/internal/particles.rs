@@ -11,7 +11,13 @@ impl EmittersCache {
 
     pub fn spawn(&mut self, position: Vec2) {}
 
-    pub fn draw(&mut self) {}
+    /// This draws the particles of the cache, advancing their simulation by the frame time,
+    /// scaled by `time_scale`
+    pub fn draw(&mut self, time_scale: f32) {}
+}
+
+pub fn draw_emitters_cache(cache: &mut EmittersCache, time_scale: f32) {
+    cache.draw(time_scale);
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
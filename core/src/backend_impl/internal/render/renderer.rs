@@ -264,9 +264,7 @@ impl Renderer {
             .source
             .unwrap_or_else(|| Rect::new(0.0, 0.0, texture_size.width, texture_size.height));
 
-        let size = params
-            .dest_size
-            .unwrap_or_else(|| source_rect.size().into());
+        let size = params.dest_size.unwrap_or_else(|| source_rect.size());
 
         let mut uv_rect = Rect::new(
             source_rect.x / texture_size.width,
@@ -5,7 +5,7 @@ use std::path::Path;
 
 use crate::color::{colors, Color};
 use crate::gl::gl_context;
-use crate::math::Size;
+use crate::math::{Size, Vec2};
 use crate::result::Result;
 use crate::text::TextParams;
 use crate::viewport::viewport_size;
@@ -58,16 +58,21 @@ pub fn draw_text(text: &str, x: f32, y: f32, params: TextParams) {
 
     let font_size = (params.font_size as f32 * params.font_scale).round();
 
-    brush().queue(Section {
-        screen_position: (x, y),
-        bounds: (bounds.width, bounds.height),
-        text: vec![Text::default()
-            .with_text(text)
-            .with_font_id(FontId(font.0))
-            .with_color(params.color.to_array())
-            .with_scale(font_size)],
-        ..Section::default()
-    })
+    let mut sections = params.get_effect_offsets();
+    sections.push((Vec2::ZERO, params.color));
+
+    for (offset, color) in sections {
+        brush().queue(Section {
+            screen_position: (x + offset.x, y + offset.y),
+            bounds: (bounds.width, bounds.height),
+            text: vec![Text::default()
+                .with_text(text)
+                .with_font_id(FontId(font.0))
+                .with_color(color.to_array())
+                .with_scale(font_size)],
+            ..Section::default()
+        })
+    }
 }
 
 pub fn draw_queued_text() -> Result<()> {
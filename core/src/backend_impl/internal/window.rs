@@ -4,6 +4,7 @@ use std::ops::{Deref, DerefMut};
 
 use glow::Context;
 use glutin::event_loop::EventLoop;
+use glutin::monitor::MonitorHandle;
 use glutin::window::{Fullscreen, Window, WindowBuilder};
 use glutin::window::{Window as GlutinWindow, WindowId};
 use glutin::ContextBuilder;
@@ -61,8 +62,14 @@ pub fn create_window<E: 'static + Debug>(
                 .with_inner_size(size)
                 .with_resizable(true)
         }
-        WindowMode::Borderless => {
-            let fullscreen = Fullscreen::Borderless(None);
+        WindowMode::Borderless { monitor_index } => {
+            let monitor = select_monitor(
+                event_loop.available_monitors(),
+                event_loop.primary_monitor(),
+                monitor_index,
+            );
+
+            let fullscreen = Fullscreen::Borderless(monitor);
 
             window_builder.with_fullscreen(Some(fullscreen))
         }
@@ -70,12 +77,19 @@ pub fn create_window<E: 'static + Debug>(
             resolution,
             bit_depth,
             refresh_rate,
+            monitor_index,
         } => {
             //let video_mode = video_mode.clone().unwrap().into();
 
             //let fullscreen = Fullscreen::Exclusive(video_mode);
 
-            let fullscreen = Fullscreen::Borderless(None);
+            let monitor = select_monitor(
+                event_loop.available_monitors(),
+                event_loop.primary_monitor(),
+                monitor_index,
+            );
+
+            let fullscreen = Fullscreen::Borderless(monitor);
 
             window_builder.with_fullscreen(Some(fullscreen))
         }
@@ -108,11 +122,17 @@ pub(crate) fn apply_window_config(config: &WindowConfig) {
             window.set_inner_size(size);
             window.set_resizable(true);
         }
-        WindowMode::Borderless => {
-            let fullscreen = Fullscreen::Borderless(None);
-
+        WindowMode::Borderless { monitor_index } => {
             let window = window();
 
+            let monitor = select_monitor(
+                window.available_monitors(),
+                window.primary_monitor(),
+                monitor_index,
+            );
+
+            let fullscreen = Fullscreen::Borderless(monitor);
+
             window.set_fullscreen(Some(fullscreen));
             window.set_resizable(false);
         }
@@ -120,19 +140,39 @@ pub(crate) fn apply_window_config(config: &WindowConfig) {
             resolution,
             bit_depth,
             refresh_rate,
+            monitor_index,
         } => {
             //let video_mode = video_mode.clone().unwrap().into();
 
             //let fullscreen = Fullscreen::Exclusive(video_mode);
 
-            let fullscreen = Fullscreen::Borderless(None);
-
             let window = window();
 
+            let monitor = select_monitor(
+                window.available_monitors(),
+                window.primary_monitor(),
+                monitor_index,
+            );
+
+            let fullscreen = Fullscreen::Borderless(monitor);
+
             window.set_fullscreen(Some(fullscreen));
             window.set_resizable(false);
         }
     }
 }
 
+// This returns the monitor at `monitor_index`, in `monitors`, or `primary` if the index is out of
+// range. If no index is specified, `None` is returned, which means that the current monitor will
+// be used.
+fn select_monitor<I: Iterator<Item = MonitorHandle>>(
+    mut monitors: I,
+    primary: Option<MonitorHandle>,
+    monitor_index: Option<usize>,
+) -> Option<MonitorHandle> {
+    let monitor_index = monitor_index?;
+
+    monitors.nth(monitor_index).or(primary)
+}
+
 pub struct WindowIcon {}
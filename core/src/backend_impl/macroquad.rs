@@ -51,4 +51,5 @@ pub use macroquad::ui;
 
 pub use macroquad::experimental::scene;
 
-pub use ff_particles as particles;
+#[path = "macroquad/particles.rs"]
+pub mod particles;
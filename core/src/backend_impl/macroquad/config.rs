@@ -34,7 +34,7 @@ impl Config {
             WindowMode::Windowed {
                 size: Size { width, height },
             } => (false, width, height),
-            WindowMode::Borderless => (
+            WindowMode::Borderless { .. } => (
                 true,
                 DEFAULT_BORDERLESS_RESOLUTION.width,
                 DEFAULT_BORDERLESS_RESOLUTION.height,
@@ -1,14 +1,24 @@
-use crate::event::Event;
+use macroquad::input::is_quit_requested;
 
-static mut EVENTS: Vec<Event<()>> = Vec::new();
+use crate::event::{DefaultCustomEvent, Event};
 
-pub fn dispatch_event(event: Event<()>) {
+static mut EVENTS: Vec<Event<DefaultCustomEvent>> = Vec::new();
+
+/// This adds an event to the event queue. Custom events, created with `Event::custom`, can be used
+/// to pass data of any type to whatever is consuming the queue, with `iter_events`.
+pub fn dispatch_event(event: Event<DefaultCustomEvent>) {
     unsafe {
         EVENTS.push(event);
     }
 }
 
+/// This returns an iterator over all the events in the event queue. If the window has been closed
+/// by the OS, since the last call, an `Event::QuitRequested` will be added to the queue first.
 pub fn iter_events() -> EventIterator {
+    if is_quit_requested() {
+        dispatch_event(Event::QuitRequested);
+    }
+
     EventIterator::new()
 }
 
@@ -23,7 +33,7 @@ impl EventIterator {
 }
 
 impl Iterator for EventIterator {
-    type Item = Event<()>;
+    type Item = Event<DefaultCustomEvent>;
 
     fn next(&mut self) -> Option<Self::Item> {
         unsafe { EVENTS.pop() }
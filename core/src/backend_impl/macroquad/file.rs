@@ -10,3 +10,66 @@ pub async fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, Error> {
         Ok(res) => Ok(res),
     }
 }
+
+/// This runs `f` on a separate thread and resolves to its result. The future yields once per
+/// frame until `f` returns, so awaiting it from a coroutine will not block the frame loop.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn run_in_background<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    use std::sync::mpsc::{channel, TryRecvError};
+
+    let (sender, receiver) = channel();
+
+    std::thread::spawn(move || {
+        let _ = sender.send(f());
+    });
+
+    loop {
+        match receiver.try_recv() {
+            Ok(res) => return res,
+            Err(TryRecvError::Empty) => macroquad::window::next_frame().await,
+            Err(TryRecvError::Disconnected) => panic!("Background thread exited without a result"),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_to_file_sync<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
+    match std::fs::write(&path, data) {
+        Err(err) => Err(Error::new(&path, err)),
+        Ok(res) => Ok(res),
+    }
+}
+
+/// This writes `data` to the file at `path` on a separate thread, creating it if it does not
+/// exist and replacing its contents if it does
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn write_to_file<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
+    let path = path.as_ref().to_path_buf();
+    let data = data.to_vec();
+
+    run_in_background(move || write_to_file_sync(path, &data)).await
+}
+
+/// This writes `data` to the file at `path` on a separate thread, through a temporary file that
+/// is renamed over `path` once it has been written. See `write_to_file_atomic_sync`.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn write_to_file_atomic<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
+    let path = path.as_ref().to_path_buf();
+    let data = data.to_vec();
+
+    run_in_background(move || crate::file::write_to_file_atomic_sync(path, &data)).await
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn write_to_file<P: AsRef<Path>>(path: P, _data: &[u8]) -> Result<(), Error> {
+    Err(Error::new(path, "Writing files is not supported on wasm"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn write_to_file_atomic<P: AsRef<Path>>(path: P, _data: &[u8]) -> Result<(), Error> {
+    Err(Error::new(path, "Writing files is not supported on wasm"))
+}
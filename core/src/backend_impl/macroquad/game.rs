@@ -1,25 +1,50 @@
 use std::time::Duration;
 
-use macroquad::prelude::scene::{Node, RefMut};
+use macroquad::camera::{pop_camera_state, push_camera_state, set_default_camera};
+use macroquad::input::prevent_quit;
+use macroquad::prelude::scene::{self, Handle, Node, RefMut};
 use macroquad::time::get_frame_time;
+use macroquad::window::{screen_height, screen_width};
 
+use crate::color::colors;
+use crate::config::config;
+use crate::event::Transition;
+use crate::render::{
+    begin_scene_render, draw_rectangle, end_scene_render, update_scene_render_target,
+};
 use crate::result::Result;
 use crate::state::GameState;
-use crate::viewport::{resize_viewport, viewport_size};
+use crate::viewport::{set_render_scale, update_viewport, viewport};
 use crate::window::window_size;
 
 pub fn delta_time() -> Duration {
     Duration::from_secs_f32(get_frame_time())
 }
 
+// This holds a transition that is in progress. `next_state` is taken when the transition reaches
+// its midpoint, which is when the state is actually changed.
+struct ActiveTransition {
+    transition: Transition,
+    elapsed: f32,
+    next_state: Option<Box<dyn GameState>>,
+}
+
 pub struct Game {
     state: Box<dyn GameState>,
+    transition: Option<ActiveTransition>,
+    overlay: Option<Handle<GameOverlay>>,
 }
 
 impl Game {
     pub fn new<S: 'static + GameState>(state: S) -> Result<Self> {
+        // Closing the window is routed through the event queue, as an `Event::QuitRequested`, so
+        // that quit handlers get a chance to save state, or defer quitting, first
+        prevent_quit();
+
         Ok(Game {
             state: Box::new(state),
+            transition: None,
+            overlay: None,
         })
     }
 
@@ -30,8 +55,108 @@ impl Game {
 
         self.state.begin(world)?;
 
+        self.add_overlay();
+
         Ok(())
     }
+
+    // This (re)adds the overlay node, so that it is drawn after any nodes added by the state
+    fn add_overlay(&mut self) {
+        if let Some(handle) = self.overlay.take() {
+            if let Some(overlay) = scene::try_get_node(handle) {
+                overlay.delete();
+            }
+        }
+
+        self.overlay = Some(scene::add_node(GameOverlay));
+    }
+
+    /// This will change state using the specified transition. Instant transitions will change the
+    /// state immediately, while any other transition will change it at its midpoint.
+    /// A transition started while another is in progress replaces it.
+    pub fn change_state_with_transition(
+        &mut self,
+        state: Box<dyn GameState>,
+        transition: Transition,
+    ) -> Result<()> {
+        if transition == Transition::Instant || transition.duration() <= 0.0 {
+            self.transition = None;
+            return self.change_state(state);
+        }
+
+        self.transition = Some(ActiveTransition {
+            transition,
+            elapsed: 0.0,
+            next_state: Some(state),
+        });
+
+        Ok(())
+    }
+
+    fn update_transition(&mut self, delta_time: f32) -> Result<()> {
+        let mut next_state = None;
+        let mut is_done = false;
+
+        if let Some(active) = &mut self.transition {
+            active.elapsed += delta_time;
+
+            let duration = active.transition.duration();
+
+            if active.elapsed >= duration / 2.0 {
+                next_state = active.next_state.take();
+            }
+
+            is_done = active.elapsed >= duration;
+        }
+
+        if let Some(state) = next_state {
+            self.change_state(state)?;
+        }
+
+        if is_done {
+            self.transition = None;
+        }
+
+        Ok(())
+    }
+
+    fn draw_transition(&self) {
+        if let Some(active) = &self.transition {
+            if let Some(color) = active.transition.get_overlay_color(active.elapsed) {
+                push_camera_state();
+                set_default_camera();
+
+                draw_rectangle(0.0, 0.0, screen_width(), screen_height(), color);
+
+                pop_camera_state();
+            }
+        }
+    }
+
+    // This draws black bars over the parts of the window that are outside of the viewport, when
+    // the viewport is letterboxed
+    fn draw_letterbox(&self) {
+        let viewport = viewport();
+
+        let (width, height) = (screen_width(), screen_height());
+
+        if viewport.width >= width && viewport.height >= height {
+            return;
+        }
+
+        push_camera_state();
+        set_default_camera();
+
+        let right = viewport.x + viewport.width;
+        let bottom = viewport.y + viewport.height;
+
+        draw_rectangle(0.0, 0.0, width, viewport.y, colors::BLACK);
+        draw_rectangle(0.0, bottom, width, height - bottom, colors::BLACK);
+        draw_rectangle(0.0, 0.0, viewport.x, height, colors::BLACK);
+        draw_rectangle(right, 0.0, width - right, height, colors::BLACK);
+
+        pop_camera_state();
+    }
 }
 
 impl Node for Game {
@@ -40,13 +165,22 @@ impl Node for Game {
         Self: Sized,
     {
         node.state.begin(None).unwrap();
+
+        node.add_overlay();
     }
 
     fn update(mut node: RefMut<Self>)
     where
         Self: Sized,
     {
-        node.state.update(get_frame_time()).unwrap();
+        let delta_time = get_frame_time();
+
+        set_render_scale(config().video.render_scale);
+        update_scene_render_target();
+
+        node.update_transition(delta_time).unwrap();
+
+        node.state.update(delta_time).unwrap();
     }
 
     fn fixed_update(mut node: RefMut<Self>)
@@ -60,12 +194,30 @@ impl Node for Game {
     where
         Self: Sized,
     {
-        let window_size = window_size();
-        let viewport_size = viewport_size();
-        if viewport_size != window_size {
-            resize_viewport(window_size.width, window_size.height);
-        }
+        update_viewport(window_size(), config().video.letterbox_aspect_ratio);
+
+        begin_scene_render();
 
         node.state.draw(get_frame_time()).unwrap();
     }
 }
+
+// Nodes are drawn in the order they were added, so this is used to draw the scene render target
+// to the window, as well as the transition overlay and letterbox, after the nodes of the current
+// state, like the editor, which are added after the `Game` node, have drawn the scene
+struct GameOverlay;
+
+impl Node for GameOverlay {
+    fn draw(_node: RefMut<Self>)
+    where
+        Self: Sized,
+    {
+        if let Some(game) = scene::find_node_by_type::<Game>() {
+            end_scene_render();
+
+            game.draw_transition();
+
+            game.draw_letterbox();
+        }
+    }
+}
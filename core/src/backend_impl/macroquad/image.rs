@@ -73,6 +73,12 @@ impl ImageImpl {
     }
 }
 
+impl From<MQImage> for ImageImpl {
+    fn from(mq_image: MQImage) -> Self {
+        ImageImpl(mq_image)
+    }
+}
+
 impl Deref for ImageImpl {
     type Target = MQImage;
 
@@ -86,3 +92,24 @@ impl DerefMut for ImageImpl {
         &mut self.0
     }
 }
+
+impl ImageImpl {
+    /// This does the same as `export_png`, but the image is encoded and written on a separate
+    /// thread, so awaiting it from a coroutine will not block the frame loop
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn export_png_async(&self, path: &str) {
+        let image = self.0.clone();
+        let path = path.to_string();
+
+        crate::backend_impl::file::run_in_background(move || image.export_png(&path)).await
+    }
+
+    /// Files can not be written on wasm, so the image is not exported
+    #[cfg(target_arch = "wasm32")]
+    pub async fn export_png_async(&self, path: &str) {
+        println!(
+            "WARNING: Unable to export '{}': Writing files is not supported on wasm",
+            path
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::config::config;
 use crate::input::{InputMapping, KeyCode, MouseButton};
 use crate::math::{vec2, Vec2};
+use crate::viewport::to_viewport_position;
 use std::borrow::Borrow;
 
 pub(crate) fn input_mapping() -> &'static InputMapping {
@@ -19,6 +20,11 @@ pub fn is_key_released(key_code: KeyCode) -> bool {
     macroquad::input::is_key_released(key_code.into())
 }
 
+/// This returns the last key that was pressed this frame, if any
+pub fn get_last_key_pressed() -> Option<KeyCode> {
+    macroquad::input::get_last_key_pressed().map(KeyCode::from)
+}
+
 pub fn is_mouse_button_down(button: MouseButton) -> bool {
     macroquad::input::is_mouse_button_down(button.into())
 }
@@ -31,9 +37,11 @@ pub fn is_mouse_button_released(button: MouseButton) -> bool {
     macroquad::input::is_mouse_button_released(button.into())
 }
 
+/// This returns the cursor position in viewport space, so that it is relative to the top-left
+/// corner of the viewport, also when the viewport is letterboxed.
 pub fn mouse_position() -> Vec2 {
     let (x, y) = macroquad::input::mouse_position();
-    vec2(x, y)
+    to_viewport_position(vec2(x, y))
 }
 
 pub fn mouse_wheel() -> Vec2 {
@@ -0,0 +1,9 @@
+pub use ff_particles::*;
+
+/// This draws the particles of `cache`, advancing their simulation.
+///
+/// ff-particles advances its simulation by the macroquad frame time, internally, so `time_scale`
+/// is ignored by this backend.
+pub fn draw_emitters_cache(cache: &mut EmittersCache, _time_scale: f32) {
+    cache.draw();
+}
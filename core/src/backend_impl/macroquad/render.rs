@@ -1,12 +1,16 @@
+use macroquad::camera::{
+    pop_camera_state, push_camera_state, set_camera, set_default_camera, Camera2D,
+};
 use macroquad::shapes::{draw_circle_lines, draw_rectangle_lines};
-use macroquad::texture::draw_texture_ex;
+use macroquad::texture::{draw_texture_ex, render_target, FilterMode, RenderTarget};
 use macroquad::window::{clear_background, next_frame};
 use std::ops::Deref;
 
 use crate::color::{colors, Color};
-use crate::math::Vec2;
+use crate::math::{Size, Vec2};
 use crate::render::DrawTextureParams;
 use crate::texture::Texture2D;
+use crate::viewport::{render_resolution, render_scale, viewport};
 
 pub fn clear_screen<C: Into<Option<Color>>>(color: C) {
     clear_background(color.into().unwrap_or(colors::BLACK).into());
@@ -58,3 +62,105 @@ pub fn draw_line(x: f32, y: f32, end_x: f32, end_y: f32, weight: f32, color: Col
 pub fn fps() -> u32 {
     macroquad::time::get_fps() as u32
 }
+
+static mut SCENE_RENDER_TARGET: Option<(RenderTarget, Size<u32>)> = None;
+
+/// This returns the offscreen target that the scene is rendered to, when the render scale is not
+/// `1.0`. Cameras that draw the scene should use this as their render target and `scene_viewport`
+/// as their viewport.
+pub fn scene_render_target() -> Option<RenderTarget> {
+    unsafe { SCENE_RENDER_TARGET.map(|(target, _)| target) }
+}
+
+/// This returns the viewport that cameras that draw the scene should use. This is `None`, meaning
+/// the whole target, when the scene is rendered to an offscreen target.
+pub fn scene_viewport() -> Option<(i32, i32, i32, i32)> {
+    if scene_render_target().is_some() {
+        None
+    } else {
+        let viewport = viewport();
+
+        Some((
+            viewport.x as i32,
+            viewport.y as i32,
+            viewport.width as i32,
+            viewport.height as i32,
+        ))
+    }
+}
+
+// This (re)creates the scene render target, if the render resolution has changed. The target is
+// removed when the render scale is `1.0`, as the scene is then drawn straight to the window.
+// This is called before the game state is updated, so that cameras never refer to a target that
+// has been deleted.
+pub(crate) fn update_scene_render_target() {
+    let resolution = render_resolution();
+
+    let current = unsafe { SCENE_RENDER_TARGET };
+
+    if render_scale() == 1.0 {
+        if let Some((target, _)) = current {
+            target.texture.delete();
+
+            unsafe { SCENE_RENDER_TARGET = None };
+        }
+
+        return;
+    }
+
+    let is_outdated = current.map_or(true, |(_, target_resolution)| {
+        target_resolution != resolution
+    });
+
+    if is_outdated {
+        if let Some((target, _)) = current {
+            target.texture.delete();
+        }
+
+        let target = render_target(resolution.width, resolution.height);
+        target.texture.set_filter(FilterMode::Nearest);
+
+        unsafe { SCENE_RENDER_TARGET = Some((target, resolution)) };
+    }
+}
+
+// This clears the scene render target, if the scene is rendered to one
+pub(crate) fn begin_scene_render() {
+    if let Some(target) = scene_render_target() {
+        push_camera_state();
+
+        set_camera(&Camera2D {
+            render_target: Some(target),
+            ..Camera2D::default()
+        });
+
+        clear_background(colors::BLACK.into());
+
+        pop_camera_state();
+    }
+}
+
+// This draws the scene render target to the viewport, scaling it to fit
+pub(crate) fn end_scene_render() {
+    if let Some(target) = scene_render_target() {
+        let viewport = viewport();
+
+        push_camera_state();
+        set_default_camera();
+
+        // Render targets are stored upside down, compared to the window
+        draw_texture_ex(
+            target.texture,
+            viewport.x,
+            viewport.y,
+            colors::WHITE.into(),
+            macroquad::texture::DrawTextureParams {
+                dest_size: Some(macroquad::math::vec2(viewport.width, viewport.height)),
+                flip_y: true,
+                ..Default::default()
+            },
+        );
+
+        pop_camera_state();
+    }
+}
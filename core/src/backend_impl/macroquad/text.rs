@@ -70,6 +70,20 @@ pub fn draw_text(text: &str, x: f32, y: f32, params: TextParams) {
                 VerticalAlignment::Center => y - (measure.height / 2.0),
             };
 
+            for (offset, color) in params.get_effect_offsets() {
+                let params = TextParams {
+                    color,
+                    ..params.clone()
+                };
+
+                macroquad::text::draw_text_ex(
+                    &line,
+                    x + offset.x,
+                    y + y_offset + offset.y,
+                    params.into(),
+                );
+            }
+
             macroquad::text::draw_text_ex(&line, x, y + y_offset, params.clone().into());
 
             y_offset += measure.height + (BASE_LINE_MARGIN * font_size);
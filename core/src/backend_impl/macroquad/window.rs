@@ -1,6 +1,7 @@
 use crate::math::Size;
+use macroquad::miniquad::clipboard;
 pub use macroquad::miniquad::conf::Icon as WindowIcon;
-use macroquad::window::{screen_height, screen_width};
+use macroquad::window::{get_internal_gl, screen_height, screen_width};
 
 pub fn window_size() -> Size<f32> {
     Size {
@@ -8,3 +9,15 @@ pub fn window_size() -> Size<f32> {
         height: screen_height(),
     }
 }
+
+/// This returns the text content of the system clipboard, if it holds any
+pub fn get_clipboard_text() -> Option<String> {
+    let gl = unsafe { get_internal_gl() };
+    clipboard::get(gl.quad_context)
+}
+
+/// This replaces the content of the system clipboard with `text`
+pub fn set_clipboard_text(text: &str) {
+    let gl = unsafe { get_internal_gl() };
+    clipboard::set(gl.quad_context, text);
+}
@@ -1,5 +1,7 @@
 //! Color types and helpers.
 
+use std::ops::Mul;
+
 use serde::{Deserialize, Serialize};
 
 pub use crate::backend_impl::color::*;
@@ -39,11 +41,12 @@ impl Color {
         }
     }
 
+    /// Components outside of the `0.0..=1.0` range are clamped
     pub fn to_bytes(self) -> (u8, u8, u8, u8) {
-        let r = self.red * 255.0;
-        let g = self.green * 255.0;
-        let b = self.blue * 255.0;
-        let a = self.alpha * 255.0;
+        let r = self.red.clamp(0.0, 1.0) * 255.0;
+        let g = self.green.clamp(0.0, 1.0) * 255.0;
+        let b = self.blue.clamp(0.0, 1.0) * 255.0;
+        let a = self.alpha.clamp(0.0, 1.0) * 255.0;
 
         (r as u8, g as u8, b as u8, a as u8)
     }
@@ -118,38 +121,53 @@ impl Color {
         (h, s, l)
     }
 
+    /// This will panic if `str` is not a valid hex color. Use `try_from_hex` for user input.
     pub fn from_hex(str: &str) -> Color {
-        let hex = if str.starts_with('#') {
-            &str[1..str.len()]
-        } else {
-            str
-        };
+        Self::try_from_hex(str).unwrap_or_else(|| panic!("Invalid hex color '{}'", str))
+    }
+
+    /// Parses a hex color on the form `RRGGBB` or `RRGGBBAA`, optionally prefixed by `#`.
+    /// If alpha is omitted, the color will be opaque.
+    pub fn try_from_hex(str: &str) -> Option<Color> {
+        let hex = str.strip_prefix('#').unwrap_or(str);
+
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
 
-        let r = u8::from_str_radix(&hex[0..2], 16).unwrap();
-        let g = u8::from_str_radix(&hex[2..4], 16).unwrap();
-        let b = u8::from_str_radix(&hex[4..6], 16).unwrap();
+        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
         let a = if hex.len() > 6 {
-            u8::from_str_radix(&hex[6..8], 16).unwrap()
+            u8::from_str_radix(&hex[6..8], 16).ok()?
         } else {
             255
         };
 
-        Color::new(
-            r as f32 / 255.0,
-            g as f32 / 255.0,
-            b as f32 / 255.0,
-            a as f32 / 255.0,
-        )
+        Some(Color::from_bytes(r, g, b, a))
     }
 
     pub fn to_hex(self) -> String {
         let (r, g, b, _) = self.to_bytes();
-        format!("{:X}{:X}{:X}", r, g, b)
+        format!("{:02X}{:02X}{:02X}", r, g, b)
     }
 
     pub fn to_hex_alpha(self) -> String {
         let (r, g, b, a) = self.to_bytes();
-        format!("{:X}{:X}{:X}{:X}", r, g, b, a)
+        format!("{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+
+    /// Linearly interpolates between `a` and `b`, including alpha. `t` is clamped to the
+    /// `0.0..=1.0` range.
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+
+        Color::new(
+            a.red + (b.red - a.red) * t,
+            a.green + (b.green - a.green) * t,
+            a.blue + (b.blue - a.blue) * t,
+            a.alpha + (b.alpha - a.alpha) * t,
+        )
     }
 
     pub fn to_array(self) -> [f32; 4] {
@@ -168,6 +186,20 @@ impl Default for Color {
     }
 }
 
+/// Colors are multiplied component-wise, including alpha
+impl Mul for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: Color) -> Self::Output {
+        Color::new(
+            self.red * rhs.red,
+            self.green * rhs.green,
+            self.blue * rhs.blue,
+            self.alpha * rhs.alpha,
+        )
+    }
+}
+
 impl From<[f32; 3]> for Color {
     fn from(color: [f32; 3]) -> Self {
         Color::new(color[0], color[1], color[2], 1.0)
@@ -425,6 +457,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_color_try_from_hex_invalid() {
+        assert_eq!(Color::try_from_hex(""), None);
+        assert_eq!(Color::try_from_hex("#"), None);
+        assert_eq!(Color::try_from_hex("12ab6"), None);
+        assert_eq!(Color::try_from_hex("12ab6fb"), None);
+        assert_eq!(Color::try_from_hex("12ab6fb2a"), None);
+        assert_eq!(Color::try_from_hex("12ab6g"), None);
+        assert_eq!(Color::try_from_hex("+1ab6f"), None);
+    }
+
+    #[test]
+    fn test_color_to_hex() {
+        let color = Color::from_bytes(1, 171, 0, 15);
+
+        assert_eq!(color.to_hex(), "01AB00");
+        assert_eq!(color.to_hex_alpha(), "01AB000F");
+
+        assert_eq!(
+            Color::from_hex(&color.to_hex()),
+            Color::from_bytes(1, 171, 0, 255)
+        );
+        assert_eq!(Color::from_hex(&color.to_hex_alpha()), color);
+    }
+
+    #[test]
+    fn test_color_to_hex_clamped() {
+        let color = Color::new(1.5, -0.5, 0.0, 2.0);
+
+        assert_eq!(color.to_hex_alpha(), "FF0000FF");
+    }
+
+    #[test]
+    fn test_color_lerp() {
+        let a = Color::new(0.0, 0.5, 1.0, 0.0);
+        let b = Color::new(1.0, 0.5, 0.0, 1.0);
+
+        assert_eq!(Color::lerp(a, b, 0.0), a);
+        assert_eq!(Color::lerp(a, b, 1.0), b);
+        assert_eq!(Color::lerp(a, b, 0.5), Color::new(0.5, 0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_color_lerp_clamped() {
+        let a = Color::new(0.0, 0.5, 1.0, 0.0);
+        let b = Color::new(1.0, 0.5, 0.0, 1.0);
+
+        assert_eq!(Color::lerp(a, b, -1.0), a);
+        assert_eq!(Color::lerp(a, b, 2.0), b);
+    }
+
     #[test]
     fn test_color_from_bytes() {
         assert_eq!(Color::new(1.0, 0.0, 0.0, 1.0), color_u8!(255, 0, 0));
@@ -441,4 +524,15 @@ mod tests {
             color_u8!(0, 255, 127.5, 255)
         );
     }
+
+    #[test]
+    fn test_color_mul() {
+        let color = Color::new(0.5, 1.0, 0.25, 1.0);
+
+        assert_eq!(color * colors::WHITE, color);
+        assert_eq!(
+            color * Color::new(0.5, 0.5, 1.0, 0.5),
+            Color::new(0.25, 0.5, 0.25, 0.5)
+        );
+    }
 }
@@ -4,8 +4,9 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::audio::AudioConfig;
-use crate::input::InputMapping;
-use crate::parsing::{deserialize_toml_bytes, load_toml_file};
+use crate::input::{InputMapping, KeyCode, Keybind};
+use crate::math::Vec2;
+use crate::parsing::{deserialize_toml_bytes, load_toml_file, serialize_bytes_by_extension};
 use crate::result::Result;
 use crate::video::VideoConfig;
 use crate::window::WindowConfig;
@@ -22,6 +23,301 @@ pub struct Config {
     pub audio: AudioConfig,
     #[serde(default)]
     pub input: InputMapping,
+    #[serde(default)]
+    pub editor: EditorConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorConfig {
+    /// If this is `true`, changes to the selected tool, layer, tileset and tile will be recorded
+    /// in the editor history, so that they can be stepped through with undo and redo.
+    /// By default, only changes to map data are recorded.
+    #[serde(default, rename = "record-selection-history")]
+    pub should_record_selection_history: bool,
+    /// Key bindings for editor actions. Any binding that is not specified will use its default.
+    #[serde(default)]
+    pub keybinds: EditorKeybinds,
+    /// The number of info messages kept in the editor message log
+    #[serde(
+        default = "EditorConfig::default_message_log_size",
+        rename = "message-log-size"
+    )]
+    pub message_log_size: usize,
+    /// This moves the origin of the grid drawn in the editor, and of the grid that positions are
+    /// snapped to, relative to the world offset of the map. Tiles are not affected by this.
+    #[serde(
+        default,
+        rename = "grid-origin-offset",
+        with = "crate::parsing::vec2_def"
+    )]
+    pub grid_origin_offset: Vec2,
+    /// If this is `true`, the tile picker tool will copy a tile's attributes along with its id, so
+    /// that tiles placed afterwards are given the same attributes.
+    #[serde(default, rename = "pick-tile-attributes")]
+    pub should_pick_tile_attributes: bool,
+    /// If this is `true`, dragged objects will be aligned to the edges and centers of other
+    /// objects that are within `object_snap_threshold`, when snap to grid is disabled
+    #[serde(
+        default = "EditorConfig::default_should_snap_to_objects",
+        rename = "snap-to-objects"
+    )]
+    pub should_snap_to_objects: bool,
+    /// The distance, in world units, within which dragged objects are aligned to other objects
+    #[serde(
+        default = "EditorConfig::default_object_snap_threshold",
+        rename = "object-snap-threshold"
+    )]
+    pub object_snap_threshold: f32,
+    /// The distance, in world units, that the camera moves every update while panning with the
+    /// keyboard, or with the cursor at the edge of the viewport
+    #[serde(
+        default = "EditorConfig::default_camera_pan_speed",
+        rename = "camera-pan-speed"
+    )]
+    pub camera_pan_speed: f32,
+    /// The change in camera scale for every step of the zoom input
+    #[serde(
+        default = "EditorConfig::default_camera_zoom_step",
+        rename = "camera-zoom-step"
+    )]
+    pub camera_zoom_step: f32,
+    /// The fraction of the viewport, along each edge, that will pan the camera when the cursor is
+    /// inside it. Set this to `0.0` to disable panning at the edges of the viewport.
+    #[serde(
+        default = "EditorConfig::default_camera_pan_threshold",
+        rename = "camera-pan-threshold"
+    )]
+    pub camera_pan_threshold: f32,
+}
+
+impl EditorConfig {
+    const DEFAULT_MESSAGE_LOG_SIZE: usize = 50;
+    const DEFAULT_OBJECT_SNAP_THRESHOLD: f32 = 8.0;
+
+    const DEFAULT_CAMERA_PAN_SPEED: f32 = 5.0;
+    const MIN_CAMERA_PAN_SPEED: f32 = 0.5;
+    const MAX_CAMERA_PAN_SPEED: f32 = 50.0;
+
+    const DEFAULT_CAMERA_ZOOM_STEP: f32 = 0.1;
+    const MIN_CAMERA_ZOOM_STEP: f32 = 0.01;
+    const MAX_CAMERA_ZOOM_STEP: f32 = 1.0;
+
+    const DEFAULT_CAMERA_PAN_THRESHOLD: f32 = 0.025;
+    const MAX_CAMERA_PAN_THRESHOLD: f32 = 0.25;
+
+    fn default_message_log_size() -> usize {
+        Self::DEFAULT_MESSAGE_LOG_SIZE
+    }
+
+    fn default_should_snap_to_objects() -> bool {
+        true
+    }
+
+    fn default_object_snap_threshold() -> f32 {
+        Self::DEFAULT_OBJECT_SNAP_THRESHOLD
+    }
+
+    fn default_camera_pan_speed() -> f32 {
+        Self::DEFAULT_CAMERA_PAN_SPEED
+    }
+
+    fn default_camera_zoom_step() -> f32 {
+        Self::DEFAULT_CAMERA_ZOOM_STEP
+    }
+
+    fn default_camera_pan_threshold() -> f32 {
+        Self::DEFAULT_CAMERA_PAN_THRESHOLD
+    }
+
+    /// This returns the camera pan speed, clamped to a range that keeps the camera usable
+    pub fn get_camera_pan_speed(&self) -> f32 {
+        self.camera_pan_speed
+            .clamp(Self::MIN_CAMERA_PAN_SPEED, Self::MAX_CAMERA_PAN_SPEED)
+    }
+
+    /// This returns the camera zoom step, clamped to a range that keeps the camera usable
+    pub fn get_camera_zoom_step(&self) -> f32 {
+        self.camera_zoom_step
+            .clamp(Self::MIN_CAMERA_ZOOM_STEP, Self::MAX_CAMERA_ZOOM_STEP)
+    }
+
+    /// This returns the camera pan threshold, clamped so that the edges can not cover the whole
+    /// viewport
+    pub fn get_camera_pan_threshold(&self) -> f32 {
+        self.camera_pan_threshold
+            .clamp(0.0, Self::MAX_CAMERA_PAN_THRESHOLD)
+    }
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        EditorConfig {
+            should_record_selection_history: false,
+            keybinds: EditorKeybinds::default(),
+            message_log_size: Self::DEFAULT_MESSAGE_LOG_SIZE,
+            grid_origin_offset: Vec2::ZERO,
+            should_pick_tile_attributes: false,
+            should_snap_to_objects: true,
+            object_snap_threshold: Self::DEFAULT_OBJECT_SNAP_THRESHOLD,
+            camera_pan_speed: Self::DEFAULT_CAMERA_PAN_SPEED,
+            camera_zoom_step: Self::DEFAULT_CAMERA_ZOOM_STEP,
+            camera_pan_threshold: Self::DEFAULT_CAMERA_PAN_THRESHOLD,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct EditorKeybinds {
+    pub toggle_menu: Keybind,
+    pub save: Keybind,
+    pub save_as: Keybind,
+    pub load: Keybind,
+    pub test_play: Keybind,
+    pub undo: Keybind,
+    pub redo: Keybind,
+    pub copy: Keybind,
+    pub paste: Keybind,
+    pub delete: Keybind,
+    pub toggle_draw_grid: Keybind,
+    pub toggle_snap_to_grid: Keybind,
+    pub cycle_snap_increment: Keybind,
+    pub toggle_disable_parallax: Keybind,
+    pub toggle_parallax_preview: Keybind,
+    pub toggle_empty_tiles: Keybind,
+    pub move_layer_up: Keybind,
+    pub move_layer_down: Keybind,
+    pub toggle_solo_layer: Keybind,
+    pub cycle_tileset: Keybind,
+    pub toggle_message_log: Keybind,
+    pub cursor_tool: Keybind,
+    pub tile_placement_tool: Keybind,
+    pub object_placement_tool: Keybind,
+    pub spawn_point_placement_tool: Keybind,
+    pub eraser_tool: Keybind,
+    pub region_selection_tool: Keybind,
+    pub prefab_placement_tool: Keybind,
+    pub stamp_tool: Keybind,
+    pub tile_picker_tool: Keybind,
+}
+
+impl Default for EditorKeybinds {
+    fn default() -> Self {
+        EditorKeybinds {
+            toggle_menu: Keybind::new(KeyCode::Escape),
+            save: Keybind::ctrl(KeyCode::S),
+            save_as: Keybind::ctrl_shift(KeyCode::S),
+            load: Keybind::ctrl(KeyCode::L),
+            test_play: Keybind::new(KeyCode::F5),
+            undo: Keybind::ctrl(KeyCode::Z),
+            redo: Keybind::ctrl_shift(KeyCode::Z),
+            copy: Keybind::ctrl(KeyCode::C),
+            paste: Keybind::ctrl(KeyCode::V),
+            delete: Keybind::new(KeyCode::Delete),
+            toggle_draw_grid: Keybind::new(KeyCode::G),
+            toggle_snap_to_grid: Keybind::ctrl(KeyCode::G),
+            cycle_snap_increment: Keybind::ctrl_shift(KeyCode::G),
+            toggle_disable_parallax: Keybind::new(KeyCode::P),
+            toggle_parallax_preview: Keybind::ctrl(KeyCode::P),
+            toggle_empty_tiles: Keybind::ctrl(KeyCode::E),
+            move_layer_up: Keybind::new(KeyCode::PageUp),
+            move_layer_down: Keybind::new(KeyCode::PageDown),
+            toggle_solo_layer: Keybind::new(KeyCode::I),
+            cycle_tileset: Keybind::new(KeyCode::T),
+            toggle_message_log: Keybind::ctrl(KeyCode::M),
+            cursor_tool: Keybind::new(KeyCode::Q),
+            tile_placement_tool: Keybind::new(KeyCode::B),
+            object_placement_tool: Keybind::new(KeyCode::O),
+            spawn_point_placement_tool: Keybind::new(KeyCode::N),
+            eraser_tool: Keybind::new(KeyCode::E),
+            region_selection_tool: Keybind::new(KeyCode::R),
+            prefab_placement_tool: Keybind::new(KeyCode::F),
+            stamp_tool: Keybind::new(KeyCode::M),
+            tile_picker_tool: Keybind::new(KeyCode::K),
+        }
+    }
+}
+
+// This implements lookup of editor key bindings by name, so that they can be listed and rebound
+// at runtime. The names are the same as the ones used in the config file.
+macro_rules! editor_keybind_names {
+    ($($field:ident => $name:literal),* $(,)?) => {
+        impl EditorKeybinds {
+            /// This returns the name and binding of every editor action, in declaration order
+            pub fn iter(&self) -> Vec<(&'static str, Keybind)> {
+                vec![$(($name, self.$field)),*]
+            }
+
+            pub fn get(&self, name: &str) -> Option<Keybind> {
+                match name {
+                    $($name => Some(self.$field),)*
+                    _ => None,
+                }
+            }
+
+            pub fn get_mut(&mut self, name: &str) -> Option<&mut Keybind> {
+                match name {
+                    $($name => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+editor_keybind_names! {
+    toggle_menu => "toggle-menu",
+    save => "save",
+    save_as => "save-as",
+    load => "load",
+    test_play => "test-play",
+    undo => "undo",
+    redo => "redo",
+    copy => "copy",
+    paste => "paste",
+    delete => "delete",
+    toggle_draw_grid => "toggle-draw-grid",
+    toggle_snap_to_grid => "toggle-snap-to-grid",
+    cycle_snap_increment => "cycle-snap-increment",
+    toggle_disable_parallax => "toggle-disable-parallax",
+    toggle_parallax_preview => "toggle-parallax-preview",
+    toggle_empty_tiles => "toggle-empty-tiles",
+    move_layer_up => "move-layer-up",
+    move_layer_down => "move-layer-down",
+    toggle_solo_layer => "toggle-solo-layer",
+    cycle_tileset => "cycle-tileset",
+    toggle_message_log => "toggle-message-log",
+    cursor_tool => "cursor-tool",
+    tile_placement_tool => "tile-placement-tool",
+    object_placement_tool => "object-placement-tool",
+    spawn_point_placement_tool => "spawn-point-placement-tool",
+    eraser_tool => "eraser-tool",
+    region_selection_tool => "region-selection-tool",
+    prefab_placement_tool => "prefab-placement-tool",
+    stamp_tool => "stamp-tool",
+    tile_picker_tool => "tile-picker-tool",
+}
+
+impl EditorKeybinds {
+    /// This rebinds the action with the specified name. Returns `false` if there is no action with
+    /// that name.
+    pub fn set(&mut self, name: &str, keybind: Keybind) -> bool {
+        if let Some(current) = self.get_mut(name) {
+            *current = keybind;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// This returns the names of all actions, other than `name`, that are bound to `keybind`
+    pub fn find_conflicts(&self, name: &str, keybind: Keybind) -> Vec<&'static str> {
+        self.iter()
+            .into_iter()
+            .filter(|(other_name, other)| *other_name != name && *other == keybind)
+            .map(|(other_name, _)| other_name)
+            .collect()
+    }
 }
 
 pub async fn load_config<P: AsRef<Path>>(path: P) -> Result<Config> {
@@ -37,3 +333,72 @@ pub fn load_config_sync<P: AsRef<Path>>(path: P) -> Result<Config> {
     cfg.input.verify()?;
     Ok(cfg)
 }
+
+/// This writes `config` to `path`, so that changes made at runtime, like rebound keys, persist.
+/// The file is replaced atomically, so an interrupted write will not leave a corrupt config.
+#[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+pub fn save_config<P: AsRef<Path>>(path: P, config: &Config) -> Result<()> {
+    let bytes = serialize_bytes_by_extension("toml", config)?;
+    crate::file::write_to_file_atomic_sync(path, &bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebind_editor_keybind() {
+        let mut keybinds = EditorKeybinds::default();
+
+        assert_eq!(keybinds.get("undo"), Some(Keybind::ctrl(KeyCode::Z)));
+        assert_eq!(keybinds.get("not-an-action"), None);
+
+        assert!(keybinds.set("undo", Keybind::ctrl(KeyCode::U)));
+        assert_eq!(keybinds.undo, Keybind::ctrl(KeyCode::U));
+        assert!(!keybinds.set("not-an-action", Keybind::new(KeyCode::U)));
+
+        assert_eq!(
+            keybinds.find_conflicts("undo", Keybind::new(KeyCode::G)),
+            vec!["toggle-draw-grid"]
+        );
+        assert!(keybinds
+            .find_conflicts("toggle-draw-grid", Keybind::new(KeyCode::G))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_partial_editor_keybinds_use_defaults() {
+        let keybinds: EditorKeybinds =
+            deserialize_toml_bytes(b"undo = { key = \"U\", ctrl = true }").unwrap();
+
+        assert_eq!(keybinds.undo, Keybind::ctrl(KeyCode::U));
+        assert_eq!(keybinds.redo, EditorKeybinds::default().redo);
+    }
+
+    #[test]
+    fn test_editor_camera_settings_clamped() {
+        let editor_config: EditorConfig = deserialize_toml_bytes(b"").unwrap();
+        assert_eq!(editor_config.get_camera_pan_speed(), 5.0);
+        assert_eq!(editor_config.get_camera_zoom_step(), 0.1);
+        assert_eq!(editor_config.get_camera_pan_threshold(), 0.025);
+
+        let editor_config: EditorConfig = deserialize_toml_bytes(
+            b"camera-pan-speed = 1000.0\ncamera-zoom-step = 0.0\ncamera-pan-threshold = -1.0",
+        )
+        .unwrap();
+        assert_eq!(editor_config.get_camera_pan_speed(), 50.0);
+        assert_eq!(editor_config.get_camera_zoom_step(), 0.01);
+        assert_eq!(editor_config.get_camera_pan_threshold(), 0.0);
+    }
+
+    #[test]
+    fn test_editor_grid_origin_offset() {
+        let editor_config: EditorConfig = deserialize_toml_bytes(b"").unwrap();
+        assert_eq!(editor_config.grid_origin_offset, Vec2::ZERO);
+
+        let editor_config: EditorConfig =
+            deserialize_toml_bytes(b"grid-origin-offset = { x = 8.0, y = -4.0 }").unwrap();
+        assert_eq!(editor_config.grid_origin_offset, Vec2::new(8.0, -4.0));
+    }
+}
@@ -77,7 +77,7 @@ pub struct Keyframe {
 }
 
 pub struct AnimatedSpriteParams {
-    pub scale: f32,
+    pub scale: Vec2,
     pub offset: Vec2,
     pub pivot: Option<Vec2>,
     pub tint: Color,
@@ -89,7 +89,7 @@ pub struct AnimatedSpriteParams {
 impl Default for AnimatedSpriteParams {
     fn default() -> Self {
         AnimatedSpriteParams {
-            scale: 1.0,
+            scale: Vec2::ONE,
             offset: Vec2::ZERO,
             pivot: None,
             tint: colors::WHITE,
@@ -103,7 +103,7 @@ impl Default for AnimatedSpriteParams {
 impl From<AnimatedSpriteMetadata> for AnimatedSpriteParams {
     fn from(meta: AnimatedSpriteMetadata) -> Self {
         AnimatedSpriteParams {
-            scale: meta.scale.unwrap_or(1.0),
+            scale: Vec2::splat(meta.scale.unwrap_or(1.0)),
             offset: meta.offset,
             pivot: meta.pivot,
             tint: meta.tint.unwrap_or(colors::WHITE),
@@ -131,7 +131,8 @@ impl QueuedAnimationAction {
 pub struct AnimatedSprite {
     pub texture: Texture2D,
     pub frame_size: Size<f32>,
-    pub scale: f32,
+    /// The scale of the sprite along each axis, which does not have to be uniform
+    pub scale: Vec2,
     pub offset: Vec2,
     pub pivot: Option<Vec2>,
     pub tint: Color,
@@ -199,7 +200,7 @@ impl AnimatedSprite {
     }
 
     pub fn size(&self) -> Size<f32> {
-        self.frame_size.to_scaled(self.scale)
+        self.frame_size.to_scaled_xy(self.scale)
     }
 
     pub fn source_rect(&self) -> Rect {
@@ -386,7 +387,7 @@ pub fn draw_one_animated_sprite(transform: &Transform, sprite: &AnimatedSprite)
                 flip_y: sprite.is_flipped_y,
                 rotation: transform.rotation,
                 source: Some(sprite.source_rect()),
-                dest_size: Some(sprite.frame_size.to_scaled(sprite.scale)),
+                dest_size: Some(sprite.frame_size.to_scaled_xy(sprite.scale)),
                 pivot: sprite.pivot,
                 tint: Some(sprite.tint),
             },
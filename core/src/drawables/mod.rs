@@ -7,7 +7,7 @@ pub use sprite::*;
 use std::borrow::{Borrow, BorrowMut};
 
 use crate::math::Size;
-use hecs::World;
+use hecs::{Entity, World};
 
 use crate::result::Result;
 use crate::texture::Texture2D;
@@ -132,6 +132,14 @@ pub enum DrawableKind {
     AnimatedSpriteSet(AnimatedSpriteSet),
 }
 
+// Drawables with the same draw order are drawn in the order their entities were spawned in, so
+// that the order of map objects is kept
+fn sort_by_draw_order(ordered: &mut [(Entity, u32)]) {
+    ordered.sort_by(|&(a_entity, a), &(b_entity, b)| {
+        a.cmp(&b).then(a_entity.id().cmp(&b_entity.id()))
+    });
+}
+
 pub fn draw_drawables(world: &mut World, _delta_time: f32) -> Result<()> {
     let mut ordered = world
         .query_mut::<&Drawable>()
@@ -139,7 +147,7 @@ pub fn draw_drawables(world: &mut World, _delta_time: f32) -> Result<()> {
         .map(|(e, drawable)| (e, drawable.draw_order))
         .collect::<Vec<_>>();
 
-    ordered.sort_by(|&(_, a), &(_, b)| a.cmp(&b));
+    sort_by_draw_order(&mut ordered);
 
     for e in ordered.into_iter().map(|(e, _)| e) {
         let transform = world.get_mut::<Transform>(e).unwrap();
@@ -177,7 +185,7 @@ pub fn debug_draw_drawables(world: &mut World, _delta_time: f32) -> Result<()> {
         .map(|(e, drawable)| (e, drawable.draw_order))
         .collect::<Vec<_>>();
 
-    ordered.sort_by(|&(_, a), &(_, b)| a.cmp(&b));
+    sort_by_draw_order(&mut ordered);
 
     for e in ordered.into_iter().map(|(e, _)| e) {
         let position = world.get_mut::<Transform>(e).map(|t| t.position).unwrap();
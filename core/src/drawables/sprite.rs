@@ -97,12 +97,7 @@ impl Sprite {
                 (params.index as u32 / grid_size.width) as f32 * sprite_size.height,
             );
 
-            Rect::new(
-                position.x,
-                position.y,
-                sprite_size.width,
-                sprite_size.height,
-            )
+            Rect::from_point_size(position, sprite_size)
         };
 
         let tint = params.tint.unwrap_or(colors::WHITE);
@@ -121,8 +116,7 @@ impl Sprite {
     }
 
     pub fn size(&self) -> Size<f32> {
-        let vec = self.source_rect.size() * self.scale;
-        Size::new(vec.x, vec.y)
+        self.source_rect.size() * self.scale
     }
 
     pub fn set_scale(&mut self, scale: f32) {
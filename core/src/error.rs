@@ -214,6 +214,18 @@ impl From<toml::de::Error> for Error {
     }
 }
 
+impl From<rmp_serde::encode::Error> for Error {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        Error::new(ErrorKind::Parsing, err)
+    }
+}
+
+impl From<rmp_serde::decode::Error> for Error {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        Error::new(ErrorKind::Parsing, err)
+    }
+}
+
 /// This will create an error based on the parameters you provide.
 /// It follows the same rules as `format!`, only this takes an optional `ErrorKind`, as its
 /// first argument (before the format string), which will be the kind of `Error` returned.
@@ -1,25 +1,102 @@
 pub use crate::backend_impl::event::*;
 
+use std::any::{type_name, Any};
 use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
 
+use crate::color::{colors, Color};
 use crate::config::Config;
 use crate::prelude::GameState;
 
-pub type DefaultCustomEvent = ();
+pub type DefaultCustomEvent = CustomEvent;
 
-/// This holds all the event types
+/// This holds the payload of a custom event, which can be of any type. This makes it possible for
+/// game code and plugins to push their own events into the same event queue that is used for
+/// state transitions and quitting, without the event type having to be known by the core.
+///
+/// Custom events are created with `Event::custom` and the payload is retrieved by downcasting it
+/// to the type it was created with:
+///
+/// ```
+/// use fishfight_core::event::Event;
+///
+/// #[derive(Debug)]
+/// struct RoundEnded {
+///     winner: u8,
+/// }
+///
+/// // This would typically be passed to `dispatch_event`
+/// let event = Event::custom(RoundEnded { winner: 1 });
+///
+/// // And this is what a consumer of `iter_events` would do with it
+/// if let Event::Custom(payload) = event {
+///     if let Some(RoundEnded { winner }) = payload.downcast_ref::<RoundEnded>() {
+///         assert_eq!(*winner, 1);
+///     }
+/// }
+/// ```
+pub struct CustomEvent {
+    type_name: &'static str,
+    payload: Box<dyn Any>,
+}
+
+impl CustomEvent {
+    pub fn new<T: 'static>(payload: T) -> Self {
+        CustomEvent {
+            type_name: type_name::<T>(),
+            payload: Box::new(payload),
+        }
+    }
+
+    /// Returns `true` if the payload is of type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Returns a reference to the payload if it is of type `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Returns the payload if it is of type `T`. If it is not, the event is returned unchanged, so
+    /// that it can be tried against other types.
+    pub fn downcast<T: 'static>(self) -> Result<T, Self> {
+        let type_name = self.type_name;
+
+        self.payload
+            .downcast()
+            .map(|payload| *payload)
+            .map_err(|payload| CustomEvent { type_name, payload })
+    }
+}
+
+impl Debug for CustomEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomEvent({})", self.type_name)
+    }
+}
+
+/// This holds all the event types. `E` is the type of custom events, which will be
+/// `DefaultCustomEvent` unless a backend is set up with another type.
 pub enum Event<E: 'static + Debug> {
-    /// Custom event
+    /// Custom event, as created by game code. See `Event::custom` and `CustomEvent`.
     Custom(E),
     /// Config changed
     ConfigChanged(Config),
     /// Change game state
     #[cfg(not(feature = "macroquad-backend"))]
-    StateTransition(Rc<RefCell<dyn GameState>>),
+    StateTransition(Rc<RefCell<dyn GameState>>, Transition),
     #[cfg(feature = "macroquad-backend")]
-    StateTransition(Box<dyn GameState>),
-    /// Quit to desktop
+    StateTransition(Box<dyn GameState>, Transition),
+    /// Request to quit to desktop. This gives the registered quit handlers a chance to save state,
+    /// or to defer quitting, before the `Quit` event is acted upon. See `handle_quit_request`.
+    QuitRequested,
+    /// Quit to desktop, without consulting the quit handlers
     Quit,
+    /// A map was saved by the editor. This holds the resolved path of the map file.
+    MapSaved(PathBuf),
+    /// A map was opened in the editor. This holds the resolved path of the map file.
+    MapOpened(PathBuf),
 }
 
 impl<E: 'static + Debug> Event<E> {
@@ -28,6 +105,19 @@ impl<E: 'static + Debug> Event<E> {
     pub fn state_transition<S: 'static + GameState>(state: S) -> Self {
         state_transition(state)
     }
+
+    /// This is the same as `state_transition` but with a transition other than `Transition::Instant`
+    pub fn state_transition_with<S: 'static + GameState>(state: S, transition: Transition) -> Self {
+        state_transition_with(state, transition)
+    }
+}
+
+impl Event<CustomEvent> {
+    /// This creates a custom event with a payload of any type, that can be passed to
+    /// `dispatch_event`. See `CustomEvent` for how to consume it.
+    pub fn custom<T: 'static>(payload: T) -> Self {
+        Event::Custom(CustomEvent::new(payload))
+    }
 }
 
 impl<T: 'static + Debug> Debug for Event<T> {
@@ -35,17 +125,189 @@ impl<T: 'static + Debug> Debug for Event<T> {
         match self {
             Event::Custom(event) => format!("Event::Custom({:?})", &event).fmt(f),
             Event::ConfigChanged(..) => "Event::ConfigChanged(Config)".to_string().fmt(f),
-            Event::StateTransition(..) => "Event::StateTransition".to_string().fmt(f),
+            Event::StateTransition(_, transition) => {
+                format!("Event::StateTransition({:?})", transition).fmt(f)
+            }
+            Event::QuitRequested => "Event::QuitRequested".to_string().fmt(f),
             Event::Quit => "Event::Quit".to_string().fmt(f),
+            Event::MapSaved(path) => format!("Event::MapSaved({:?})", path).fmt(f),
+            Event::MapOpened(path) => format!("Event::MapOpened({:?})", path).fmt(f),
         }
     }
 }
 
+/// This is returned by quit handlers to decide whether quitting should proceed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuitResponse {
+    Proceed,
+    /// Quitting is deferred. The handler is responsible for requesting to quit again, if and when
+    /// it is ready, like after the user has confirmed a prompt.
+    Defer,
+}
+
+pub type QuitHandlerFn = fn() -> QuitResponse;
+
+static mut QUIT_HANDLERS: Vec<(String, QuitHandlerFn)> = Vec::new();
+
+/// This registers a function that will be called when quitting is requested, replacing any
+/// handler previously registered with the same `id`.
+pub fn set_quit_handler(id: &str, f: QuitHandlerFn) {
+    let handlers = unsafe { &mut QUIT_HANDLERS };
+
+    if let Some((_, handler)) = handlers.iter_mut().find(|(other_id, _)| other_id == id) {
+        *handler = f;
+    } else {
+        handlers.push((id.to_string(), f));
+    }
+}
+
+pub fn remove_quit_handler(id: &str) {
+    unsafe { QUIT_HANDLERS.retain(|(other_id, _)| other_id != id) };
+}
+
+/// This calls all the registered quit handlers and returns `true` if none of them deferred
+/// quitting. All handlers are called, even if one defers, so that they all get a chance to save
+/// their state. This should be called by backends when `Event::QuitRequested` is received, or
+/// when the window is closed by the OS.
+pub fn handle_quit_request() -> bool {
+    let handlers = unsafe { QUIT_HANDLERS.clone() };
+
+    handlers
+        .iter()
+        .map(|(_, f)| f())
+        .fold(true, |res, response| {
+            res && response == QuitResponse::Proceed
+        })
+}
+
 /// This allow construction of state transition events without worrying about the different
 /// types used by the two backends
 pub fn state_transition<E: 'static + Debug, S: 'static + GameState>(state: S) -> Event<E> {
+    state_transition_with(state, Transition::Instant)
+}
+
+/// This is the same as `state_transition` but with a transition other than `Transition::Instant`
+pub fn state_transition_with<E: 'static + Debug, S: 'static + GameState>(
+    state: S,
+    transition: Transition,
+) -> Event<E> {
     #[cfg(not(feature = "macroquad-backend"))]
-    return Event::StateTransition(Rc::new(RefCell::new(state)));
+    return Event::StateTransition(Rc::new(RefCell::new(state)), transition);
     #[cfg(feature = "macroquad-backend")]
-    return Event::StateTransition(Box::new(state));
+    return Event::StateTransition(Box::new(state), transition);
+}
+
+/// This describes how a change of game state is presented
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Transition {
+    /// Change state immediately, without any effect
+    Instant,
+    /// Fade out to `color` over the first half of `duration`, change state and then fade back in
+    /// over the second half. `duration` is in seconds.
+    Fade { duration: f32, color: Color },
+}
+
+impl Transition {
+    pub const DEFAULT_FADE_DURATION: f32 = 0.5;
+
+    /// Fade through black over `duration` seconds
+    pub fn fade(duration: f32) -> Self {
+        Transition::Fade {
+            duration,
+            color: colors::BLACK,
+        }
+    }
+
+    pub fn duration(&self) -> f32 {
+        match self {
+            Transition::Instant => 0.0,
+            Transition::Fade { duration, .. } => *duration,
+        }
+    }
+
+    /// This returns the color of the overlay that should be drawn on top of the game state,
+    /// `elapsed` seconds into the transition, if any
+    pub fn get_overlay_color(&self, elapsed: f32) -> Option<Color> {
+        match *self {
+            Transition::Instant => None,
+            Transition::Fade { duration, color } => {
+                let half = duration / 2.0;
+
+                if half <= 0.0 {
+                    return None;
+                }
+
+                let factor = if elapsed < half {
+                    elapsed / half
+                } else {
+                    1.0 - (elapsed - half) / half
+                };
+
+                Some(Color {
+                    alpha: color.alpha * factor.clamp(0.0, 1.0),
+                    ..color
+                })
+            }
+        }
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition::Instant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proceed() -> QuitResponse {
+        QuitResponse::Proceed
+    }
+
+    fn defer() -> QuitResponse {
+        QuitResponse::Defer
+    }
+
+    #[test]
+    fn test_quit_handlers() {
+        assert!(handle_quit_request());
+
+        set_quit_handler("a", proceed);
+        set_quit_handler("b", defer);
+        assert!(!handle_quit_request());
+
+        set_quit_handler("b", proceed);
+        assert!(handle_quit_request());
+
+        set_quit_handler("a", defer);
+        remove_quit_handler("a");
+        assert!(handle_quit_request());
+
+        remove_quit_handler("b");
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestPayload(u32);
+
+    #[test]
+    fn test_custom_event_payload() {
+        let event = Event::custom(TestPayload(3));
+        assert_eq!(
+            format!("{:?}", event),
+            format!("Event::Custom(CustomEvent({}))", type_name::<TestPayload>())
+        );
+
+        match event {
+            Event::Custom(payload) => {
+                assert!(payload.is::<TestPayload>());
+                assert!(payload.downcast_ref::<u32>().is_none());
+
+                let payload = payload.downcast::<u32>().unwrap_err();
+                assert_eq!(payload.downcast::<TestPayload>().unwrap(), TestPayload(3));
+            }
+            _ => panic!("Expected a custom event"),
+        }
+    }
 }
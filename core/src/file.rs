@@ -36,3 +36,120 @@ impl Debug for Error {
 }
 
 impl std::error::Error for Error {}
+
+/// This returns the path of the temporary file that is used when writing to `path` atomically.
+/// It is placed in the same directory as `path` so that it can be renamed over it.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_atomic_temp_path<P: AsRef<Path>>(path: P) -> std::path::PathBuf {
+    let path = path.as_ref();
+
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+
+    path.with_file_name(file_name)
+}
+
+/// This writes `data` to a temporary file next to `path` and then renames it over `path`, so that
+/// the file at `path` is either left as it was or completely replaced, even if the process exits
+/// in the middle of the write. If the write fails, the partially written temporary file is
+/// removed. If the rename fails, the temporary file is kept, so that the data can still be
+/// recovered, and its path is included in the returned error.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_to_file_atomic_sync<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<(), Error> {
+    use std::io::Write;
+
+    let path = path.as_ref();
+    let temp_path = get_atomic_temp_path(path);
+
+    {
+        let mut file =
+            std::fs::File::create(&temp_path).map_err(|err| Error::new(&temp_path, err))?;
+
+        if let Err(err) = file.write_all(data).and_then(|_| file.sync_all()) {
+            drop(file);
+            let _ = std::fs::remove_file(&temp_path);
+
+            return Err(Error::new(&temp_path, err));
+        }
+    }
+
+    std::fs::rename(&temp_path, path).map_err(|err| {
+        let message = format!(
+            "Unable to replace the file ({}). The data was kept in '{}'",
+            err,
+            temp_path.display()
+        );
+
+        Error::new(path, message)
+    })
+}
+
+/// This opens the file browser of the OS at the directory that contains `path`, with the file
+/// selected, where the platform supports that. This is not available on wasm.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reveal_in_file_browser<P: AsRef<Path>>(path: P) -> Result<(), Error> {
+    use std::process::Command;
+
+    let path = path.as_ref();
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    };
+
+    // Most Linux file managers can not select a file, so the containing directory is opened
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(path.parent().unwrap_or(path));
+        command
+    };
+
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|err| Error::new(path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_to_file_atomic() {
+        // The directory is unique to this process, so that concurrent test runs do not interfere
+        let dir = std::env::temp_dir().join(format!(
+            "fishfight_test_write_to_file_atomic_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("map.json");
+        std::fs::write(&path, "old").unwrap();
+
+        write_to_file_atomic_sync(&path, b"new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert!(!get_atomic_temp_path(&path).exists());
+
+        // Renaming a file over a directory fails, so the temporary file should be kept
+        let dir_path = dir.join("dir.json");
+        std::fs::create_dir_all(&dir_path).unwrap();
+
+        assert!(write_to_file_atomic_sync(&dir_path, b"data").is_err());
+
+        let temp_path = get_atomic_temp_path(&dir_path);
+        assert_eq!(std::fs::read_to_string(&temp_path).unwrap(), "data");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
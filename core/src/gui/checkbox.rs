@@ -17,6 +17,7 @@ pub struct Checkbox {
 
 impl Checkbox {
     const ALLOW_CLICK_ON_LABEL: bool = true;
+    const INDETERMINATE_MARKER: &'static str = "-";
 
     pub fn new<P: Into<Option<Vec2>>>(id: Id, position: P, label: &str) -> Self {
         Checkbox {
@@ -41,9 +42,35 @@ impl Checkbox {
     }
 
     pub fn ui(&self, ui: &mut Ui, value: &mut bool) {
+        if self.draw(ui, *value, "") {
+            *value = !*value;
+        }
+    }
+
+    /// This draws a checkbox that can also be in an indeterminate state, represented by `None`,
+    /// like when it shows a value that is mixed across a selection. Clicking it cycles through
+    /// checked, unchecked and indeterminate, in that order.
+    pub fn ui_tri_state(&self, ui: &mut Ui, value: &mut Option<bool>) {
+        let is_clicked = match value {
+            Some(is_checked) => self.draw(ui, *is_checked, ""),
+            None => self.draw(ui, false, Self::INDETERMINATE_MARKER),
+        };
+
+        if is_clicked {
+            *value = match value {
+                Some(true) => Some(false),
+                Some(false) => None,
+                None => Some(true),
+            };
+        }
+    }
+
+    // This draws the checkbox, with `marker` drawn inside the box, and returns `true` if either
+    // the box or, if allowed, the label was clicked
+    fn draw(&self, ui: &mut Ui, is_checked: bool, marker: &str) -> bool {
         let gui_theme = get_gui_theme();
 
-        if *value {
+        if is_checked {
             ui.push_skin(&gui_theme.checkbox_selected);
         } else {
             ui.push_skin(&gui_theme.checkbox);
@@ -70,6 +97,8 @@ impl Checkbox {
             group = group.position(position);
         }
 
+        let mut is_clicked = false;
+
         group.ui(ui, |ui| {
             let mut checkbox_position = vec2(0.0, (label_size.y - element_height) / 2.0);
             let mut label_position = vec2(checkbox_size.x + ELEMENT_MARGIN, 0.0);
@@ -79,26 +108,22 @@ impl Checkbox {
                 label_position.x += self.margin;
             }
 
-            let checkbox = widgets::Button::new("")
+            let checkbox = widgets::Button::new(marker)
                 .position(checkbox_position)
                 .size(checkbox_size)
                 .ui(ui);
 
-            if checkbox {
-                *value = !*value;
-            }
-
             ui.push_skin(&gui_theme.label_button);
             let label_btn = widgets::Button::new(self.label.deref())
                 .position(label_position)
                 .ui(ui);
             ui.pop_skin();
 
-            if label_btn && self.allow_click_on_label {
-                *value = !*value;
-            }
+            is_clicked = checkbox || (label_btn && self.allow_click_on_label);
         });
 
         ui.pop_skin();
+
+        is_clicked
     }
 }
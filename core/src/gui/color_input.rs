@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use macroquad::hash;
+
+use crate::color::Color;
+use crate::gui::{widgets, Id, Ui};
+use crate::math::Vec2;
+
+use super::NumberInput;
+
+// This is the hex text of each color input, kept between frames, so that incomplete input is
+// allowed while typing. The color it was last drawn with is kept with it, so that colors that
+// are changed from outside the input are shown.
+static mut COLOR_INPUT_STATES: Option<HashMap<Id, (String, Color)>> = None;
+
+fn color_input_states() -> &'static mut HashMap<Id, (String, Color)> {
+    unsafe { COLOR_INPUT_STATES.get_or_insert_with(HashMap::new) }
+}
+
+/// An input for colors, with a number input for each component, clamped to between 0 and 1, and
+/// a text input for the color in hex, on the form `RRGGBBAA`. Changes to either are reflected in
+/// the other.
+pub struct ColorInput {
+    id: Id,
+    component_size: Option<Vec2>,
+    hex_size: Option<Vec2>,
+}
+
+impl ColorInput {
+    pub fn new(id: Id) -> Self {
+        ColorInput {
+            id,
+            component_size: None,
+            hex_size: None,
+        }
+    }
+
+    /// This sets the size of each of the component inputs
+    pub fn with_component_size(self, size: Vec2) -> Self {
+        ColorInput {
+            component_size: Some(size),
+            ..self
+        }
+    }
+
+    /// This sets the size of the hex input
+    pub fn with_hex_size(self, size: Vec2) -> Self {
+        ColorInput {
+            hex_size: Some(size),
+            ..self
+        }
+    }
+
+    /// This returns `true` if `color` was changed by the input.
+    pub fn ui(&self, ui: &mut Ui, color: &mut Color) -> bool {
+        let component_inputs = [
+            (hash!(self.id, "r"), "r", &mut color.red),
+            (hash!(self.id, "g"), "g", &mut color.green),
+            (hash!(self.id, "b"), "b", &mut color.blue),
+            (hash!(self.id, "a"), "a", &mut color.alpha),
+        ];
+
+        let mut is_changed = false;
+
+        for (input_id, label, value) in component_inputs {
+            let mut input = NumberInput::new(input_id).with_ratio(1.0).with_label(label);
+
+            if let Some(size) = self.component_size {
+                input = input.with_size(size);
+            }
+
+            if input.ui(ui, value) {
+                *value = value.clamp(0.0, 1.0);
+                is_changed = true;
+            }
+        }
+
+        let (hex, last_color) = color_input_states()
+            .entry(self.id)
+            .or_insert_with(|| (color.to_hex_alpha(), *color));
+
+        if *last_color != *color {
+            *hex = color.to_hex_alpha();
+        }
+
+        let old_hex = hex.clone();
+
+        let mut input = widgets::InputText::new(hash!(self.id, "hex"))
+            .ratio(1.0)
+            .label("hex");
+
+        if let Some(size) = self.hex_size {
+            input = input.size(size);
+        }
+
+        input.ui(ui, hex);
+
+        if *hex != old_hex {
+            if let Some(new_color) = Color::try_from_hex(hex) {
+                *color = new_color;
+                is_changed = true;
+            }
+        }
+
+        *last_color = *color;
+
+        is_changed
+    }
+}
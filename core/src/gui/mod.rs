@@ -9,9 +9,15 @@ pub mod theme;
 #[cfg(feature = "macroquad-backend")]
 pub mod checkbox;
 
+#[cfg(feature = "macroquad-backend")]
+pub mod color_input;
+
 #[cfg(feature = "macroquad-backend")]
 pub mod menu;
 
+#[cfg(feature = "macroquad-backend")]
+pub mod number_input;
+
 pub mod background;
 
 #[cfg(feature = "macroquad-backend")]
@@ -27,10 +33,14 @@ pub use theme::{
 #[cfg(feature = "macroquad-backend")]
 pub use checkbox::*;
 #[cfg(feature = "macroquad-backend")]
+pub use color_input::*;
+#[cfg(feature = "macroquad-backend")]
 pub use combobox::*;
 #[cfg(feature = "macroquad-backend")]
 pub use menu::*;
 #[cfg(feature = "macroquad-backend")]
+pub use number_input::*;
+#[cfg(feature = "macroquad-backend")]
 pub use panel::*;
 
 pub const ELEMENT_MARGIN: f32 = 8.0;
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::gui::{widgets, Id, Ui};
+
+use crate::math::Vec2;
+
+use crate::gui::theme::get_gui_theme;
+
+struct NumberInputState {
+    text: String,
+    // This is the value, as a string, after the input was last drawn, used to detect values that
+    // are changed from outside the input
+    value: String,
+}
+
+static mut NUMBER_INPUT_STATES: Option<HashMap<Id, NumberInputState>> = None;
+
+fn number_input_states() -> &'static mut HashMap<Id, NumberInputState> {
+    unsafe { NUMBER_INPUT_STATES.get_or_insert_with(HashMap::new) }
+}
+
+/// A text input for numeric values. The text is kept in a buffer, between frames, so incomplete
+/// input, like a lone minus sign, can be typed. The value is only updated when the text parses,
+/// so invalid input is shown in an error state, without losing the last valid value.
+pub struct NumberInput {
+    id: Id,
+    label: Option<String>,
+    size: Option<Vec2>,
+    ratio: f32,
+}
+
+impl NumberInput {
+    const DEFAULT_RATIO: f32 = 0.4;
+
+    pub fn new(id: Id) -> Self {
+        NumberInput {
+            id,
+            label: None,
+            size: None,
+            ratio: Self::DEFAULT_RATIO,
+        }
+    }
+
+    pub fn with_label(self, label: &str) -> Self {
+        NumberInput {
+            label: Some(label.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_size(self, size: Vec2) -> Self {
+        NumberInput {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn with_ratio(self, ratio: f32) -> Self {
+        NumberInput { ratio, ..self }
+    }
+
+    /// This returns `true` if `value` was changed by the input.
+    pub fn ui<T: Copy + PartialEq + Display + FromStr>(&self, ui: &mut Ui, value: &mut T) -> bool {
+        let value_str = value.to_string();
+
+        let state = number_input_states()
+            .entry(self.id)
+            .or_insert_with(|| NumberInputState {
+                text: value_str.clone(),
+                value: value_str.clone(),
+            });
+
+        if state.value != value_str {
+            state.text = value_str;
+        }
+
+        let is_valid = state.text.parse::<T>().is_ok();
+
+        if !is_valid {
+            let gui_theme = get_gui_theme();
+            ui.push_skin(&gui_theme.editbox_invalid);
+        }
+
+        let mut input = widgets::InputText::new(self.id).ratio(self.ratio);
+
+        if let Some(size) = self.size {
+            input = input.size(size);
+        }
+
+        if let Some(label) = &self.label {
+            input = input.label(label);
+        }
+
+        input.ui(ui, &mut state.text);
+
+        if !is_valid {
+            ui.pop_skin();
+        }
+
+        let mut is_changed = false;
+
+        if let Ok(new_value) = state.text.parse::<T>() {
+            if new_value != *value {
+                *value = new_value;
+                is_changed = true;
+            }
+        }
+
+        state.value = value.to_string();
+
+        is_changed
+    }
+}
+
+/// Draw a `NumberInput` with the specified label. This returns `true` if `value` was changed.
+pub fn number_input<T: Copy + PartialEq + Display + FromStr>(
+    ui: &mut Ui,
+    id: Id,
+    label: &str,
+    value: &mut T,
+) -> bool {
+    NumberInput::new(id).with_label(label).ui(ui, value)
+}
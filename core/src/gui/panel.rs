@@ -14,6 +14,7 @@ use crate::gui::{widgets, Id, Ui};
 use crate::math::{vec2, UVec2, Vec2};
 use crate::render::draw_rectangle;
 use crate::texture::get_texture;
+use crate::viewport::to_window_position;
 
 pub struct Panel {
     id: Id,
@@ -58,6 +59,8 @@ impl Panel {
     /// the inner size of the panel as arguments. The inner size will be the size of the window,
     /// minus the window margins.
     pub fn ui<F: FnOnce(&mut Ui, Vec2)>(&self, ui: &mut Ui, f: F) {
+        let position = to_window_position(self.position);
+
         {
             let gui_theme = get_gui_theme();
 
@@ -65,8 +68,8 @@ impl Panel {
                 ui.push_skin(&gui_theme.panel_no_bg);
 
                 draw_rectangle(
-                    self.position.x + Self::BG_OFFSET,
-                    self.position.y + Self::BG_OFFSET,
+                    position.x + Self::BG_OFFSET,
+                    position.y + Self::BG_OFFSET,
                     self.size.x - (Self::BG_OFFSET * 2.0),
                     self.size.y - (Self::BG_OFFSET * 2.0),
                     background_color,
@@ -77,13 +80,13 @@ impl Panel {
         }
 
         let _ = widgets::Button::new("")
-            .position(self.position)
+            .position(position)
             .size(self.size)
             .ui(ui);
 
         let window_margins = vec2(WINDOW_MARGIN_H, WINDOW_MARGIN_V);
 
-        let mut content_position = self.position + window_margins;
+        let mut content_position = position + window_margins;
         let mut content_size = self.size - (window_margins * 2.0);
 
         if let Some(title) = &self.title {
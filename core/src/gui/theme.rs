@@ -117,6 +117,7 @@ const WINDOW_BORDER_IMAGE_ID: &str = "window_border";
 pub struct GuiTheme {
     pub default: Skin,
     pub button_disabled: Skin,
+    pub editbox_invalid: Skin,
     pub window_header: Skin,
     pub checkbox: Skin,
     pub checkbox_selected: Skin,
@@ -345,6 +346,33 @@ impl GuiTheme {
             }
         };
 
+        let editbox_invalid = {
+            let editbox_style = root_ui()
+                .style_builder()
+                .background(editbox_background.deref().deref().clone())
+                .background_clicked(editbox_background_clicked.deref().deref().clone())
+                .background_margin(RectOffset::new(
+                    EDITBOX_BG_MARGIN_H,
+                    EDITBOX_BG_MARGIN_H,
+                    EDITBOX_BG_MARGIN_V,
+                    EDITBOX_BG_MARGIN_V,
+                ))
+                .margin(RectOffset::new(
+                    EDITBOX_MARGIN_H - EDITBOX_BG_MARGIN_H,
+                    EDITBOX_MARGIN_H - EDITBOX_BG_MARGIN_H,
+                    EDITBOX_MARGIN_V - EDITBOX_BG_MARGIN_V,
+                    EDITBOX_MARGIN_V - EDITBOX_BG_MARGIN_V,
+                ))
+                .text_color(color_u8!(200, 48, 48, 255).into())
+                .font_size(FONT_SIZE as u16)
+                .build();
+
+            Skin {
+                editbox_style,
+                ..default.clone()
+            }
+        };
+
         let window_header = {
             let label_style = root_ui()
                 .style_builder()
@@ -892,6 +920,7 @@ impl GuiTheme {
         GuiTheme {
             default,
             button_disabled,
+            editbox_invalid,
             window_header,
             checkbox,
             checkbox_selected,
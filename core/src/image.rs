@@ -58,6 +58,12 @@ impl Image {
     }
 }
 
+impl From<ImageImpl> for Image {
+    fn from(image_impl: ImageImpl) -> Self {
+        Image(image_impl)
+    }
+}
+
 static mut IMAGES: Option<HashMap<String, Image>> = None;
 
 fn image_map() -> &'static mut HashMap<String, Image> {
@@ -2,6 +2,8 @@ mod gamepad;
 
 pub use gamepad::*;
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use crate::error::ErrorKind;
@@ -204,6 +206,102 @@ pub enum KeyCode {
     Unknown,
 }
 
+impl KeyCode {
+    /// This returns `true` for modifier keys, which can not be bound on their own
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::LeftShift
+                | KeyCode::LeftControl
+                | KeyCode::LeftAlt
+                | KeyCode::LeftSuper
+                | KeyCode::RightShift
+                | KeyCode::RightControl
+                | KeyCode::RightAlt
+                | KeyCode::RightSuper
+        )
+    }
+}
+
+/// A key binding, consisting of a key and the modifier keys that must be held when it is pressed.
+/// Modifiers must match exactly, so `Ctrl+Z` and `Ctrl+Shift+Z` can be bound to different actions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keybind {
+    pub key: KeyCode,
+    #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
+    pub ctrl: bool,
+    #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
+    pub shift: bool,
+}
+
+impl Keybind {
+    pub const fn new(key: KeyCode) -> Self {
+        Keybind {
+            key,
+            ctrl: false,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Keybind {
+            key,
+            ctrl: true,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl_shift(key: KeyCode) -> Self {
+        Keybind {
+            key,
+            ctrl: true,
+            shift: true,
+        }
+    }
+
+    /// This creates a binding for `key`, with the modifier keys that are currently held down
+    pub fn with_held_modifiers(key: KeyCode) -> Self {
+        Keybind {
+            key,
+            ctrl: is_key_down(KeyCode::LeftControl),
+            shift: is_key_down(KeyCode::LeftShift),
+        }
+    }
+
+    /// This returns `true` if the key was pressed this frame, with the modifiers of the binding
+    pub fn is_pressed(&self) -> bool {
+        is_key_pressed(self.key)
+            && is_key_down(KeyCode::LeftControl) == self.ctrl
+            && is_key_down(KeyCode::LeftShift) == self.shift
+    }
+}
+
+impl From<KeyCode> for Keybind {
+    fn from(key: KeyCode) -> Self {
+        Keybind::new(key)
+    }
+}
+
+impl fmt::Display for Keybind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+
+        let key = format!("{:?}", self.key);
+
+        // Number keys are named `Key0` through `Key9`
+        match key.strip_prefix("Key") {
+            Some(number) if !number.is_empty() => write!(f, "{}", number),
+            _ => write!(f, "{}", key),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyMapping {
     primary: KeyCode,
@@ -26,6 +26,7 @@ pub mod prelude;
 pub mod render;
 pub mod resources;
 pub mod result;
+pub mod rng;
 pub mod state;
 pub mod storage;
 pub mod text;
@@ -5,7 +5,7 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::drawables::{AnimatedSpriteMetadata, Drawable};
+use crate::drawables::{AnimatedSpriteMetadata, AnimatedSpriteParams, Drawable};
 use crate::file::read_from_file;
 use crate::math::Vec2;
 use crate::parsing::deserialize_bytes_by_extension;
@@ -21,6 +21,14 @@ pub struct DecorationMetadata {
     pub sprite: AnimatedSpriteMetadata,
 }
 
+impl DecorationMetadata {
+    /// This returns the scale of the decoration sprite, which is uniform, as the metadata only
+    /// holds a single scale factor. Map objects can override this with a non-uniform scale.
+    pub fn get_scale(&self) -> Vec2 {
+        Vec2::splat(self.sprite.scale.unwrap_or(1.0))
+    }
+}
+
 pub struct Decoration {
     pub id: String,
 }
@@ -31,7 +39,13 @@ impl Decoration {
     }
 }
 
-pub fn spawn_decoration(world: &mut World, position: Vec2, meta: DecorationMetadata) -> Entity {
+/// This spawns a decoration. If `scale` is `None`, the scale from the metadata is used.
+pub fn spawn_decoration(
+    world: &mut World,
+    position: Vec2,
+    meta: DecorationMetadata,
+    scale: Option<Vec2>,
+) -> Entity {
     let texture = get_texture(&meta.sprite.texture_id);
 
     let animations = meta
@@ -42,6 +56,11 @@ pub fn spawn_decoration(world: &mut World, position: Vec2, meta: DecorationMetad
         .map(|m| m.into())
         .collect::<Vec<_>>();
 
+    let mut params: AnimatedSpriteParams = meta.sprite.clone().into();
+    if let Some(scale) = scale {
+        params.scale = scale;
+    }
+
     world.spawn((
         Decoration::new(&meta.id),
         Transform::from(position),
@@ -50,7 +69,7 @@ pub fn spawn_decoration(world: &mut World, position: Vec2, meta: DecorationMetad
             texture,
             texture.frame_size(),
             animations.as_slice(),
-            meta.sprite.clone().into(),
+            params,
         ),
     ))
 }
@@ -67,10 +86,17 @@ pub fn get_decoration(id: &str) -> &DecorationMetadata {
     try_get_decoration(id).unwrap()
 }
 
-pub fn iter_decoration() -> Iter<'static, String, DecorationMetadata> {
+/// This returns an iterator over the ids and metadata of all loaded decoration, including any
+/// added by mods
+pub fn iter_decorations() -> Iter<'static, String, DecorationMetadata> {
     unsafe { DECORATION.get_or_insert_with(HashMap::new) }.iter()
 }
 
+#[deprecated(note = "use `iter_decorations` instead")]
+pub fn iter_decoration() -> Iter<'static, String, DecorationMetadata> {
+    iter_decorations()
+}
+
 pub async fn load_decoration<P: AsRef<Path>>(
     path: P,
     ext: &str,
@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use crate::error::{Error, ErrorKind};
+use crate::parsing::{deserialize_bytes_by_extension, serialize_bytes_by_extension};
+use crate::result::Result;
+
+use super::{Map, MAP_EXPORTS_BINARY_EXTENSION, MAP_EXPORTS_EXTENSION};
+
+/// A file format that maps can be loaded from and saved in. Formats are registered with
+/// `add_map_format` and are looked up by the extension of the map file, so custom formats can be
+/// added without changes to the code that loads and saves maps.
+pub trait MapFormat {
+    /// The file extension of the format, without the leading dot
+    fn extension(&self) -> &str;
+
+    fn load(&self, bytes: &[u8]) -> Result<Map>;
+
+    fn save(&self, map: &Map) -> Result<Vec<u8>>;
+}
+
+/// The default format, which is human-editable
+pub struct JsonMapFormat;
+
+impl MapFormat for JsonMapFormat {
+    fn extension(&self) -> &str {
+        MAP_EXPORTS_EXTENSION
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<Map> {
+        deserialize_bytes_by_extension(self.extension(), bytes)
+    }
+
+    fn save(&self, map: &Map) -> Result<Vec<u8>> {
+        serialize_bytes_by_extension(self.extension(), map)
+    }
+}
+
+/// A binary format, which produces smaller files that load faster, for large maps
+pub struct BinaryMapFormat;
+
+impl MapFormat for BinaryMapFormat {
+    fn extension(&self) -> &str {
+        MAP_EXPORTS_BINARY_EXTENSION
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<Map> {
+        deserialize_bytes_by_extension(self.extension(), bytes)
+    }
+
+    fn save(&self, map: &Map) -> Result<Vec<u8>> {
+        serialize_bytes_by_extension(self.extension(), map)
+    }
+}
+
+/// A registry of map formats, keyed by extension. Formats are leaked when they are added, so that
+/// references to them stay valid for the rest of the program, even if they are replaced.
+pub struct MapFormatRegistry {
+    formats: Vec<&'static dyn MapFormat>,
+}
+
+impl MapFormatRegistry {
+    /// This creates a registry that holds the built-in formats
+    pub fn new() -> Self {
+        let mut res = MapFormatRegistry {
+            formats: Vec::new(),
+        };
+
+        res.add(JsonMapFormat);
+        res.add(BinaryMapFormat);
+
+        res
+    }
+
+    /// Adding a format with the same extension as one that is already registered will replace
+    /// the existing format.
+    pub fn add<F: MapFormat + 'static>(&mut self, format: F) {
+        let format: &'static dyn MapFormat = Box::leak(Box::new(format));
+
+        match self
+            .formats
+            .iter()
+            .position(|other| other.extension() == format.extension())
+        {
+            Some(i) => self.formats[i] = format,
+            None => self.formats.push(format),
+        }
+    }
+
+    pub fn get(&self, extension: &str) -> Option<&'static dyn MapFormat> {
+        self.formats
+            .iter()
+            .find(|format| format.extension() == extension)
+            .copied()
+    }
+
+    /// This returns the extensions of all registered formats, in the order they were registered
+    pub fn extensions(&self) -> Vec<String> {
+        self.formats
+            .iter()
+            .map(|format| format.extension().to_string())
+            .collect()
+    }
+}
+
+impl Default for MapFormatRegistry {
+    fn default() -> Self {
+        MapFormatRegistry::new()
+    }
+}
+
+static mut MAP_FORMATS: Option<MapFormatRegistry> = None;
+
+unsafe fn get_map_format_directory() -> &'static mut MapFormatRegistry {
+    MAP_FORMATS.get_or_insert_with(MapFormatRegistry::new)
+}
+
+/// This registers a map format. Registering a format with the same extension as one that is
+/// already registered, including the built-in formats, will replace the existing format.
+pub fn add_map_format<F: MapFormat + 'static>(format: F) {
+    unsafe { get_map_format_directory() }.add(format);
+}
+
+pub fn try_get_map_format(extension: &str) -> Option<&'static dyn MapFormat> {
+    unsafe { get_map_format_directory() }.get(extension)
+}
+
+/// This returns the format that is registered for the extension of `path`, if any
+pub fn get_map_format_of_path<P: AsRef<Path>>(path: P) -> Option<&'static dyn MapFormat> {
+    let extension = path.as_ref().extension()?.to_str()?;
+    try_get_map_format(extension)
+}
+
+/// This returns the format that is used when a map is saved to a path without a registered
+/// extension
+pub fn default_map_format() -> &'static dyn MapFormat {
+    try_get_map_format(MAP_EXPORTS_EXTENSION).unwrap()
+}
+
+/// This returns the extensions of all registered formats, in the order they were registered
+pub fn map_format_extensions() -> Vec<String> {
+    unsafe { get_map_format_directory() }.extensions()
+}
+
+/// This returns the error used when there is no format registered for the extension of `path`
+pub fn unsupported_map_format_error<P: AsRef<Path>>(path: P) -> Error {
+    Error::new_message(
+        ErrorKind::Parsing,
+        &format!(
+            "Unsupported map file '{}', expected one of the extensions: {}",
+            path.as_ref().display(),
+            map_format_extensions().join(", ")
+        ),
+    )
+}
@@ -1,13 +1,21 @@
 use std::borrow::BorrowMut;
+use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::slice::Iter;
-use std::{collections::HashMap, path::Path};
 
 use serde::{Deserialize, Serialize};
 
 mod decoration;
+mod format;
+mod version;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 pub use decoration::*;
+pub use format::*;
+pub use version::*;
 
 use crate::error::ErrorKind;
 use crate::prelude::*;
@@ -31,9 +39,91 @@ pub struct MapBackgroundLayer {
     pub offset: Vec2,
 }
 
+/// A position where players can be spawned. If `is_facing_left` is set, players will be facing
+/// left when spawned, in stead of right. The `tag` can be used by match setup to assign spawn
+/// points to specific teams.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "parsing::MapSpawnPointDef", from = "parsing::MapSpawnPointDef")]
+pub struct MapSpawnPoint {
+    pub position: Vec2,
+    pub is_facing_left: bool,
+    pub tag: MapSpawnPointTag,
+}
+
+impl MapSpawnPoint {
+    pub fn new(position: Vec2) -> Self {
+        MapSpawnPoint {
+            position,
+            is_facing_left: false,
+            tag: MapSpawnPointTag::Free,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MapSpawnPointTag {
+    Free,
+    TeamA,
+    TeamB,
+}
+
+impl MapSpawnPointTag {
+    pub fn options() -> &'static [&'static str] {
+        &["Free", "Team A", "Team B"]
+    }
+
+    pub fn is_free(&self) -> bool {
+        *self == MapSpawnPointTag::Free
+    }
+}
+
+#[cfg(feature = "macroquad-backend")]
+impl ComboBoxValue for MapSpawnPointTag {
+    fn get_index(&self) -> usize {
+        match self {
+            Self::Free => 0,
+            Self::TeamA => 1,
+            Self::TeamB => 2,
+        }
+    }
+
+    fn set_index(&mut self, index: usize) {
+        *self = match index {
+            0 => Self::Free,
+            1 => Self::TeamA,
+            2 => Self::TeamB,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_options(&self) -> Vec<String> {
+        Self::options().iter().map(|s| s.to_string()).collect()
+    }
+}
+
+impl Default for MapSpawnPointTag {
+    fn default() -> Self {
+        MapSpawnPointTag::Free
+    }
+}
+
+impl From<Vec2> for MapSpawnPoint {
+    fn from(position: Vec2) -> Self {
+        MapSpawnPoint::new(position)
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(into = "parsing::MapDef", from = "parsing::MapDef")]
 pub struct Map {
+    /// The version of the map format that the map was saved with. See `MAP_FORMAT_VERSION`.
+    #[serde(default)]
+    pub format_version: u32,
+    /// A checksum of the contents of the map file, used to detect maps that have been edited by
+    /// hand. This is only set on save.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
     #[serde(
         default = "Map::default_background_color",
         with = "crate::parsing::ColorDef"
@@ -41,6 +131,17 @@ pub struct Map {
     pub background_color: Color,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub background_layers: Vec<MapBackgroundLayer>,
+    /// This is multiplied with everything the map draws, its background included, to give the
+    /// whole map a uniform look. It is folded into the tint of each draw, so it costs nothing
+    /// extra to draw.
+    #[serde(default = "Map::default_ambient_color")]
+    pub ambient_color: Color,
+    /// The id of the music that is played while the map is loaded. If this is `None`, the
+    /// default music is played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_id: Option<String>,
+    /// The position of the map in world space. Object and spawn point positions are relative to
+    /// this, so changing it moves them along with the tiles.
     #[serde(with = "crate::parsing::def_vec2")]
     pub world_offset: Vec2,
     pub grid_size: Size<u32>,
@@ -51,8 +152,8 @@ pub struct Map {
     pub draw_order: Vec<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub properties: HashMap<String, MapProperty>,
-    #[serde(default, with = "crate::parsing::vec2_vec")]
-    pub spawn_points: Vec<Vec2>,
+    #[serde(default)]
+    pub spawn_points: Vec<MapSpawnPoint>,
 }
 
 impl Map {
@@ -67,8 +168,12 @@ impl Map {
 
     pub fn new(tile_size: Vec2, grid_size: UVec2) -> Self {
         Map {
+            format_version: MAP_FORMAT_VERSION,
+            checksum: None,
             background_color: Self::default_background_color(),
             background_layers: Vec::new(),
+            ambient_color: Self::default_ambient_color(),
+            music_id: None,
             world_offset: Vec2::ZERO,
             grid_size: grid_size.into(),
             tile_size: tile_size.into(),
@@ -81,13 +186,9 @@ impl Map {
     }
 
     pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let extension = path.as_ref().extension().unwrap().to_str().unwrap();
-
         let bytes = read_from_file(&path).await?;
 
-        let map = deserialize_bytes_by_extension(extension, &bytes).unwrap();
-
-        Ok(map)
+        deserialize_map(path, &bytes)
     }
 
     pub async fn load_tiled<P: AsRef<Path>>(path: P, export_path: Option<P>) -> Result<Self> {
@@ -113,12 +214,7 @@ impl Map {
 
     pub fn contains(&self, position: Vec2) -> bool {
         let map_size = Size::from(self.grid_size.as_uvec2().as_vec2() * self.tile_size.as_vec2());
-        let rect = Rect::new(
-            self.world_offset.x,
-            self.world_offset.y,
-            map_size.width,
-            map_size.height,
-        );
+        let rect = Rect::from_point_size(self.world_offset, map_size);
         rect.contains(position)
     }
 
@@ -139,10 +235,167 @@ impl Map {
         uvec2(x, y)
     }
 
+    /// This returns the inclusive range of tile coords, as `(min, max)`, that are covered by the
+    /// world space `rect`, clamped to the grid. A tile is covered if any part of it is inside
+    /// `rect`, so a rect that ends exactly on the edge of a tile does not cover the next one.
+    pub fn world_to_tile_rect(&self, rect: Rect) -> (UVec2, UVec2) {
+        let min = self.to_coords(rect.point());
+
+        let max = {
+            let right = ((rect.right() - self.world_offset.x) / self.tile_size.width).ceil() - 1.0;
+            let bottom =
+                ((rect.bottom() - self.world_offset.y) / self.tile_size.height).ceil() - 1.0;
+
+            let x = (right.max(0.0) as u32).clamp(min.x, self.grid_size.width - 1);
+            let y = (bottom.max(0.0) as u32).clamp(min.y, self.grid_size.height - 1);
+
+            uvec2(x, y)
+        };
+
+        (min, max)
+    }
+
     pub fn to_index(&self, coords: UVec2) -> usize {
         ((coords.y * self.grid_size.width) + coords.x) as usize
     }
 
+    /// This returns the tile index of `coords`, or `None` if they are outside of the grid.
+    pub fn try_to_index(&self, coords: UVec2) -> Option<usize> {
+        if coords.x < self.grid_size.width && coords.y < self.grid_size.height {
+            Some(self.to_index(coords))
+        } else {
+            None
+        }
+    }
+
+    /// This returns the coords of a tile index, or `None` if it is outside of the grid. Indices
+    /// held across edits, like a selection, should be converted with this, as they may have been
+    /// invalidated by a resize.
+    pub fn try_to_tile_coords(&self, index: usize) -> Option<UVec2> {
+        let width = self.grid_size.width as usize;
+        let height = self.grid_size.height as usize;
+
+        if index < width * height {
+            Some(uvec2((index % width) as u32, (index / width) as u32))
+        } else {
+            None
+        }
+    }
+
+    /// This snaps `position` to the top-left corner of the cell it is in, on a grid subdivided by
+    /// `increment`, which is a fraction of a tile (`1.0` snaps to whole tiles, `0.5` to half tiles
+    /// and so on). The result is clamped to the map.
+    pub fn snap_position(&self, position: Vec2, increment: f32) -> Vec2 {
+        self.snap_position_with_origin(position, increment, Vec2::ZERO)
+    }
+
+    /// This snaps `position` like `snap_position`, on a grid that has its origin moved by
+    /// `origin_offset`, relative to `world_offset`. The result is clamped to the cells of the
+    /// offset grid that are fully inside the map.
+    pub fn snap_position_with_origin(
+        &self,
+        position: Vec2,
+        increment: f32,
+        origin_offset: Vec2,
+    ) -> Vec2 {
+        let cell_size = self.tile_size.as_vec2() * increment;
+        let map_size = self.grid_size.as_uvec2().as_vec2() * self.tile_size.as_vec2();
+
+        let origin = self.world_offset + origin_offset;
+
+        let min_cell = (-origin_offset / cell_size).ceil();
+        let max_cell = ((map_size - cell_size - origin_offset) / cell_size)
+            .floor()
+            .max(min_cell);
+
+        let cell = ((position - origin) / cell_size)
+            .floor()
+            .clamp(min_cell, max_cell);
+
+        origin + cell * cell_size
+    }
+
+    /// This resizes the grid to `new_grid_size`, keeping existing tiles in place relative to
+    /// `anchor`. Tiles that end up outside of the new bounds are discarded and new cells are left
+    /// empty. Objects and spawn points are moved along with the tiles and, if they end up outside
+    /// of the map, they are clamped to its edges.
+    ///
+    /// This returns the number of objects and spawn points that had to be clamped. Use
+    /// `get_resize_clamped_cnt` to get this before resizing.
+    pub fn resize(&mut self, new_grid_size: Size<u32>, anchor: Anchor) -> usize {
+        let (position_offset, min, max) = self.get_resize_bounds(new_grid_size, anchor);
+        let offset = anchor.get_offset(self.grid_size, new_grid_size);
+
+        for layer in self.layers.values_mut() {
+            layer.tiles = layer.get_resized_tiles(new_grid_size, offset);
+            layer.grid_size = new_grid_size;
+        }
+
+        self.grid_size = new_grid_size;
+
+        let mut clamped_cnt = 0;
+
+        let positions = self
+            .layers
+            .values_mut()
+            .flat_map(|layer| layer.objects.iter_mut().map(|object| &mut object.position))
+            .chain(
+                self.spawn_points
+                    .iter_mut()
+                    .map(|spawn_point| &mut spawn_point.position),
+            );
+
+        for position in positions {
+            let moved = *position + position_offset;
+            let clamped = moved.clamp(min, max);
+
+            if clamped != moved {
+                clamped_cnt += 1;
+            }
+
+            *position = clamped;
+        }
+
+        clamped_cnt
+    }
+
+    /// This returns the number of objects and spawn points that would have to be clamped to the
+    /// edges of the map, if it was resized with the specified parameters.
+    pub fn get_resize_clamped_cnt(&self, new_grid_size: Size<u32>, anchor: Anchor) -> usize {
+        let (position_offset, min, max) = self.get_resize_bounds(new_grid_size, anchor);
+
+        self.layers
+            .values()
+            .flat_map(|layer| layer.objects.iter().map(|object| object.position))
+            .chain(
+                self.spawn_points
+                    .iter()
+                    .map(|spawn_point| spawn_point.position),
+            )
+            .filter(|&position| {
+                let moved = position + position_offset;
+                moved.clamp(min, max) != moved
+            })
+            .count()
+    }
+
+    // This returns the translation of objects and spawn points, in world units, along with the
+    // bounds that they should be clamped to, after a resize. Things are clamped to the top-left
+    // corner of the last column and row, so that they remain visible and selectable in the editor.
+    // The bounds are in map-local space, as object and spawn point positions are not offset by
+    // `world_offset`.
+    fn get_resize_bounds(&self, new_grid_size: Size<u32>, anchor: Anchor) -> (Vec2, Vec2, Vec2) {
+        let offset = anchor.get_offset(self.grid_size, new_grid_size);
+        let position_offset = offset.as_vec2() * self.tile_size.as_vec2();
+
+        let new_size = new_grid_size.as_uvec2().as_vec2() * self.tile_size.as_vec2();
+
+        let min = Vec2::ZERO;
+        let max = (new_size - self.tile_size.as_vec2()).max(min);
+
+        (position_offset, min, max)
+    }
+
     pub fn to_position(&self, point: UVec2) -> Vec2 {
         vec2(
             (point.x as f32 * self.tile_size.width) + self.world_offset.x,
@@ -175,6 +428,27 @@ impl Map {
         MapTileIterator::new(layer, rect)
     }
 
+    /// This returns an iterator over only the occupied cells of a tile layer, as `(x, y, tile)`.
+    /// Use `get_tiles` if you need to visit empty cells as well.
+    pub fn iter_tiles<'a>(
+        &'a self,
+        layer_id: &str,
+    ) -> impl Iterator<Item = (u32, u32, &'a MapTile)> + 'a {
+        let layer = self
+            .layers
+            .get(layer_id)
+            .unwrap_or_else(|| panic!("No layer with id '{}'!", layer_id));
+
+        let width = layer.grid_size.width;
+
+        layer.tiles.iter().enumerate().filter_map(move |(i, tile)| {
+            tile.as_ref().map(|tile| {
+                let i = i as u32;
+                (i % width, i / width, tile)
+            })
+        })
+    }
+
     pub fn get_collisions(&self, collider: &Rect, should_ignore_platforms: bool) -> Vec<Rect> {
         let collider = Rect::new(
             collider.x - Self::COLLIDER_PADDING,
@@ -201,12 +475,7 @@ impl Map {
                         if !(should_ignore_platforms && tile.attributes.contains(&platform_attr)) {
                             let tile_position = self.to_position(uvec2(x, y));
 
-                            let tile_rect = Rect::new(
-                                tile_position.x,
-                                tile_position.y,
-                                self.tile_size.width,
-                                self.tile_size.height,
-                            );
+                            let tile_rect = Rect::from_point_size(tile_position, self.tile_size);
 
                             if tile_rect.overlaps(&collider) {
                                 collisions.push(tile_rect);
@@ -276,7 +545,7 @@ impl Map {
             self.world_offset.y,
             rect.width as f32 * self.tile_size.width,
             rect.height as f32 * self.tile_size.height,
-            self.background_color,
+            self.background_color * self.ambient_color,
         );
 
         {
@@ -311,6 +580,7 @@ impl Map {
                     texture,
                     DrawTextureParams {
                         dest_size: Some(Size::new(dest_rect.width, dest_rect.height)),
+                        tint: Some(self.ambient_color),
                         ..Default::default()
                     },
                 )
@@ -320,6 +590,16 @@ impl Map {
 
     /// This will draw the map
     pub fn draw<P: Into<Option<Vec2>>>(&self, rect: Option<URect>, camera_position: P) {
+        self.draw_filtered(rect, camera_position, |layer| layer.is_visible)
+    }
+
+    /// This will draw the map, drawing the tile layers for which `filter` returns `true`, in stead
+    /// of the visible ones
+    pub fn draw_filtered<P, F>(&self, rect: Option<URect>, camera_position: P, filter: F)
+    where
+        P: Into<Option<Vec2>>,
+        F: Fn(&MapLayer) -> bool,
+    {
         if let Some(camera_position) = camera_position.into() {
             self.draw_background(rect, camera_position, false);
         }
@@ -332,7 +612,7 @@ impl Map {
 
         for layer_id in draw_order {
             if let Some(layer) = self.layers.get(&layer_id) {
-                if layer.is_visible && layer.kind == MapLayerKind::TileLayer {
+                if filter(layer) && layer.kind == MapLayerKind::TileLayer {
                     for (x, y, tile) in self.get_tiles(&layer_id, Some(rect)) {
                         if let Some(tile) = tile {
                             let world_position = self.world_offset
@@ -361,6 +641,7 @@ impl Map {
                                         self.tile_size.height, // - 0.2,
                                     )),
                                     dest_size: Some(self.tile_size),
+                                    tint: Some(layer.tint * self.ambient_color),
                                     ..Default::default()
                                 },
                             );
@@ -371,6 +652,89 @@ impl Map {
         }
     }
 
+    /// This returns `None` if the layer does not exist or if `index` is out of bounds, so it can be
+    /// used with indices that may have been invalidated by an edit.
+    pub fn get_object(&self, layer_id: &str, index: usize) -> Option<&MapObject> {
+        self.layers
+            .get(layer_id)
+            .and_then(|layer| layer.objects.get(index))
+    }
+
+    /// This returns the indices of all the objects in the same group as the object at `index`,
+    /// in the order they are stored in, including the object itself. If the object is not in a
+    /// group, only its own index is returned.
+    pub fn get_object_group_indices(&self, layer_id: &str, index: usize) -> Vec<usize> {
+        let group_id = self
+            .get_object(layer_id, index)
+            .and_then(|object| object.group_id.as_ref());
+
+        match (group_id, self.layers.get(layer_id)) {
+            (Some(group_id), Some(layer)) => layer
+                .objects
+                .iter()
+                .enumerate()
+                .filter(|(_, object)| object.group_id.as_ref() == Some(group_id))
+                .map(|(i, _)| i)
+                .collect(),
+            _ => vec![index],
+        }
+    }
+
+    /// This returns the indices of the objects in the specified layer that are positioned inside
+    /// of `region`, which is in grid coordinates
+    pub fn get_object_indices_in_region(&self, layer_id: &str, region: URect) -> Vec<usize> {
+        let position = self.to_position(region.point());
+        let rect = Rect::new(
+            position.x,
+            position.y,
+            region.width as f32 * self.tile_size.width,
+            region.height as f32 * self.tile_size.height,
+        );
+
+        self.layers
+            .get(layer_id)
+            .map(|layer| {
+                layer
+                    .objects
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, object)| rect.contains(self.world_offset + object.position))
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// This returns an object group id that is not used by any object in the map
+    pub fn get_unused_object_group_id(&self) -> String {
+        self.get_unused_object_group_ids(1).remove(0)
+    }
+
+    /// This returns `cnt` distinct object group ids that are not used by any object in the map
+    pub fn get_unused_object_group_ids(&self, cnt: usize) -> Vec<String> {
+        let mut res = Vec::with_capacity(cnt);
+        let mut i = 1;
+
+        while res.len() < cnt {
+            let id = format!("group_{}", i);
+
+            let is_used = self.layers.values().any(|layer| {
+                layer
+                    .objects
+                    .iter()
+                    .any(|object| object.group_id.as_ref() == Some(&id))
+            });
+
+            if !is_used {
+                res.push(id);
+            }
+
+            i += 1;
+        }
+
+        res
+    }
+
     pub fn get_layer_kind(&self, layer_id: &str) -> Option<MapLayerKind> {
         if let Some(layer) = self.layers.get(layer_id) {
             return Some(layer.kind);
@@ -379,14 +743,175 @@ impl Map {
         None
     }
 
+    /// This returns the ids of the layers in the specified group, in draw order
+    pub fn get_group_layer_ids(&self, group: &str) -> Vec<String> {
+        self.draw_order
+            .iter()
+            .filter(|id| {
+                self.layers
+                    .get(*id)
+                    .map(|layer| layer.group.as_deref() == Some(group))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// This sets the group of the specified layer. The layer is moved in the draw order, to
+    /// directly after the other layers of the group that it is added to, or removed from, so that
+    /// groups stay contiguous.
+    pub fn set_layer_group(&mut self, layer_id: &str, group: Option<String>) {
+        let old_group = if let Some(layer) = self.layers.get_mut(layer_id) {
+            std::mem::replace(&mut layer.group, group.clone())
+        } else {
+            return;
+        };
+
+        if let Some(group) = group.or(old_group) {
+            if let Some(i) = self.draw_order.iter().position(|id| id == layer_id) {
+                self.draw_order.remove(i);
+
+                let index = self
+                    .get_group_layer_ids(&group)
+                    .last()
+                    .and_then(|last_id| self.draw_order.iter().position(|id| id == last_id))
+                    .map(|last_index| last_index + 1)
+                    .unwrap_or(i);
+
+                self.draw_order.insert(index, layer_id.to_string());
+            }
+        }
+    }
+
+    /// This returns the draw order index that the specified layer should be moved to, to move it
+    /// one step up, or down, without splitting up any groups. Layers in a group can only be moved
+    /// within the group and layers that are not in a group are moved past neighbouring groups as
+    /// a whole.
+    pub fn get_layer_move_index(&self, layer_id: &str, is_up: bool) -> Option<usize> {
+        let index = self.draw_order.iter().position(|id| id == layer_id)?;
+        let group = self.layers.get(layer_id)?.group.as_deref();
+
+        let get_group = |i: usize| -> Option<&str> {
+            self.draw_order
+                .get(i)
+                .and_then(|id| self.layers.get(id))
+                .and_then(|layer| layer.group.as_deref())
+        };
+
+        let step = |i: usize| {
+            if is_up {
+                i.checked_sub(1)
+            } else {
+                Some(i + 1).filter(|&i| i < self.draw_order.len())
+            }
+        };
+
+        let mut res = step(index)?;
+
+        if group.is_some() {
+            if get_group(res) != group {
+                return None;
+            }
+        } else if let Some(other) = get_group(res) {
+            while let Some(next) = step(res).filter(|&next| get_group(next) == Some(other)) {
+                res = next;
+            }
+        }
+
+        Some(res)
+    }
+
     pub fn default_background_color() -> Color {
         Color::new(0.0, 0.0, 0.0, 1.0)
     }
 
+    pub fn default_ambient_color() -> Color {
+        colors::WHITE
+    }
+
+    pub fn is_default_ambient_color(color: &Color) -> bool {
+        *color == Self::default_ambient_color()
+    }
+
+    /// This checks that all the references held by the map are valid, meaning that all layers
+    /// are in the draw order (and vice versa), that all tiles reference existing tilesets and
+    /// tile ids and that all decoration objects reference loaded decorations.
+    /// Serializing a map that fails this check will either panic or silently drop data.
+    pub fn validate_references(&self) -> Result<()> {
+        for layer_id in &self.draw_order {
+            if !self.layers.contains_key(layer_id) {
+                return Err(formaterr!(
+                    ErrorKind::General,
+                    "Map: Layer '{}' is in the draw order but does not exist",
+                    layer_id,
+                ));
+            }
+        }
+
+        for (layer_id, layer) in &self.layers {
+            if !self.draw_order.contains(layer_id) {
+                return Err(formaterr!(
+                    ErrorKind::General,
+                    "Map: Layer '{}' is not in the draw order",
+                    layer_id,
+                ));
+            }
+
+            match layer.kind {
+                MapLayerKind::TileLayer => {
+                    for (i, tile) in layer.tiles.iter().enumerate() {
+                        if let Some(tile) = tile {
+                            let tileset = self.tilesets.get(&tile.tileset_id).ok_or_else(|| {
+                                formaterr!(
+                                    ErrorKind::General,
+                                    "Map: Tile {} in layer '{}' references missing tileset '{}'",
+                                    i,
+                                    layer_id,
+                                    &tile.tileset_id,
+                                )
+                            })?;
+
+                            if tile.tile_id >= tileset.tile_cnt {
+                                return Err(formaterr!(
+                                    ErrorKind::General,
+                                    "Map: Tile {} in layer '{}' references tile id {}, but tileset '{}' only has {} tiles",
+                                    i,
+                                    layer_id,
+                                    tile.tile_id,
+                                    &tile.tileset_id,
+                                    tileset.tile_cnt,
+                                ));
+                            }
+                        }
+                    }
+                }
+                MapLayerKind::ObjectLayer => {
+                    for object in &layer.objects {
+                        if object.kind == MapObjectKind::Decoration
+                            && try_get_decoration(&object.id).is_none()
+                        {
+                            return Err(formaterr!(
+                                ErrorKind::General,
+                                "Map: Object layer '{}' references missing decoration '{}'",
+                                layer_id,
+                                &object.id,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The format is determined by the extension of `path`, defaulting to JSON.
+    /// The map is written to a temporary file first, which is then renamed over `path`, so that an
+    /// existing map is never left truncated if the write is interrupted.
     #[cfg(any(target_family = "unix", target_family = "windows"))]
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, json)?;
+        let bytes = serialize_map(&path, self)?;
+        crate::file::write_to_file_atomic_sync(path, &bytes)?;
         Ok(())
     }
 
@@ -395,10 +920,69 @@ impl Map {
         Ok(())
     }
 
-    pub fn get_random_spawn_point(&self) -> Vec2 {
-        let i = crate::rand::gen_range(0, self.spawn_points.len()) as usize;
+    pub fn get_random_spawn_point(&self) -> MapSpawnPoint {
+        let i = crate::rng::gen_range(0, self.spawn_points.len()) as usize;
         self.spawn_points[i]
     }
+
+    /// This returns positions, in map-local space, that spread the spawn points evenly along the
+    /// width of the map, at `y`, keeping their order from left to right. Each spawn point gets an
+    /// equal share of the width and is centered in it. `spawn_point_width` is the width of the
+    /// area that a spawn point occupies, to its right.
+    pub fn get_distributed_spawn_positions(&self, y: f32, spawn_point_width: f32) -> Vec<Vec2> {
+        let cnt = self.spawn_points.len();
+        let section_width = self.get_size().width / cnt as f32;
+
+        let mut res = vec![Vec2::ZERO; cnt];
+
+        for (i, index) in self.get_spawn_point_indices_by_x().into_iter().enumerate() {
+            let x = section_width * (i as f32 + 0.5) - spawn_point_width / 2.0;
+            res[index] = vec2(x, y);
+        }
+
+        res
+    }
+
+    /// This returns positions, in map-local space, that make the layout of the spawn points
+    /// symmetric around the horizontal center of the map. The spawn points on the left are kept
+    /// in place and the ones on the right are moved to mirror them. With an odd number of spawn
+    /// points, the middle one is centered horizontally.
+    pub fn get_mirrored_spawn_positions(&self, spawn_point_width: f32) -> Vec<Vec2> {
+        let width = self.get_size().width;
+
+        let mut res = self
+            .spawn_points
+            .iter()
+            .map(|spawn_point| spawn_point.position)
+            .collect::<Vec<_>>();
+
+        let indices = self.get_spawn_point_indices_by_x();
+        let cnt = indices.len();
+
+        for i in 0..cnt / 2 {
+            let left = res[indices[i]];
+            res[indices[cnt - 1 - i]] = vec2(width - left.x - spawn_point_width, left.y);
+        }
+
+        if cnt % 2 == 1 {
+            res[indices[cnt / 2]].x = (width - spawn_point_width) / 2.0;
+        }
+
+        res
+    }
+
+    // This returns the indices of the spawn points, sorted by their position from left to right
+    fn get_spawn_point_indices_by_x(&self) -> Vec<usize> {
+        let mut res = (0..self.spawn_points.len()).collect::<Vec<_>>();
+
+        res.sort_by(|a, b| {
+            let a = self.spawn_points[*a].position.x;
+            let b = self.spawn_points[*b].position.x;
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        res
+    }
 }
 
 pub struct MapTileIterator<'a> {
@@ -441,6 +1025,101 @@ impl<'a> Iterator for MapTileIterator<'a> {
     }
 }
 
+/// The point of a map that is kept in place when it is resized. Resizing a map anchored at
+/// `TopLeft`, for example, will add or remove rows and columns at the bottom and right edges.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    pub fn options() -> &'static [&'static str] {
+        &[
+            "Top Left",
+            "Top",
+            "Top Right",
+            "Left",
+            "Center",
+            "Right",
+            "Bottom Left",
+            "Bottom",
+            "Bottom Right",
+        ]
+    }
+
+    /// This returns the offset, in tiles, that existing tiles should be moved by when a grid of
+    /// `old_size` is resized to `new_size`. When centering and the difference is uneven, the
+    /// extra row or column is added to, or removed from, the bottom or right edge.
+    pub fn get_offset(&self, old_size: Size<u32>, new_size: Size<u32>) -> IVec2 {
+        let (x_factor, y_factor) = match self {
+            Self::TopLeft => (0, 0),
+            Self::Top => (1, 0),
+            Self::TopRight => (2, 0),
+            Self::Left => (0, 1),
+            Self::Center => (1, 1),
+            Self::Right => (2, 1),
+            Self::BottomLeft => (0, 2),
+            Self::Bottom => (1, 2),
+            Self::BottomRight => (2, 2),
+        };
+
+        let width_diff = new_size.width as i32 - old_size.width as i32;
+        let height_diff = new_size.height as i32 - old_size.height as i32;
+
+        ivec2((width_diff * x_factor) / 2, (height_diff * y_factor) / 2)
+    }
+}
+
+#[cfg(feature = "macroquad-backend")]
+impl ComboBoxValue for Anchor {
+    fn get_index(&self) -> usize {
+        match self {
+            Self::TopLeft => 0,
+            Self::Top => 1,
+            Self::TopRight => 2,
+            Self::Left => 3,
+            Self::Center => 4,
+            Self::Right => 5,
+            Self::BottomLeft => 6,
+            Self::Bottom => 7,
+            Self::BottomRight => 8,
+        }
+    }
+
+    fn set_index(&mut self, index: usize) {
+        *self = match index {
+            0 => Self::TopLeft,
+            1 => Self::Top,
+            2 => Self::TopRight,
+            3 => Self::Left,
+            4 => Self::Center,
+            5 => Self::Right,
+            6 => Self::BottomLeft,
+            7 => Self::Bottom,
+            8 => Self::BottomRight,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_options(&self) -> Vec<String> {
+        Self::options().iter().map(|s| s.to_string()).collect()
+    }
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Anchor::TopLeft
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MapLayerKind {
@@ -495,11 +1174,38 @@ pub struct MapLayer {
     pub objects: Vec<MapObject>,
     #[serde(default)]
     pub is_visible: bool,
+    /// Locked layers are drawn normally but can not be modified by editor tools
+    #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
+    pub is_locked: bool,
+    /// This overrides the editor's global snap to grid setting when placing and moving things on
+    /// this layer. `None` means that the global setting is used. It is stored with the map data,
+    /// so that the preference is kept between editing sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub should_snap_to_grid: Option<bool>,
+    /// The name of the group that the layer belongs to, if any. Groups are used to organize layers
+    /// in the editor and the layers of a group are kept together in the draw order, but the group
+    /// itself has no effect on how the map is drawn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// This is multiplied with the color of every tile in the layer, when it is drawn
+    #[serde(
+        default = "MapLayer::default_tint",
+        skip_serializing_if = "MapLayer::is_default_tint"
+    )]
+    pub tint: Color,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub properties: HashMap<String, MapProperty>,
 }
 
 impl MapLayer {
+    pub fn default_tint() -> Color {
+        colors::WHITE
+    }
+
+    pub fn is_default_tint(tint: &Color) -> bool {
+        *tint == Self::default_tint()
+    }
+
     pub fn new(id: &str, kind: MapLayerKind, has_collision: bool, grid_size: Size<u32>) -> Self {
         let has_collision = if kind == MapLayerKind::TileLayer {
             has_collision
@@ -519,24 +1225,121 @@ impl MapLayer {
             ..Default::default()
         }
     }
-}
 
-impl Default for MapLayer {
-    fn default() -> Self {
-        MapLayer {
-            id: "".to_string(),
-            has_collision: false,
-            kind: MapLayerKind::TileLayer,
-            grid_size: Size::zero(),
-            tiles: Vec::new(),
-            objects: Vec::new(),
-            is_visible: true,
-            properties: HashMap::new(),
+    /// This returns the tiles of the layer, translated by `offset`, in tiles. Tiles that are moved
+    /// beyond the edges of the layer are wrapped around to the opposite edge if `should_wrap` is
+    /// `true`, or discarded if it is `false`.
+    pub fn get_shifted_tiles(&self, offset: IVec2, should_wrap: bool) -> Vec<Option<MapTile>> {
+        let width = self.grid_size.width as i32;
+        let height = self.grid_size.height as i32;
+
+        let mut res = vec![None; self.tiles.len()];
+
+        if width == 0 || height == 0 {
+            return res;
         }
+
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if tile.is_none() {
+                continue;
+            }
+
+            let mut x = i as i32 % width + offset.x;
+            let mut y = i as i32 / width + offset.y;
+
+            if should_wrap {
+                x = x.rem_euclid(width);
+                y = y.rem_euclid(height);
+            } else if x < 0 || x >= width || y < 0 || y >= height {
+                continue;
+            }
+
+            res[(y * width + x) as usize] = tile.clone();
+        }
+
+        res
+    }
+
+    /// This returns the tiles of the layer with the tiles of `upper` drawn on top of them, as if
+    /// the two layers were flattened into one. Where both layers have a tile, the tile of `upper`
+    /// is kept. Both layers should have the same grid size.
+    pub fn get_merged_tiles(&self, upper: &MapLayer) -> Vec<Option<MapTile>> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(i, tile)| match upper.tiles.get(i) {
+                Some(Some(upper_tile)) => Some(upper_tile.clone()),
+                _ => tile.clone(),
+            })
+            .collect()
+    }
+
+    /// This returns the number of cells that have a tile in both this layer and `other`
+    pub fn get_overlapping_tile_cnt(&self, other: &MapLayer) -> usize {
+        self.tiles
+            .iter()
+            .zip(&other.tiles)
+            .filter(|(a, b)| a.is_some() && b.is_some())
+            .count()
+    }
+
+    /// This returns the tiles of the layer, as they would be on a grid of `new_grid_size`, with
+    /// existing tiles translated by `offset`, in tiles. Tiles that end up outside of the new grid
+    /// are discarded and new cells are left empty.
+    pub fn get_resized_tiles(
+        &self,
+        new_grid_size: Size<u32>,
+        offset: IVec2,
+    ) -> Vec<Option<MapTile>> {
+        let width = self.grid_size.width as i32;
+        let new_width = new_grid_size.width as i32;
+        let new_height = new_grid_size.height as i32;
+
+        let mut res = vec![None; (new_grid_size.width * new_grid_size.height) as usize];
+
+        if width == 0 {
+            return res;
+        }
+
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if tile.is_none() {
+                continue;
+            }
+
+            let x = i as i32 % width + offset.x;
+            let y = i as i32 / width + offset.y;
+
+            if x < 0 || x >= new_width || y < 0 || y >= new_height {
+                continue;
+            }
+
+            res[(y * new_width + x) as usize] = tile.clone();
+        }
+
+        res
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+impl Default for MapLayer {
+    fn default() -> Self {
+        MapLayer {
+            id: "".to_string(),
+            has_collision: false,
+            kind: MapLayerKind::TileLayer,
+            grid_size: Size::zero(),
+            tiles: Vec::new(),
+            objects: Vec::new(),
+            is_visible: true,
+            is_locked: false,
+            should_snap_to_grid: None,
+            group: None,
+            tint: Self::default_tint(),
+            properties: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapTile {
     pub tile_id: u32,
     pub tileset_id: String,
@@ -629,6 +1432,22 @@ pub struct MapObject {
     pub position: Vec2,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub properties: HashMap<String, MapProperty>,
+    /// Objects in the same layer that share a group id are selected and moved together in the
+    /// editor. This has no effect in game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// This overrides the collider size of items. If this is not set, the collider size from the
+    /// item metadata is used. This has no effect on other kinds of objects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collider_size: Option<Size<f32>>,
+    /// This overrides the scale of decorations, along each axis. If this is not set, the uniform
+    /// scale from the decoration metadata is used. This has no effect on other kinds of objects.
+    #[serde(
+        default,
+        with = "crate::parsing::vec2_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scale: Option<Vec2>,
 }
 
 impl MapObject {
@@ -638,6 +1457,9 @@ impl MapObject {
             kind,
             position,
             properties: HashMap::new(),
+            group_id: None,
+            collider_size: None,
+            scale: None,
         }
     }
 }
@@ -762,6 +1584,22 @@ pub fn draw_map(world: &mut World, _delta_time: f32) -> Result<()> {
 
 static mut MAPS: Vec<MapResource> = Vec::new();
 
+/// This returns the indices of the maps that support `game_mode` with `player_cnt` players. If no
+/// map does, the indices of all maps are returned, so that there is always a map to choose.
+pub fn get_map_indices_for(game_mode: &str, player_cnt: u32) -> Vec<usize> {
+    let res = iter_maps()
+        .enumerate()
+        .filter(|(_, res)| res.meta.requirements.supports(game_mode, player_cnt))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    if res.is_empty() {
+        return (0..iter_maps().len()).collect();
+    }
+
+    res
+}
+
 pub fn iter_maps() -> Iter<'static, MapResource> {
     unsafe { MAPS.iter() }
 }
@@ -778,11 +1616,14 @@ const MAP_RESOURCES_FILE: &str = "maps";
 
 pub const MAP_EXPORTS_DEFAULT_DIR: &str = "maps";
 pub const MAP_EXPORTS_EXTENSION: &str = "json";
+pub const MAP_EXPORTS_BINARY_EXTENSION: &str = "msgpack";
 pub const MAP_EXPORT_NAME_MIN_LEN: usize = 1;
 
 pub const MAP_PREVIEW_PLACEHOLDER_PATH: &str = "maps/no_preview.png";
 pub const MAP_PREVIEW_PLACEHOLDER_ID: &str = "map_preview_placeholder";
 
+pub const MAP_PREVIEW_EXTENSION: &str = "png";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapMetadata {
     pub name: String,
@@ -796,6 +1637,44 @@ pub struct MapMetadata {
     pub is_tiled_map: bool,
     #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
     pub is_user_map: bool,
+    #[serde(default, flatten)]
+    pub requirements: MapRequirements,
+}
+
+/// The game modes and player counts that a map is made for, so that maps can be filtered when
+/// setting up a match. Fields that are empty, or not set, do not restrict anything.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapRequirements {
+    /// The ids of the game modes that the map supports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub game_modes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_players: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_players: Option<u32>,
+}
+
+impl MapRequirements {
+    pub fn supports_game_mode(&self, game_mode: &str) -> bool {
+        self.game_modes.is_empty() || self.game_modes.iter().any(|mode| mode == game_mode)
+    }
+
+    pub fn supports_player_cnt(&self, player_cnt: u32) -> bool {
+        let is_above_min = self
+            .min_players
+            .map(|min| player_cnt >= min)
+            .unwrap_or(true);
+        let is_below_max = self
+            .max_players
+            .map(|max| player_cnt <= max)
+            .unwrap_or(true);
+
+        is_above_min && is_below_max
+    }
+
+    pub fn supports(&self, game_mode: &str, player_cnt: u32) -> bool {
+        self.supports_game_mode(game_mode) && self.supports_player_cnt(player_cnt)
+    }
 }
 
 #[derive(Clone)]
@@ -827,6 +1706,7 @@ pub fn create_map(
         preview_format: None,
         is_tiled_map: false,
         is_user_map: true,
+        requirements: MapRequirements::default(),
     };
 
     let map = Map::new(tile_size, grid_size);
@@ -836,54 +1716,210 @@ pub fn create_map(
     Ok(MapResource { map, preview, meta })
 }
 
+/// This returns the map at `path`, which can be either relative to the assets directory or a path
+/// to any map file. If the map is in the map index, its resource is returned, so that its metadata
+/// is kept when it is saved. Otherwise the map is loaded from the file, with a placeholder preview.
+pub async fn load_map_resource<P: AsRef<Path>>(path: P) -> Result<MapResource> {
+    let path = path.as_ref();
+
+    let assets_dir = assets_dir();
+    let existing = iter_maps().find(|res| {
+        let map_path = Path::new(&res.meta.path);
+        map_path == path || Path::new(&assets_dir).join(map_path) == path
+    });
+
+    if let Some(res) = existing {
+        return Ok(res.clone());
+    }
+
+    // The path in the metadata is relative to the assets directory when the map is saved, so
+    // paths are resolved against it first. Other paths are stored as absolute paths, which are
+    // left unchanged when joined with the assets directory, so that the map is saved to the file
+    // it was loaded from.
+    let assets_path = Path::new(&assets_dir).join(path);
+
+    let (file_path, meta_path) = if assets_path.is_file() {
+        (assets_path, path.to_path_buf())
+    } else {
+        let absolute_path = std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf());
+
+        (absolute_path.clone(), absolute_path)
+    };
+
+    let bytes = read_from_file(&file_path).await?;
+    let map = deserialize_map(&file_path, &bytes)?;
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let meta = MapMetadata {
+        name,
+        description: None,
+        path: meta_path.to_string_lossy().to_string(),
+        preview_path: MAP_PREVIEW_PLACEHOLDER_PATH.to_string(),
+        preview_format: None,
+        is_tiled_map: false,
+        is_user_map: true,
+        requirements: MapRequirements::default(),
+    };
+
+    let preview = get_texture(MAP_PREVIEW_PLACEHOLDER_ID);
+
+    Ok(MapResource { map, preview, meta })
+}
+
+/// This performs all the checks that `save_map` depends on, serializing the map and its
+/// metadata to an in-memory buffer instead of to disk. If this returns `Ok`, the map can be saved
+/// without producing an invalid or partially written map file.
+pub fn validate_map_for_save(map_resource: &MapResource) -> Result<()> {
+    map_resource.map.validate_references()?;
+
+    serialize_map(&map_resource.meta.path, &map_resource.map)?;
+
+    parsing::serialize_bytes_by_extension(DEFAULT_RESOURCE_FILE_EXTENSION, &map_resource.meta)?;
+
+    Ok(())
+}
+
 pub fn save_map(map_resource: &MapResource) -> Result<()> {
+    validate_map_for_save(map_resource)?;
+
     let assets_dir = assets_dir();
     let export_dir = Path::new(&assets_dir).join(&map_resource.meta.path);
 
-    {
-        let maps: &mut Vec<MapResource> = unsafe { MAPS.borrow_mut() };
-
-        if export_dir.exists() {
-            let mut i = 0;
-            while i < maps.len() {
-                let res = &maps[i];
-                if res.meta.path == map_resource.meta.path {
-                    if res.meta.is_user_map {
-                        maps.remove(i);
-                        break;
-                    } else {
-                        return Err(formaterr!(
-                                ErrorKind::General,
-                                "Resources: The path '{}' is in use and it is not possible to overwrite. Please choose a different map name",
-                                &map_resource.meta.path,
-                            ));
-                    }
-                }
+    if let Some(i) = get_map_save_index(map_resource, &export_dir)? {
+        unsafe { MAPS.remove(i) };
+    }
 
-                i += 1;
-            }
-        }
+    map_resource.map.save(export_dir)?;
+
+    unsafe { MAPS.push(map_resource.clone()) };
+
+    save_maps_file()?;
+
+    Ok(())
+}
 
-        map_resource.map.save(export_dir)?;
+/// This does the same as `save_map`, but the map is serialized and written on a separate thread,
+/// so awaiting it from a coroutine will not block the frame loop. The map resource is not added
+/// to the loaded maps before the file has been written, so a failed save leaves them unchanged.
+pub async fn save_map_async(map_resource: MapResource) -> Result<()> {
+    // The map is only serialized once, in the background, so the checks of
+    // `validate_map_for_save` that do not serialize it are done here. A map that fails to
+    // serialize is never written, so the guarantee of `validate_map_for_save` still holds.
+    map_resource.map.validate_references()?;
 
-        maps.push(map_resource.clone());
+    parsing::serialize_bytes_by_extension(DEFAULT_RESOURCE_FILE_EXTENSION, &map_resource.meta)?;
+
+    let assets_dir = assets_dir();
+    let export_dir = Path::new(&assets_dir).join(&map_resource.meta.path);
+
+    get_map_save_index(&map_resource, &export_dir)?;
+
+    save_map_file_in_background(map_resource.map.clone(), export_dir.clone()).await?;
+
+    // The loaded maps might have changed while the file was written
+    if let Some(i) = get_map_save_index(&map_resource, &export_dir)? {
+        unsafe { MAPS.remove(i) };
     }
 
+    unsafe { MAPS.push(map_resource) };
+
     save_maps_file()?;
 
     Ok(())
 }
 
-pub fn delete_map(index: usize) -> Result<()> {
-    let map_resource = unsafe { MAPS.remove(index) };
+#[cfg(not(target_arch = "wasm32"))]
+async fn save_map_file_in_background(map: Map, path: PathBuf) -> Result<()> {
+    crate::backend_impl::file::run_in_background(move || map.save(path)).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn save_map_file_in_background(map: Map, path: PathBuf) -> Result<()> {
+    let bytes = serialize_map(&path, &map)?;
+    crate::file::write_to_file_atomic(&path, &bytes).await?;
+    Ok(())
+}
+
+// This returns the index of the loaded user map that will be replaced by saving `map_resource`,
+// if any, or an error if its path belongs to a map that can not be overwritten
+fn get_map_save_index(map_resource: &MapResource, export_dir: &Path) -> Result<Option<usize>> {
+    if !export_dir.exists() {
+        return Ok(None);
+    }
+
+    let maps = unsafe { &MAPS };
+
+    for (i, res) in maps.iter().enumerate() {
+        if res.meta.path == map_resource.meta.path {
+            if res.meta.is_user_map {
+                return Ok(Some(i));
+            } else {
+                return Err(formaterr!(
+                        ErrorKind::General,
+                        "Resources: The path '{}' is in use and it is not possible to overwrite. Please choose a different map name",
+                        &map_resource.meta.path,
+                    ));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// This deletes the map file, and the generated preview, of the user map with the specified path
+/// and removes it from the loaded maps. Built-in maps can not be deleted.
+/// The map is only removed from the loaded maps once its file has been deleted, so that it is
+/// still listed if that fails.
+pub fn delete_map(path: &str) -> Result<()> {
+    let index = iter_maps()
+        .position(|res| res.meta.path == path)
+        .ok_or_else(|| {
+            formaterr!(
+                ErrorKind::General,
+                "Resources: The map '{}' was not found",
+                path
+            )
+        })?;
+
+    let meta = get_map(index).meta.clone();
+
+    if !meta.is_user_map {
+        return Err(formaterr!(
+            ErrorKind::General,
+            "Resources: The map '{}' is a built-in map and can not be deleted",
+            &meta.path,
+        ));
+    }
 
     let assets_dir = assets_dir();
-    let path = Path::new(&assets_dir).join(&map_resource.meta.path);
 
-    fs::remove_file(path)?;
+    fs::remove_file(Path::new(&assets_dir).join(&meta.path))?;
+
+    unsafe { MAPS.remove(index) };
 
     save_maps_file()?;
 
+    // The map is already deleted, so a preview that can not be removed is only reported
+    if meta.preview_path != MAP_PREVIEW_PLACEHOLDER_PATH {
+        let preview_path = Path::new(&assets_dir).join(&meta.preview_path);
+
+        if preview_path.exists() {
+            if let Err(err) = fs::remove_file(&preview_path) {
+                println!(
+                    "WARNING: Unable to delete the map preview '{}': {}",
+                    preview_path.display(),
+                    err
+                );
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -896,8 +1932,8 @@ fn save_maps_file() -> Result<()> {
 
     let metadata: Vec<MapMetadata> = iter_maps().map(|res| res.meta.clone()).collect();
 
-    let str = serde_json::to_string_pretty(&metadata)?;
-    fs::write(maps_file_path, &str)?;
+    let bytes = parsing::serialize_bytes_by_extension(DEFAULT_RESOURCE_FILE_EXTENSION, &metadata)?;
+    crate::file::write_to_file_atomic_sync(maps_file_path, &bytes)?;
 
     Ok(())
 }
@@ -925,10 +1961,10 @@ pub fn map_name_to_filename(name: &str) -> String {
 }
 
 pub fn is_valid_map_file_name(file_name: &str) -> bool {
-    if file_name.len() - MAP_EXPORTS_EXTENSION.len() > MAP_EXPORT_NAME_MIN_LEN {
-        if let Some(extension) = Path::new(file_name).extension() {
-            return extension == MAP_EXPORTS_EXTENSION;
-        }
+    if let Some(format) = get_map_format_of_path(file_name) {
+        // The extension is preceded by a dot
+        let name_len = file_name.len() - format.extension().len() - 1;
+        return name_len >= MAP_EXPORT_NAME_MIN_LEN;
     }
 
     false
@@ -985,3 +2021,846 @@ pub async fn load_maps<P: AsRef<Path>>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::map_with_layers;
+    use super::*;
+
+    // Objects and spawn points are stored in map-local space
+    fn local_position(map: &Map, point: UVec2) -> Vec2 {
+        map.to_position(point) - map.world_offset
+    }
+
+    fn non_square_tile_map() -> Map {
+        let mut map = Map::new(vec2(16.0, 32.0), uvec2(10, 5));
+        map.world_offset = vec2(8.0, 4.0);
+        map
+    }
+
+    #[test]
+    fn test_world_to_tile_rect() {
+        let map = non_square_tile_map();
+
+        // This ends exactly on the edge of tile (3, 2), so it only covers up to (2, 1)
+        let rect = Rect::new(8.0 + 20.0, 4.0 + 10.0, 28.0, 54.0);
+        assert_eq!(map.world_to_tile_rect(rect), (uvec2(1, 0), uvec2(2, 1)));
+
+        // A rect inside a single tile covers only that tile
+        let rect = Rect::new(8.0 + 17.0, 4.0 + 33.0, 2.0, 2.0);
+        assert_eq!(map.world_to_tile_rect(rect), (uvec2(1, 1), uvec2(1, 1)));
+    }
+
+    #[test]
+    fn test_world_to_tile_rect_partially_outside() {
+        let map = non_square_tile_map();
+
+        // Overlapping the top left corner of the map
+        let rect = Rect::new(-100.0, -100.0, 8.0 + 100.0 + 40.0, 4.0 + 100.0 + 40.0);
+        assert_eq!(map.world_to_tile_rect(rect), (uvec2(0, 0), uvec2(2, 1)));
+
+        // Overlapping the bottom right corner of the map
+        let rect = Rect::new(8.0 + 150.0, 4.0 + 150.0, 100.0, 100.0);
+        assert_eq!(map.world_to_tile_rect(rect), (uvec2(9, 4), uvec2(9, 4)));
+
+        // Covering the whole map and more
+        let rect = Rect::new(-1000.0, -1000.0, 2000.0, 2000.0);
+        assert_eq!(map.world_to_tile_rect(rect), (uvec2(0, 0), uvec2(9, 4)));
+    }
+
+    #[test]
+    fn test_get_size_non_square_tiles() {
+        let map = non_square_tile_map();
+        assert_eq!(map.get_size(), Size::new(160.0, 160.0));
+    }
+
+    #[test]
+    fn test_contains_non_square_tiles() {
+        let map = non_square_tile_map();
+        assert!(map.contains(vec2(8.0 + 159.0, 4.0 + 159.0)));
+        assert!(!map.contains(vec2(8.0 + 161.0, 4.0 + 80.0)));
+        assert!(!map.contains(vec2(8.0 + 80.0, 4.0 + 161.0)));
+    }
+
+    #[test]
+    fn test_to_coords_non_square_tiles() {
+        let map = non_square_tile_map();
+        assert_eq!(map.to_coords(vec2(8.0 + 20.0, 4.0 + 20.0)), uvec2(1, 0));
+        assert_eq!(map.to_coords(vec2(8.0 + 49.0, 4.0 + 65.0)), uvec2(3, 2));
+    }
+
+    #[test]
+    fn test_to_position_non_square_tiles() {
+        let map = non_square_tile_map();
+        assert_eq!(map.to_position(uvec2(3, 2)), vec2(8.0 + 48.0, 4.0 + 64.0));
+    }
+
+    #[test]
+    fn test_snap_to_grid_non_square_tiles() {
+        let map = non_square_tile_map();
+        let position = vec2(8.0 + 70.0, 4.0 + 70.0);
+        let snapped = map.to_position(map.to_coords(position));
+        assert_eq!(snapped, vec2(8.0 + 64.0, 4.0 + 64.0));
+    }
+
+    fn tile(tileset_id: &str, tile_id: u32) -> MapTile {
+        MapTile {
+            tile_id,
+            tileset_id: tileset_id.to_string(),
+            texture_id: "texture".to_string(),
+            texture: None,
+            texture_coords: Vec2::ZERO,
+            attributes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_snap_position_sub_tile() {
+        let map = non_square_tile_map();
+        let position = vec2(8.0 + 70.0, 4.0 + 70.0);
+
+        assert_eq!(
+            map.snap_position(position, 1.0),
+            vec2(8.0 + 64.0, 4.0 + 64.0)
+        );
+        assert_eq!(
+            map.snap_position(position, 0.5),
+            vec2(8.0 + 64.0, 4.0 + 64.0)
+        );
+        assert_eq!(
+            map.snap_position(position, 0.25),
+            vec2(8.0 + 68.0, 4.0 + 64.0)
+        );
+
+        let position = vec2(8.0 + 13.0, 4.0 + 17.0);
+        assert_eq!(
+            map.snap_position(position, 0.5),
+            vec2(8.0 + 8.0, 4.0 + 16.0)
+        );
+    }
+
+    #[test]
+    fn test_snap_position_clamps_to_map() {
+        let map = non_square_tile_map();
+
+        assert_eq!(map.snap_position(vec2(0.0, 0.0), 0.5), vec2(8.0, 4.0));
+        assert_eq!(
+            map.snap_position(vec2(1000.0, 1000.0), 0.25),
+            vec2(8.0 + 156.0, 4.0 + 152.0)
+        );
+    }
+
+    #[test]
+    fn test_snap_position_with_origin() {
+        let map = non_square_tile_map();
+        let origin_offset = vec2(5.0, 10.0);
+
+        assert_eq!(
+            map.snap_position_with_origin(vec2(8.0 + 70.0, 4.0 + 70.0), 1.0, origin_offset),
+            vec2(8.0 + 69.0, 4.0 + 42.0)
+        );
+
+        // Only cells of the offset grid that are fully inside the map are snapped to
+        assert_eq!(
+            map.snap_position_with_origin(vec2(0.0, 0.0), 1.0, origin_offset),
+            vec2(8.0 + 5.0, 4.0 + 10.0)
+        );
+        assert_eq!(
+            map.snap_position_with_origin(vec2(1000.0, 1000.0), 1.0, origin_offset),
+            vec2(8.0 + 133.0, 4.0 + 106.0)
+        );
+        assert_eq!(
+            map.snap_position_with_origin(vec2(0.0, 0.0), 1.0, vec2(-4.0, 0.0)),
+            vec2(8.0 + 12.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_checked_index_conversion() {
+        let map = non_square_tile_map();
+
+        assert_eq!(map.try_to_index(uvec2(9, 4)), Some(49));
+        assert_eq!(map.try_to_index(uvec2(10, 0)), None);
+        assert_eq!(map.try_to_index(uvec2(0, 5)), None);
+
+        assert_eq!(map.try_to_tile_coords(49), Some(uvec2(9, 4)));
+        assert_eq!(map.try_to_tile_coords(50), None);
+    }
+
+    fn map_with_tile_layer() -> Map {
+        let layers = [("tiles", MapLayerKind::TileLayer)];
+
+        let mut map = map_with_layers(vec2(16.0, 32.0), uvec2(10, 5), &layers);
+        map.world_offset = vec2(8.0, 4.0);
+        map
+    }
+
+    #[test]
+    fn test_iter_tiles_skips_empty() {
+        let mut map = map_with_tile_layer();
+
+        {
+            let layer = map.layers.get_mut("tiles").unwrap();
+            layer.tiles[3] = Some(tile("tileset", 1));
+            layer.tiles[24] = Some(tile("tileset", 2));
+        }
+
+        let tiles = map
+            .iter_tiles("tiles")
+            .map(|(x, y, tile)| (x, y, tile.tile_id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(tiles, vec![(3, 0, 1), (4, 2, 2)]);
+    }
+
+    #[test]
+    fn test_validate_references_valid_map() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[0] = Some(tile("tileset", 3));
+        assert!(map.validate_references().is_ok());
+    }
+
+    #[test]
+    fn test_validate_references_missing_tileset() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[0] = Some(tile("missing", 0));
+        assert!(map.validate_references().is_err());
+    }
+
+    #[test]
+    fn test_validate_references_tile_id_out_of_range() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[0] = Some(tile("tileset", 4));
+        assert!(map.validate_references().is_err());
+    }
+
+    #[test]
+    fn test_validate_references_draw_order_mismatch() {
+        let mut map = map_with_tile_layer();
+        map.draw_order.push("missing".to_string());
+        assert!(map.validate_references().is_err());
+
+        let mut map = map_with_tile_layer();
+        map.draw_order.clear();
+        assert!(map.validate_references().is_err());
+    }
+
+    // This returns a 3x2 tile layer with tiles in the first column, with tile ids matching the row
+    fn shift_test_layer() -> MapLayer {
+        let mut layer = MapLayer::new("tiles", MapLayerKind::TileLayer, false, Size::new(3, 2));
+        layer.tiles[0] = Some(tile("tileset", 0));
+        layer.tiles[3] = Some(tile("tileset", 1));
+        layer
+    }
+
+    fn tile_ids(tiles: &[Option<MapTile>]) -> Vec<Option<u32>> {
+        tiles
+            .iter()
+            .map(|tile| tile.as_ref().map(|tile| tile.tile_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_tiles_within_bounds() {
+        let layer = shift_test_layer();
+        let tiles = layer.get_shifted_tiles(ivec2(1, 0), false);
+        assert_eq!(
+            tile_ids(&tiles),
+            vec![None, Some(0), None, None, Some(1), None]
+        );
+    }
+
+    #[test]
+    fn test_shift_tiles_clip() {
+        let layer = shift_test_layer();
+
+        let tiles = layer.get_shifted_tiles(ivec2(-1, 0), false);
+        assert_eq!(tile_ids(&tiles), vec![None; 6]);
+
+        let tiles = layer.get_shifted_tiles(ivec2(0, 1), false);
+        assert_eq!(
+            tile_ids(&tiles),
+            vec![None, None, None, Some(0), None, None]
+        );
+    }
+
+    #[test]
+    fn test_merge_tiles() {
+        let lower = shift_test_layer();
+
+        let mut upper = MapLayer::new("upper", MapLayerKind::TileLayer, true, Size::new(3, 2));
+        upper.tiles[1] = Some(tile("tileset", 2));
+        upper.tiles[3] = Some(tile("tileset", 3));
+
+        assert_eq!(
+            tile_ids(&lower.get_merged_tiles(&upper)),
+            vec![Some(0), Some(2), None, Some(3), None, None]
+        );
+
+        assert_eq!(lower.get_overlapping_tile_cnt(&upper), 1);
+    }
+
+    #[test]
+    fn test_shift_tiles_wrap() {
+        let layer = shift_test_layer();
+
+        let tiles = layer.get_shifted_tiles(ivec2(-1, 0), true);
+        assert_eq!(
+            tile_ids(&tiles),
+            vec![None, None, Some(0), None, None, Some(1)]
+        );
+
+        let tiles = layer.get_shifted_tiles(ivec2(0, 1), true);
+        assert_eq!(
+            tile_ids(&tiles),
+            vec![Some(1), None, None, Some(0), None, None]
+        );
+
+        let tiles = layer.get_shifted_tiles(ivec2(4, -3), true);
+        assert_eq!(
+            tile_ids(&tiles),
+            vec![None, Some(1), None, None, Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_layer_lock_round_trip() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().is_locked = true;
+
+        let json = serde_json::to_string(&map).unwrap();
+        let map: Map = serde_json::from_str(&json).unwrap();
+        assert!(map.layers.get("tiles").unwrap().is_locked);
+
+        let json = json.replace(",\"is_locked\":true", "");
+        let map: Map = serde_json::from_str(&json).unwrap();
+        assert!(!map.layers.get("tiles").unwrap().is_locked);
+    }
+
+    #[test]
+    fn test_set_layer_group() {
+        let mut map = map_with_tile_layer();
+
+        for id in ["a", "b", "c"] {
+            let layer = MapLayer::new(id, MapLayerKind::TileLayer, false, map.grid_size);
+            map.layers.insert(layer.id.clone(), layer);
+            map.draw_order.push(id.to_string());
+        }
+
+        map.set_layer_group("tiles", Some("group".to_string()));
+        assert_eq!(map.draw_order, vec!["tiles", "a", "b", "c"]);
+
+        // Layers added to a group are moved to after the other layers of the group
+        map.set_layer_group("c", Some("group".to_string()));
+        assert_eq!(map.draw_order, vec!["tiles", "c", "a", "b"]);
+        assert_eq!(map.get_group_layer_ids("group"), vec!["tiles", "c"]);
+
+        // Layers removed from a group are moved to after the remaining layers of the group
+        map.set_layer_group("tiles", None);
+        assert_eq!(map.draw_order, vec!["c", "tiles", "a", "b"]);
+        assert_eq!(map.get_group_layer_ids("group"), vec!["c"]);
+
+        let json = serde_json::to_string(&map).unwrap();
+        let map: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(map.layers["c"].group.as_deref(), Some("group"));
+        assert_eq!(map.layers["tiles"].group, None);
+    }
+
+    #[test]
+    fn test_get_layer_move_index() {
+        let mut map = map_with_tile_layer();
+
+        for id in ["a", "b", "c"] {
+            let layer = MapLayer::new(id, MapLayerKind::TileLayer, false, map.grid_size);
+            map.layers.insert(layer.id.clone(), layer);
+            map.draw_order.push(id.to_string());
+        }
+
+        map.set_layer_group("a", Some("group".to_string()));
+        map.set_layer_group("b", Some("group".to_string()));
+        assert_eq!(map.draw_order, vec!["tiles", "a", "b", "c"]);
+
+        // Layers that are not in a group are moved past the whole of a neighbouring group
+        assert_eq!(map.get_layer_move_index("tiles", false), Some(2));
+        assert_eq!(map.get_layer_move_index("c", true), Some(1));
+        assert_eq!(map.get_layer_move_index("tiles", true), None);
+        assert_eq!(map.get_layer_move_index("c", false), None);
+
+        // Layers in a group are only moved within the group
+        assert_eq!(map.get_layer_move_index("a", false), Some(2));
+        assert_eq!(map.get_layer_move_index("a", true), None);
+        assert_eq!(map.get_layer_move_index("b", true), Some(1));
+        assert_eq!(map.get_layer_move_index("b", false), None);
+    }
+
+    #[test]
+    fn test_distributed_spawn_positions() {
+        // The map is 160 wide
+        let mut map = non_square_tile_map();
+
+        for x in [100.0, 0.0, 50.0, 10.0] {
+            map.spawn_points.push(MapSpawnPoint::new(vec2(x, 0.0)));
+        }
+
+        let positions = map.get_distributed_spawn_positions(32.0, 10.0);
+
+        // The order from left to right is kept
+        assert_eq!(
+            positions,
+            vec![
+                vec2(135.0, 32.0),
+                vec2(15.0, 32.0),
+                vec2(95.0, 32.0),
+                vec2(55.0, 32.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mirrored_spawn_positions() {
+        let mut map = non_square_tile_map();
+
+        for position in [vec2(20.0, 16.0), vec2(70.0, 8.0), vec2(90.0, 40.0)] {
+            map.spawn_points.push(MapSpawnPoint::new(position));
+        }
+
+        let positions = map.get_mirrored_spawn_positions(10.0);
+
+        assert_eq!(
+            positions,
+            vec![vec2(20.0, 16.0), vec2(75.0, 8.0), vec2(130.0, 16.0)]
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut map = map_with_tile_layer();
+        {
+            let layer = map.layers.get_mut("tiles").unwrap();
+            layer.tiles[2] = Some(tile("tileset", 3));
+            layer.is_locked = true;
+        }
+
+        let bytes = BinaryMapFormat.save(&map).unwrap();
+        let json = JsonMapFormat.save(&map).unwrap();
+        assert!(bytes.len() < json.len());
+
+        let map: Map = BinaryMapFormat.load(&bytes).unwrap();
+        let layer = map.layers.get("tiles").unwrap();
+
+        assert!(layer.is_locked);
+        assert_eq!(layer.tiles[2].as_ref().map(|tile| tile.tile_id), Some(3));
+        assert_eq!(layer.tiles.iter().flatten().count(), 1);
+        assert_eq!(map.tilesets.get("tileset").unwrap().tile_cnt, 4);
+    }
+
+    #[test]
+    fn test_layer_tint_round_trip() {
+        let mut map = map_with_tile_layer();
+
+        // The default tint is not stored, so existing maps are unchanged
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(!json.contains("\"tint\""));
+
+        let tint = Color::new(0.5, 0.6, 1.0, 1.0);
+        map.layers.get_mut("tiles").unwrap().tint = tint;
+
+        let json = serde_json::to_string(&map).unwrap();
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.layers["tiles"].tint, tint);
+
+        let bytes = BinaryMapFormat.save(&map).unwrap();
+        let loaded: Map = BinaryMapFormat.load(&bytes).unwrap();
+        assert_eq!(loaded.layers["tiles"].tint, tint);
+    }
+
+    #[test]
+    fn test_ambient_color_round_trip() {
+        let mut map = map_with_tile_layer();
+
+        // The default ambient color is not stored, so existing maps are unchanged
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(!json.contains("\"ambient_color\""));
+
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.ambient_color, Map::default_ambient_color());
+
+        let ambient_color = Color::new(0.4, 0.4, 0.8, 1.0);
+        map.ambient_color = ambient_color;
+
+        let json = serde_json::to_string(&map).unwrap();
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.ambient_color, ambient_color);
+    }
+
+    #[test]
+    fn test_music_id_round_trip() {
+        let mut map = map_with_tile_layer();
+
+        // Maps without music do not store the id, so existing maps are unchanged
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(!json.contains("\"music_id\""));
+
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.music_id, None);
+
+        map.music_id = Some("fish_tide".to_string());
+
+        let json = serde_json::to_string(&map).unwrap();
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.music_id.as_deref(), Some("fish_tide"));
+
+        let bytes = BinaryMapFormat.save(&map).unwrap();
+        let loaded: Map = BinaryMapFormat.load(&bytes).unwrap();
+        assert_eq!(loaded.music_id.as_deref(), Some("fish_tide"));
+    }
+
+    #[test]
+    fn test_spawn_point_facing_round_trip() {
+        let mut map = map_with_tile_layer();
+        map.spawn_points.push(MapSpawnPoint::new(vec2(16.0, 32.0)));
+        map.spawn_points.push(MapSpawnPoint {
+            position: vec2(48.0, 32.0),
+            is_facing_left: true,
+            ..Default::default()
+        });
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(json.contains("\"spawn_points\":[{\"x\":16.0,\"y\":32.0},"));
+
+        let loaded: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.spawn_points, map.spawn_points);
+
+        let bytes = BinaryMapFormat.save(&map).unwrap();
+        let loaded: Map = BinaryMapFormat.load(&bytes).unwrap();
+        assert_eq!(loaded.spawn_points, map.spawn_points);
+    }
+
+    #[test]
+    fn test_spawn_point_tag_round_trip() {
+        // Free spawn points are serialized without a tag, so that existing maps are unchanged
+        let cases = [
+            (MapSpawnPointTag::Free, false, r#"{"x":16.0,"y":32.0}"#),
+            (
+                MapSpawnPointTag::TeamA,
+                false,
+                r#"{"x":16.0,"y":32.0,"tag":"team_a"}"#,
+            ),
+            (
+                MapSpawnPointTag::TeamB,
+                true,
+                r#"{"x":16.0,"y":32.0,"is_facing_left":true,"tag":"team_b"}"#,
+            ),
+        ];
+
+        for (tag, is_facing_left, expected_json) in cases {
+            let spawn_point = MapSpawnPoint {
+                position: vec2(16.0, 32.0),
+                is_facing_left,
+                tag,
+            };
+
+            let json = serde_json::to_string(&spawn_point).unwrap();
+            assert_eq!(json, expected_json);
+
+            let loaded: MapSpawnPoint = serde_json::from_str(&json).unwrap();
+            assert_eq!(loaded, spawn_point);
+
+            let mut map = map_with_tile_layer();
+            map.spawn_points.push(spawn_point);
+
+            let bytes = BinaryMapFormat.save(&map).unwrap();
+            let loaded: Map = BinaryMapFormat.load(&bytes).unwrap();
+            assert_eq!(loaded.spawn_points, map.spawn_points);
+        }
+
+        // Spawn points with an unknown tag are rejected, rather than silently made free
+        let res = serde_json::from_str::<MapSpawnPoint>(r#"{"x":0.0,"y":0.0,"tag":"team_c"}"#);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_map_format_from_path() {
+        let extension_of = |path| get_map_format_of_path(path).map(|format| format.extension());
+
+        assert_eq!(extension_of("maps/a.json"), Some(MAP_EXPORTS_EXTENSION));
+        assert_eq!(
+            extension_of("maps/a.msgpack"),
+            Some(MAP_EXPORTS_BINARY_EXTENSION)
+        );
+        assert_eq!(extension_of("maps/a.toml"), None);
+
+        assert!(is_valid_map_file_name("map.json"));
+        assert!(is_valid_map_file_name("map.msgpack"));
+        assert!(!is_valid_map_file_name(".msgpack"));
+        assert!(!is_valid_map_file_name("map.toml"));
+    }
+
+    #[test]
+    fn test_map_version_and_checksum() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[1] = Some(tile("tileset", 2));
+
+        for path in ["maps/a.json", "maps/a.msgpack"] {
+            let bytes = serialize_map(path, &map).unwrap();
+
+            let format = get_map_format_of_path(path).unwrap();
+            let saved = format.load(&bytes).unwrap();
+            assert_eq!(saved.format_version, MAP_FORMAT_VERSION);
+            assert!(saved.checksum.is_some());
+            assert!(is_map_checksum_valid(&saved).unwrap());
+
+            let loaded = deserialize_map(path, &bytes).unwrap();
+            assert_eq!(loaded.format_version, MAP_FORMAT_VERSION);
+            assert_eq!(loaded.checksum, None);
+        }
+
+        let bytes = serialize_map("maps/a.json", &map).unwrap();
+        let mut saved = JsonMapFormat.load(&bytes).unwrap();
+
+        // An edited map should still load, with a warning
+        saved.music_id = Some("edited".to_string());
+        assert!(!is_map_checksum_valid(&saved).unwrap());
+        let bytes = JsonMapFormat.save(&saved).unwrap();
+        assert!(deserialize_map("maps/a.json", &bytes).is_ok());
+
+        saved.format_version = MAP_FORMAT_VERSION + 1;
+        let bytes = JsonMapFormat.save(&saved).unwrap();
+        let err = deserialize_map("maps/a.json", &bytes).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Parsing);
+    }
+
+    #[test]
+    fn test_map_migration() {
+        add_map_migration(0, |map| {
+            map.properties
+                .insert("migrated".to_string(), MapProperty::Bool(true));
+            Ok(())
+        });
+
+        let mut map = map_with_tile_layer();
+        map.format_version = 0;
+
+        let bytes = JsonMapFormat.save(&map).unwrap();
+        let loaded = deserialize_map("maps/a.json", &bytes).unwrap();
+
+        assert_eq!(loaded.format_version, MAP_FORMAT_VERSION);
+        assert!(loaded.properties.contains_key("migrated"));
+    }
+
+    #[test]
+    fn test_add_map_format() {
+        struct TestMapFormat;
+
+        impl MapFormat for TestMapFormat {
+            fn extension(&self) -> &str {
+                "testmap"
+            }
+
+            fn load(&self, bytes: &[u8]) -> Result<Map> {
+                JsonMapFormat.load(bytes)
+            }
+
+            fn save(&self, map: &Map) -> Result<Vec<u8>> {
+                JsonMapFormat.save(map)
+            }
+        }
+
+        // A local registry is used, as the global one is shared with tests running in parallel
+        let mut registry = MapFormatRegistry::new();
+
+        assert!(registry.get("testmap").is_none());
+
+        registry.add(TestMapFormat);
+
+        let format = registry.get("testmap").unwrap();
+        assert!(registry.extensions().contains(&"testmap".to_string()));
+
+        let map = map_with_tile_layer();
+        let bytes = format.save(&map).unwrap();
+        let loaded = format.load(&bytes).unwrap();
+        assert_eq!(loaded.draw_order, map.draw_order);
+
+        // Replacing a format leaves references to the old one valid
+        let json_format = registry.get(MAP_EXPORTS_EXTENSION).unwrap();
+
+        registry.add(JsonMapFormat);
+
+        assert_eq!(json_format.extension(), MAP_EXPORTS_EXTENSION);
+        assert_eq!(registry.extensions().len(), 3);
+    }
+
+    #[test]
+    fn test_anchor_offsets() {
+        let old_size = Size::new(10, 5);
+
+        let new_size = Size::new(14, 9);
+        assert_eq!(Anchor::TopLeft.get_offset(old_size, new_size), ivec2(0, 0));
+        assert_eq!(Anchor::Center.get_offset(old_size, new_size), ivec2(2, 2));
+        assert_eq!(
+            Anchor::BottomRight.get_offset(old_size, new_size),
+            ivec2(4, 4)
+        );
+        assert_eq!(Anchor::Top.get_offset(old_size, new_size), ivec2(2, 0));
+
+        let new_size = Size::new(7, 4);
+        assert_eq!(
+            Anchor::TopRight.get_offset(old_size, new_size),
+            ivec2(-3, 0)
+        );
+        assert_eq!(Anchor::Center.get_offset(old_size, new_size), ivec2(-1, 0));
+        assert_eq!(
+            Anchor::BottomLeft.get_offset(old_size, new_size),
+            ivec2(0, -1)
+        );
+    }
+
+    #[test]
+    fn test_resize_grow() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[3] = Some(tile("tileset", 1));
+
+        map.resize(Size::new(12, 7), Anchor::TopLeft);
+
+        let layer = map.layers.get("tiles").unwrap();
+        assert_eq!(map.grid_size, Size::new(12, 7));
+        assert_eq!(layer.grid_size, map.grid_size);
+        assert_eq!(layer.tiles.len(), 12 * 7);
+
+        let tiles = map
+            .iter_tiles("tiles")
+            .map(|(x, y, tile)| (x, y, tile.tile_id))
+            .collect::<Vec<_>>();
+        assert_eq!(tiles, vec![(3, 0, 1)]);
+    }
+
+    #[test]
+    fn test_resize_grow_anchored() {
+        let mut map = map_with_tile_layer();
+        map.layers.get_mut("tiles").unwrap().tiles[3] = Some(tile("tileset", 1));
+        map.spawn_points
+            .push(MapSpawnPoint::new(local_position(&map, uvec2(3, 0))));
+
+        let clamped_cnt = map.resize(Size::new(12, 7), Anchor::BottomRight);
+        assert_eq!(clamped_cnt, 0);
+
+        let tiles = map
+            .iter_tiles("tiles")
+            .map(|(x, y, tile)| (x, y, tile.tile_id))
+            .collect::<Vec<_>>();
+        assert_eq!(tiles, vec![(5, 2, 1)]);
+
+        // The spawn point follows the tiles
+        assert_eq!(
+            map.spawn_points[0].position,
+            local_position(&map, uvec2(5, 2))
+        );
+    }
+
+    #[test]
+    fn test_resize_shrink() {
+        let mut map = map_with_tile_layer();
+        {
+            let layer = map.layers.get_mut("tiles").unwrap();
+            layer.tiles[3] = Some(tile("tileset", 1));
+            layer.tiles[24] = Some(tile("tileset", 2));
+        }
+
+        map.resize(Size::new(4, 2), Anchor::TopLeft);
+
+        let layer = map.layers.get("tiles").unwrap();
+        assert_eq!(layer.tiles.len(), 8);
+
+        let tiles = map
+            .iter_tiles("tiles")
+            .map(|(x, y, tile)| (x, y, tile.tile_id))
+            .collect::<Vec<_>>();
+        assert_eq!(tiles, vec![(3, 0, 1)]);
+    }
+
+    #[test]
+    fn test_resize_shrink_anchored() {
+        let mut map = map_with_tile_layer();
+        {
+            let layer = map.layers.get_mut("tiles").unwrap();
+            layer.tiles[3] = Some(tile("tileset", 1));
+            layer.tiles[24] = Some(tile("tileset", 2));
+        }
+
+        map.resize(Size::new(6, 3), Anchor::BottomRight);
+
+        let tiles = map
+            .iter_tiles("tiles")
+            .map(|(x, y, tile)| (x, y, tile.tile_id))
+            .collect::<Vec<_>>();
+        assert_eq!(tiles, vec![(0, 0, 2)]);
+    }
+
+    #[test]
+    fn test_resize_clamps_out_of_bounds() {
+        let mut map = map_with_tile_layer();
+
+        let mut layer = MapLayer::new("objects", MapLayerKind::ObjectLayer, false, map.grid_size);
+        layer.objects.push(MapObject::new(
+            "item",
+            MapObjectKind::Item,
+            local_position(&map, uvec2(1, 1)),
+        ));
+        map.layers.insert(layer.id.clone(), layer);
+
+        map.spawn_points
+            .push(MapSpawnPoint::new(local_position(&map, uvec2(9, 4))));
+
+        assert_eq!(
+            map.get_resize_clamped_cnt(Size::new(5, 3), Anchor::TopLeft),
+            1
+        );
+        assert_eq!(
+            map.get_resize_clamped_cnt(Size::new(12, 7), Anchor::TopLeft),
+            0
+        );
+
+        let clamped_cnt = map.resize(Size::new(5, 3), Anchor::TopLeft);
+        assert_eq!(clamped_cnt, 1);
+
+        let layer = map.layers.get("objects").unwrap();
+        assert_eq!(layer.objects[0].position, local_position(&map, uvec2(1, 1)));
+        assert_eq!(
+            map.spawn_points[0].position,
+            local_position(&map, uvec2(4, 2))
+        );
+        assert!(map.contains(map.world_offset + map.spawn_points[0].position));
+    }
+
+    #[test]
+    fn test_to_grid_non_square_tiles() {
+        let map = non_square_tile_map();
+        let rect = Rect::new(8.0 + 16.0, 4.0 + 32.0, 48.0, 64.0);
+        assert_eq!(map.to_grid(&rect), URect::new(1, 1, 3, 2));
+    }
+
+    #[test]
+    fn test_map_requirements() {
+        let json = r#"{"name":"map","path":"maps/map.json","preview_path":"maps/map.png"}"#;
+        let meta: MapMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.requirements, MapRequirements::default());
+        assert!(meta.requirements.supports("deathmatch", 4));
+
+        // Unset requirements are not serialized, so existing map indices are not changed
+        assert!(!serde_json::to_string(&meta).unwrap().contains("players"));
+
+        let json = r#"{"name":"map","path":"maps/map.json","preview_path":"maps/map.png",
+            "game_modes":["deathmatch"],"min_players":2,"max_players":3}"#;
+        let meta: MapMetadata = serde_json::from_str(json).unwrap();
+
+        let requirements = &meta.requirements;
+        assert!(requirements.supports("deathmatch", 2));
+        assert!(!requirements.supports("deathmatch", 1));
+        assert!(!requirements.supports("deathmatch", 4));
+        assert!(!requirements.supports("capture_the_flag", 2));
+
+        let res: MapMetadata =
+            serde_json::from_str(&serde_json::to_string(&meta).unwrap()).unwrap();
+        assert_eq!(&res.requirements, requirements);
+    }
+}
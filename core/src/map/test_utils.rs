@@ -0,0 +1,50 @@
+//! Fixtures for tests of maps and of the code that edits them, like the editor. This is only
+//! compiled for tests, or with the `test-utils` feature, so that dependent crates can use it in
+//! their own tests.
+
+use crate::prelude::*;
+
+use super::{Map, MapLayer, MapLayerKind, MapObject, MapObjectKind, MapTileset};
+
+/// The id of the tileset in the maps created by `map_with_layers`
+pub const TEST_TILESET_ID: &str = "tileset";
+
+/// This creates a map with the specified layers, in draw order, none of which have collision.
+/// The map has a single tileset, with the id `TEST_TILESET_ID` and the texture id `texture`,
+/// that has a 2x2 grid of tiles of `tile_size`, with ids starting at 1.
+pub fn map_with_layers(tile_size: Vec2, grid_size: UVec2, layers: &[(&str, MapLayerKind)]) -> Map {
+    let mut map = Map::new(tile_size, grid_size);
+
+    for &(id, kind) in layers {
+        let layer = MapLayer::new(id, kind, false, map.grid_size);
+        map.layers.insert(layer.id.clone(), layer);
+        map.draw_order.push(id.to_string());
+    }
+
+    let tileset = MapTileset::new(
+        TEST_TILESET_ID,
+        "texture",
+        Size::new(tile_size.x as u32 * 2, tile_size.y as u32 * 2),
+        Size::new(tile_size.x, tile_size.y),
+        1,
+    );
+    map.tilesets.insert(tileset.id.clone(), tileset);
+
+    map
+}
+
+/// This adds an object to the specified layer, which must be an object layer in `map`, and
+/// returns its index
+pub fn add_test_object(
+    map: &mut Map,
+    layer_id: &str,
+    id: &str,
+    kind: MapObjectKind,
+    position: Vec2,
+) -> usize {
+    let layer = map.layers.get_mut(layer_id).unwrap();
+    assert_eq!(layer.kind, MapLayerKind::ObjectLayer);
+
+    layer.objects.push(MapObject::new(id, kind, position));
+    layer.objects.len() - 1
+}
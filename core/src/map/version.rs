@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::{Error, ErrorKind};
+use crate::result::Result;
+
+use super::{default_map_format, get_map_format_of_path, unsupported_map_format_error, Map};
+
+/// This should be incremented whenever a change is made to the map format that older versions of
+/// the game can not read. If the change requires existing maps to be modified, a migration should
+/// be registered for the previous version, with `add_map_migration`.
+/// Maps made before the version was added to the map files will have version `0`.
+pub const MAP_FORMAT_VERSION: u32 = 1;
+
+/// A migration upgrades a map from the version it is registered for to the next version.
+/// Migrations are applied to the deserialized map, so fields that have been removed or changed
+/// must remain deserializable, through serde defaults, until they are no longer migrated.
+pub type MapMigration = fn(&mut Map) -> Result<()>;
+
+static mut MAP_MIGRATIONS: Option<HashMap<u32, MapMigration>> = None;
+
+unsafe fn get_map_migration_directory() -> &'static mut HashMap<u32, MapMigration> {
+    if MAP_MIGRATIONS.is_none() {
+        MAP_MIGRATIONS = Some(HashMap::new());
+    }
+
+    MAP_MIGRATIONS.as_mut().unwrap()
+}
+
+/// This registers a migration that upgrades maps of `version` to `version + 1`. Registering a
+/// migration for a version that already has one will replace the existing migration.
+pub fn add_map_migration(version: u32, migration: MapMigration) {
+    unsafe { get_map_migration_directory() }.insert(version, migration);
+}
+
+/// This returns a checksum of the contents of `map`, not including its current checksum.
+/// The map is normalized through a JSON value, which orders the keys of maps, so the checksum
+/// will be the same regardless of the format that the map is stored in.
+pub fn get_map_checksum(map: &Map) -> Result<String> {
+    let mut value = serde_json::to_value(map)?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("checksum");
+    }
+
+    let bytes = serde_json::to_vec(&value)?;
+
+    // FNV-1a, as the standard library hashers are not guaranteed to be stable between releases
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    Ok(format!("{:016x}", hash))
+}
+
+/// This returns `false` if `map` has a checksum that does not match its contents, which means
+/// that the map file has been edited by something other than the editor. Maps without a checksum
+/// are always considered valid.
+pub fn is_map_checksum_valid(map: &Map) -> Result<bool> {
+    if let Some(checksum) = &map.checksum {
+        let res = get_map_checksum(map)?;
+        return Ok(&res == checksum);
+    }
+
+    Ok(true)
+}
+
+/// This serializes `map` in the format registered for the extension of `path`, defaulting to
+/// JSON, with the current format version and a checksum of its contents.
+pub fn serialize_map<P: AsRef<Path>>(path: P, map: &Map) -> Result<Vec<u8>> {
+    let mut map = map.clone();
+    map.format_version = MAP_FORMAT_VERSION;
+    map.checksum = None;
+    map.checksum = Some(get_map_checksum(&map)?);
+
+    get_map_format_of_path(&path)
+        .unwrap_or_else(default_map_format)
+        .save(&map)
+}
+
+/// This deserializes a map in the format registered for the extension of `path`.
+/// An error is returned if the map was saved with a format version that is newer than this
+/// version of the game can read. Maps of older versions are upgraded by the registered
+/// migrations. A warning is printed if the checksum does not match the contents of the map.
+pub fn deserialize_map<P: AsRef<Path>>(path: P, bytes: &[u8]) -> Result<Map> {
+    let path = path.as_ref();
+
+    let format = get_map_format_of_path(path).ok_or_else(|| unsupported_map_format_error(path))?;
+
+    let mut map = format.load(bytes)?;
+
+    if map.format_version > MAP_FORMAT_VERSION {
+        return Err(Error::new_message(
+            ErrorKind::Parsing,
+            &format!(
+                "The map '{}' has format version {}, but only versions up to {} are supported",
+                path.display(),
+                map.format_version,
+                MAP_FORMAT_VERSION
+            ),
+        ));
+    }
+
+    if !is_map_checksum_valid(&map)? {
+        println!(
+            "WARNING: The checksum of map '{}' does not match its contents. It might have been edited by hand",
+            path.display()
+        );
+    }
+
+    let migrations = unsafe { get_map_migration_directory() };
+
+    while map.format_version < MAP_FORMAT_VERSION {
+        if let Some(migration) = migrations.get(&map.format_version) {
+            migration(&mut map)?;
+        }
+
+        map.format_version += 1;
+    }
+
+    // The checksum only applies to the file, which might have been changed by migrations
+    map.checksum = None;
+
+    Ok(map)
+}
@@ -31,14 +31,19 @@ impl Rect {
         }
     }
 
+    /// Creates a new rectangle from its top-left corner and a `Size`.
+    pub fn from_point_size(point: Vec2, size: Size<f32>) -> Rect {
+        Rect::new(point.x, point.y, size.width, size.height)
+    }
+
     /// Returns the top-left corner of the `Rect`.
     pub fn point(&self) -> Vec2 {
         vec2(self.x, self.y)
     }
 
     /// Returns the size (width and height) of the `Rect`.
-    pub fn size(&self) -> Vec2 {
-        vec2(self.width, self.height)
+    pub fn size(&self) -> Size<f32> {
+        Size::new(self.width, self.height)
     }
 
     /// Returns the left edge of the `Rect`
@@ -147,6 +152,22 @@ impl From<URect> for Rect {
 
 impl From<(Vec2, Size<f32>)> for Rect {
     fn from((pos, size): (Vec2, Size<f32>)) -> Self {
-        Rect::new(pos.x, pos.y, size.width, size.height)
+        Rect::from_point_size(pos, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_size_round_trip() {
+        let rect = Rect::new(4.0, 8.0, 16.0, 32.0);
+        let size = rect.size();
+
+        assert_eq!(size, Size::new(16.0, 32.0));
+        assert_eq!(Rect::from_point_size(rect.point(), size), rect);
+        assert_eq!(Rect::from((rect.point(), size)), rect);
+        assert_eq!(size.as_vec2(), vec2(16.0, 32.0));
     }
 }
@@ -30,6 +30,14 @@ impl Size<f32> {
         res.height *= scale;
         res
     }
+
+    /// This scales the width by `scale.x` and the height by `scale.y`
+    pub fn to_scaled_xy(self, scale: Vec2) -> Size<f32> {
+        let mut res = self;
+        res.width *= scale.x;
+        res.height *= scale.y;
+        res
+    }
 }
 
 impl Mul<f32> for Size<f32> {
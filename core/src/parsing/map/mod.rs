@@ -7,17 +7,29 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::map::{
-    Map, MapBackgroundLayer, MapLayer, MapLayerKind, MapObject, MapProperty, MapTile, MapTileset,
+    Map, MapBackgroundLayer, MapLayer, MapLayerKind, MapObject, MapProperty, MapSpawnPoint,
+    MapSpawnPointTag, MapTile, MapTileset,
 };
 
 pub use tiled::TiledMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct MapDef {
+    #[serde(default)]
+    pub format_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
     #[serde(default = "Map::default_background_color")]
     pub background_color: Color,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub background_layers: Vec<MapBackgroundLayer>,
+    #[serde(
+        default = "Map::default_ambient_color",
+        skip_serializing_if = "Map::is_default_ambient_color"
+    )]
+    pub ambient_color: Color,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_id: Option<String>,
     #[serde(with = "crate::parsing::vec2_def", default)]
     pub world_offset: Vec2,
     pub grid_size: Size<u32>,
@@ -26,8 +38,41 @@ pub(crate) struct MapDef {
     pub tilesets: Vec<MapTileset>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub properties: HashMap<String, MapProperty>,
-    #[serde(default, with = "crate::parsing::vec2_vec")]
-    pub spawn_points: Vec<Vec2>,
+    #[serde(default)]
+    pub spawn_points: Vec<MapSpawnPoint>,
+}
+
+/// Spawn points were originally stored as plain positions, so the facing and tag are optional, to
+/// keep maps made before they were added valid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MapSpawnPointDef {
+    pub x: f32,
+    pub y: f32,
+    #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
+    pub is_facing_left: bool,
+    #[serde(default, skip_serializing_if = "MapSpawnPointTag::is_free")]
+    pub tag: MapSpawnPointTag,
+}
+
+impl From<MapSpawnPoint> for MapSpawnPointDef {
+    fn from(other: MapSpawnPoint) -> Self {
+        MapSpawnPointDef {
+            x: other.position.x,
+            y: other.position.y,
+            is_facing_left: other.is_facing_left,
+            tag: other.tag,
+        }
+    }
+}
+
+impl From<MapSpawnPointDef> for MapSpawnPoint {
+    fn from(def: MapSpawnPointDef) -> Self {
+        MapSpawnPoint {
+            position: vec2(def.x, def.y),
+            is_facing_left: def.is_facing_left,
+            tag: def.tag,
+        }
+    }
 }
 
 impl From<Map> for MapDef {
@@ -75,6 +120,10 @@ impl From<Map> for MapDef {
                         objects,
                         tiles,
                         is_visible: layer.is_visible,
+                        is_locked: layer.is_locked,
+                        should_snap_to_grid: layer.should_snap_to_grid,
+                        group: layer.group.clone(),
+                        tint: layer.tint,
                         properties: layer.properties.clone(),
                     };
 
@@ -85,15 +134,22 @@ impl From<Map> for MapDef {
             })
             .collect();
 
-        let tilesets = other
+        // Tilesets are ordered by their first tile id, so that the output is deterministic
+        let mut tilesets: Vec<MapTileset> = other
             .tilesets
             .into_iter()
             .map(|(_, tileset)| tileset)
             .collect();
 
+        tilesets.sort_by_key(|tileset| tileset.first_tile_id);
+
         MapDef {
+            format_version: other.format_version,
+            checksum: other.checksum,
             background_color: other.background_color,
             background_layers: other.background_layers,
+            ambient_color: other.ambient_color,
+            music_id: other.music_id,
             world_offset: other.world_offset,
             grid_size: other.grid_size,
             tile_size: other.tile_size,
@@ -174,6 +230,10 @@ impl From<MapDef> for Map {
                 tiles,
                 objects,
                 is_visible: layer.is_visible,
+                is_locked: layer.is_locked,
+                should_snap_to_grid: layer.should_snap_to_grid,
+                group: layer.group.clone(),
+                tint: layer.tint,
                 properties: layer.properties.clone(),
             };
 
@@ -181,8 +241,12 @@ impl From<MapDef> for Map {
         }));
 
         Map {
+            format_version: def.format_version,
+            checksum: def.checksum,
             background_color: def.background_color,
             background_layers: def.background_layers,
+            ambient_color: def.ambient_color,
+            music_id: def.music_id,
             world_offset: def.world_offset,
             grid_size: def.grid_size,
             tile_size: def.tile_size,
@@ -207,6 +271,17 @@ pub struct MapLayerDef {
     pub objects: Option<Vec<MapObject>>,
     #[serde(default)]
     pub is_visible: bool,
+    #[serde(default, skip_serializing_if = "crate::parsing::is_false")]
+    pub is_locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub should_snap_to_grid: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(
+        default = "MapLayer::default_tint",
+        skip_serializing_if = "MapLayer::is_default_tint"
+    )]
+    pub tint: Color,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub properties: HashMap<String, MapProperty>,
 }
@@ -220,6 +295,10 @@ impl Default for MapLayerDef {
             tiles: Some(Vec::new()),
             objects: None,
             is_visible: true,
+            is_locked: false,
+            should_snap_to_grid: None,
+            group: None,
+            tint: MapLayer::default_tint(),
             properties: HashMap::new(),
         }
     }
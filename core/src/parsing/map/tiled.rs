@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 
 use crate::map::MapObjectKind;
-use crate::map::{Map, MapLayer, MapLayerKind, MapObject, MapProperty, MapTile, MapTileset};
+use crate::map::{
+    Map, MapLayer, MapLayerKind, MapObject, MapProperty, MapTile, MapTileset, MAP_FORMAT_VERSION,
+};
 
 const SPAWN_POINT_MAP_OBJECT_TYPE: &str = "spawn_point";
 
@@ -245,7 +247,7 @@ impl TiledMap {
                 let position = vec2(tiled_object.x, tiled_object.y);
 
                 if tiled_object.object_type == *SPAWN_POINT_MAP_OBJECT_TYPE {
-                    spawn_points.push(position);
+                    spawn_points.push(position.into());
                 } else {
                     let mut properties = HashMap::new();
                     if let Some(tiled_props) = tiled_object.properties.clone() {
@@ -262,6 +264,9 @@ impl TiledMap {
                         kind,
                         position,
                         properties,
+                        group_id: None,
+                        collider_size: None,
+                        scale: None,
                     };
 
                     objects.push(object);
@@ -300,6 +305,10 @@ impl TiledMap {
                 tiles,
                 objects,
                 is_visible: tiled_layer.visible,
+                is_locked: false,
+                should_snap_to_grid: None,
+                group: None,
+                tint: MapLayer::default_tint(),
                 properties,
             };
 
@@ -316,8 +325,12 @@ impl TiledMap {
         }
 
         Map {
+            format_version: MAP_FORMAT_VERSION,
+            checksum: None,
             background_color,
             background_layers: Vec::new(),
+            ambient_color: Map::default_ambient_color(),
+            music_id: None,
             world_offset: Vec2::ZERO,
             grid_size: Size::new(self.width, self.height),
             tile_size: Size::new(self.tilewidth as f32, self.tileheight as f32),
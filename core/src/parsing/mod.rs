@@ -11,6 +11,7 @@ use std::path::Path;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{self, ErrorKind};
 use crate::file::{read_from_file, Error};
 use crate::result::Result;
 
@@ -21,12 +22,35 @@ where
     let res = match extension {
         "json" => deserialize_json_bytes(bytes)?,
         "toml" => deserialize_toml_bytes(bytes)?,
+        "msgpack" => deserialize_msgpack_bytes(bytes)?,
         _ => panic!("Invalid file extension '{}'", &extension),
     };
 
     Ok(res)
 }
 
+/// Serialize a value into the format that corresponds to `extension`, which can be any of the
+/// extensions supported by `deserialize_bytes_by_extension`.
+/// Will return an error if the extension is not supported or if a parsing error is encountered.
+pub fn serialize_bytes_by_extension<T>(extension: &str, value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let res = match extension {
+        "json" => serialize_json_bytes(value)?,
+        "toml" => serialize_toml_bytes(value)?,
+        "msgpack" => serialize_msgpack_bytes(value)?,
+        _ => {
+            return Err(error::Error::new_message(
+                ErrorKind::Parsing,
+                &format!("Unable to serialize to file extension '{}'", extension),
+            ))
+        }
+    };
+
+    Ok(res)
+}
+
 pub async fn deserialize_file_by_extension<T, P: AsRef<Path>>(path: P) -> Result<T>
 where
     T: DeserializeOwned,
@@ -149,6 +173,32 @@ where
     Ok(res)
 }
 
+/// Serialize a value into a slice of MessagePack.
+/// Structs are serialized as maps, with field names, so that the result is self-describing and
+/// supports the same optional fields as the text formats.
+/// Will return a `rmp_serde::encode::Error` if a parsing error is encountered.
+pub fn serialize_msgpack_bytes<T>(
+    value: &T,
+) -> std::result::Result<Vec<u8>, rmp_serde::encode::Error>
+where
+    T: Serialize,
+{
+    let res = rmp_serde::to_vec_named(value)?;
+    Ok(res)
+}
+
+/// Deserialize a slice of MessagePack into a value.
+/// Will return a `rmp_serde::decode::Error` if a parsing error is encountered.
+pub fn deserialize_msgpack_bytes<'a, T>(
+    value: &'a [u8],
+) -> std::result::Result<T, rmp_serde::decode::Error>
+where
+    T: Deserialize<'a>,
+{
+    let res = rmp_serde::from_slice(value)?;
+    Ok(res)
+}
+
 /// Load and deserialize a TOML file into a value
 pub async fn load_toml_file<T, P: AsRef<Path>>(path: P) -> Result<T>
 where
@@ -160,3 +210,40 @@ where
         Ok(res) => Ok(res),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TestValue {
+        name: String,
+        cnt: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scale: Option<f32>,
+        properties: HashMap<String, String>,
+    }
+
+    #[test]
+    fn test_serialize_bytes_by_extension() {
+        let mut properties = HashMap::new();
+        properties.insert("key".to_string(), "value".to_string());
+
+        let value = TestValue {
+            name: "test".to_string(),
+            cnt: 3,
+            scale: None,
+            properties,
+        };
+
+        for extension in ["json", "toml", "msgpack"] {
+            let bytes = serialize_bytes_by_extension(extension, &value).unwrap();
+            let res: TestValue = deserialize_bytes_by_extension(extension, &bytes).unwrap();
+            assert_eq!(res, value, "round trip through '{}'", extension);
+        }
+
+        assert!(serialize_bytes_by_extension("yaml", &value).is_err());
+    }
+}
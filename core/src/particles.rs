@@ -1,9 +1,11 @@
-use hecs::World;
+use hecs::{Entity, World};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Iter;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use futures::future::join_all;
 use num_traits::*;
 
 use crate::math::Vec2;
@@ -15,6 +17,29 @@ use crate::parsing::deserialize_bytes_by_extension;
 use crate::result::Result;
 use crate::transform::Transform;
 
+/// This determines how the offset of a particle emitter relates to the rotation of its parent
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticleEmitterOffsetMode {
+    /// The offset is rotated around the parent position, along with the parent
+    Local,
+    /// The offset is applied as is, ignoring the rotation of the parent. It will still be flipped
+    /// along with the parent, though
+    World,
+}
+
+impl ParticleEmitterOffsetMode {
+    pub fn is_local(&self) -> bool {
+        *self == ParticleEmitterOffsetMode::Local
+    }
+}
+
+impl Default for ParticleEmitterOffsetMode {
+    fn default() -> Self {
+        ParticleEmitterOffsetMode::Local
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ParticleEmitterMetadata {
     /// The id of the particle effect.
@@ -23,6 +48,9 @@ pub struct ParticleEmitterMetadata {
     /// The offset is added to the `position` provided when calling `draw`
     #[serde(default, with = "crate::parsing::vec2_def")]
     pub offset: Vec2,
+    /// Set this to `world` if the offset should not be rotated with the parent
+    #[serde(default, skip_serializing_if = "ParticleEmitterOffsetMode::is_local")]
+    pub offset_mode: ParticleEmitterOffsetMode,
     /// Delay before emission will begin
     #[serde(default, skip_serializing_if = "f32::is_zero")]
     pub delay: f32,
@@ -46,6 +74,7 @@ impl Default for ParticleEmitterMetadata {
         ParticleEmitterMetadata {
             particle_effect_id: "".to_string(),
             offset: Vec2::ZERO,
+            offset_mode: ParticleEmitterOffsetMode::default(),
             delay: 0.0,
             emissions: None,
             interval: 0.0,
@@ -55,9 +84,87 @@ impl Default for ParticleEmitterMetadata {
     }
 }
 
+impl ParticleEmitterMetadata {
+    /// This returns a builder for emitter metadata for the particle effect with the specified id,
+    /// which is more convenient than struct update syntax when creating effects from code
+    pub fn builder(particle_effect_id: &str) -> ParticleEmitterMetadataBuilder {
+        ParticleEmitterMetadataBuilder::new(particle_effect_id)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParticleEmitterMetadataBuilder {
+    meta: ParticleEmitterMetadata,
+}
+
+impl ParticleEmitterMetadataBuilder {
+    pub fn new(particle_effect_id: &str) -> Self {
+        ParticleEmitterMetadataBuilder {
+            meta: ParticleEmitterMetadata {
+                particle_effect_id: particle_effect_id.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn offset(self, offset: Vec2) -> Self {
+        let mut builder = self;
+        builder.meta.offset = offset;
+        builder
+    }
+
+    pub fn offset_mode(self, offset_mode: ParticleEmitterOffsetMode) -> Self {
+        let mut builder = self;
+        builder.meta.offset_mode = offset_mode;
+        builder
+    }
+
+    pub fn delay(self, delay: f32) -> Self {
+        let mut builder = self;
+        builder.meta.delay = delay;
+        builder
+    }
+
+    pub fn interval(self, interval: f32) -> Self {
+        let mut builder = self;
+        builder.meta.interval = interval;
+        builder
+    }
+
+    pub fn emissions(self, emissions: u32) -> Self {
+        let mut builder = self;
+        builder.meta.emissions = Some(emissions);
+        builder
+    }
+
+    pub fn animations(self, animations: AnimatedSpriteMetadata) -> Self {
+        let mut builder = self;
+        builder.meta.animations = Some(animations);
+        builder
+    }
+
+    /// This makes the emitter start emitting as soon as it is created
+    pub fn autostart(self) -> Self {
+        let mut builder = self;
+        builder.meta.should_autostart = true;
+        builder
+    }
+
+    pub fn build(self) -> ParticleEmitterMetadata {
+        self.meta
+    }
+}
+
+impl From<ParticleEmitterMetadataBuilder> for ParticleEmitterMetadata {
+    fn from(builder: ParticleEmitterMetadataBuilder) -> Self {
+        builder.build()
+    }
+}
+
 pub struct ParticleEmitter {
     pub particle_effect_id: String,
     pub offset: Vec2,
+    pub offset_mode: ParticleEmitterOffsetMode,
     pub delay: f32,
     pub emissions: Option<u32>,
     pub interval: f32,
@@ -65,6 +172,9 @@ pub struct ParticleEmitter {
     pub delay_timer: f32,
     pub interval_timer: f32,
     pub is_active: bool,
+    /// This should mirror the flip of the parent's sprite, so that the offset is flipped with it
+    pub is_flipped_x: bool,
+    pub is_flipped_y: bool,
 }
 
 impl ParticleEmitter {
@@ -72,6 +182,7 @@ impl ParticleEmitter {
         ParticleEmitter {
             particle_effect_id: meta.particle_effect_id,
             offset: meta.offset,
+            offset_mode: meta.offset_mode,
             delay: meta.delay,
             interval: meta.interval,
             emissions: meta.emissions,
@@ -79,6 +190,8 @@ impl ParticleEmitter {
             delay_timer: 0.0,
             interval_timer: meta.interval,
             is_active: meta.should_autostart,
+            is_flipped_x: false,
+            is_flipped_y: false,
         }
     }
 
@@ -96,12 +209,38 @@ impl ParticleEmitter {
         offset
     }
 
+    /// This returns the position particles should be spawned at, for a parent at `position`, with
+    /// the specified `rotation`. The offset is flipped according to `is_flipped_x` and
+    /// `is_flipped_y` before it is rotated, the same way a flipped sprite is drawn, so that the
+    /// emitter stays attached to the same point on the sprite. The offset is only rotated if the
+    /// offset mode is `Local`.
+    pub fn get_spawn_position(&self, position: Vec2, rotation: f32) -> Vec2 {
+        let offset = self.get_offset(self.is_flipped_x, self.is_flipped_y);
+
+        if rotation == 0.0 || self.offset_mode == ParticleEmitterOffsetMode::World {
+            return position + offset;
+        }
+
+        let sin = rotation.sin();
+        let cos = rotation.cos();
+
+        position
+            + Vec2::new(
+                cos * offset.x - sin * offset.y,
+                sin * offset.x + cos * offset.y,
+            )
+    }
+
     pub fn activate(&mut self) {
         self.delay_timer = 0.0;
         self.interval_timer = self.interval;
         self.emission_cnt = 0;
         self.is_active = true;
     }
+
+    pub fn deactivate(&mut self) {
+        self.is_active = false;
+    }
 }
 
 impl From<ParticleEmitterMetadata> for ParticleEmitter {
@@ -110,20 +249,176 @@ impl From<ParticleEmitterMetadata> for ParticleEmitter {
     }
 }
 
-#[derive(Default)]
+/// This activates all the emitters in `emitters`, resetting their timers and emission counts
+pub fn activate_all_emitters(emitters: &mut [ParticleEmitter]) {
+    for emitter in emitters {
+        emitter.activate();
+    }
+}
+
+/// This deactivates all the emitters in `emitters`
+pub fn deactivate_all_emitters(emitters: &mut [ParticleEmitter]) {
+    for emitter in emitters {
+        emitter.deactivate();
+    }
+}
+
+/// This activates, or deactivates, all the particle emitters of `entity`, whether it has a single
+/// `ParticleEmitter` or a `Vec<ParticleEmitter>`. Entities without emitters are ignored.
+pub fn set_entity_emitters_active(world: &World, entity: Entity, is_active: bool) {
+    if let Ok(mut emitter) = world.get_mut::<ParticleEmitter>(entity) {
+        if is_active {
+            emitter.activate();
+        } else {
+            emitter.deactivate();
+        }
+    }
+
+    if let Ok(mut emitters) = world.get_mut::<Vec<ParticleEmitter>>(entity) {
+        if is_active {
+            activate_all_emitters(&mut emitters);
+        } else {
+            deactivate_all_emitters(&mut emitters);
+        }
+    }
+}
+
+/// The default maximum number of emitter caches that are kept alive at the same time
+pub const DEFAULT_MAX_EMITTER_CACHES: usize = 64;
+/// The default time, in seconds, that an emitter cache is kept alive after its last spawn
+pub const DEFAULT_EMITTER_CACHE_IDLE_TIMEOUT: f32 = 30.0;
+
+struct PooledCache<T> {
+    cache: T,
+    last_used: f32,
+}
+
+/// This holds caches by particle effect id. Caches are created on demand and are evicted when they
+/// have not been used for `idle_timeout` seconds. If the pool is full when a new cache is created,
+/// the least recently used cache is evicted to make room for it. A `max_size` of zero means that
+/// there is no limit to the number of caches.
+pub struct EmitterCachePool<T> {
+    caches: HashMap<String, PooledCache<T>>,
+    max_size: usize,
+    idle_timeout: f32,
+    time: f32,
+}
+
+impl<T> EmitterCachePool<T> {
+    pub fn new(max_size: usize, idle_timeout: f32) -> Self {
+        EmitterCachePool {
+            caches: HashMap::new(),
+            max_size,
+            idle_timeout,
+            time: 0.0,
+        }
+    }
+
+    /// This returns the cache with the specified id, creating it with `f` if it is not in the pool.
+    /// Returns `None` if the cache did not exist and `f` returned `None`.
+    pub fn get_or_insert_with<F>(&mut self, id: &str, f: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> Option<T>,
+    {
+        if !self.caches.contains_key(id) {
+            let cache = f()?;
+
+            while self.max_size > 0 && self.caches.len() >= self.max_size {
+                self.evict_least_recently_used();
+            }
+
+            let entry = PooledCache {
+                cache,
+                last_used: self.time,
+            };
+
+            self.caches.insert(id.to_string(), entry);
+        }
+
+        let entry = self.caches.get_mut(id).unwrap();
+        entry.last_used = self.time;
+
+        Some(&mut entry.cache)
+    }
+
+    /// This advances the clock of the pool and evicts any caches that have timed out
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time;
+
+        let (time, idle_timeout) = (self.time, self.idle_timeout);
+        self.caches
+            .retain(|_, entry| time - entry.last_used < idle_timeout);
+    }
+
+    pub fn set_limits(&mut self, max_size: usize, idle_timeout: f32) {
+        self.max_size = max_size;
+        self.idle_timeout = idle_timeout;
+
+        while self.max_size > 0 && self.caches.len() > self.max_size {
+            self.evict_least_recently_used();
+        }
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let id = self
+            .caches
+            .iter()
+            .min_by(|(_, a), (_, b)| a.last_used.partial_cmp(&b.last_used).unwrap())
+            .map(|(id, _)| id.clone());
+
+        if let Some(id) = id {
+            self.caches.remove(&id);
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.caches.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.caches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.caches.is_empty()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.caches.values_mut().map(|entry| &mut entry.cache)
+    }
+}
+
 pub struct ParticleEmitterCache {
-    pub cache_map: HashMap<String, EmittersCache>,
+    pub pool: EmitterCachePool<EmittersCache>,
 }
 
 impl ParticleEmitterCache {
     pub fn new() -> Self {
-        let mut cache_map = HashMap::new();
+        ParticleEmitterCache {
+            pool: EmitterCachePool::new(
+                DEFAULT_MAX_EMITTER_CACHES,
+                DEFAULT_EMITTER_CACHE_IDLE_TIMEOUT,
+            ),
+        }
+    }
+
+    /// This spawns particles of the effect with the specified id at `position`. The cache of the
+    /// effect is created on the first spawn.
+    pub fn spawn(&mut self, particle_effect_id: &str, position: Vec2) {
+        let cache = self.pool.get_or_insert_with(particle_effect_id, || {
+            try_get_particle_effect(particle_effect_id)
+                .map(|config| EmittersCache::new(config.clone()))
+        });
 
-        for (id, config) in iter_particle_effects() {
-            cache_map.insert(id.clone(), EmittersCache::new(config.clone()));
+        if let Some(cache) = cache {
+            cache.spawn(position);
         }
+    }
+}
 
-        ParticleEmitterCache { cache_map }
+impl Default for ParticleEmitterCache {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -133,9 +428,18 @@ fn particle_emitter_cache() -> &'static mut ParticleEmitterCache {
     unsafe { PARTICLE_EMITTER_CACHE.get_or_insert_with(ParticleEmitterCache::new) }
 }
 
+/// This sets the maximum number of particle emitter caches that are kept alive at the same time,
+/// and the time, in seconds, that a cache is kept alive after its last spawn. A `max_caches` of
+/// zero means that there is no limit.
+pub fn set_particle_emitter_cache_limits(max_caches: usize, idle_timeout: f32) {
+    particle_emitter_cache()
+        .pool
+        .set_limits(max_caches, idle_timeout);
+}
+
 fn update_one_particle_emitter(
     delta_time: f32,
-    mut position: Vec2,
+    position: Vec2,
     rotation: f32,
     emitter: &mut ParticleEmitter,
 ) {
@@ -149,30 +453,9 @@ fn update_one_particle_emitter(
         if emitter.delay_timer >= emitter.delay && emitter.interval_timer >= emitter.interval {
             emitter.interval_timer = 0.0;
 
-            if rotation == 0.0 {
-                position += emitter.offset;
-            } else {
-                let offset_position = position + emitter.offset;
-
-                let sin = rotation.sin();
-                let cos = rotation.cos();
-
-                position = Vec2::new(
-                    cos * (offset_position.x - position.x) - sin * (offset_position.y - position.y)
-                        + position.x,
-                    sin * (offset_position.x - position.x)
-                        + cos * (offset_position.y - position.y)
-                        + position.y,
-                );
-            }
+            let position = emitter.get_spawn_position(position, rotation);
 
-            let particles = particle_emitter_cache();
-            let cache = particles
-                .cache_map
-                .get_mut(&emitter.particle_effect_id)
-                .unwrap();
-
-            cache.spawn(position);
+            particle_emitter_cache().spawn(&emitter.particle_effect_id, position);
 
             if let Some(emissions) = emitter.emissions {
                 emitter.emission_cnt += 1;
@@ -186,6 +469,8 @@ fn update_one_particle_emitter(
 }
 
 pub fn update_particle_emitters(world: &mut World, delta_time: f32) -> Result<()> {
+    particle_emitter_cache().pool.update(delta_time);
+
     for (_, (transform, emitter)) in world.query_mut::<(&Transform, &mut ParticleEmitter)>() {
         update_one_particle_emitter(delta_time, transform.position, transform.rotation, emitter);
     }
@@ -204,11 +489,27 @@ pub fn update_particle_emitters(world: &mut World, delta_time: f32) -> Result<()
     Ok(())
 }
 
+static mut PARTICLE_TIME_SCALE: f32 = 1.0;
+
+/// This sets the factor that is passed on to the backend particle simulation, for effects like
+/// slow-motion and hit-stop, on backends that can scale particles that are already in flight.
+/// The macroquad backend can not, so there it has no effect, and emission should be scaled by
+/// the delta time passed to `update_particle_emitters` instead.
+/// Negative values are clamped to `0.0`.
+pub fn set_particle_time_scale(time_scale: f32) {
+    unsafe { PARTICLE_TIME_SCALE = time_scale.max(0.0) };
+}
+
+pub fn get_particle_time_scale() -> f32 {
+    unsafe { PARTICLE_TIME_SCALE }
+}
+
 pub fn draw_particles(_world: &mut World, _delta_time: f32) -> Result<()> {
     let particles = particle_emitter_cache();
+    let time_scale = get_particle_time_scale();
 
-    for cache in particles.cache_map.values_mut() {
-        cache.draw();
+    for cache in particles.pool.values_mut() {
+        draw_emitters_cache(cache, time_scale);
     }
 
     Ok(())
@@ -236,6 +537,35 @@ struct ParticleEffectMetadata {
     path: String,
 }
 
+static PARTICLE_EFFECTS_LOADED: AtomicUsize = AtomicUsize::new(0);
+static PARTICLE_EFFECTS_TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+/// This returns the number of particle effect files loaded, and the total number of files to load,
+/// by the most recent call to `load_particle_effects`. This can be polled from a loading screen.
+pub fn get_particle_effects_loading_progress() -> (usize, usize) {
+    (
+        PARTICLE_EFFECTS_LOADED.load(Ordering::Relaxed),
+        PARTICLE_EFFECTS_TOTAL.load(Ordering::Relaxed),
+    )
+}
+
+// This reads and parses a single particle effect file, updating the loading progress when done
+async fn load_particle_effect_file(file_path: &Path) -> Result<EmitterConfig> {
+    let res = match read_from_file(file_path).await {
+        Ok(bytes) => {
+            let extension = file_path.extension().unwrap().to_str().unwrap();
+            deserialize_bytes_by_extension(extension, &bytes)
+        }
+        Err(err) => Err(err.into()),
+    };
+
+    PARTICLE_EFFECTS_LOADED.fetch_add(1, Ordering::Relaxed);
+
+    res
+}
+
+/// The individual effect files are loaded concurrently. If any of them fails to load, the error
+/// of the first one, in the order they are listed in, is returned.
 pub async fn load_particle_effects<P: AsRef<Path>>(
     path: P,
     ext: &str,
@@ -248,6 +578,9 @@ pub async fn load_particle_effects<P: AsRef<Path>>(
         particle_effects.clear();
     }
 
+    PARTICLE_EFFECTS_LOADED.store(0, Ordering::Relaxed);
+    PARTICLE_EFFECTS_TOTAL.store(0, Ordering::Relaxed);
+
     let particle_effects_file_path = path
         .as_ref()
         .join(PARTICLE_EFFECT_RESOURCES_FILE)
@@ -263,19 +596,216 @@ pub async fn load_particle_effects<P: AsRef<Path>>(
             let metadata: Vec<ParticleEffectMetadata> =
                 deserialize_bytes_by_extension(ext, &bytes)?;
 
-            for meta in metadata {
-                let file_path = path.as_ref().join(&meta.path);
+            PARTICLE_EFFECTS_TOTAL.store(metadata.len(), Ordering::Relaxed);
 
-                let extension = file_path.extension().unwrap().to_str().unwrap();
+            let file_paths = metadata
+                .iter()
+                .map(|meta| path.as_ref().join(&meta.path))
+                .collect::<Vec<_>>();
 
-                let bytes = read_from_file(&file_path).await?;
+            let results = join_all(
+                file_paths
+                    .iter()
+                    .map(|file_path| load_particle_effect_file(file_path)),
+            )
+            .await;
 
-                let cfg: EmitterConfig = deserialize_bytes_by_extension(extension, &bytes)?;
-
-                particle_effects.insert(meta.id, cfg);
+            for (meta, res) in metadata.into_iter().zip(results) {
+                particle_effects.insert(meta.id, res?);
             }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emitter_offset_mode() {
+        let position = Vec2::new(10.0, 20.0);
+        let rotation = std::f32::consts::FRAC_PI_2;
+
+        let mut emitter = ParticleEmitter::new(ParticleEmitterMetadata {
+            offset: Vec2::new(4.0, 0.0),
+            ..Default::default()
+        });
+
+        let local = emitter.get_spawn_position(position, rotation);
+        assert!((local - Vec2::new(10.0, 24.0)).length() < 0.0001);
+
+        emitter.offset_mode = ParticleEmitterOffsetMode::World;
+
+        let world = emitter.get_spawn_position(position, rotation);
+        assert_eq!(world, Vec2::new(14.0, 20.0));
+    }
+
+    fn assert_spawn_position(
+        emitter: &ParticleEmitter,
+        position: Vec2,
+        rotation: f32,
+        expected: Vec2,
+    ) {
+        let spawn_position = emitter.get_spawn_position(position, rotation);
+        assert!(
+            (spawn_position - expected).length() < 0.0001,
+            "expected {:?}, got {:?}",
+            expected,
+            spawn_position
+        );
+    }
+
+    #[test]
+    fn test_emitter_flip() {
+        let position = Vec2::new(10.0, 20.0);
+
+        let mut emitter = ParticleEmitter::new(ParticleEmitterMetadata {
+            offset: Vec2::new(4.0, 2.0),
+            ..Default::default()
+        });
+
+        emitter.is_flipped_x = true;
+        assert_spawn_position(&emitter, position, 0.0, Vec2::new(6.0, 22.0));
+
+        emitter.is_flipped_y = true;
+        assert_spawn_position(&emitter, position, 0.0, Vec2::new(6.0, 18.0));
+    }
+
+    #[test]
+    fn test_emitter_flip_with_rotation() {
+        let position = Vec2::new(10.0, 20.0);
+        let rotation = std::f32::consts::FRAC_PI_2;
+
+        let mut emitter = ParticleEmitter::new(ParticleEmitterMetadata {
+            offset: Vec2::new(4.0, 2.0),
+            ..Default::default()
+        });
+
+        assert_spawn_position(&emitter, position, rotation, Vec2::new(8.0, 24.0));
+
+        // The offset is flipped before it is rotated, like the sprite
+        emitter.is_flipped_x = true;
+        assert_spawn_position(&emitter, position, rotation, Vec2::new(8.0, 16.0));
+
+        emitter.is_flipped_y = true;
+        assert_spawn_position(&emitter, position, rotation, Vec2::new(12.0, 16.0));
+
+        emitter.is_flipped_x = false;
+        assert_spawn_position(&emitter, position, rotation, Vec2::new(12.0, 24.0));
+
+        // A half turn of a flipped emitter mirrors it back
+        emitter.is_flipped_x = true;
+        emitter.is_flipped_y = false;
+        assert_spawn_position(
+            &emitter,
+            position,
+            std::f32::consts::PI,
+            Vec2::new(14.0, 18.0),
+        );
+    }
+
+    #[test]
+    fn test_emitter_flip_world_offset_mode() {
+        let position = Vec2::new(10.0, 20.0);
+        let rotation = std::f32::consts::FRAC_PI_2;
+
+        let mut emitter = ParticleEmitter::new(ParticleEmitterMetadata {
+            offset: Vec2::new(4.0, 2.0),
+            offset_mode: ParticleEmitterOffsetMode::World,
+            ..Default::default()
+        });
+
+        emitter.is_flipped_x = true;
+        assert_spawn_position(&emitter, position, rotation, Vec2::new(6.0, 22.0));
+    }
+
+    #[test]
+    fn test_emitter_metadata_builder() {
+        let meta = ParticleEmitterMetadata::builder("muzzle_flash")
+            .offset(Vec2::new(4.0, 2.0))
+            .interval(0.25)
+            .emissions(3)
+            .autostart()
+            .build();
+
+        assert_eq!(meta.particle_effect_id, "muzzle_flash");
+        assert_eq!(meta.offset, Vec2::new(4.0, 2.0));
+        assert_eq!(meta.offset_mode, ParticleEmitterOffsetMode::Local);
+        assert_eq!(meta.delay, 0.0);
+        assert_eq!(meta.interval, 0.25);
+        assert_eq!(meta.emissions, Some(3));
+        assert!(meta.should_autostart);
+
+        let meta: ParticleEmitterMetadata = ParticleEmitterMetadata::builder("smoke").into();
+        assert_eq!(meta.emissions, None);
+        assert!(!meta.should_autostart);
+    }
+
+    #[test]
+    fn test_toggle_all_emitters() {
+        let mut world = World::new();
+
+        let emitters = [Some(3), None]
+            .iter()
+            .map(|emissions| {
+                let mut emitter = ParticleEmitter::new(ParticleEmitterMetadata {
+                    interval: 0.5,
+                    emissions: *emissions,
+                    ..Default::default()
+                });
+
+                emitter.emission_cnt = 2;
+                emitter.delay_timer = 1.0;
+                emitter.interval_timer = 0.1;
+
+                emitter
+            })
+            .collect::<Vec<_>>();
+
+        let entity = world.spawn((emitters,));
+
+        set_entity_emitters_active(&world, entity, true);
+
+        for emitter in world.get::<Vec<ParticleEmitter>>(entity).unwrap().iter() {
+            assert!(emitter.is_active);
+            assert_eq!(emitter.emission_cnt, 0);
+            assert_eq!(emitter.delay_timer, 0.0);
+            assert_eq!(emitter.interval_timer, emitter.interval);
+        }
+
+        set_entity_emitters_active(&world, entity, false);
+
+        let emitters = world.get::<Vec<ParticleEmitter>>(entity).unwrap();
+        assert!(emitters.iter().all(|emitter| !emitter.is_active));
+    }
+
+    #[test]
+    fn test_emitter_cache_pool() {
+        let mut pool = EmitterCachePool::new(2, 10.0);
+
+        assert_eq!(pool.get_or_insert_with("a", || Some(1)).copied(), Some(1));
+        assert!(pool.get_or_insert_with("missing", || None).is_none());
+
+        pool.update(1.0);
+        pool.get_or_insert_with("b", || Some(2));
+
+        // Existing caches are returned, in stead of being created again
+        pool.update(1.0);
+        assert_eq!(pool.get_or_insert_with("a", || Some(10)).copied(), Some(1));
+
+        // "b" is now the least recently used cache
+        pool.update(1.0);
+        pool.get_or_insert_with("c", || Some(3));
+        assert_eq!(pool.len(), 2);
+        assert!(pool.contains("a") && pool.contains("c") && !pool.contains("b"));
+
+        // "a" was last used at 2.0 and "c" at 3.0
+        pool.update(9.5);
+        assert!(!pool.contains("a") && pool.contains("c"));
+
+        pool.update(1.0);
+        assert!(pool.is_empty());
+    }
+}
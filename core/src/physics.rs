@@ -114,7 +114,7 @@ impl PhysicsBody {
 
     pub fn as_rect(&self, position: Vec2) -> Rect {
         let position = position + self.offset;
-        Rect::new(position.x, position.y, self.size.width, self.size.height)
+        Rect::from_point_size(position, self.size)
     }
 }
 
@@ -247,7 +247,7 @@ impl RigidBody {
 
     pub fn as_rect(&self, position: Vec2) -> Rect {
         let position = position + self.offset;
-        Rect::new(position.x, position.y, self.size.width, self.size.height)
+        Rect::from_point_size(position, self.size)
     }
 }
 
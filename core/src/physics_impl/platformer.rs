@@ -323,8 +323,8 @@ impl PhysicsWorld {
 
     pub fn is_tag_at(&self, position: Vec2, tag: u8) -> bool {
         for layer in &self.tile_layers {
-            let x = (position.x / layer.tile_size.height) as i32;
-            let y = (position.y / layer.tile_size.width) as i32;
+            let x = (position.x / layer.tile_size.width) as i32;
+            let y = (position.y / layer.tile_size.height) as i32;
 
             let ix = y * (layer.width as i32) + x;
 
@@ -364,8 +364,8 @@ impl PhysicsWorld {
     pub fn collide_tag_at(&self, tag: u8, position: Vec2, size: Size<f32>) -> ColliderKind {
         for layer in &self.tile_layers {
             let check = |position: Vec2| {
-                let y = (position.y / layer.tile_size.width) as i32;
-                let x = (position.x / layer.tile_size.height) as i32;
+                let x = (position.x / layer.tile_size.width) as i32;
+                let y = (position.y / layer.tile_size.height) as i32;
                 let ix = y * (layer.width as i32) + x;
                 if ix >= 0
                     && ix < layer.tiles.len() as i32
@@ -491,3 +491,53 @@ impl PhysicsWorld {
         self.tile_layers.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::map::{MapLayerKind, MapTile};
+
+    fn tile() -> MapTile {
+        MapTile {
+            tile_id: 0,
+            tileset_id: "tileset".to_string(),
+            texture_id: "texture".to_string(),
+            texture: None,
+            texture_coords: Vec2::ZERO,
+            attributes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_solid_at_non_square_tiles() {
+        let mut layer = MapLayer::new("layer", MapLayerKind::TileLayer, true, Size::new(10, 5));
+        layer.tiles[10 + 3] = Some(tile());
+
+        let mut world = PhysicsWorld::default();
+        world.add_layer(DEFAULT_TAG, Size::new(16.0, 32.0), &layer);
+
+        assert!(world.is_solid_at(vec2(3.0 * 16.0 + 1.0, 32.0 + 1.0)));
+        assert!(!world.is_solid_at(vec2(16.0 + 1.0, 2.0 * 32.0 + 1.0)));
+    }
+
+    #[test]
+    fn test_collide_tag_at_non_square_tiles() {
+        let mut layer = MapLayer::new("layer", MapLayerKind::TileLayer, true, Size::new(10, 5));
+        layer.tiles[10 + 3] = Some(tile());
+
+        let mut world = PhysicsWorld::default();
+        world.add_layer(DEFAULT_TAG, Size::new(16.0, 32.0), &layer);
+
+        let size = Size::new(4.0, 4.0);
+
+        assert_eq!(
+            world.collide_tag_at(DEFAULT_TAG, vec2(3.0 * 16.0 + 1.0, 32.0 + 1.0), size),
+            ColliderKind::Solid
+        );
+        assert_eq!(
+            world.collide_tag_at(DEFAULT_TAG, vec2(16.0 + 1.0, 2.0 * 32.0 + 1.0), size),
+            ColliderKind::Empty
+        );
+    }
+}
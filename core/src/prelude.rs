@@ -37,4 +37,5 @@ pub use crate::resources::{assets_dir, loaded_mods, mods_dir};
 pub use macros::*;
 
 pub use crate::rand;
+pub use crate::rng;
 pub use crate::storage;
@@ -101,7 +101,7 @@ pub trait ResourceVecMut: ResourceVec {
     }
 }
 
-const DEFAULT_ASSETS_DIR: &str = "assets/";
+pub const DEFAULT_ASSETS_DIR: &str = "assets/";
 
 static mut ASSETS_DIR: Option<String> = None;
 
@@ -120,6 +120,33 @@ pub fn assets_dir() -> String {
     }
 }
 
+/// This returns an error if `path` is not an existing directory. It is not checked on wasm, where
+/// assets are fetched over the network, so the path is always considered valid.
+pub fn validate_assets_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+
+    if cfg!(not(target_arch = "wasm32")) && !path.is_dir() {
+        return Err(Error::new_message(
+            ErrorKind::File,
+            &format!(
+                "The assets directory '{}' does not exist or is not a directory",
+                path.display()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// This sets the assets directory, like `set_assets_dir`, but only if it passes
+/// `validate_assets_dir`. It must be called before resources are loaded, to have any effect.
+pub fn try_set_assets_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    validate_assets_dir(&path)?;
+    set_assets_dir(path);
+
+    Ok(())
+}
+
 const DEFAULT_MODS_DIR: &str = "mods/";
 
 static mut MODS_DIR: Option<String> = None;
@@ -0,0 +1,220 @@
+//! Deterministic random number generation.
+//!
+//! All gameplay randomness should go through this module, rather than through `rand`, so that a
+//! match can be reproduced from its seed. This is required for lockstep networking, where all
+//! peers have to make the same random choices, in the same order.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub const DEFAULT_RNG_SEED: u64 = 0x5EED_F15F_1647;
+
+// This is the increment of the SplitMix64 generator
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// This scrambles a SplitMix64 state into an output value
+const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Types that can be sampled uniformly from a range. For integers, `high` is exclusive, while
+/// floats are sampled from `low..high`. If `high` is not greater than `low`, `low` is returned.
+pub trait RandomRange: Sized {
+    fn from_sample(sample: u64, low: Self, high: Self) -> Self;
+}
+
+macro_rules! impl_random_range_int {
+    ($($t:ty),*) => {
+        $(
+            impl RandomRange for $t {
+                fn from_sample(sample: u64, low: Self, high: Self) -> Self {
+                    if high <= low {
+                        return low;
+                    }
+
+                    let span = (high as i128 - low as i128) as u64;
+                    (low as i128 + (sample % span) as i128) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_random_range_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl RandomRange for f32 {
+    fn from_sample(sample: u64, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+
+        // The 24 most significant bits fill the mantissa of an `f32` in `0.0..1.0`
+        let unit = (sample >> 40) as f32 / (1u64 << 24) as f32;
+        low + (high - low) * unit
+    }
+}
+
+impl RandomRange for f64 {
+    fn from_sample(sample: u64, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+
+        let unit = (sample >> 11) as f64 / (1u64 << 53) as f64;
+        low + (high - low) * unit
+    }
+}
+
+/// A small, seedable pseudo random number generator (SplitMix64). The same seed will always
+/// produce the same sequence, on all platforms.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix(self.state)
+    }
+
+    pub fn gen_range<T: RandomRange>(&mut self, low: T, high: T) -> T {
+        T::from_sample(self.next_u64(), low, high)
+    }
+
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::new(DEFAULT_RNG_SEED)
+    }
+}
+
+static RNG_SEED: AtomicU64 = AtomicU64::new(DEFAULT_RNG_SEED);
+static RNG_STATE: AtomicU64 = AtomicU64::new(DEFAULT_RNG_SEED);
+
+/// This seeds the match-global RNG and resets its sequence. This should be called with the same
+/// seed, on all peers, when a match starts.
+/// The `rand` RNG is seeded as well, so that randomness in dependencies, like particle jitter in
+/// `ff-particles`, is also repeatable.
+pub fn set_rng_seed(seed: u64) {
+    RNG_SEED.store(seed, Ordering::SeqCst);
+    RNG_STATE.store(seed, Ordering::SeqCst);
+
+    crate::rand::srand(seed);
+}
+
+/// This returns the seed that the match-global RNG was last seeded with
+pub fn rng_seed() -> u64 {
+    RNG_SEED.load(Ordering::SeqCst)
+}
+
+/// This returns the next value from the match-global RNG. This is thread-safe, as every call
+/// advances the shared state atomically, but the sequence will only be repeatable if calls are
+/// made in the same order.
+pub fn next_u64() -> u64 {
+    let state = RNG_STATE
+        .fetch_add(GOLDEN_GAMMA, Ordering::SeqCst)
+        .wrapping_add(GOLDEN_GAMMA);
+
+    mix(state)
+}
+
+/// This samples the match-global RNG uniformly from `low..high`
+pub fn gen_range<T: RandomRange>(low: T, high: T) -> T {
+    T::from_sample(next_u64(), low, high)
+}
+
+pub fn gen_bool() -> bool {
+    next_u64() >> 63 == 1
+}
+
+/// This generates a new seed from the system clock. The result is not deterministic, so this
+/// should only be used to pick the seed of a new match, which must then be shared with any peers.
+pub fn generate_rng_seed() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    let time = js_sys::Date::now() as u64;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(DEFAULT_RNG_SEED);
+
+    mix(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::new(1234);
+        let mut b = Rng::new(1234);
+
+        let a_values = (0..100).map(|_| a.next_u64()).collect::<Vec<_>>();
+        let b_values = (0..100).map(|_| b.next_u64()).collect::<Vec<_>>();
+        assert_eq!(a_values, b_values);
+
+        let mut c = Rng::new(4321);
+        let c_values = (0..100).map(|_| c.next_u64()).collect::<Vec<_>>();
+        assert_ne!(a_values, c_values);
+    }
+
+    #[test]
+    fn test_known_sequence() {
+        // These are the reference outputs of SplitMix64, which guards against the sequence
+        // changing between versions, as that would break replays and netplay between versions
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+        assert_eq!(rng.next_u64(), 0x06C4_5D18_8009_454F);
+    }
+
+    #[test]
+    fn test_gen_range_bounds() {
+        let mut rng = Rng::new(42);
+
+        for _ in 0..1000 {
+            let i = rng.gen_range(-3, 3);
+            assert!((-3..3).contains(&i));
+
+            let u = rng.gen_range(0usize, 7);
+            assert!(u < 7);
+
+            let f = rng.gen_range(0.5f32, 1.5);
+            assert!((0.5..1.5).contains(&f));
+        }
+
+        assert_eq!(rng.gen_range(5, 5), 5);
+        assert_eq!(rng.gen_range(2.0f32, 1.0), 2.0);
+    }
+
+    #[test]
+    fn test_global_rng_is_repeatable() {
+        set_rng_seed(99);
+        let first = (0..10).map(|_| gen_range(0u32, 1000)).collect::<Vec<_>>();
+
+        set_rng_seed(99);
+        let second = (0..10).map(|_| gen_range(0u32, 1000)).collect::<Vec<_>>();
+
+        assert_eq!(first, second);
+        assert_eq!(rng_seed(), 99);
+
+        // The global RNG produces the same sequence as a local one with the same seed
+        let mut rng = Rng::new(99);
+        set_rng_seed(99);
+        for _ in 0..10 {
+            assert_eq!(next_u64(), rng.next_u64());
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::file::read_from_file;
-use crate::math::Size;
+use crate::math::{vec2, Size, Vec2};
 use crate::result::Result;
 
 pub use crate::backend_impl::text::*;
@@ -26,6 +26,8 @@ pub struct TextParams {
     pub font_size: u16,
     pub font_scale: f32,
     pub color: Color,
+    pub outline: Option<TextEffect>,
+    pub shadow: Option<TextEffect>,
 }
 
 impl Default for TextParams {
@@ -38,10 +40,101 @@ impl Default for TextParams {
             font_size: 20,
             font_scale: 1.0,
             color: colors::WHITE,
+            outline: None,
+            shadow: None,
         }
     }
 }
 
+impl TextParams {
+    pub fn with_font(self, font: Font) -> Self {
+        TextParams {
+            font: Some(font),
+            ..self
+        }
+    }
+
+    pub fn with_bounds(self, bounds: Size<f32>) -> Self {
+        TextParams {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    pub fn with_alignment(
+        self,
+        horizontal_align: HorizontalAlignment,
+        vertical_align: VerticalAlignment,
+    ) -> Self {
+        TextParams {
+            horizontal_align,
+            vertical_align,
+            ..self
+        }
+    }
+
+    pub fn with_font_size(self, font_size: u16) -> Self {
+        TextParams { font_size, ..self }
+    }
+
+    pub fn with_color(self, color: Color) -> Self {
+        TextParams { color, ..self }
+    }
+
+    /// Draw the text in `color` at every combination of `offset` around the text, before drawing
+    /// the text itself.
+    pub fn with_outline(self, color: Color, offset: Vec2) -> Self {
+        TextParams {
+            outline: Some(TextEffect { color, offset }),
+            ..self
+        }
+    }
+
+    /// Draw the text in `color`, once, at `offset`, before drawing the text itself.
+    pub fn with_shadow(self, color: Color, offset: Vec2) -> Self {
+        TextParams {
+            shadow: Some(TextEffect { color, offset }),
+            ..self
+        }
+    }
+
+    /// This returns the offsets, and the colors, that the text should be drawn at, in order,
+    /// before the text itself is drawn, to produce the shadow and the outline, if any.
+    pub fn get_effect_offsets(&self) -> Vec<(Vec2, Color)> {
+        let mut res = Vec::new();
+
+        if let Some(shadow) = self.shadow {
+            res.push((shadow.offset, shadow.color));
+        }
+
+        if let Some(outline) = self.outline {
+            let Vec2 { x, y } = outline.offset;
+
+            for offset in [
+                vec2(-x, -y),
+                vec2(0.0, -y),
+                vec2(x, -y),
+                vec2(-x, 0.0),
+                vec2(x, 0.0),
+                vec2(-x, y),
+                vec2(0.0, y),
+                vec2(x, y),
+            ] {
+                res.push((offset, outline.color));
+            }
+        }
+
+        res
+    }
+}
+
+/// A color and an offset, used for text outlines and shadows.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextEffect {
+    pub color: Color,
+    pub offset: Vec2,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HorizontalAlignment {
@@ -103,6 +103,12 @@ impl Texture2D {
         Self::from_bytes(&bytes, format, kind, filter_mode, frame_size)
     }
 
+    /// This returns a handle that does not refer to any texture, for use where a texture is
+    /// required but never drawn, like in tests. Dereferencing it will panic.
+    pub const fn invalid() -> Self {
+        Texture2D(usize::MAX)
+    }
+
     pub(crate) fn set_id(&self, id: &str) {
         unsafe { TEXTURE_IDS.get_or_insert_with(HashMap::new) }.insert(id.to_string(), self.0);
     }
@@ -6,6 +6,7 @@ use crate::math::{Size, Vec2};
 
 pub const DEFAULT_MSAA_SAMPLES: Option<u16> = Some(1);
 pub const DEFAULT_MAX_FPS: Option<u16> = Some(120);
+pub const DEFAULT_RENDER_SCALE: f32 = 1.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoConfig {
@@ -25,6 +26,19 @@ pub struct VideoConfig {
     pub is_vsync_enabled: bool,
     #[serde(default, rename = "show-fps")]
     pub should_show_fps: bool,
+    /// If this is set, the viewport will be letterboxed to keep this aspect ratio (width divided
+    /// by height), with black bars filling the rest of the window.
+    #[serde(
+        default,
+        rename = "letterbox-aspect-ratio",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub letterbox_aspect_ratio: Option<f32>,
+    /// The scene is rendered at the viewport resolution multiplied by this and then scaled to fit
+    /// the viewport. Values below `1.0` can be used to improve performance on weak hardware.
+    /// The GUI is always drawn at the full resolution of the window.
+    #[serde(default = "VideoConfig::default_render_scale", rename = "render-scale")]
+    pub render_scale: f32,
 }
 
 impl VideoConfig {
@@ -35,6 +49,10 @@ impl VideoConfig {
     pub(crate) fn default_max_fps() -> Option<u16> {
         DEFAULT_MAX_FPS
     }
+
+    pub(crate) fn default_render_scale() -> f32 {
+        DEFAULT_RENDER_SCALE
+    }
 }
 
 impl Default for VideoConfig {
@@ -44,6 +62,8 @@ impl Default for VideoConfig {
             max_fps: DEFAULT_MAX_FPS,
             is_vsync_enabled: false,
             should_show_fps: false,
+            letterbox_aspect_ratio: None,
+            render_scale: DEFAULT_RENDER_SCALE,
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::math::{vec2, Mat4, Size, Vec2};
 use crate::window::window_size;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Viewport {
     pub x: f32,
     pub y: f32,
@@ -65,3 +65,135 @@ pub fn resize_viewport(width: f32, height: f32) {
     viewport.width = width;
     viewport.height = height;
 }
+
+/// This returns the largest viewport with the specified aspect ratio that will fit inside a window
+/// of `window_size`, centered in the window. The remaining area of the window is left for the
+/// letterbox (or pillarbox) bars. Viewport position and size are rounded to whole pixels.
+pub fn letterboxed_viewport(window_size: Size<f32>, aspect_ratio: f32) -> Viewport {
+    let window_aspect_ratio = window_size.width / window_size.height;
+
+    let (width, height) = if window_aspect_ratio > aspect_ratio {
+        let height = window_size.height;
+        ((height * aspect_ratio).round(), height)
+    } else {
+        let width = window_size.width;
+        (width, (width / aspect_ratio).round())
+    };
+
+    let x = ((window_size.width - width) / 2.0).floor();
+    let y = ((window_size.height - height) / 2.0).floor();
+
+    Viewport::new(x, y, width, height)
+}
+
+/// This updates the viewport to fit a window of `window_size`. If `letterbox_aspect_ratio` is set,
+/// the viewport will be letterboxed to keep that aspect ratio, otherwise it will fill the window.
+pub fn update_viewport(window_size: Size<f32>, letterbox_aspect_ratio: Option<f32>) {
+    let new_viewport = match letterbox_aspect_ratio {
+        Some(aspect_ratio) if aspect_ratio > 0.0 => letterboxed_viewport(window_size, aspect_ratio),
+        _ => Viewport::new(0.0, 0.0, window_size.width, window_size.height),
+    };
+
+    *viewport_mut() = new_viewport;
+}
+
+pub const MIN_RENDER_SCALE: f32 = 0.25;
+pub const MAX_RENDER_SCALE: f32 = 2.0;
+
+static mut RENDER_SCALE: f32 = 1.0;
+
+/// This returns the scale of the resolution that the scene is rendered at, relative to the
+/// viewport.
+pub fn render_scale() -> f32 {
+    unsafe { RENDER_SCALE }
+}
+
+/// This sets the scale of the resolution that the scene is rendered at, relative to the viewport.
+/// The scale is clamped to the range between `MIN_RENDER_SCALE` and `MAX_RENDER_SCALE`.
+pub fn set_render_scale(scale: f32) {
+    unsafe { RENDER_SCALE = clamp_render_scale(scale) };
+}
+
+fn clamp_render_scale(scale: f32) -> f32 {
+    if scale.is_nan() {
+        1.0
+    } else {
+        scale.clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE)
+    }
+}
+
+/// This returns the resolution that a viewport of `viewport_size` is rendered at, with a render
+/// scale of `render_scale`. Each dimension is rounded to whole pixels and will be at least one.
+pub fn scaled_render_resolution(viewport_size: Size<f32>, render_scale: f32) -> Size<u32> {
+    let render_scale = clamp_render_scale(render_scale);
+
+    Size::new(
+        ((viewport_size.width * render_scale).round() as u32).max(1),
+        ((viewport_size.height * render_scale).round() as u32).max(1),
+    )
+}
+
+/// This returns the resolution that the scene is rendered at, with the current viewport and
+/// render scale.
+pub fn render_resolution() -> Size<u32> {
+    scaled_render_resolution(viewport_size(), render_scale())
+}
+
+/// This translates a position in window space, like the cursor position reported by the window,
+/// into viewport space, where the origin is the top-left corner of the viewport.
+pub fn to_viewport_position(window_position: Vec2) -> Vec2 {
+    window_position - viewport().position()
+}
+
+/// This translates a position in viewport space into window space. This is needed when drawing
+/// GUI elements, as the GUI is drawn to, and hit-tested against, the whole window.
+pub fn to_window_position(viewport_position: Vec2) -> Vec2 {
+    viewport_position + viewport().position()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letterboxed_viewport() {
+        let window_size = Size::new(1000.0, 500.0);
+
+        let viewport = letterboxed_viewport(window_size, 16.0 / 9.0);
+        assert_eq!(viewport, Viewport::new(55.0, 0.0, 889.0, 500.0));
+
+        let viewport = letterboxed_viewport(window_size, 4.0);
+        assert_eq!(viewport, Viewport::new(0.0, 125.0, 1000.0, 250.0));
+
+        let viewport = letterboxed_viewport(window_size, 2.0);
+        assert_eq!(viewport, Viewport::new(0.0, 0.0, 1000.0, 500.0));
+    }
+
+    #[test]
+    fn test_scaled_render_resolution() {
+        let viewport_size = Size::new(1920.0, 1080.0);
+
+        assert_eq!(
+            scaled_render_resolution(viewport_size, 1.0),
+            Size::new(1920, 1080)
+        );
+        assert_eq!(
+            scaled_render_resolution(viewport_size, 0.5),
+            Size::new(960, 540)
+        );
+        assert_eq!(
+            scaled_render_resolution(viewport_size, 0.3333),
+            Size::new(640, 360)
+        );
+
+        // The scale is clamped, and the resolution is never zero
+        assert_eq!(
+            scaled_render_resolution(viewport_size, 0.0),
+            Size::new(480, 270)
+        );
+        assert_eq!(
+            scaled_render_resolution(Size::new(1.0, 1.0), 0.25),
+            Size::new(1, 1)
+        );
+    }
+}
@@ -24,7 +24,7 @@ pub struct WindowConfig {
 impl Default for WindowConfig {
     fn default() -> Self {
         WindowConfig {
-            mode: WindowMode::Borderless,
+            mode: WindowMode::default(),
             is_high_dpi: false,
         }
     }
@@ -37,11 +37,19 @@ pub enum WindowMode {
         #[serde(flatten, default = "WindowMode::default_window_size")]
         size: Resolution,
     },
-    Borderless,
+    /// `monitor_index` is the index of the monitor to use, in the list of available monitors. If
+    /// it is `None`, the current monitor is used and if it is out of range, the primary monitor.
+    Borderless {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        monitor_index: Option<usize>,
+    },
+    /// `monitor_index` works the same way as it does for `Borderless`
     Fullscreen {
         resolution: Resolution,
         bit_depth: u16,
         refresh_rate: u16,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        monitor_index: Option<usize>,
     },
 }
 
@@ -53,6 +61,42 @@ impl WindowMode {
 
 impl Default for WindowMode {
     fn default() -> Self {
-        WindowMode::Borderless
+        WindowMode::Borderless {
+            monitor_index: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parsing::{deserialize_toml_bytes, serialize_toml_string};
+
+    #[test]
+    fn test_window_mode_monitor_index() {
+        let config: WindowConfig = deserialize_toml_bytes(b"mode = \"borderless\"").unwrap();
+        assert_eq!(
+            config.mode,
+            WindowMode::Borderless {
+                monitor_index: None
+            }
+        );
+
+        let config: WindowConfig =
+            deserialize_toml_bytes(b"mode = \"borderless\"\nmonitor_index = 1").unwrap();
+        assert_eq!(
+            config.mode,
+            WindowMode::Borderless {
+                monitor_index: Some(1)
+            }
+        );
+
+        let toml = serialize_toml_string(&config).unwrap();
+        let loaded: WindowConfig = deserialize_toml_bytes(toml.as_bytes()).unwrap();
+        assert_eq!(loaded.mode, config.mode);
+
+        let toml = serialize_toml_string(&WindowConfig::default()).unwrap();
+        assert!(!toml.contains("monitor_index"));
     }
 }
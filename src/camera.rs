@@ -22,6 +22,9 @@ enum ShakeType {
 
 const CAMERA_FOLLOW_BUFFER_CAPACITY: usize = 20;
 
+const CAMERA_BORDER_X: f32 = 150.0;
+const CAMERA_BORDER_Y: f32 = 200.0;
+
 pub struct CameraController {
     follow_buffer: Vec<(Vec2, f32)>,
     shake: Vec<Shake>,
@@ -169,6 +172,41 @@ impl Default for CameraController {
     }
 }
 
+// This returns the size of the area that the camera will show, in order to fit players spread
+// across `spread`
+fn get_camera_scale(spread: Vec2, aspect_ratio: f32) -> Vec2 {
+    let mut scale = spread.abs() + vec2(CAMERA_BORDER_X * 2.0, CAMERA_BORDER_Y * 2.0);
+
+    if scale.x > scale.y * aspect_ratio {
+        scale.y = scale.x / aspect_ratio;
+    }
+
+    scale
+}
+
+fn clamp_camera_position(position: Vec2, scale: Vec2, bounds: Size<f32>) -> Vec2 {
+    let mut res = position;
+
+    // bottom camera bound
+    if scale.y / 2.0 + res.y > bounds.height {
+        res.y = bounds.height - scale.y / 2.0;
+    }
+
+    res
+}
+
+/// This returns the position that the camera will settle at when following a single player,
+/// centered at `player_position`. The editor uses this to preview background parallax the way it
+/// will look in game.
+pub fn get_single_player_camera_position(
+    player_position: Vec2,
+    aspect_ratio: f32,
+    bounds: Size<f32>,
+) -> Vec2 {
+    let scale = get_camera_scale(Vec2::ZERO, aspect_ratio);
+    clamp_camera_position(player_position, scale, bounds)
+}
+
 pub fn update_camera(world: &mut World, _delta_time: f32) -> Result<()> {
     let mut player_rects = Vec::new();
 
@@ -210,7 +248,7 @@ pub fn update_camera(world: &mut World, _delta_time: f32) -> Result<()> {
 
         let player_cnt = player_rects.len();
         for rect in player_rects {
-            let camera_pox_middle = rect.point() + rect.size() / 2.0;
+            let camera_pox_middle = rect.point() + rect.size().as_vec2() / 2.0;
             middle_point += camera_pox_middle;
 
             min = min.min(camera_pox_middle);
@@ -219,22 +257,11 @@ pub fn update_camera(world: &mut World, _delta_time: f32) -> Result<()> {
 
         middle_point /= player_cnt as f32;
 
-        let border_x = 150.0;
-        let border_y = 200.0;
-        let mut scale = (max - min).abs() + vec2(border_x * 2.0, border_y * 2.0);
-
-        if scale.x > scale.y * aspect_ratio {
-            scale.y = scale.x / aspect_ratio;
-        }
+        let scale = get_camera_scale(max - min, aspect_ratio);
 
         let mut zoom = scale.y;
 
-        let bounds = camera.bounds;
-
-        // bottom camera bound
-        if scale.y / 2.0 + middle_point.y > bounds.height {
-            middle_point.y = bounds.height - scale.y / 2.0;
-        }
+        middle_point = clamp_camera_position(middle_point, scale, camera.bounds);
 
         if let Some(override_position) = camera_ctrl.position_override {
             middle_point = override_position;
@@ -282,6 +309,8 @@ pub fn update_camera(world: &mut World, _delta_time: f32) -> Result<()> {
             target: transform.position,
             zoom: camera.zoom,
             rotation: camera.rotation,
+            viewport: scene_viewport(),
+            render_target: scene_render_target(),
             ..Camera2D::default()
         };
 
@@ -105,8 +105,8 @@ pub fn update_crabs(world: &mut World) {
 
         let pos = transform.position;
 
-        let rand_bool = |true_bias: u8| rand::gen_range(0u8, 2 + true_bias) > 0;
-        let rand_delay = |min, max| Timer::from_secs_f32(rand::gen_range(min, max));
+        let rand_bool = |true_bias: u8| rng::gen_range(0u8, 2 + true_bias) > 0;
+        let rand_delay = |min, max| Timer::from_secs_f32(rng::gen_range(min, max));
 
         let next_scary_thing = || {
             for (scary_entity, transform) in world.query::<With<Player, &Transform>>().iter() {
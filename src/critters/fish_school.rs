@@ -66,7 +66,7 @@ pub fn spawn_fish_school(world: &mut World, spawn_position: Vec2) -> Result<Enti
     let fish_school_icon_texture = get_texture(FISH_SCHOOL_ICON_TEXTURE_ID);
     let fish_school_icon_texture_size = fish_school_icon_texture.size();
 
-    let rand_bool = || rand::gen_range(0u8, 2) == 0;
+    let rand_bool = || rng::gen_range(0u8, 2) == 0;
 
     let mut fish_count = FISH_COUNT_BASE as i32;
     if rand_bool() {
@@ -89,11 +89,11 @@ pub fn spawn_fish_school(world: &mut World, spawn_position: Vec2) -> Result<Enti
 
     for _ in 0..fish_count {
         let spawn_point = vec2(
-            rand::gen_range(fish_spawn_min.x, fish_spawn_max.x),
-            rand::gen_range(fish_spawn_min.y, fish_spawn_max.y),
+            rng::gen_range(fish_spawn_min.x, fish_spawn_max.x),
+            rng::gen_range(fish_spawn_min.y, fish_spawn_max.y),
         );
 
-        let texture_index = rand::gen_range(0, FISH_TEXTURE_IDS.len());
+        let texture_index = rng::gen_range(0, FISH_TEXTURE_IDS.len());
         let texture = get_texture(FISH_TEXTURE_IDS[texture_index]);
 
         let fish_entity = world.spawn((
@@ -102,11 +102,11 @@ pub fn spawn_fish_school(world: &mut World, spawn_position: Vec2) -> Result<Enti
                     from: spawn_point,
                     to: spawn_point,
                 },
-                state_timer: Timer::from_secs_f32(rand::gen_range(0.2, 1.0)),
+                state_timer: Timer::from_secs_f32(rng::gen_range(0.2, 1.0)),
             },
             Transform::from(spawn_point),
             Drawable::new_animated_sprite(
-                rand::gen_range(DRAW_ORDER_MIN, DRAW_ORDER_MAX + 1),
+                rng::gen_range(DRAW_ORDER_MIN, DRAW_ORDER_MAX + 1),
                 texture,
                 texture.frame_size(),
                 &[Animation {
@@ -187,12 +187,12 @@ pub fn update_fish_schools(world: &mut World) {
                 }
             }
 
-            let rand_bool = || rand::gen_range(0u8, 2) > 0;
-            let rand_delay = |min, max| Timer::from_secs_f32(rand::gen_range(min, max));
+            let rand_bool = || rng::gen_range(0u8, 2) > 0;
+            let rand_delay = |min, max| Timer::from_secs_f32(rng::gen_range(min, max));
 
             let pick_next_move = || {
                 if !school.is_grouped {
-                    let target_point = pos.lerp(school.center, rand::gen_range(0.1, 0.4));
+                    let target_point = pos.lerp(school.center, rng::gen_range(0.1, 0.4));
 
                     (
                         FishState::Moving {
@@ -203,8 +203,8 @@ pub fn update_fish_schools(world: &mut World) {
                     )
                 } else if rand_bool() {
                     let target_point = vec2(
-                        pos.x + rand::gen_range(-20.0, 20.0),
-                        pos.y + rand::gen_range(-20.0, 20.0),
+                        pos.x + rng::gen_range(-20.0, 20.0),
+                        pos.y + rng::gen_range(-20.0, 20.0),
                     );
                     (
                         FishState::Moving {
@@ -214,7 +214,7 @@ pub fn update_fish_schools(world: &mut World) {
                         rand_delay(0.5, 1.5),
                     )
                 } else {
-                    let target_point = pos.lerp(school.spawn_pos, rand::gen_range(0.10, 0.25));
+                    let target_point = pos.lerp(school.spawn_pos, rng::gen_range(0.10, 0.25));
                     (
                         FishState::Moving {
                             from: *pos,
@@ -226,11 +226,12 @@ pub fn update_fish_schools(world: &mut World) {
             };
 
             if let Some(collision_rect) = collides_with {
-                let collision_center = collision_rect.point() + collision_rect.size() / 2.0;
+                let collision_center =
+                    collision_rect.point() + collision_rect.size().as_vec2() / 2.0;
                 let diff = *pos - collision_center;
                 fish.state = FishState::Moving {
                     from: *pos,
-                    to: *pos + diff.normalize() * rand::gen_range(30.0, 60.0),
+                    to: *pos + diff.normalize() * rng::gen_range(30.0, 60.0),
                 };
                 fish.state_timer = rand_delay(0.2, 0.6);
             } else if fish.state_timer.has_finished() {
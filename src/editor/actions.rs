@@ -1,11 +1,15 @@
 use std::any::TypeId;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 use ff_core::prelude::*;
 
 use crate::editor::gui::windows::Window;
-use ff_core::map::{Map, MapLayer, MapLayerKind, MapTile, MapTileset};
-use ff_core::map::{MapBackgroundLayer, MapObject, MapObjectKind};
+use crate::editor::EditorSelection;
+use ff_core::map::{Anchor, Map, MapLayer, MapLayerKind, MapTile, MapTileset};
+use ff_core::map::{
+    MapBackgroundLayer, MapObject, MapObjectKind, MapRequirements, MapSpawnPoint, MapSpawnPointTag,
+};
 
 /// These are all the actions available for the GUI and other sub-systems of the editor.
 /// If you need to perform multiple actions in one call, use the `Batch` variant.
@@ -35,16 +39,48 @@ pub enum EditorAction {
         layer_id: String,
         index: usize,
     },
+    OpenSpawnPointPropertiesWindow(usize),
+    ToggleMessageLogWindow,
+    ClearMessageLog,
+    OpenKeybindsWindow,
+    /// This rebinds the editor action with the specified name, as it is named in the config file
+    SetKeybind {
+        name: String,
+        keybind: Keybind,
+    },
+    ResetKeybinds,
     CloseWindow(TypeId),
     SelectTile {
         id: u32,
         tileset_id: String,
     },
+    /// This selects a placed tile's tile id, and, if enabled in the editor config, its
+    /// attributes, so that subsequently placed tiles inherit them
+    PickTile {
+        id: u32,
+        tileset_id: String,
+        attributes: Vec<String>,
+    },
+    /// This clears the attributes picked by `PickTile`, so that placed tiles get no attributes
+    ClearPickedTileAttributes,
     UpdateTileAttributes {
         index: usize,
         layer_id: String,
         attributes: Vec<String>,
     },
+    OpenTileAttributesWindow {
+        layer_id: String,
+        region: URect,
+    },
+    /// This adds the attributes in `set` to, and removes the attributes in `unset` from, every
+    /// tile in `region` of the specified tile layer, as a single undoable action. Attributes that
+    /// are in neither list are left as they are on each tile.
+    UpdateTileAttributesInRegion {
+        layer_id: String,
+        region: URect,
+        set: Vec<String>,
+        unset: Vec<String>,
+    },
     SelectLayer(String),
     SetLayerDrawOrderIndex {
         id: String,
@@ -57,10 +93,81 @@ pub enum EditorAction {
         index: Option<usize>,
     },
     DeleteLayer(String),
+    /// This flattens the specified tile layer into the tile layer beneath it, in the draw order,
+    /// and removes it. Where both layers have a tile, the tile of the upper layer is kept.
+    MergeLayerDown(String),
+    /// This changes the kind of a layer, or whether it has collision, in place. Only conversions
+    /// that can not lose any data are supported:
+    ///
+    /// - A tile layer can have its collision enabled or disabled
+    /// - An empty tile layer can be converted to an object layer, which never has collision
+    /// - An empty object layer can be converted to a tile layer, with or without collision
+    ///
+    /// Tiles can not be represented as objects, or the other way around, so converting a tile
+    /// layer that has tiles, or an object layer that has objects, is refused.
+    ConvertLayer {
+        id: String,
+        kind: MapLayerKind,
+        has_collision: bool,
+    },
     UpdateLayer {
         id: String,
         is_visible: bool,
+        is_locked: bool,
+    },
+    OpenLayerGroupWindow(String),
+    /// This moves a layer into the specified group, or out of its current group, if `group` is
+    /// `None`
+    SetLayerGroup {
+        id: String,
+        group: Option<String>,
+    },
+    /// This sets the visibility of all the layers in a group, as a single undoable action
+    SetLayerGroupVisibility {
+        group: String,
+        is_visible: bool,
+    },
+    OpenLayerTintWindow(String),
+    /// This sets the tint of a layer, which is multiplied with the color of all its tiles
+    SetLayerTint {
+        id: String,
+        tint: Color,
+    },
+    /// This sets the snap to grid override of a layer. Set to `None` to use the global setting.
+    SetLayerSnapToGrid {
+        id: String,
+        should_snap_to_grid: Option<bool>,
+    },
+    /// This will hide all layers but the selected one, without changing the visibility of any
+    /// layers, or show them again if a layer is already soloed
+    ToggleSoloLayer,
+    OpenShiftLayerWindow(String),
+    /// This will translate all the tiles of a tile layer by `offset`, in tiles. Tiles moved beyond
+    /// the edges of the layer are wrapped around if `wrap` is `true` and discarded if not.
+    ShiftLayer {
+        id: String,
+        offset: IVec2,
+        wrap: bool,
     },
+    OpenResizeMapWindow,
+    /// This resizes the map grid to `grid_size`, keeping existing tiles in place relative to
+    /// `anchor`. Objects and spawn points that end up outside of the map are clamped to its edges.
+    ResizeMap {
+        grid_size: Size<u32>,
+        anchor: Anchor,
+    },
+    OpenMapPropertiesWindow,
+    /// This sets the position of the map in world space. Objects and spawn points are stored
+    /// relative to this, so they move along with the tiles.
+    SetWorldOffset(Vec2),
+    /// This sets the ambient color of the map, which is multiplied with everything the map draws
+    SetAmbientColor(Color),
+    /// This sets the id of the music that is played while the map is loaded. Set to `None` to
+    /// play the default music.
+    SetMapMusic(Option<String>),
+    /// This sets the game modes and player counts that the map is made for. These are part of the
+    /// map metadata, rather than the map, so this can not be undone.
+    SetMapRequirements(MapRequirements),
     SelectTileset(String),
     OpenImportWindow(usize),
     Import {
@@ -99,17 +206,89 @@ pub enum EditorAction {
         kind: MapObjectKind,
         position: Vec2,
     },
+    /// This sets the collider size override of an item. Setting it to `None` will make the item
+    /// use the collider size from its metadata.
+    SetObjectColliderSize {
+        layer_id: String,
+        index: usize,
+        collider_size: Option<Size<f32>>,
+    },
+    /// This sets the scale override of a decoration. Setting it to `None` will make the decoration
+    /// use the uniform scale from its metadata.
+    SetObjectScale {
+        layer_id: String,
+        index: usize,
+        scale: Option<Vec2>,
+    },
+    /// This moves the specified objects by `offset`, as a single undoable action. This is used to
+    /// move the members of an object group together.
+    MoveObjects {
+        layer_id: String,
+        indices: Vec<usize>,
+        offset: Vec2,
+    },
+    /// This adds the specified objects to a new group, so that they are selected and moved
+    /// together. Objects that are already in a group are moved to the new group.
+    GroupObjects {
+        layer_id: String,
+        indices: Vec<usize>,
+    },
+    /// This removes all the objects in the group from it
+    UngroupObjects {
+        layer_id: String,
+        group_id: String,
+    },
+    /// This moves an object to the end of another object layer, keeping all its fields
+    MoveObjectToLayer {
+        from_layer: String,
+        index: usize,
+        to_layer: String,
+    },
+    /// This moves an object to `new_index` in its layer. Objects are drawn in the order they are
+    /// stored in, so this is used to bring an object to the front, or send it to the back.
+    ReorderObject {
+        layer_id: String,
+        index: usize,
+        new_index: usize,
+    },
+    /// This copies the specified objects to the editor clipboard, replacing its contents
+    CopyObjects {
+        layer_id: String,
+        indices: Vec<usize>,
+    },
+    /// This pastes the objects in the editor clipboard, with the first object at `position`.
+    /// Objects are pasted into the selected layer, if it is an object layer, or the first object
+    /// layer in the draw order, if it is not.
+    PasteObjects(Vec2),
     CreateSpawnPoint(Vec2),
     DeleteSpawnPoint(usize),
     MoveSpawnPoint {
         index: usize,
         position: Vec2,
     },
+    OpenArrangeSpawnPointsWindow,
+    /// This spreads the spawn points evenly along the width of the map, at `y`, as a single
+    /// undoable action
+    DistributeSpawnPoints {
+        y: f32,
+    },
+    /// This moves the spawn points on the right half of the map to mirror the ones on the left
+    /// half, as a single undoable action
+    MirrorSpawnPoints,
+    SetSpawnPointFacing {
+        index: usize,
+        is_facing_left: bool,
+    },
+    SetSpawnPointTag {
+        index: usize,
+        tag: MapSpawnPointTag,
+    },
     PlaceTile {
         id: u32,
         layer_id: String,
         tileset_id: String,
         coords: UVec2,
+        attributes: Vec<String>,
     },
     RemoveTile {
         layer_id: String,
@@ -124,11 +303,85 @@ pub enum EditorAction {
     OpenCreateMapWindow,
     OpenMap(usize),
     OpenLoadMapWindow,
-    SaveMap(Option<String>),
+    /// If this holds a name, the map will be saved as a new user map, with that name and in the
+    /// format registered for the specified extension. If not, the map will be saved to its
+    /// current path.
+    SaveMap(Option<(String, String)>),
     OpenSaveMapWindow,
-    DeleteMap(usize),
+    /// This will render the current map to an offscreen target and replace the preview of the
+    /// map resource with the result. The new preview will be written to disk on next save.
+    RegeneratePreview,
+    /// This sets the region, in map coords, that prefabs and stamps are captured from. Set to
+    /// `None` to clear the current selection.
+    SelectRegion(Option<URect>),
+    OpenSavePrefabWindow(URect),
+    /// This will capture the tiles and objects in `region` to a prefab and save it to disk
+    SavePrefab {
+        id: String,
+        region: URect,
+    },
+    OpenPrefabListWindow,
+    /// This will select the prefab with the specified id and switch to the prefab placement tool
+    SelectPrefab(String),
+    /// This will stamp the prefab with the specified id onto the map, with its top-left corner
+    /// at `coords`, as a single undoable action
+    StampPrefab {
+        id: String,
+        coords: UVec2,
+    },
+    /// This will copy the tiles and objects in `region` to the system clipboard, as text
+    CopyRegionToClipboard(URect),
+    /// This will paste a region of tiles and objects from the system clipboard onto the map, with
+    /// its top-left corner at `coords`, as a single undoable action
+    PasteRegionFromClipboard(UVec2),
+    OpenSaveStampWindow {
+        layer_id: String,
+        region: URect,
+    },
+    /// This will capture the tiles of the specified tile layer in `region` to a stamp and save it
+    /// to disk
+    SaveStamp {
+        id: String,
+        layer_id: String,
+        region: URect,
+    },
+    OpenStampPaletteWindow,
+    /// This will select the stamp with the specified id and switch to the stamp tool
+    SelectStamp(String),
+    /// This will paint the stamp with the specified id onto the specified tile layer, with its
+    /// top-left corner at `coords`, as a single undoable action
+    PaintStamp {
+        id: String,
+        layer_id: String,
+        coords: UVec2,
+    },
+    OpenReplaceTileWindow {
+        layer_id: Option<String>,
+        from: Option<(String, u32)>,
+    },
+    /// This will replace every tile matching `from`, as a tileset id and a tile id, with `to`, as
+    /// a single undoable action. If `layer_id` is `None`, tiles are replaced in all tile layers.
+    ReplaceTile {
+        layer_id: Option<String>,
+        from: (String, u32),
+        to: (String, u32),
+    },
+    /// This asks the user to confirm, before the map is deleted with `DeleteMap`
+    OpenDeleteMapDialog(usize),
+    /// This deletes the file of the user map with the specified path. Built-in maps can not be
+    /// deleted.
+    DeleteMap(String),
+    /// This will start a local game on a copy of the map being edited. The editor, including its
+    /// history, is kept, and will resume when the test play session is stopped.
+    TestPlay,
     ExitToMainMenu,
+    /// This opens the file browser of the OS at the location of the map file
+    RevealMapInFolder,
+    /// This requests to quit to desktop. If there are unsaved changes, the user will be asked to
+    /// confirm first.
     QuitToDesktop,
+    /// This quits to desktop without asking about unsaved changes
+    ConfirmQuit,
 }
 
 impl EditorAction {
@@ -144,6 +397,42 @@ impl EditorAction {
     pub fn then(self, action: EditorAction) -> Self {
         Self::batch(&[self, action])
     }
+
+    /// This returns the ids of the layers, named by the action, whose contents will be modified by
+    /// it. This is used to discard actions targeting locked layers. Layers that an action only
+    /// finds when it is applied, like the layer that objects are pasted into, are not included.
+    pub fn get_layer_ids(&self) -> Vec<&str> {
+        match self {
+            EditorAction::OpenCreateObjectWindow { layer_id, .. }
+            | EditorAction::CreateObject { layer_id, .. }
+            | EditorAction::DeleteObject { layer_id, .. }
+            | EditorAction::UpdateObject { layer_id, .. }
+            | EditorAction::SetObjectColliderSize { layer_id, .. }
+            | EditorAction::SetObjectScale { layer_id, .. }
+            | EditorAction::MoveObjects { layer_id, .. }
+            | EditorAction::GroupObjects { layer_id, .. }
+            | EditorAction::UngroupObjects { layer_id, .. }
+            | EditorAction::ReorderObject { layer_id, .. }
+            | EditorAction::UpdateTileAttributes { layer_id, .. }
+            | EditorAction::UpdateTileAttributesInRegion { layer_id, .. }
+            | EditorAction::PlaceTile { layer_id, .. }
+            | EditorAction::RemoveTile { layer_id, .. }
+            | EditorAction::PaintStamp { layer_id, .. }
+            | EditorAction::ReplaceTile {
+                layer_id: Some(layer_id),
+                ..
+            } => vec![layer_id],
+            EditorAction::MergeLayerDown(id)
+            | EditorAction::ConvertLayer { id, .. }
+            | EditorAction::ShiftLayer { id, .. } => vec![id],
+            EditorAction::MoveObjectToLayer {
+                from_layer,
+                to_layer,
+                ..
+            } => vec![from_layer, to_layer],
+            _ => Vec::new(),
+        }
+    }
 }
 
 /// All actions that modify map data should implement this trait
@@ -168,6 +457,145 @@ pub trait UndoableAction {
     fn is_redundant(&self, _map: &Map) -> bool {
         false
     }
+
+    /// Implement this for actions that record a change of editor state, in stead of a change to
+    /// map data. It should return the selection that the editor should restore after the action
+    /// has been undone (`is_undo` is `true`) or redone.
+    fn get_selection(&self, _is_undo: bool) -> Option<EditorSelection> {
+        None
+    }
+
+    /// Implement this for actions that change a limited area of the map. It should return the
+    /// rect, in world space, that holds the changes, so that they can be highlighted when the
+    /// action is undone or redone. It is called after the action has been undone or redone, so
+    /// `map` is in the resulting state.
+    fn get_affected_rect(&self, _map: &Map) -> Option<Rect> {
+        None
+    }
+}
+
+// This returns the rect of the tile at `coords`, in world space
+fn get_tile_rect(map: &Map, coords: UVec2) -> Rect {
+    Rect::from_point_size(map.to_position(coords), map.tile_size)
+}
+
+// Objects and spawn points have no size of their own, so the rect of a tile at their position,
+// which is relative to the world offset of the map, is used in stead
+fn get_point_rect(map: &Map, position: Vec2) -> Rect {
+    Rect::from_point_size(map.world_offset + position, map.tile_size)
+}
+
+/// This records a change of selection and does not modify the map.
+/// It is only added to history if `record-selection-history` is enabled in the editor config.
+#[derive(Debug)]
+pub struct SelectionAction {
+    selection: EditorSelection,
+    old_selection: EditorSelection,
+}
+
+impl SelectionAction {
+    pub fn new(old_selection: EditorSelection, selection: EditorSelection) -> Self {
+        SelectionAction {
+            selection,
+            old_selection,
+        }
+    }
+}
+
+impl UndoableAction for SelectionAction {
+    fn apply(&mut self, _map: &mut Map) -> Result<()> {
+        Ok(())
+    }
+
+    fn undo(&mut self, _map: &mut Map) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_redundant(&self, _map: &Map) -> bool {
+        self.selection == self.old_selection
+    }
+
+    fn get_selection(&self, is_undo: bool) -> Option<EditorSelection> {
+        if is_undo {
+            Some(self.old_selection.clone())
+        } else {
+            Some(self.selection.clone())
+        }
+    }
+}
+
+/// This applies several actions as one, so that they are undone and redone together.
+/// If any of the actions fail to apply, the ones that have already been applied will be undone.
+pub struct BatchedAction {
+    actions: Vec<Box<dyn UndoableAction>>,
+}
+
+impl BatchedAction {
+    pub fn new(actions: Vec<Box<dyn UndoableAction>>) -> Self {
+        BatchedAction { actions }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl UndoableAction for BatchedAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        for i in 0..self.actions.len() {
+            if let Err(err) = self.actions[i].apply(map) {
+                for action in self.actions[..i].iter_mut().rev() {
+                    action.undo(map)?;
+                }
+
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        for action in self.actions.iter_mut().rev() {
+            action.undo(map)?;
+        }
+
+        Ok(())
+    }
+
+    fn redo(&mut self, map: &mut Map) -> Result<()> {
+        for action in &mut self.actions {
+            action.redo(map)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.actions.iter().all(|action| action.is_redundant(map))
+    }
+
+    // Undo restores the selection from before the first change of selection in the batch and
+    // redo the one after the last
+    fn get_selection(&self, is_undo: bool) -> Option<EditorSelection> {
+        if is_undo {
+            self.actions
+                .iter()
+                .find_map(|action| action.get_selection(true))
+        } else {
+            self.actions
+                .iter()
+                .rev()
+                .find_map(|action| action.get_selection(false))
+        }
+    }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        self.actions
+            .iter()
+            .filter_map(|action| action.get_affected_rect(map))
+            .reduce(|a, b| a.combine_with(b))
+    }
 }
 
 #[derive(Debug)]
@@ -359,18 +787,17 @@ impl UndoableAction for UpdateTileAttributesAction {
     fn is_redundant(&self, map: &Map) -> bool {
         if let Some(layer) = map.layers.get(&self.layer_id) {
             if let Some(Some(tile)) = layer.tiles.get(self.index) {
-                let matching = tile
-                    .attributes
-                    .iter()
-                    .zip(&self.attributes)
-                    .filter(|&(a, b)| a == b)
-                    .count();
-                return matching == self.attributes.len();
+                return tile.attributes == self.attributes;
             }
         }
 
         false
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        map.try_to_tile_coords(self.index)
+            .map(|coords| get_tile_rect(map, coords))
+    }
 }
 
 #[derive(Debug)]
@@ -514,14 +941,18 @@ pub struct UpdateLayerAction {
     id: String,
     is_visible: bool,
     old_is_visible: Option<bool>,
+    is_locked: bool,
+    old_is_locked: Option<bool>,
 }
 
 impl UpdateLayerAction {
-    pub fn new(id: String, is_visible: bool) -> Self {
+    pub fn new(id: String, is_visible: bool, is_locked: bool) -> Self {
         UpdateLayerAction {
             id,
             is_visible,
             old_is_visible: None,
+            is_locked,
+            old_is_locked: None,
         }
     }
 }
@@ -531,6 +962,9 @@ impl UndoableAction for UpdateLayerAction {
         if let Some(layer) = map.layers.get_mut(&self.id) {
             self.old_is_visible = Some(layer.is_visible);
             layer.is_visible = self.is_visible;
+
+            self.old_is_locked = Some(layer.is_locked);
+            layer.is_locked = self.is_locked;
         } else {
             return Err(Error::new_const(
                 ErrorKind::EditorAction,
@@ -548,6 +982,12 @@ impl UndoableAction for UpdateLayerAction {
             } else {
                 return Err(Error::new_const(ErrorKind::EditorAction, &"UpdateLayerAction (Undo): No `old_is_visible` on action. Undo was probably called on an action that was never applied"));
             }
+
+            if let Some(old_is_locked) = self.old_is_locked.take() {
+                layer.is_locked = old_is_locked;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"UpdateLayerAction (Undo): No `old_is_locked` on action. Undo was probably called on an action that was never applied"));
+            }
         } else {
             return Err(Error::new_const(
                 ErrorKind::EditorAction,
@@ -560,127 +1000,794 @@ impl UndoableAction for UpdateLayerAction {
 }
 
 #[derive(Debug)]
-pub struct ImportAction {
-    tilesets: Vec<MapTileset>,
-    background_color: Option<Color>,
-    old_background_color: Option<Color>,
-    background_layers: Vec<MapBackgroundLayer>,
-    old_background_layers: Vec<MapBackgroundLayer>,
+pub struct SetLayerSnapToGridAction {
+    id: String,
+    should_snap_to_grid: Option<bool>,
+    old_should_snap_to_grid: Option<Option<bool>>,
 }
 
-impl ImportAction {
-    pub fn new(
-        tilesets: Vec<MapTileset>,
-        background_color: Option<Color>,
-        background_layers: Vec<MapBackgroundLayer>,
-    ) -> Self {
-        ImportAction {
-            tilesets,
-            background_color,
-            old_background_color: None,
-            background_layers,
-            old_background_layers: Vec::new(),
+impl SetLayerSnapToGridAction {
+    pub fn new(id: String, should_snap_to_grid: Option<bool>) -> Self {
+        SetLayerSnapToGridAction {
+            id,
+            should_snap_to_grid,
+            old_should_snap_to_grid: None,
         }
     }
 }
 
-impl UndoableAction for ImportAction {
+impl UndoableAction for SetLayerSnapToGridAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
-        for tileset in &self.tilesets {
-            let mut first_tile_id = 1;
-            for tileset in map.tilesets.values() {
-                let next_tile_id = tileset.first_tile_id + tileset.tile_cnt;
-                if next_tile_id > first_tile_id {
-                    first_tile_id = next_tile_id;
-                }
-            }
-
-            let tileset = MapTileset {
-                id: tileset.id.clone(),
-                texture_id: tileset.texture_id.clone(),
-                texture_size: tileset.texture_size,
-                tile_size: tileset.tile_size,
-                grid_size: tileset.grid_size,
-                first_tile_id,
-                tile_cnt: tileset.tile_cnt,
-                tile_subdivisions: tileset.tile_subdivisions,
-                autotile_mask: tileset.autotile_mask.clone(),
-                tile_attributes: tileset.tile_attributes.clone(),
-                properties: tileset.properties.clone(),
-                bitmasks: None,
-            };
-
-            map.tilesets.insert(tileset.id.clone(), tileset);
-        }
-
-        if let Some(background_color) = self.background_color {
-            self.old_background_color = Some(map.background_color);
-            map.background_color = background_color;
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            self.old_should_snap_to_grid = Some(layer.should_snap_to_grid);
+            layer.should_snap_to_grid = self.should_snap_to_grid;
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerSnapToGridAction: The specified layer does not exist",
+            ));
         }
 
-        self.old_background_layers = map.background_layers.clone();
-
-        map.background_layers
-            .append(&mut self.background_layers.clone());
-
         Ok(())
     }
 
     fn undo(&mut self, map: &mut Map) -> Result<()> {
-        for tileset in &self.tilesets {
-            if map.tilesets.remove(&tileset.id).is_none() {
-                return Err(Error::new_const(ErrorKind::EditorAction, &"ImportTilesetsAction (Undo): One of the imported tilesets could not be found in the map"));
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let Some(old_should_snap_to_grid) = self.old_should_snap_to_grid.take() {
+                layer.should_snap_to_grid = old_should_snap_to_grid;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"SetLayerSnapToGridAction (Undo): No `old_should_snap_to_grid` on action. Undo was probably called on an action that was never applied"));
             }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerSnapToGridAction (Undo): The specified layer does not exist",
+            ));
         }
 
-        if let Some(background_color) = self.old_background_color.take() {
-            map.background_color = background_color;
-        }
+        Ok(())
+    }
 
-        map.background_layers = self.old_background_layers.drain(..).collect();
+    fn is_redundant(&self, map: &Map) -> bool {
+        if let Some(layer) = map.layers.get(&self.id) {
+            return layer.should_snap_to_grid == self.should_snap_to_grid;
+        }
 
-        Ok(())
+        false
     }
 }
 
-#[derive(Debug)]
-pub struct CreateTilesetAction {
+pub struct MergeLayerDownAction {
     id: String,
-    texture_id: String,
+    target_id: String,
+    layer: Option<MapLayer>,
+    draw_order_index: Option<usize>,
+    old_target_tiles: Option<Vec<Option<MapTile>>>,
+    old_target_has_collision: Option<bool>,
 }
 
-impl CreateTilesetAction {
-    pub fn new(id: String, texture_id: String) -> Self {
-        CreateTilesetAction { id, texture_id }
+impl MergeLayerDownAction {
+    pub fn new(id: String, target_id: String) -> Self {
+        MergeLayerDownAction {
+            id,
+            target_id,
+            layer: None,
+            draw_order_index: None,
+            old_target_tiles: None,
+            old_target_has_collision: None,
+        }
     }
 }
 
-impl UndoableAction for CreateTilesetAction {
+impl UndoableAction for MergeLayerDownAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
-        if let Some(texture) = try_get_texture(&self.texture_id) {
-            let texture_size: Vec2 = texture.size().into();
-
-            let mut first_tile_id = 1;
-            for tileset in map.tilesets.values() {
-                let next_tile_id = tileset.first_tile_id + tileset.tile_cnt;
-                if next_tile_id > first_tile_id {
-                    first_tile_id = next_tile_id;
-                }
-            }
-
-            let tileset = MapTileset::new(
-                &self.id,
-                &self.texture_id,
-                Size::from(texture_size.as_u32()),
-                map.tile_size,
-                first_tile_id,
-            );
+        let is_tile_layer = |id: &str| map.get_layer_kind(id) == Some(MapLayerKind::TileLayer);
 
-            map.tilesets.insert(self.id.clone(), tileset);
-        } else {
+        if !is_tile_layer(&self.id) || !is_tile_layer(&self.target_id) {
             return Err(Error::new_const(
                 ErrorKind::EditorAction,
-                &"CreateTilesetAction: The specified texture does not exist",
+                &"MergeLayerDownAction: Both of the specified layers must be existing tile layers",
+            ));
+        }
+
+        if map.layers[&self.id].is_locked || map.layers[&self.target_id].is_locked {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"MergeLayerDownAction: Locked layers can not be merged",
+            ));
+        }
+
+        let draw_order_index = map.draw_order.iter().position(|id| id == &self.id);
+
+        if draw_order_index.is_none() {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"MergeLayerDownAction: The specified layer was not found in the draw order array",
+            ));
+        }
+
+        let layer = map.layers.remove(&self.id).unwrap();
+        let target = map.layers.get_mut(&self.target_id).unwrap();
+
+        let tiles = target.get_merged_tiles(&layer);
+        self.old_target_tiles = Some(std::mem::replace(&mut target.tiles, tiles));
+
+        // The merged layer has collision if either of the layers had it
+        self.old_target_has_collision = Some(target.has_collision);
+        target.has_collision |= layer.has_collision;
+
+        self.draw_order_index = draw_order_index;
+        map.draw_order.retain(|id| id != &self.id);
+
+        self.layer = Some(layer);
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(target) = map.layers.get_mut(&self.target_id) {
+            if let (Some(tiles), Some(has_collision)) = (
+                self.old_target_tiles.take(),
+                self.old_target_has_collision.take(),
+            ) {
+                target.tiles = tiles;
+                target.has_collision = has_collision;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"MergeLayerDownAction (Undo): No target layer state stored in action. Undo was probably called on an action that was never applied"));
+            }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"MergeLayerDownAction (Undo): The target layer does not exist",
+            ));
+        }
+
+        if let (Some(layer), Some(i)) = (self.layer.take(), self.draw_order_index.take()) {
+            map.layers.insert(self.id.clone(), layer);
+
+            if i >= map.draw_order.len() {
+                map.draw_order.push(self.id.clone());
+            } else {
+                map.draw_order.insert(i, self.id.clone());
+            }
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"MergeLayerDownAction (Undo): No layer stored in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ConvertLayerAction {
+    id: String,
+    kind: MapLayerKind,
+    has_collision: bool,
+    old_kind: Option<MapLayerKind>,
+    old_has_collision: Option<bool>,
+    old_tiles: Option<Vec<Option<MapTile>>>,
+}
+
+impl ConvertLayerAction {
+    pub fn new(id: String, kind: MapLayerKind, has_collision: bool) -> Self {
+        ConvertLayerAction {
+            id,
+            kind,
+            has_collision,
+            old_kind: None,
+            old_has_collision: None,
+            old_tiles: None,
+        }
+    }
+
+    /// This returns the reason that `layer` can not be converted to `kind`, with `has_collision`,
+    /// if the conversion is not supported. See `EditorAction::ConvertLayer` for the supported
+    /// conversions.
+    pub fn get_conversion_error(
+        layer: &MapLayer,
+        kind: MapLayerKind,
+        has_collision: bool,
+    ) -> Option<String> {
+        if kind == MapLayerKind::ObjectLayer && has_collision {
+            return Some("Object layers can not have collision".to_string());
+        }
+
+        if kind == layer.kind {
+            return None;
+        }
+
+        match layer.kind {
+            MapLayerKind::TileLayer if layer.tiles.iter().any(Option::is_some) => Some(format!(
+                "'{}' has tiles, which would be lost if it was converted to an object layer",
+                layer.id
+            )),
+            MapLayerKind::ObjectLayer if !layer.objects.is_empty() => Some(format!(
+                "'{}' has objects, which would be lost if it was converted to a tile layer",
+                layer.id
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl UndoableAction for ConvertLayerAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let Some(err) = Self::get_conversion_error(layer, self.kind, self.has_collision) {
+                return Err(Error::new_message(
+                    ErrorKind::EditorAction,
+                    &format!("ConvertLayerAction: {}", err),
+                ));
+            }
+
+            // Object layers loaded from a map file have no tiles, so the tiles are reset to fit
+            // the grid when a layer is converted to a tile layer
+            if self.kind == MapLayerKind::TileLayer && layer.kind != MapLayerKind::TileLayer {
+                let tile_cnt = (layer.grid_size.width * layer.grid_size.height) as usize;
+                self.old_tiles = Some(std::mem::replace(&mut layer.tiles, vec![None; tile_cnt]));
+            }
+
+            self.old_kind = Some(layer.kind);
+            layer.kind = self.kind;
+
+            self.old_has_collision = Some(layer.has_collision);
+            layer.has_collision = self.has_collision;
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ConvertLayerAction: The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let (Some(kind), Some(has_collision)) =
+                (self.old_kind.take(), self.old_has_collision.take())
+            {
+                layer.kind = kind;
+                layer.has_collision = has_collision;
+
+                if let Some(tiles) = self.old_tiles.take() {
+                    layer.tiles = tiles;
+                }
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"ConvertLayerAction (Undo): No old layer state stored in action. Undo was probably called on an action that was never applied"));
+            }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ConvertLayerAction (Undo): The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        if let Some(layer) = map.layers.get(&self.id) {
+            return layer.kind == self.kind && layer.has_collision == self.has_collision;
+        }
+
+        false
+    }
+}
+
+#[derive(Debug)]
+pub struct SetLayerGroupAction {
+    id: String,
+    group: Option<String>,
+    old_group: Option<Option<String>>,
+    old_draw_order_index: Option<usize>,
+}
+
+impl SetLayerGroupAction {
+    pub fn new(id: String, group: Option<String>) -> Self {
+        SetLayerGroupAction {
+            id,
+            group,
+            old_group: None,
+            old_draw_order_index: None,
+        }
+    }
+}
+
+impl UndoableAction for SetLayerGroupAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get(&self.id) {
+            self.old_group = Some(layer.group.clone());
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerGroupAction: The specified layer does not exist",
+            ));
+        }
+
+        self.old_draw_order_index = map.draw_order.iter().position(|id| id == &self.id);
+
+        if self.old_draw_order_index.is_none() {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetLayerGroupAction: Could not find the specified layer in the map draw order array"));
+        }
+
+        map.set_layer_group(&self.id, self.group.clone());
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let Some(old_group) = self.old_group.take() {
+                layer.group = old_group;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"SetLayerGroupAction (Undo): No `old_group` on action. Undo was probably called on an action that was never applied"));
+            }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerGroupAction (Undo): The specified layer does not exist",
+            ));
+        }
+
+        if let Some(old_index) = self.old_draw_order_index.take() {
+            map.draw_order.retain(|id| id != &self.id);
+
+            if old_index > map.draw_order.len() {
+                map.draw_order.push(self.id.clone());
+            } else {
+                map.draw_order.insert(old_index, self.id.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        if let Some(layer) = map.layers.get(&self.id) {
+            return layer.group == self.group;
+        }
+
+        false
+    }
+}
+
+pub struct SetLayerTintAction {
+    id: String,
+    tint: Color,
+    old_tint: Option<Color>,
+}
+
+impl SetLayerTintAction {
+    pub fn new(id: String, tint: Color) -> Self {
+        SetLayerTintAction {
+            id,
+            tint,
+            old_tint: None,
+        }
+    }
+}
+
+impl UndoableAction for SetLayerTintAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            self.old_tint = Some(layer.tint);
+            layer.tint = self.tint;
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerTintAction: The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let Some(old_tint) = self.old_tint.take() {
+                layer.tint = old_tint;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"SetLayerTintAction (Undo): No `old_tint` on action. Undo was probably called on an action that was never applied"));
+            }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetLayerTintAction (Undo): The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        if let Some(layer) = map.layers.get(&self.id) {
+            return layer.tint == self.tint;
+        }
+
+        false
+    }
+}
+
+#[derive(Debug)]
+pub struct ShiftLayerAction {
+    id: String,
+    offset: IVec2,
+    should_wrap: bool,
+    old_tiles: Option<Vec<Option<MapTile>>>,
+}
+
+impl ShiftLayerAction {
+    pub fn new(id: String, offset: IVec2, should_wrap: bool) -> Self {
+        ShiftLayerAction {
+            id,
+            offset,
+            should_wrap,
+            old_tiles: None,
+        }
+    }
+}
+
+impl UndoableAction for ShiftLayerAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if layer.kind != MapLayerKind::TileLayer {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"ShiftLayerAction: The specified layer is not a tile layer",
+                ));
+            }
+
+            let tiles = layer.get_shifted_tiles(self.offset, self.should_wrap);
+            self.old_tiles = Some(std::mem::replace(&mut layer.tiles, tiles));
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ShiftLayerAction: The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(&self.id) {
+            if let Some(old_tiles) = self.old_tiles.take() {
+                layer.tiles = old_tiles;
+            } else {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"ShiftLayerAction (Undo): No `old_tiles` on action. Undo was probably called on an action that was never applied"));
+            }
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ShiftLayerAction (Undo): The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, _map: &Map) -> bool {
+        self.offset == IVec2::ZERO
+    }
+}
+
+#[derive(Debug)]
+pub struct ResizeMapAction {
+    grid_size: Size<u32>,
+    anchor: Anchor,
+    old_grid_size: Option<Size<u32>>,
+    old_tiles: HashMap<String, Vec<Option<MapTile>>>,
+    old_object_positions: HashMap<String, Vec<Vec2>>,
+    old_spawn_points: Vec<MapSpawnPoint>,
+}
+
+impl ResizeMapAction {
+    pub fn new(grid_size: Size<u32>, anchor: Anchor) -> Self {
+        ResizeMapAction {
+            grid_size,
+            anchor,
+            old_grid_size: None,
+            old_tiles: HashMap::new(),
+            old_object_positions: HashMap::new(),
+            old_spawn_points: Vec::new(),
+        }
+    }
+}
+
+impl UndoableAction for ResizeMapAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if self.grid_size.width == 0 || self.grid_size.height == 0 {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ResizeMapAction: The new grid size can not be zero",
+            ));
+        }
+
+        self.old_grid_size = Some(map.grid_size);
+
+        for (id, layer) in &map.layers {
+            self.old_tiles.insert(id.clone(), layer.tiles.clone());
+
+            let positions = layer.objects.iter().map(|object| object.position).collect();
+            self.old_object_positions.insert(id.clone(), positions);
+        }
+
+        self.old_spawn_points = map.spawn_points.clone();
+
+        map.resize(self.grid_size, self.anchor);
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let old_grid_size = self.old_grid_size.take().ok_or_else(|| Error::new_const(ErrorKind::EditorAction, &"ResizeMapAction (Undo): No `old_grid_size` on action. Undo was probably called on an action that was never applied"))?;
+
+        map.grid_size = old_grid_size;
+
+        for (id, layer) in &mut map.layers {
+            layer.grid_size = old_grid_size;
+
+            if let Some(tiles) = self.old_tiles.remove(id) {
+                layer.tiles = tiles;
+            }
+
+            if let Some(positions) = self.old_object_positions.remove(id) {
+                for (object, position) in layer.objects.iter_mut().zip(positions) {
+                    object.position = position;
+                }
+            }
+        }
+
+        map.spawn_points = std::mem::take(&mut self.old_spawn_points);
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.grid_size == map.grid_size
+    }
+}
+
+#[derive(Debug)]
+pub struct SetWorldOffsetAction {
+    world_offset: Vec2,
+    old_world_offset: Option<Vec2>,
+}
+
+impl SetWorldOffsetAction {
+    pub fn new(world_offset: Vec2) -> Self {
+        SetWorldOffsetAction {
+            world_offset,
+            old_world_offset: None,
+        }
+    }
+}
+
+impl UndoableAction for SetWorldOffsetAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        self.old_world_offset = Some(map.world_offset);
+
+        map.world_offset = self.world_offset;
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(old_world_offset) = self.old_world_offset.take() {
+            map.world_offset = old_world_offset;
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetWorldOffsetAction (Undo): No old world offset saved in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.world_offset == map.world_offset
+    }
+}
+
+#[derive(Debug)]
+pub struct SetAmbientColorAction {
+    color: Color,
+    old_color: Option<Color>,
+}
+
+impl SetAmbientColorAction {
+    pub fn new(color: Color) -> Self {
+        SetAmbientColorAction {
+            color,
+            old_color: None,
+        }
+    }
+}
+
+impl UndoableAction for SetAmbientColorAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        self.old_color = Some(map.ambient_color);
+
+        map.ambient_color = self.color;
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(old_color) = self.old_color.take() {
+            map.ambient_color = old_color;
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetAmbientColorAction (Undo): No old ambient color saved in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.color == map.ambient_color
+    }
+}
+
+#[derive(Debug)]
+pub struct SetMapMusicAction {
+    music_id: Option<String>,
+    old_music_id: Option<Option<String>>,
+}
+
+impl SetMapMusicAction {
+    pub fn new(music_id: Option<String>) -> Self {
+        SetMapMusicAction {
+            music_id,
+            old_music_id: None,
+        }
+    }
+}
+
+impl UndoableAction for SetMapMusicAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        self.old_music_id = Some(map.music_id.clone());
+
+        map.music_id = self.music_id.clone();
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(old_music_id) = self.old_music_id.take() {
+            map.music_id = old_music_id;
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetMapMusicAction (Undo): No old music id saved in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.music_id == map.music_id
+    }
+}
+
+#[derive(Debug)]
+pub struct ImportAction {
+    tilesets: Vec<MapTileset>,
+    background_color: Option<Color>,
+    old_background_color: Option<Color>,
+    background_layers: Vec<MapBackgroundLayer>,
+    old_background_layers: Vec<MapBackgroundLayer>,
+}
+
+impl ImportAction {
+    pub fn new(
+        tilesets: Vec<MapTileset>,
+        background_color: Option<Color>,
+        background_layers: Vec<MapBackgroundLayer>,
+    ) -> Self {
+        ImportAction {
+            tilesets,
+            background_color,
+            old_background_color: None,
+            background_layers,
+            old_background_layers: Vec::new(),
+        }
+    }
+}
+
+impl UndoableAction for ImportAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        for tileset in &self.tilesets {
+            let mut first_tile_id = 1;
+            for tileset in map.tilesets.values() {
+                let next_tile_id = tileset.first_tile_id + tileset.tile_cnt;
+                if next_tile_id > first_tile_id {
+                    first_tile_id = next_tile_id;
+                }
+            }
+
+            let tileset = MapTileset {
+                id: tileset.id.clone(),
+                texture_id: tileset.texture_id.clone(),
+                texture_size: tileset.texture_size,
+                tile_size: tileset.tile_size,
+                grid_size: tileset.grid_size,
+                first_tile_id,
+                tile_cnt: tileset.tile_cnt,
+                tile_subdivisions: tileset.tile_subdivisions,
+                autotile_mask: tileset.autotile_mask.clone(),
+                tile_attributes: tileset.tile_attributes.clone(),
+                properties: tileset.properties.clone(),
+                bitmasks: None,
+            };
+
+            map.tilesets.insert(tileset.id.clone(), tileset);
+        }
+
+        if let Some(background_color) = self.background_color {
+            self.old_background_color = Some(map.background_color);
+            map.background_color = background_color;
+        }
+
+        self.old_background_layers = map.background_layers.clone();
+
+        map.background_layers
+            .append(&mut self.background_layers.clone());
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        for tileset in &self.tilesets {
+            if map.tilesets.remove(&tileset.id).is_none() {
+                return Err(Error::new_const(ErrorKind::EditorAction, &"ImportTilesetsAction (Undo): One of the imported tilesets could not be found in the map"));
+            }
+        }
+
+        if let Some(background_color) = self.old_background_color.take() {
+            map.background_color = background_color;
+        }
+
+        map.background_layers = self.old_background_layers.drain(..).collect();
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateTilesetAction {
+    id: String,
+    texture_id: String,
+}
+
+impl CreateTilesetAction {
+    pub fn new(id: String, texture_id: String) -> Self {
+        CreateTilesetAction { id, texture_id }
+    }
+}
+
+impl UndoableAction for CreateTilesetAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(texture) = try_get_texture(&self.texture_id) {
+            let texture_size: Vec2 = texture.size().into();
+
+            let mut first_tile_id = 1;
+            for tileset in map.tilesets.values() {
+                let next_tile_id = tileset.first_tile_id + tileset.tile_cnt;
+                if next_tile_id > first_tile_id {
+                    first_tile_id = next_tile_id;
+                }
+            }
+
+            let tileset = MapTileset::new(
+                &self.id,
+                &self.texture_id,
+                Size::from(texture_size.as_u32()),
+                map.tile_size,
+                first_tile_id,
+            );
+
+            map.tilesets.insert(self.id.clone(), tileset);
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"CreateTilesetAction: The specified texture does not exist",
             ));
         }
 
@@ -811,29 +1918,27 @@ impl UndoableAction for UpdateTilesetAction {
 
 #[derive(Debug)]
 pub struct CreateObjectAction {
-    id: String,
-    kind: MapObjectKind,
-    position: Vec2,
+    object: MapObject,
     layer_id: String,
 }
 
 impl CreateObjectAction {
     pub fn new(id: String, kind: MapObjectKind, position: Vec2, layer_id: String) -> Self {
-        CreateObjectAction {
-            id,
-            kind,
-            position,
-            layer_id,
-        }
+        let object = MapObject::new(&id, kind, position);
+
+        CreateObjectAction { object, layer_id }
+    }
+
+    /// This creates a copy of `object`, including its properties, group and overrides
+    pub fn from_object(object: MapObject, layer_id: String) -> Self {
+        CreateObjectAction { object, layer_id }
     }
 }
 
 impl UndoableAction for CreateObjectAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
         if let Some(layer) = map.layers.get_mut(&self.layer_id) {
-            let object = MapObject::new(&self.id, self.kind, self.position);
-
-            layer.objects.insert(0, object);
+            layer.objects.insert(0, self.object.clone());
         } else {
             return Err(Error::new_const(
                 ErrorKind::EditorAction,
@@ -856,6 +1961,10 @@ impl UndoableAction for CreateObjectAction {
 
         Ok(())
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        Some(get_point_rect(map, self.object.position))
+    }
 }
 
 #[derive(Debug)]
@@ -904,7 +2013,155 @@ impl UndoableAction for DeleteObjectAction {
             return Err(Error::new_const(ErrorKind::EditorAction, &"DeleteObjectAction (Undo): No object stored in action. Undo was probably called on an action that was never applied"));
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        // The object is held by the action while it is deleted and is in the map otherwise
+        self.object
+            .as_ref()
+            .or_else(|| map.get_object(&self.layer_id, self.index))
+            .map(|object| get_point_rect(map, object.position))
+    }
+}
+
+pub struct MoveObjectToLayerAction {
+    from_layer: String,
+    index: usize,
+    to_layer: String,
+    // This holds the index of the object in the destination layer, after the action is applied
+    new_index: Option<usize>,
+}
+
+impl MoveObjectToLayerAction {
+    pub fn new(from_layer: String, index: usize, to_layer: String) -> Self {
+        MoveObjectToLayerAction {
+            from_layer,
+            index,
+            to_layer,
+            new_index: None,
+        }
+    }
+}
+
+impl UndoableAction for MoveObjectToLayerAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        match map.layers.get(&self.to_layer) {
+            Some(layer) if layer.kind == MapLayerKind::ObjectLayer => {}
+            Some(_) => {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"MoveObjectToLayerAction: The destination layer is not an object layer",
+                ))
+            }
+            None => {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"MoveObjectToLayerAction: The destination layer does not exist",
+                ))
+            }
+        }
+
+        let object = if let Some(layer) = map.layers.get_mut(&self.from_layer) {
+            if self.index >= layer.objects.len() {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"MoveObjectToLayerAction: Index out of bounds",
+                ));
+            }
+
+            layer.objects.remove(self.index)
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"MoveObjectToLayerAction: The source layer does not exist",
+            ));
+        };
+
+        let layer = map.layers.get_mut(&self.to_layer).unwrap();
+        layer.objects.push(object);
+
+        self.new_index = Some(layer.objects.len() - 1);
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(new_index) = self.new_index.take() {
+            let object = if let Some(layer) = map.layers.get_mut(&self.to_layer) {
+                layer.objects.remove(new_index)
+            } else {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"MoveObjectToLayerAction (Undo): The destination layer does not exist",
+                ));
+            };
+
+            if let Some(layer) = map.layers.get_mut(&self.from_layer) {
+                layer.objects.insert(self.index, object);
+            } else {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"MoveObjectToLayerAction (Undo): The source layer does not exist",
+                ));
+            }
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"MoveObjectToLayerAction (Undo): No index stored in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReorderObjectAction {
+    layer_id: String,
+    index: usize,
+    new_index: usize,
+}
+
+impl ReorderObjectAction {
+    pub fn new(layer_id: String, index: usize, new_index: usize) -> Self {
+        ReorderObjectAction {
+            layer_id,
+            index,
+            new_index,
+        }
+    }
+
+    fn move_object(map: &mut Map, layer_id: &str, from: usize, to: usize) -> Result<()> {
+        if let Some(layer) = map.layers.get_mut(layer_id) {
+            if from >= layer.objects.len() || to >= layer.objects.len() {
+                return Err(Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"ReorderObjectAction: Index out of bounds",
+                ));
+            }
+
+            let object = layer.objects.remove(from);
+            layer.objects.insert(to, object);
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"ReorderObjectAction: The specified layer does not exist",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl UndoableAction for ReorderObjectAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        Self::move_object(map, &self.layer_id, self.index, self.new_index)
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        Self::move_object(map, &self.layer_id, self.new_index, self.index)
+    }
+
+    fn is_redundant(&self, _map: &Map) -> bool {
+        self.index == self.new_index
     }
 }
 
@@ -978,6 +2235,228 @@ impl UndoableAction for UpdateObjectAction {
 
         Ok(())
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        let rect = get_point_rect(map, self.position);
+
+        // When redone, the action holds the object as it was before the update
+        let old_position = self
+            .object
+            .as_ref()
+            .or_else(|| map.get_object(&self.layer_id, self.index))
+            .map(|object| object.position);
+
+        match old_position {
+            Some(position) => Some(rect.combine_with(get_point_rect(map, position))),
+            None => Some(rect),
+        }
+    }
+}
+
+/// This sets the group of the specified objects. Setting the group to `None` will remove the
+/// objects from their groups.
+#[derive(Debug)]
+pub struct SetObjectGroupAction {
+    layer_id: String,
+    indices: Vec<usize>,
+    group_id: Option<String>,
+    old_group_ids: Vec<Option<String>>,
+}
+
+impl SetObjectGroupAction {
+    pub fn new(layer_id: String, indices: Vec<usize>, group_id: Option<String>) -> Self {
+        SetObjectGroupAction {
+            layer_id,
+            indices,
+            group_id,
+            old_group_ids: Vec::new(),
+        }
+    }
+}
+
+impl UndoableAction for SetObjectGroupAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        let layer = map.layers.get_mut(&self.layer_id).ok_or_else(|| {
+            Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetObjectGroupAction: The specified layer does not exist",
+            )
+        })?;
+
+        if self.indices.iter().any(|&i| i >= layer.objects.len()) {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetObjectGroupAction: The specified object index does not exist",
+            ));
+        }
+
+        self.old_group_ids = self
+            .indices
+            .iter()
+            .map(|&i| std::mem::replace(&mut layer.objects[i].group_id, self.group_id.clone()))
+            .collect();
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let layer = map.layers.get_mut(&self.layer_id).ok_or_else(|| {
+            Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetObjectGroupAction (Undo): The specified layer does not exist",
+            )
+        })?;
+
+        for (&i, group_id) in self.indices.iter().zip(self.old_group_ids.drain(..)) {
+            if let Some(object) = layer.objects.get_mut(i) {
+                object.group_id = group_id;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        self.indices.iter().all(|&i| {
+            map.get_object(&self.layer_id, i)
+                .map(|object| object.group_id == self.group_id)
+                .unwrap_or(false)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SetObjectColliderSizeAction {
+    layer_id: String,
+    index: usize,
+    collider_size: Option<Size<f32>>,
+    old_collider_size: Option<Size<f32>>,
+}
+
+impl SetObjectColliderSizeAction {
+    pub fn new(layer_id: String, index: usize, collider_size: Option<Size<f32>>) -> Self {
+        SetObjectColliderSizeAction {
+            layer_id,
+            index,
+            collider_size,
+            old_collider_size: None,
+        }
+    }
+}
+
+impl UndoableAction for SetObjectColliderSizeAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        let object = map
+            .layers
+            .get_mut(&self.layer_id)
+            .and_then(|layer| layer.objects.get_mut(self.index))
+            .ok_or_else(|| {
+                Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"SetObjectColliderSizeAction: The specified object does not exist",
+                )
+            })?;
+
+        self.old_collider_size = object.collider_size;
+        object.collider_size = self.collider_size;
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let object = map
+            .layers
+            .get_mut(&self.layer_id)
+            .and_then(|layer| layer.objects.get_mut(self.index))
+            .ok_or_else(|| {
+                Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"SetObjectColliderSizeAction (Undo): The specified object does not exist",
+                )
+            })?;
+
+        object.collider_size = self.old_collider_size.take();
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        map.get_object(&self.layer_id, self.index)
+            .map(|object| object.collider_size == self.collider_size)
+            .unwrap_or(false)
+    }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        map.get_object(&self.layer_id, self.index)
+            .map(|object| get_point_rect(map, object.position))
+    }
+}
+
+#[derive(Debug)]
+pub struct SetObjectScaleAction {
+    layer_id: String,
+    index: usize,
+    scale: Option<Vec2>,
+    old_scale: Option<Vec2>,
+}
+
+impl SetObjectScaleAction {
+    pub fn new(layer_id: String, index: usize, scale: Option<Vec2>) -> Self {
+        SetObjectScaleAction {
+            layer_id,
+            index,
+            scale,
+            old_scale: None,
+        }
+    }
+}
+
+impl UndoableAction for SetObjectScaleAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        let object = map
+            .layers
+            .get_mut(&self.layer_id)
+            .and_then(|layer| layer.objects.get_mut(self.index))
+            .ok_or_else(|| {
+                Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"SetObjectScaleAction: The specified object does not exist",
+                )
+            })?;
+
+        self.old_scale = object.scale;
+        object.scale = self.scale;
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let object = map
+            .layers
+            .get_mut(&self.layer_id)
+            .and_then(|layer| layer.objects.get_mut(self.index))
+            .ok_or_else(|| {
+                Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"SetObjectScaleAction (Undo): The specified object does not exist",
+                )
+            })?;
+
+        object.scale = self.old_scale.take();
+
+        Ok(())
+    }
+
+    fn is_redundant(&self, map: &Map) -> bool {
+        map.get_object(&self.layer_id, self.index)
+            .map(|object| object.scale == self.scale)
+            .unwrap_or(false)
+    }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        map.get_object(&self.layer_id, self.index)
+            .map(|object| get_point_rect(map, object.position))
+    }
 }
 
 #[derive(Debug)]
@@ -993,7 +2472,7 @@ impl CreateSpawnPointAction {
 
 impl UndoableAction for CreateSpawnPointAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
-        map.spawn_points.push(self.position);
+        map.spawn_points.push(MapSpawnPoint::new(self.position));
 
         Ok(())
     }
@@ -1003,12 +2482,16 @@ impl UndoableAction for CreateSpawnPointAction {
 
         Ok(())
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        Some(get_point_rect(map, self.position))
+    }
 }
 
 #[derive(Debug)]
 pub struct DeleteSpawnPointAction {
     index: usize,
-    spawn_point: Option<Vec2>,
+    spawn_point: Option<MapSpawnPoint>,
 }
 
 impl DeleteSpawnPointAction {
@@ -1041,6 +2524,13 @@ impl UndoableAction for DeleteSpawnPointAction {
 
         Ok(())
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        self.spawn_point
+            .as_ref()
+            .or_else(|| map.spawn_points.get(self.index))
+            .map(|spawn_point| get_point_rect(map, spawn_point.position))
+    }
 }
 
 #[derive(Debug)]
@@ -1062,12 +2552,17 @@ impl MoveSpawnPointAction {
 
 impl UndoableAction for MoveSpawnPointAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
-        let old_position = map.spawn_points.remove(self.index);
-        self.old_position = Some(old_position);
+        let old_spawn_point = map.spawn_points.remove(self.index);
+        self.old_position = Some(old_spawn_point.position);
+
+        let spawn_point = MapSpawnPoint {
+            position: self.position,
+            ..old_spawn_point
+        };
 
         match map.spawn_points.len().cmp(&self.index) {
-            Ordering::Equal => map.spawn_points.push(self.position),
-            Ordering::Greater => map.spawn_points.insert(self.index, self.position),
+            Ordering::Equal => map.spawn_points.push(spawn_point),
+            Ordering::Greater => map.spawn_points.insert(self.index, spawn_point),
             _ => {
                 return Err(Error::new_const(
                     ErrorKind::EditorAction,
@@ -1081,13 +2576,111 @@ impl UndoableAction for MoveSpawnPointAction {
 
     fn undo(&mut self, map: &mut Map) -> Result<()> {
         if let Some(old_position) = self.old_position {
-            map.spawn_points[self.index] = old_position;
+            map.spawn_points[self.index].position = old_position;
         } else {
             return Err(Error::new_const(ErrorKind::EditorAction, &"MoveSpawnPointAction (Undo): No old position saved in action. Undo was probably called on an action that was never applied"));
         }
 
         Ok(())
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        let rect = get_point_rect(map, self.position);
+
+        match self.old_position {
+            Some(old_position) => Some(rect.combine_with(get_point_rect(map, old_position))),
+            None => Some(rect),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetSpawnPointFacingAction {
+    index: usize,
+    is_facing_left: bool,
+    was_facing_left: Option<bool>,
+}
+
+impl SetSpawnPointFacingAction {
+    pub fn new(index: usize, is_facing_left: bool) -> Self {
+        SetSpawnPointFacingAction {
+            index,
+            is_facing_left,
+            was_facing_left: None,
+        }
+    }
+}
+
+impl UndoableAction for SetSpawnPointFacingAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(spawn_point) = map.spawn_points.get_mut(self.index) {
+            self.was_facing_left = Some(spawn_point.is_facing_left);
+            spawn_point.is_facing_left = self.is_facing_left;
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetSpawnPointFacingAction: Index out of bounds",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let spawn_point = map.spawn_points.get_mut(self.index);
+
+        if let (Some(spawn_point), Some(was_facing_left)) = (spawn_point, self.was_facing_left) {
+            spawn_point.is_facing_left = was_facing_left;
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetSpawnPointFacingAction (Undo): No old facing saved in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SetSpawnPointTagAction {
+    index: usize,
+    tag: MapSpawnPointTag,
+    old_tag: Option<MapSpawnPointTag>,
+}
+
+impl SetSpawnPointTagAction {
+    pub fn new(index: usize, tag: MapSpawnPointTag) -> Self {
+        SetSpawnPointTagAction {
+            index,
+            tag,
+            old_tag: None,
+        }
+    }
+}
+
+impl UndoableAction for SetSpawnPointTagAction {
+    fn apply(&mut self, map: &mut Map) -> Result<()> {
+        if let Some(spawn_point) = map.spawn_points.get_mut(self.index) {
+            self.old_tag = Some(spawn_point.tag);
+            spawn_point.tag = self.tag;
+        } else {
+            return Err(Error::new_const(
+                ErrorKind::EditorAction,
+                &"SetSpawnPointTagAction: Index out of bounds",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self, map: &mut Map) -> Result<()> {
+        let spawn_point = map.spawn_points.get_mut(self.index);
+
+        if let (Some(spawn_point), Some(old_tag)) = (spawn_point, self.old_tag) {
+            spawn_point.tag = old_tag;
+        } else {
+            return Err(Error::new_const(ErrorKind::EditorAction, &"SetSpawnPointTagAction (Undo): No old tag saved in action. Undo was probably called on an action that was never applied"));
+        }
+
+        Ok(())
+    }
 }
 
 pub struct PlaceTileAction {
@@ -1095,6 +2688,7 @@ pub struct PlaceTileAction {
     layer_id: String,
     tileset_id: String,
     coords: UVec2,
+    attributes: Vec<String>,
     replaced_tile: Option<MapTile>,
 }
 
@@ -1105,9 +2699,15 @@ impl PlaceTileAction {
             layer_id,
             tileset_id,
             coords,
+            attributes: Vec::new(),
             replaced_tile: None,
         }
     }
+
+    /// This sets the attributes that the placed tile will be given
+    pub fn with_attributes(self, attributes: Vec<String>) -> Self {
+        PlaceTileAction { attributes, ..self }
+    }
 }
 
 impl UndoableAction for PlaceTileAction {
@@ -1116,7 +2716,12 @@ impl UndoableAction for PlaceTileAction {
             let texture_id = tileset.texture_id.clone();
             let texture_coords = tileset.get_texture_coords(self.id);
 
-            let i = map.to_index(self.coords);
+            let i = map.try_to_index(self.coords).ok_or_else(|| {
+                Error::new_const(
+                    ErrorKind::EditorAction,
+                    &"PlaceTileAction: The specified coords are outside of the map",
+                )
+            })?;
 
             if let Some(layer) = map.layers.get_mut(&self.layer_id) {
                 if let MapLayerKind::TileLayer = layer.kind {
@@ -1128,7 +2733,7 @@ impl UndoableAction for PlaceTileAction {
                         texture_id,
                         texture: None,
                         texture_coords,
-                        attributes: vec![],
+                        attributes: self.attributes.clone(),
                     };
 
                     layer.tiles.insert(i as usize, Some(tile));
@@ -1188,12 +2793,18 @@ impl UndoableAction for PlaceTileAction {
         if let Some(layer) = map.layers.get(&self.layer_id) {
             let i = map.to_index(self.coords);
             if let Some(Some(tile)) = layer.tiles.get(i) {
-                return tile.tileset_id == self.tileset_id && tile.tile_id == self.id;
+                return tile.tileset_id == self.tileset_id
+                    && tile.tile_id == self.id
+                    && tile.attributes == self.attributes;
             }
         }
 
         false
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        Some(get_tile_rect(map, self.coords))
+    }
 }
 
 pub struct RemoveTileAction {
@@ -1214,7 +2825,12 @@ impl RemoveTileAction {
 
 impl UndoableAction for RemoveTileAction {
     fn apply(&mut self, map: &mut Map) -> Result<()> {
-        let i = map.to_index(self.coords);
+        let i = map.try_to_index(self.coords).ok_or_else(|| {
+            Error::new_const(
+                ErrorKind::EditorAction,
+                &"RemoveTileAction: The specified coords are outside of the map",
+            )
+        })?;
 
         if let Some(layer) = map.layers.get_mut(&self.layer_id) {
             if let MapLayerKind::TileLayer = layer.kind {
@@ -1281,4 +2897,358 @@ impl UndoableAction for RemoveTileAction {
 
         false
     }
+
+    fn get_affected_rect(&self, map: &Map) -> Option<Rect> {
+        Some(get_tile_rect(map, self.coords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff_core::map::test_utils::{add_test_object, map_with_layers, TEST_TILESET_ID};
+
+    use super::*;
+
+    fn test_map(layers: &[(&str, MapLayerKind)]) -> Map {
+        map_with_layers(vec2(16.0, 16.0), uvec2(16, 4), layers)
+    }
+
+    fn map_with_tile_layer() -> Map {
+        test_map(&[("tiles", MapLayerKind::TileLayer)])
+    }
+
+    fn place_tile(map: &mut Map, layer_id: &str, coords: UVec2) {
+        let mut action =
+            PlaceTileAction::new(0, layer_id.to_string(), TEST_TILESET_ID.to_string(), coords);
+        action.apply(map).unwrap();
+    }
+
+    fn tile_cnt(map: &Map, layer_id: &str) -> usize {
+        map.layers[layer_id].tiles.iter().flatten().count()
+    }
+
+    #[test]
+    fn test_move_object_to_layer() {
+        let mut map = test_map(&[
+            ("tiles", MapLayerKind::TileLayer),
+            ("objects", MapLayerKind::ObjectLayer),
+            ("other_objects", MapLayerKind::ObjectLayer),
+        ]);
+        add_test_object(
+            &mut map,
+            "objects",
+            "object",
+            MapObjectKind::Item,
+            vec2(8.0, 16.0),
+        );
+
+        let mut action =
+            MoveObjectToLayerAction::new("objects".to_string(), 0, "other_objects".to_string());
+        action.apply(&mut map).unwrap();
+
+        assert!(map.get_object("objects", 0).is_none());
+        let object = map.get_object("other_objects", 0).unwrap();
+        assert_eq!(object.id, "object");
+        assert_eq!(object.position, vec2(8.0, 16.0));
+
+        action.undo(&mut map).unwrap();
+        assert!(map.get_object("objects", 0).is_some());
+        assert!(map.get_object("other_objects", 0).is_none());
+
+        // Objects can not be moved to tile layers
+        let mut action =
+            MoveObjectToLayerAction::new("objects".to_string(), 0, "tiles".to_string());
+        assert!(action.apply(&mut map).is_err());
+        assert!(map.get_object("objects", 0).is_some());
+    }
+
+    #[test]
+    fn test_reorder_object_undo() {
+        let mut map = test_map(&[("objects", MapLayerKind::ObjectLayer)]);
+        for id in ["a", "b", "c"] {
+            add_test_object(&mut map, "objects", id, MapObjectKind::Item, Vec2::ZERO);
+        }
+
+        let object_ids = |map: &Map| {
+            map.layers["objects"]
+                .objects
+                .iter()
+                .map(|object| object.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Bring to front
+        let mut to_front = ReorderObjectAction::new("objects".to_string(), 0, 2);
+        to_front.apply(&mut map).unwrap();
+        assert_eq!(object_ids(&map), ["b", "c", "a"]);
+
+        // Send to back
+        let mut to_back = ReorderObjectAction::new("objects".to_string(), 1, 0);
+        to_back.apply(&mut map).unwrap();
+        assert_eq!(object_ids(&map), ["c", "b", "a"]);
+
+        to_back.undo(&mut map).unwrap();
+        assert_eq!(object_ids(&map), ["b", "c", "a"]);
+
+        to_front.undo(&mut map).unwrap();
+        assert_eq!(object_ids(&map), ["a", "b", "c"]);
+
+        let mut action = ReorderObjectAction::new("objects".to_string(), 0, 3);
+        assert!(action.apply(&mut map).is_err());
+        assert_eq!(object_ids(&map), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_resize_map_undo() {
+        let mut map = map_with_tile_layer();
+
+        place_tile(&mut map, "tiles", uvec2(15, 0));
+        map.spawn_points.push(MapSpawnPoint::new(vec2(240.0, 48.0)));
+
+        let mut action = ResizeMapAction::new(Size::new(8, 2), Anchor::TopLeft);
+        action.apply(&mut map).unwrap();
+
+        assert_eq!(map.grid_size, Size::new(8, 2));
+        assert_eq!(map.layers["tiles"].tiles.len(), 16);
+        assert_eq!(tile_cnt(&map, "tiles"), 0);
+        assert_eq!(map.spawn_points[0].position, vec2(112.0, 16.0));
+
+        action.undo(&mut map).unwrap();
+
+        assert_eq!(map.grid_size, Size::new(16, 4));
+        assert_eq!(map.layers["tiles"].grid_size, map.grid_size);
+        assert_eq!(map.layers["tiles"].tiles.len(), 64);
+        assert_eq!(tile_cnt(&map, "tiles"), 1);
+        assert_eq!(map.spawn_points[0].position, vec2(240.0, 48.0));
+    }
+
+    #[test]
+    fn test_set_world_offset_undo() {
+        let mut map = map_with_tile_layer();
+
+        map.spawn_points.push(MapSpawnPoint::new(vec2(32.0, 16.0)));
+
+        let mut action = SetWorldOffsetAction::new(vec2(256.0, -64.0));
+        action.apply(&mut map).unwrap();
+
+        assert_eq!(map.world_offset, vec2(256.0, -64.0));
+        // Spawn points are stored in map-local space, so they are not changed by the offset
+        assert_eq!(map.spawn_points[0].position, vec2(32.0, 16.0));
+
+        action.undo(&mut map).unwrap();
+        assert_eq!(map.world_offset, Vec2::ZERO);
+
+        // Setting the current offset again is redundant
+        assert!(SetWorldOffsetAction::new(Vec2::ZERO).is_redundant(&map));
+    }
+
+    #[test]
+    fn test_place_tile_with_attributes() {
+        let mut map = map_with_tile_layer();
+
+        place_tile(&mut map, "tiles", uvec2(0, 0));
+
+        let attributes = vec!["damage".to_string()];
+        let mut action = PlaceTileAction::new(
+            0,
+            "tiles".to_string(),
+            TEST_TILESET_ID.to_string(),
+            uvec2(0, 0),
+        )
+        .with_attributes(attributes.clone());
+
+        // Placing the same tile with different attributes is not redundant
+        assert!(!action.is_redundant(&map));
+
+        action.apply(&mut map).unwrap();
+        assert_eq!(
+            map.get_tile("tiles", 0, 0).as_ref().unwrap().attributes,
+            attributes
+        );
+
+        action.undo(&mut map).unwrap();
+        assert!(map
+            .get_tile("tiles", 0, 0)
+            .as_ref()
+            .unwrap()
+            .attributes
+            .is_empty());
+    }
+
+    #[test]
+    fn test_set_layer_group_undo() {
+        let mut map = test_map(&[
+            ("tiles", MapLayerKind::TileLayer),
+            ("a", MapLayerKind::TileLayer),
+            ("b", MapLayerKind::TileLayer),
+        ]);
+
+        let mut action = SetLayerGroupAction::new("tiles".to_string(), Some("group".to_string()));
+        action.apply(&mut map).unwrap();
+
+        let mut action = SetLayerGroupAction::new("b".to_string(), Some("group".to_string()));
+        action.apply(&mut map).unwrap();
+
+        assert_eq!(map.get_group_layer_ids("group"), vec!["tiles", "b"]);
+        assert_eq!(map.draw_order, vec!["tiles", "b", "a"]);
+
+        // Undoing restores both the group and the place in the draw order
+        action.undo(&mut map).unwrap();
+        assert_eq!(map.layers["b"].group, None);
+        assert_eq!(map.draw_order, vec!["tiles", "a", "b"]);
+    }
+
+    #[test]
+    fn test_merge_layer_down_undo() {
+        // The first layer in the draw order is drawn on top
+        let mut map = test_map(&[
+            ("upper", MapLayerKind::TileLayer),
+            ("tiles", MapLayerKind::TileLayer),
+        ]);
+        map.layers.get_mut("upper").unwrap().has_collision = true;
+
+        place_tile(&mut map, "tiles", uvec2(0, 0));
+        for x in 0..2 {
+            place_tile(&mut map, "upper", uvec2(x, 1));
+        }
+
+        // Locked layers can not be merged into, or from
+        map.layers.get_mut("tiles").unwrap().is_locked = true;
+
+        let mut action = MergeLayerDownAction::new("upper".to_string(), "tiles".to_string());
+        assert!(action.apply(&mut map).is_err());
+        assert_eq!(map.draw_order, vec!["upper", "tiles"]);
+
+        map.layers.get_mut("tiles").unwrap().is_locked = false;
+
+        let mut action = MergeLayerDownAction::new("upper".to_string(), "tiles".to_string());
+        action.apply(&mut map).unwrap();
+
+        assert!(!map.layers.contains_key("upper"));
+        assert_eq!(map.draw_order, vec!["tiles"]);
+        assert_eq!(tile_cnt(&map, "tiles"), 3);
+        assert!(map.layers["tiles"].has_collision);
+
+        action.undo(&mut map).unwrap();
+
+        assert_eq!(map.draw_order, vec!["upper", "tiles"]);
+        assert_eq!(tile_cnt(&map, "tiles"), 1);
+        assert!(!map.layers["tiles"].has_collision);
+        assert_eq!(tile_cnt(&map, "upper"), 2);
+    }
+
+    #[test]
+    fn test_convert_layer() {
+        let mut map = map_with_tile_layer();
+
+        let mut add_collision =
+            ConvertLayerAction::new("tiles".to_string(), MapLayerKind::TileLayer, true);
+        add_collision.apply(&mut map).unwrap();
+        assert!(map.layers["tiles"].has_collision);
+
+        // The tile layer is empty, so it can be converted to an object layer
+        let mut to_objects =
+            ConvertLayerAction::new("tiles".to_string(), MapLayerKind::ObjectLayer, false);
+        to_objects.apply(&mut map).unwrap();
+        assert_eq!(map.layers["tiles"].kind, MapLayerKind::ObjectLayer);
+        assert!(!map.layers["tiles"].has_collision);
+
+        to_objects.undo(&mut map).unwrap();
+        add_collision.undo(&mut map).unwrap();
+        assert_eq!(map.layers["tiles"].kind, MapLayerKind::TileLayer);
+        assert!(!map.layers["tiles"].has_collision);
+
+        place_tile(&mut map, "tiles", uvec2(0, 0));
+
+        let mut action =
+            ConvertLayerAction::new("tiles".to_string(), MapLayerKind::ObjectLayer, false);
+        assert!(action.apply(&mut map).is_err());
+        assert_eq!(map.layers["tiles"].kind, MapLayerKind::TileLayer);
+        assert_eq!(tile_cnt(&map, "tiles"), 1);
+
+        let layer = &map.layers["tiles"];
+        assert!(
+            ConvertLayerAction::get_conversion_error(layer, MapLayerKind::TileLayer, false)
+                .is_none()
+        );
+        assert!(
+            ConvertLayerAction::get_conversion_error(layer, MapLayerKind::ObjectLayer, true)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_object_group_undo() {
+        let mut map = test_map(&[("objects", MapLayerKind::ObjectLayer)]);
+        for x in [0.0, 32.0, 64.0] {
+            let kind = MapObjectKind::Decoration;
+            add_test_object(&mut map, "objects", "object", kind, vec2(x, 0.0));
+        }
+
+        assert_eq!(map.get_object_group_indices("objects", 1), vec![1]);
+        assert_eq!(
+            map.get_object_indices_in_region("objects", URect::new(0, 0, 3, 1)),
+            vec![0, 1]
+        );
+
+        let group_id = map.get_unused_object_group_id();
+        let mut action =
+            SetObjectGroupAction::new("objects".to_string(), vec![0, 2], Some(group_id.clone()));
+        action.apply(&mut map).unwrap();
+
+        assert_eq!(map.get_object_group_indices("objects", 2), vec![0, 2]);
+        assert_eq!(map.get_object_group_indices("objects", 1), vec![1]);
+        assert_ne!(map.get_unused_object_group_id(), group_id);
+
+        action.undo(&mut map).unwrap();
+        assert_eq!(map.get_object_group_indices("objects", 0), vec![0]);
+
+        action.apply(&mut map).unwrap();
+        assert_eq!(map.get_object_group_indices("objects", 0), vec![0, 2]);
+
+        let mut action = SetObjectGroupAction::new("objects".to_string(), vec![0, 2], None);
+        action.apply(&mut map).unwrap();
+        assert_eq!(map.get_object_group_indices("objects", 0), vec![0]);
+
+        let mut action = SetObjectGroupAction::new("objects".to_string(), vec![3], None);
+        assert!(action.apply(&mut map).is_err());
+    }
+
+    #[test]
+    fn test_object_collider_size_undo() {
+        let mut map = test_map(&[("objects", MapLayerKind::ObjectLayer)]);
+        add_test_object(&mut map, "objects", "item", MapObjectKind::Item, Vec2::ZERO);
+
+        let size = Size::new(24.0, 12.0);
+        let mut action = SetObjectColliderSizeAction::new("objects".to_string(), 0, Some(size));
+        action.apply(&mut map).unwrap();
+        assert_eq!(
+            map.get_object("objects", 0).unwrap().collider_size,
+            Some(size)
+        );
+
+        action.undo(&mut map).unwrap();
+        assert_eq!(map.get_object("objects", 0).unwrap().collider_size, None);
+
+        let action = SetObjectColliderSizeAction::new("objects".to_string(), 0, None);
+        assert!(action.is_redundant(&map));
+    }
+
+    #[test]
+    fn test_object_scale_undo() {
+        let mut map = test_map(&[("objects", MapLayerKind::ObjectLayer)]);
+        let kind = MapObjectKind::Decoration;
+        add_test_object(&mut map, "objects", "decoration", kind, Vec2::ZERO);
+
+        let scale = vec2(2.0, 0.5);
+        let mut action = SetObjectScaleAction::new("objects".to_string(), 0, Some(scale));
+        action.apply(&mut map).unwrap();
+        assert_eq!(map.get_object("objects", 0).unwrap().scale, Some(scale));
+
+        action.undo(&mut map).unwrap();
+        assert_eq!(map.get_object("objects", 0).unwrap().scale, None);
+
+        action.apply(&mut map).unwrap();
+        assert_eq!(map.get_object("objects", 0).unwrap().scale, Some(scale));
+    }
 }
@@ -21,10 +21,12 @@ impl EditorCamera {
     }
 
     pub fn get_view_rect(&self) -> Rect {
-        let window_size = window_size();
+        // The cursor is in viewport space, regardless of the resolution the scene is rendered at,
+        // so this has to match the viewport for hit-testing to line up with what is drawn
+        let viewport_size = viewport_size();
         let size = vec2(
-            window_size.width as f32 / self.scale,
-            window_size.height as f32 / self.scale,
+            viewport_size.width / self.scale,
+            viewport_size.height / self.scale,
         );
 
         let position = self.position - size / 2.0;
@@ -52,8 +54,40 @@ impl EditorCamera {
     }
 }
 
+/// This returns the active `EditorCamera`, or `None` if the editor is not running.
+pub fn try_get_editor_camera() -> Option<RefMut<EditorCamera>> {
+    scene::find_node_by_type::<EditorCamera>()
+}
+
+/// This returns the active `EditorCamera` and will panic if the editor is not running.
+pub fn get_editor_camera() -> RefMut<EditorCamera> {
+    try_get_editor_camera().unwrap()
+}
+
+/// Convert a screen space position to world space, using the active `EditorCamera`.
+/// This, like the other camera helpers, is valid during both update and draw, so it can be used
+/// by external draw code to align overlays with the map.
+pub fn to_world_space(position: Vec2) -> Vec2 {
+    get_editor_camera().to_world_space(position)
+}
+
+/// Convert a world space position to screen space, using the active `EditorCamera`.
+pub fn to_screen_space(position: Vec2) -> Vec2 {
+    get_editor_camera().to_screen_space(position)
+}
+
+/// Get the rect, in world space, that is currently visible through the active `EditorCamera`.
+pub fn get_view_rect() -> Rect {
+    get_editor_camera().get_view_rect()
+}
+
 impl Node for EditorCamera {
     fn fixed_update(node: RefMut<Self>) {
+        // The game sets its own camera during test play
+        if super::test_play::is_test_playing() {
+            return;
+        }
+
         let viewport_size = viewport_size();
 
         let camera = Some(Camera2D {
@@ -63,6 +97,8 @@ impl Node for EditorCamera {
                 node.scale / viewport_size.width,
                 -node.scale / viewport_size.height,
             ) * 2.0,
+            viewport: scene_viewport(),
+            render_target: scene_render_target(),
             ..Camera2D::default()
         });
 
@@ -0,0 +1,74 @@
+use ff_core::map::{Map, MapObject};
+use ff_core::prelude::*;
+
+#[derive(Debug, Default, Clone)]
+pub struct EditorClipboard {
+    /// Deep copies of the copied objects. Their positions are relative to the first object that
+    /// was copied, so that the objects keep their relative positions when pasted.
+    pub objects: Vec<MapObject>,
+}
+
+impl EditorClipboard {
+    /// This replaces the contents of the clipboard with deep copies of the objects at `indices`
+    /// in the specified layer.
+    pub fn copy_objects(&mut self, map: &Map, layer_id: &str, indices: &[usize]) {
+        self.objects.clear();
+
+        if let Some(layer) = map.layers.get(layer_id) {
+            let mut anchor = None;
+
+            for &i in indices {
+                if let Some(object) = layer.objects.get(i) {
+                    let anchor = *anchor.get_or_insert(object.position);
+
+                    let mut object = object.clone();
+                    object.position -= anchor;
+
+                    self.objects.push(object);
+                }
+            }
+        }
+    }
+
+    /// This returns copies of the objects in the clipboard, positioned relative to `position`.
+    /// Positions are clamped to the map bounds, so that objects can not be pasted outside of it.
+    /// Object groups are given new ids that are not used in `map`, so that the pasted objects
+    /// form groups of their own, instead of joining the groups they were copied from.
+    pub fn get_paste_objects(&self, map: &Map, position: Vec2) -> Vec<MapObject> {
+        let mut group_ids: Vec<&String> = Vec::new();
+        for group_id in self
+            .objects
+            .iter()
+            .filter_map(|object| object.group_id.as_ref())
+        {
+            if !group_ids.contains(&group_id) {
+                group_ids.push(group_id);
+            }
+        }
+
+        let new_group_ids = map.get_unused_object_group_ids(group_ids.len());
+
+        let map_size = Vec2::from(map.get_size());
+
+        self.objects
+            .iter()
+            .map(|object| {
+                let mut object = object.clone();
+                object.position = (object.position + position).clamp(Vec2::ZERO, map_size);
+
+                object.group_id = object.group_id.as_ref().and_then(|group_id| {
+                    group_ids
+                        .iter()
+                        .position(|id| *id == group_id)
+                        .map(|i| new_group_ids[i].clone())
+                });
+
+                object
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
@@ -1,6 +1,7 @@
 use ff_core::prelude::*;
 
 use super::EditorAction;
+use crate::editor::input::get_action_keybind;
 
 #[cfg(feature = "macroquad")]
 use ff_core::gui::get_gui_theme;
@@ -168,7 +169,7 @@ fn draw_entries(
     let mut sub_menus = Vec::new();
 
     widgets::Group::new(hash!(), size)
-        .position(position)
+        .position(to_window_position(position))
         .ui(ui, |ui| {
             let mut y_offset = 0.0;
 
@@ -192,6 +193,15 @@ fn draw_entries(
 
                         ui.label(entry_position, label);
 
+                        // The shortcut is looked up every draw, so that rebinding is reflected
+                        if let Some(keybind) = get_action_keybind(action) {
+                            let shortcut = keybind.to_string();
+                            let shortcut_width = ui.calc_size(&shortcut).x;
+                            let position =
+                                vec2(entry_position.x + size.x - shortcut_width, entry_position.y);
+                            ui.label(position, &shortcut);
+                        }
+
                         if button {
                             res = Some(action.clone());
                         }
@@ -1,4 +1,5 @@
-use crate::editor::EditorContext;
+use crate::editor::input::get_action_keybind;
+use crate::editor::{EditorAction, EditorContext};
 
 use ff_core::gui::{Menu, MenuEntry, MenuResult};
 use ff_core::macroquad::hash;
@@ -12,9 +13,20 @@ pub const EDITOR_MENU_RESULT_SAVE: usize = 2;
 pub const EDITOR_MENU_RESULT_SAVE_AS: usize = 3;
 pub const EDITOR_MENU_RESULT_MAIN_MENU: usize = 4;
 pub const EDITOR_MENU_RESULT_QUIT: usize = 5;
+pub const EDITOR_MENU_RESULT_TEST_PLAY: usize = 6;
+pub const EDITOR_MENU_RESULT_RESIZE_MAP: usize = 7;
+pub const EDITOR_MENU_RESULT_REVEAL_IN_FOLDER: usize = 8;
 
 static mut EDITOR_MENU_INSTANCE: Option<Menu> = None;
 
+// This appends the shortcut of `action`, if it has one, to `title`
+fn entry_title(title: &str, action: &EditorAction) -> String {
+    match get_action_keybind(action) {
+        Some(keybind) => format!("{} ({})", title, keybind),
+        None => title.to_string(),
+    }
+}
+
 pub fn open_editor_menu(ctx: &EditorContext) {
     unsafe {
         if EDITOR_MENU_INSTANCE.is_none() {
@@ -29,18 +41,34 @@ pub fn open_editor_menu(ctx: &EditorContext) {
                     },
                     MenuEntry {
                         index: EDITOR_MENU_RESULT_OPEN_IMPORT,
-                        title: "Open/Import".to_string(),
+                        title: entry_title("Open/Import", &EditorAction::OpenLoadMapWindow),
                         ..Default::default()
                     },
                     MenuEntry {
                         index: EDITOR_MENU_RESULT_SAVE,
-                        title: "Save".to_string(),
+                        title: entry_title("Save", &EditorAction::SaveMap(None)),
                         is_disabled: !ctx.is_user_map,
                         ..Default::default()
                     },
                     MenuEntry {
                         index: EDITOR_MENU_RESULT_SAVE_AS,
-                        title: "Save As".to_string(),
+                        title: entry_title("Save As", &EditorAction::OpenSaveMapWindow),
+                        ..Default::default()
+                    },
+                    MenuEntry {
+                        index: EDITOR_MENU_RESULT_REVEAL_IN_FOLDER,
+                        title: "Show in Folder".to_string(),
+                        is_disabled: !ctx.is_user_map,
+                        ..Default::default()
+                    },
+                    MenuEntry {
+                        index: EDITOR_MENU_RESULT_RESIZE_MAP,
+                        title: "Resize Map".to_string(),
+                        ..Default::default()
+                    },
+                    MenuEntry {
+                        index: EDITOR_MENU_RESULT_TEST_PLAY,
+                        title: entry_title("Test Play", &EditorAction::TestPlay),
                         ..Default::default()
                     },
                     MenuEntry {
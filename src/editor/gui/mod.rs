@@ -12,12 +12,13 @@ use ff_core::gui::get_gui_theme;
 pub use editor_menu::{
     close_editor_menu, draw_editor_menu, is_editor_menu_open, open_editor_menu, toggle_editor_menu,
     EDITOR_MENU_RESULT_MAIN_MENU, EDITOR_MENU_RESULT_NEW, EDITOR_MENU_RESULT_OPEN_IMPORT,
-    EDITOR_MENU_RESULT_QUIT, EDITOR_MENU_RESULT_SAVE, EDITOR_MENU_RESULT_SAVE_AS,
+    EDITOR_MENU_RESULT_QUIT, EDITOR_MENU_RESULT_RESIZE_MAP, EDITOR_MENU_RESULT_REVEAL_IN_FOLDER,
+    EDITOR_MENU_RESULT_SAVE, EDITOR_MENU_RESULT_SAVE_AS, EDITOR_MENU_RESULT_TEST_PLAY,
 };
 
 use ff_core::prelude::*;
 
-use super::{EditorAction, EditorContext};
+use super::{to_world_space, EditorAction, EditorContext, HoveredItem};
 
 use ff_core::{gui::ELEMENT_MARGIN, map::Map};
 
@@ -34,7 +35,7 @@ pub use windows::{
 use context_menu::{ContextMenu, ContextMenuEntry};
 use ff_core::gui::{BUTTON_FONT_SIZE, BUTTON_MARGIN_V, WINDOW_MARGIN_H, WINDOW_MARGIN_V};
 use ff_core::macroquad::hash;
-use ff_core::macroquad::ui::{root_ui, widgets};
+use ff_core::macroquad::ui::{root_ui, widgets, Ui};
 use ff_core::map::MapLayerKind;
 
 #[derive(Debug, Default, Clone)]
@@ -46,13 +47,22 @@ pub struct ButtonParams {
     // This holds the action that will be applied on click.
     // Setting this to `None` will disable the button.
     pub action: Option<EditorAction>,
+    // If this is set to `true` on a window button, its action will also be applied when enter is
+    // pressed while the window has focus.
+    pub is_default: bool,
 }
 
 pub struct EditorGui {
     left_toolbar: Option<Toolbar>,
     right_toolbar: Option<Toolbar>,
     open_windows: HashMap<TypeId, Box<dyn Window>>,
+    // This holds the ids of the open windows, in the order they were opened, so that the last one
+    // is the topmost window. The topmost window is drawn last and receives keyboard input.
+    window_stack: Vec<TypeId>,
     context_menu: Option<ContextMenu>,
+    // This holds the rect of the read-only banner, if it was drawn in the last frame, as it is
+    // only shown while a built-in map is open
+    read_only_banner_rect: Option<Rect>,
 }
 
 impl EditorGui {
@@ -65,12 +75,19 @@ impl EditorGui {
     pub const TILESET_DETAILS_HEIGHT_FACTOR: f32 = 0.5;
     pub const OBJECT_LIST_HEIGHT_FACTOR: f32 = 0.7;
 
+    pub const READ_ONLY_BANNER_HEIGHT: f32 = 40.0;
+    const READ_ONLY_BANNER_BUTTON_WIDTH: f32 = 96.0;
+    const READ_ONLY_BANNER_LABEL: &'static str =
+        "This is a built-in map and can not be overwritten";
+
     pub fn new() -> Self {
         EditorGui {
             left_toolbar: None,
             right_toolbar: None,
             open_windows: HashMap::new(),
+            window_stack: Vec::new(),
             context_menu: None,
+            read_only_banner_rect: None,
         }
     }
 
@@ -92,6 +109,14 @@ impl EditorGui {
         gui
     }
 
+    /// This returns `true` if any open window has text fields, meaning that the user might be
+    /// typing
+    pub fn has_text_input(&self) -> bool {
+        self.open_windows
+            .values()
+            .any(|window| window.get_params().has_text_input)
+    }
+
     pub fn context_menu_contains(&self, position: Vec2) -> bool {
         if let Some(context_menu) = &self.context_menu {
             if context_menu.contains(position) {
@@ -107,6 +132,12 @@ impl EditorGui {
             return true;
         }
 
+        if let Some(rect) = &self.read_only_banner_rect {
+            if rect.contains(position) {
+                return true;
+            }
+        }
+
         if let Some(left_toolbar) = &self.left_toolbar {
             if left_toolbar.contains(position) {
                 return true;
@@ -128,29 +159,348 @@ impl EditorGui {
         false
     }
 
-    pub fn open_context_menu(&mut self, position: Vec2, map: &Map, ctx: EditorContext) {
+    pub fn open_context_menu(
+        &mut self,
+        position: Vec2,
+        map: &Map,
+        ctx: EditorContext,
+        hovered_item: Option<HoveredItem>,
+    ) {
         let mut entries = vec![
             ContextMenuEntry::action("Undo", EditorAction::Undo),
             ContextMenuEntry::action("Redo", EditorAction::Redo),
+            ContextMenuEntry::separator(),
         ];
 
-        if let Some(layer_id) = &ctx.selected_layer {
-            let layer = &map.layers.get(layer_id).unwrap();
-            if layer.kind == MapLayerKind::ObjectLayer {
+        match hovered_item {
+            Some(HoveredItem::Object { layer_id, index }) => {
+                let object = &map.layers.get(&layer_id).unwrap().objects[index];
+
+                let move_entries = map
+                    .draw_order
+                    .iter()
+                    .filter(|id| {
+                        **id != layer_id
+                            && map.get_layer_kind(id) == Some(MapLayerKind::ObjectLayer)
+                    })
+                    .map(|id| {
+                        ContextMenuEntry::action(
+                            id,
+                            EditorAction::MoveObjectToLayer {
+                                from_layer: layer_id.clone(),
+                                index,
+                                to_layer: id.clone(),
+                            },
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                entries.append(&mut vec![
+                    ContextMenuEntry::action(
+                        "Edit Properties",
+                        EditorAction::OpenObjectPropertiesWindow {
+                            layer_id: layer_id.clone(),
+                            index,
+                        },
+                    ),
+                    ContextMenuEntry::action(
+                        "Duplicate",
+                        EditorAction::CreateObject {
+                            id: object.id.clone(),
+                            kind: object.kind,
+                            position: object.position + Vec2::from(map.tile_size),
+                            layer_id: layer_id.clone(),
+                        },
+                    ),
+                ]);
+
+                let object_cnt = map.layers.get(&layer_id).unwrap().objects.len();
+
+                if index + 1 < object_cnt {
+                    entries.push(ContextMenuEntry::action(
+                        "Bring to Front",
+                        EditorAction::ReorderObject {
+                            layer_id: layer_id.clone(),
+                            index,
+                            new_index: object_cnt - 1,
+                        },
+                    ));
+                }
+
+                if index > 0 {
+                    entries.push(ContextMenuEntry::action(
+                        "Send to Back",
+                        EditorAction::ReorderObject {
+                            layer_id: layer_id.clone(),
+                            index,
+                            new_index: 0,
+                        },
+                    ));
+                }
+
+                if !move_entries.is_empty() {
+                    entries.push(ContextMenuEntry::sub_menu("Move to Layer", &move_entries));
+                }
+
+                if let Some(group_id) = &object.group_id {
+                    entries.push(ContextMenuEntry::action(
+                        "Ungroup",
+                        EditorAction::UngroupObjects {
+                            layer_id: layer_id.clone(),
+                            group_id: group_id.clone(),
+                        },
+                    ));
+                }
+
                 entries.push(ContextMenuEntry::action(
-                    "Create Object",
-                    EditorAction::OpenCreateObjectWindow {
-                        position,
+                    "Delete",
+                    EditorAction::DeleteObject { index, layer_id },
+                ));
+            }
+            Some(HoveredItem::Tile { layer_id, index }) => {
+                let coords = uvec2(
+                    index as u32 % map.grid_size.width,
+                    index as u32 / map.grid_size.width,
+                );
+
+                entries.push(ContextMenuEntry::action(
+                    "Edit Properties",
+                    EditorAction::OpenTilePropertiesWindow {
                         layer_id: layer_id.clone(),
+                        index,
                     },
                 ));
+
+                // Tiles are duplicated to the next cell on the same row, if there is one
+                if coords.x + 1 < map.grid_size.width {
+                    if let Some(tile) = map.get_tile(&layer_id, coords.x, coords.y) {
+                        entries.push(ContextMenuEntry::action(
+                            "Duplicate",
+                            EditorAction::PlaceTile {
+                                id: tile.tile_id,
+                                layer_id: layer_id.clone(),
+                                tileset_id: tile.tileset_id.clone(),
+                                coords: uvec2(coords.x + 1, coords.y),
+                                attributes: tile.attributes.clone(),
+                            },
+                        ));
+                    }
+                }
+
+                if let Some(tile) = map.get_tile(&layer_id, coords.x, coords.y) {
+                    entries.push(ContextMenuEntry::action(
+                        "Replace All",
+                        EditorAction::OpenReplaceTileWindow {
+                            layer_id: Some(layer_id.clone()),
+                            from: Some((tile.tileset_id.clone(), tile.tile_id)),
+                        },
+                    ));
+                }
+
+                entries.push(ContextMenuEntry::action(
+                    "Delete",
+                    EditorAction::RemoveTile { layer_id, coords },
+                ));
             }
-        }
+            Some(HoveredItem::SpawnPoint(index)) => {
+                let spawn_point = map.spawn_points[index];
+
+                entries.append(&mut vec![
+                    ContextMenuEntry::action(
+                        "Edit Properties",
+                        EditorAction::OpenSpawnPointPropertiesWindow(index),
+                    ),
+                    ContextMenuEntry::action(
+                        "Duplicate",
+                        EditorAction::CreateSpawnPoint(
+                            spawn_point.position + Vec2::from(map.tile_size),
+                        ),
+                    ),
+                    ContextMenuEntry::action(
+                        "Flip Facing",
+                        EditorAction::SetSpawnPointFacing {
+                            index,
+                            is_facing_left: !spawn_point.is_facing_left,
+                        },
+                    ),
+                    ContextMenuEntry::action("Delete", EditorAction::DeleteSpawnPoint(index)),
+                ]);
+            }
+            None => {
+                if let Some(layer_id) = &ctx.selected_layer {
+                    let layer = &map.layers.get(layer_id).unwrap();
+
+                    let snap_entries = [
+                        (None, "Use Global"),
+                        (Some(true), "On"),
+                        (Some(false), "Off"),
+                    ]
+                    .into_iter()
+                    .filter(|(should_snap_to_grid, _)| {
+                        *should_snap_to_grid != layer.should_snap_to_grid
+                    })
+                    .map(|(should_snap_to_grid, label)| {
+                        ContextMenuEntry::action(
+                            label,
+                            EditorAction::SetLayerSnapToGrid {
+                                id: layer_id.clone(),
+                                should_snap_to_grid,
+                            },
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                    entries.push(ContextMenuEntry::sub_menu(
+                        "Layer Snap to Grid",
+                        &snap_entries,
+                    ));
+
+                    let solo_label = if ctx.soloed_layer.is_some() {
+                        "Unsolo Layer"
+                    } else {
+                        "Solo Layer"
+                    };
+
+                    entries.push(ContextMenuEntry::action(
+                        solo_label,
+                        EditorAction::ToggleSoloLayer,
+                    ));
+
+                    entries.push(ContextMenuEntry::action(
+                        "Set Layer Group",
+                        EditorAction::OpenLayerGroupWindow(layer_id.clone()),
+                    ));
+
+                    entries.push(ContextMenuEntry::action(
+                        "Set Layer Tint",
+                        EditorAction::OpenLayerTintWindow(layer_id.clone()),
+                    ));
+
+                    let convert_entries = [
+                        (MapLayerKind::TileLayer, false, "Tile Layer"),
+                        (MapLayerKind::TileLayer, true, "Tile Layer with Collision"),
+                        (MapLayerKind::ObjectLayer, false, "Object Layer"),
+                    ]
+                    .into_iter()
+                    .filter(|(kind, has_collision, _)| {
+                        *kind != layer.kind || *has_collision != layer.has_collision
+                    })
+                    .map(|(kind, has_collision, label)| {
+                        ContextMenuEntry::action(
+                            label,
+                            EditorAction::ConvertLayer {
+                                id: layer_id.clone(),
+                                kind,
+                                has_collision,
+                            },
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                    entries.push(ContextMenuEntry::sub_menu(
+                        "Convert Layer",
+                        &convert_entries,
+                    ));
+
+                    if layer.kind == MapLayerKind::TileLayer {
+                        entries.push(ContextMenuEntry::action(
+                            "Shift Layer",
+                            EditorAction::OpenShiftLayerWindow(layer_id.clone()),
+                        ));
+                    } else if layer.kind == MapLayerKind::ObjectLayer {
+                        entries.push(ContextMenuEntry::action(
+                            "Create Object",
+                            EditorAction::OpenCreateObjectWindow {
+                                position,
+                                layer_id: layer_id.clone(),
+                            },
+                        ));
+                    }
+                }
+
+                if let Some(region) = ctx.selected_region {
+                    entries.push(ContextMenuEntry::action(
+                        "Save as Prefab",
+                        EditorAction::OpenSavePrefabWindow(region),
+                    ));
+
+                    entries.push(ContextMenuEntry::action(
+                        "Copy to Clipboard",
+                        EditorAction::CopyRegionToClipboard(region),
+                    ));
+
+                    if let Some(layer_id) = &ctx.selected_layer {
+                        if map.get_layer_kind(layer_id) == Some(MapLayerKind::ObjectLayer) {
+                            let indices = map.get_object_indices_in_region(layer_id, region);
+
+                            if indices.len() > 1 {
+                                entries.push(ContextMenuEntry::action(
+                                    "Group Objects",
+                                    EditorAction::GroupObjects {
+                                        layer_id: layer_id.clone(),
+                                        indices,
+                                    },
+                                ));
+                            }
+                        }
+
+                        if map.get_layer_kind(layer_id) == Some(MapLayerKind::TileLayer) {
+                            entries.push(ContextMenuEntry::action(
+                                "Save as Stamp",
+                                EditorAction::OpenSaveStampWindow {
+                                    layer_id: layer_id.clone(),
+                                    region,
+                                },
+                            ));
+
+                            entries.push(ContextMenuEntry::action(
+                                "Edit Tile Attributes",
+                                EditorAction::OpenTileAttributesWindow {
+                                    layer_id: layer_id.clone(),
+                                    region,
+                                },
+                            ));
+                        }
+                    }
+                }
 
-        entries.append(&mut vec![
-            ContextMenuEntry::action("Add Layer", EditorAction::OpenCreateLayerWindow),
-            ContextMenuEntry::action("Background", EditorAction::OpenBackgroundPropertiesWindow),
-        ]);
+                let world_position = to_world_space(position);
+                if map.contains(world_position) {
+                    entries.push(ContextMenuEntry::action(
+                        "Paste from Clipboard",
+                        EditorAction::PasteRegionFromClipboard(map.to_coords(world_position)),
+                    ));
+                }
+
+                entries.append(&mut vec![
+                    ContextMenuEntry::action("Prefabs", EditorAction::OpenPrefabListWindow),
+                    ContextMenuEntry::action("Stamps", EditorAction::OpenStampPaletteWindow),
+                    ContextMenuEntry::action(
+                        "Replace Tiles",
+                        EditorAction::OpenReplaceTileWindow {
+                            layer_id: None,
+                            from: ctx.selected_tileset.clone().zip(ctx.selected_tile),
+                        },
+                    ),
+                    ContextMenuEntry::action("Add Layer", EditorAction::OpenCreateLayerWindow),
+                    ContextMenuEntry::action(
+                        "Arrange Spawn Points",
+                        EditorAction::OpenArrangeSpawnPointsWindow,
+                    ),
+                    ContextMenuEntry::action(
+                        "Map Properties",
+                        EditorAction::OpenMapPropertiesWindow,
+                    ),
+                    ContextMenuEntry::action(
+                        "Background",
+                        EditorAction::OpenBackgroundPropertiesWindow,
+                    ),
+                    ContextMenuEntry::action("Regenerate Preview", EditorAction::RegeneratePreview),
+                    ContextMenuEntry::action("Message Log", EditorAction::ToggleMessageLogWindow),
+                    ContextMenuEntry::action("Keybinds", EditorAction::OpenKeybindsWindow),
+                ]);
+            }
+        }
 
         self.context_menu = Some(ContextMenu::new(position, &entries));
     }
@@ -164,15 +514,103 @@ impl EditorGui {
         self.open_windows
             .entry(key)
             .or_insert_with(|| Box::new(window));
+
+        self.window_stack.retain(|id| *id != key);
+        self.window_stack.push(key);
+    }
+
+    pub fn has_window<W: Window + 'static>(&self) -> bool {
+        self.open_windows.contains_key(&TypeId::of::<W>())
     }
 
     pub fn remove_window<W: Window + 'static>(&mut self) {
         let key = TypeId::of::<W>();
-        self.open_windows.remove(&key).unwrap();
+        self.remove_window_id(key);
     }
 
     pub fn remove_window_id(&mut self, id: TypeId) {
         self.open_windows.remove(&id).unwrap();
+        self.window_stack.retain(|other_id| *other_id != id);
+    }
+
+    /// This returns the id of the most recently opened window that is still open
+    pub fn get_top_window_id(&self) -> Option<TypeId> {
+        self.window_stack.last().copied()
+    }
+
+    /// This returns the action that the topmost window applies when enter is pressed
+    pub fn get_top_window_confirm_action(
+        &self,
+        map: &Map,
+        ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        self.get_top_window_id()
+            .and_then(|id| self.open_windows.get(&id))
+            .and_then(|window| window.get_confirm_action(map, ctx))
+    }
+
+    /// This returns the action that the topmost window applies when it is dismissed with the
+    /// `toggle_menu` key binding
+    pub fn get_top_window_cancel_action(
+        &self,
+        map: &Map,
+        ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        self.get_top_window_id()
+            .and_then(|id| self.open_windows.get(&id))
+            .and_then(|window| window.get_cancel_action(map, ctx))
+    }
+
+    // This is drawn along the top of the map view, between the toolbars, while a built-in map is
+    // open, so that it is clear that changes have to be saved as a copy
+    fn draw_read_only_banner(&mut self, ui: &mut Ui) -> Option<EditorAction> {
+        let mut res = None;
+
+        let viewport_size = viewport_size();
+
+        let position = vec2(Self::LEFT_TOOLBAR_WIDTH, 0.0);
+        let size = vec2(
+            viewport_size.width - Self::LEFT_TOOLBAR_WIDTH - Self::RIGHT_TOOLBAR_WIDTH,
+            Self::READ_ONLY_BANNER_HEIGHT,
+        );
+
+        self.read_only_banner_rect = Some(Rect::new(position.x, position.y, size.x, size.y));
+
+        widgets::Window::new(
+            hash!("read_only_banner"),
+            to_window_position(position),
+            size,
+        )
+        .titlebar(false)
+        .movable(false)
+        .ui(ui, |ui| {
+            let button_height = BUTTON_FONT_SIZE + (BUTTON_MARGIN_V * 2.0);
+            let button_size = vec2(Self::READ_ONLY_BANNER_BUTTON_WIDTH, button_height);
+
+            let content_height = size.y - (WINDOW_MARGIN_V * 2.0);
+            let label_size = ui.calc_size(Self::READ_ONLY_BANNER_LABEL);
+
+            ui.label(
+                vec2(0.0, (content_height - label_size.y) / 2.0),
+                Self::READ_ONLY_BANNER_LABEL,
+            );
+
+            let button_position = vec2(
+                size.x - (WINDOW_MARGIN_H * 2.0) - button_size.x,
+                (content_height - button_size.y) / 2.0,
+            );
+
+            let save_btn = widgets::Button::new("Save a Copy")
+                .position(button_position)
+                .size(button_size)
+                .ui(ui);
+
+            if save_btn {
+                res = Some(EditorAction::OpenSaveMapWindow);
+            }
+        });
+
+        res
     }
 
     pub fn draw(&mut self, map: &Map, ctx: EditorContext) -> Option<EditorAction> {
@@ -197,13 +635,21 @@ impl EditorGui {
             }
         }
 
-        for (id, window) in &mut self.open_windows {
+        if ctx.is_user_map {
+            self.read_only_banner_rect = None;
+        } else if let Some(action) = self.draw_read_only_banner(ui) {
+            res = Some(action);
+        }
+
+        for id in &self.window_stack {
+            let window = self.open_windows.get_mut(id).unwrap();
+
             let params = window.get_params().clone();
 
             let position = params.get_absolute_position();
             let size = params.size;
 
-            widgets::Window::new(hash!(id), position, size)
+            widgets::Window::new(hash!(id), to_window_position(position), size)
                 .titlebar(false)
                 .movable(!params.is_static)
                 .ui(ui, |ui| {
@@ -285,6 +731,28 @@ impl EditorGui {
                 });
         }
 
+        if is_key_pressed(KeyCode::Enter) {
+            if let Some(action) = self.get_top_window_confirm_action(map, &ctx) {
+                res = Some(action);
+            }
+        }
+
+        if self.context_menu.is_none() {
+            let tooltip = self
+                .left_toolbar
+                .as_ref()
+                .and_then(|toolbar| toolbar.get_tooltip(map, &ctx))
+                .or_else(|| {
+                    self.right_toolbar
+                        .as_ref()
+                        .and_then(|toolbar| toolbar.get_tooltip(map, &ctx))
+                });
+
+            if let Some(lines) = tooltip {
+                draw_tooltip(ui, ctx.cursor_position, &lines);
+            }
+        }
+
         if let Some(context_menu) = &mut self.context_menu {
             if let Some(action) = context_menu.draw(ui) {
                 self.context_menu = None;
@@ -313,6 +781,18 @@ impl EditorGui {
                         let action = EditorAction::OpenSaveMapWindow;
                         res = Some(action);
                     }
+                    EDITOR_MENU_RESULT_REVEAL_IN_FOLDER => {
+                        let action = EditorAction::RevealMapInFolder;
+                        res = Some(action);
+                    }
+                    EDITOR_MENU_RESULT_RESIZE_MAP => {
+                        let action = EditorAction::OpenResizeMapWindow;
+                        res = Some(action);
+                    }
+                    EDITOR_MENU_RESULT_TEST_PLAY => {
+                        let action = EditorAction::TestPlay;
+                        res = Some(action);
+                    }
                     EDITOR_MENU_RESULT_MAIN_MENU => {
                         let action = EditorAction::ExitToMainMenu;
                         res = Some(action);
@@ -337,3 +817,46 @@ impl Default for EditorGui {
         Self::new()
     }
 }
+
+const TOOLTIP_CURSOR_OFFSET: f32 = 16.0;
+
+fn draw_tooltip(ui: &mut Ui, cursor_position: Vec2, lines: &[String]) {
+    {
+        let gui_theme = get_gui_theme();
+        ui.push_skin(&gui_theme.context_menu);
+    }
+
+    let margins = vec2(ELEMENT_MARGIN, ELEMENT_MARGIN);
+
+    let mut size = Vec2::ZERO;
+    for line in lines {
+        let line_size = ui.calc_size(line);
+        size.x = size.x.max(line_size.x);
+        size.y += line_size.y;
+    }
+
+    size += margins * 2.0;
+
+    let position = {
+        let viewport_size = viewport_size();
+        let position = cursor_position + vec2(TOOLTIP_CURSOR_OFFSET, TOOLTIP_CURSOR_OFFSET);
+
+        vec2(
+            position.x.min(viewport_size.width - size.x),
+            position.y.min(viewport_size.height - size.y),
+        )
+    };
+
+    widgets::Group::new(hash!("tooltip"), size)
+        .position(to_window_position(position))
+        .ui(ui, |ui| {
+            let mut position = margins;
+
+            for line in lines {
+                ui.label(position, line);
+                position.y += ui.calc_size(line).y;
+            }
+        });
+
+    ui.pop_skin();
+}
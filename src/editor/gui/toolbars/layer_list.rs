@@ -1,8 +1,9 @@
 use ff_core::prelude::*;
+use std::collections::HashSet;
 use std::ops::Deref;
 
 use ff_core::gui::get_gui_theme;
-use ff_core::map::{Map, MapLayerKind};
+use ff_core::map::{Map, MapLayer, MapLayerKind};
 
 use super::{
     ButtonParams, EditorAction, EditorContext, Toolbar, ToolbarElement, ToolbarElementParams,
@@ -12,9 +13,14 @@ use ff_core::macroquad::ui::{widgets, Ui};
 
 pub struct LayerListElement {
     params: ToolbarElementParams,
+    // This holds the names of the layer groups that are collapsed in the list. This is not stored
+    // in the map, so all groups are expanded when the editor is opened.
+    collapsed_groups: HashSet<String>,
 }
 
 impl LayerListElement {
+    const GROUP_ENTRY_INDENT: f32 = 12.0;
+
     pub fn new() -> Self {
         let params = ToolbarElementParams {
             header: Some("Layers".to_string()),
@@ -22,8 +28,213 @@ impl LayerListElement {
             has_margins: false,
         };
 
-        LayerListElement { params }
+        LayerListElement {
+            params,
+            collapsed_groups: HashSet::new(),
+        }
+    }
+
+    fn draw_group_entry(
+        &mut self,
+        ui: &mut Ui,
+        position: Vec2,
+        entry_size: Vec2,
+        group: &str,
+        map: &Map,
+    ) -> Option<EditorAction> {
+        let mut res = None;
+
+        let is_collapsed = self.collapsed_groups.contains(group);
+
+        let group_btn = widgets::Button::new("")
+            .size(entry_size)
+            .position(position)
+            .ui(ui);
+
+        let label = if is_collapsed {
+            format!("[+] {}", group)
+        } else {
+            format!("[-] {}", group)
+        };
+
+        ui.label(position, &label);
+
+        if group_btn {
+            if is_collapsed {
+                self.collapsed_groups.remove(group);
+            } else {
+                self.collapsed_groups.insert(group.to_string());
+            }
+        }
+
+        {
+            let gui_theme = get_gui_theme();
+            ui.push_skin(&gui_theme.list_box_no_bg);
+        }
+
+        // The group is shown as visible if any of its layers are visible
+        let is_visible = map
+            .get_group_layer_ids(group)
+            .iter()
+            .any(|id| map.layers.get(id).unwrap().is_visible);
+
+        if draw_visibility_button(ui, position, entry_size, is_visible) {
+            let action = EditorAction::SetLayerGroupVisibility {
+                group: group.to_string(),
+                is_visible: !is_visible,
+            };
+
+            res = Some(action);
+        }
+
+        ui.pop_skin();
+
+        res
+    }
+}
+
+// This draws a visibility toggle at the right edge of a list entry and returns `true` if it was
+// clicked
+fn draw_visibility_button(ui: &mut Ui, position: Vec2, entry_size: Vec2, is_visible: bool) -> bool {
+    let texture = {
+        if is_visible {
+            get_texture("visibility_icon")
+        } else {
+            get_texture("visibility_off_icon")
+        }
+    };
+
+    let btn_size = vec2(entry_size.y, entry_size.y);
+
+    let btn_position = vec2(position.x + entry_size.x - btn_size.x, position.y);
+
+    let visibility_btn = widgets::Button::new("")
+        .size(btn_size)
+        .position(btn_position)
+        .ui(ui);
+
+    {
+        let texture_size = {
+            let height = entry_size.y - ELEMENT_MARGIN;
+            vec2(height, height)
+        };
+
+        let half_margin = ELEMENT_MARGIN / 2.0;
+
+        let texture_position = vec2(btn_position.x + half_margin, btn_position.y + half_margin);
+
+        widgets::Texture::new(texture.deref().into())
+            .size(texture_size.x, texture_size.y)
+            .position(texture_position)
+            .ui(ui);
     }
+
+    visibility_btn
+}
+
+fn draw_layer_entry(
+    ui: &mut Ui,
+    position: Vec2,
+    entry_size: Vec2,
+    indent: f32,
+    layer: &MapLayer,
+    ctx: &EditorContext,
+) -> Option<EditorAction> {
+    let mut res = None;
+
+    let layer_id = &layer.id;
+
+    let is_selected = if let Some(selected_id) = &ctx.selected_layer {
+        layer_id == selected_id
+    } else {
+        false
+    };
+
+    if is_selected {
+        let gui_theme = get_gui_theme();
+        ui.push_skin(&gui_theme.list_box_selected);
+    }
+
+    let layer_btn = widgets::Button::new("")
+        .size(entry_size)
+        .position(position)
+        .ui(ui);
+
+    let label = if layer.kind == MapLayerKind::ObjectLayer {
+        format!("(O) {}", layer_id)
+    } else {
+        format!("(T) {}", layer_id)
+    };
+
+    ui.label(vec2(position.x + indent, position.y), &label);
+
+    if layer_btn {
+        res = Some(EditorAction::SelectLayer(layer_id.clone()));
+    }
+
+    if is_selected {
+        ui.pop_skin();
+    }
+
+    {
+        let gui_theme = get_gui_theme();
+        ui.push_skin(&gui_theme.list_box_no_bg);
+    }
+
+    {
+        if draw_visibility_button(ui, position, entry_size, layer.is_visible) {
+            let action = EditorAction::UpdateLayer {
+                id: layer_id.clone(),
+                is_visible: !layer.is_visible,
+                is_locked: layer.is_locked,
+            };
+
+            res = Some(action);
+        }
+
+        let btn_size = vec2(entry_size.y, entry_size.y);
+        let btn_position = vec2(position.x + entry_size.x - btn_size.x, position.y);
+
+        // There is no lock icon, so the lock toggle is a text button, placed to the left
+        // of the visibility toggle
+        let lock_label = if layer.is_locked { "L" } else { "-" };
+
+        let lock_btn = widgets::Button::new(lock_label)
+            .size(btn_size)
+            .position(vec2(btn_position.x - btn_size.x, btn_position.y))
+            .ui(ui);
+
+        if lock_btn {
+            let action = EditorAction::UpdateLayer {
+                id: layer_id.clone(),
+                is_visible: layer.is_visible,
+                is_locked: !layer.is_locked,
+            };
+
+            res = Some(action);
+        }
+
+        // This opens the tint window and is upper case if the layer is tinted. It is placed to
+        // the left of the lock toggle.
+        let tint_label = if MapLayer::is_default_tint(&layer.tint) {
+            "c"
+        } else {
+            "C"
+        };
+
+        let tint_btn = widgets::Button::new(tint_label)
+            .size(btn_size)
+            .position(vec2(btn_position.x - btn_size.x * 2.0, btn_position.y))
+            .ui(ui);
+
+        if tint_btn {
+            res = Some(EditorAction::OpenLayerTintWindow(layer_id.clone()));
+        }
+    }
+
+    ui.pop_skin();
+
+    res
 }
 
 impl ToolbarElement for LayerListElement {
@@ -46,94 +257,47 @@ impl ToolbarElement for LayerListElement {
         let gui_theme = get_gui_theme();
         ui.push_skin(&gui_theme.list_box);
 
+        // Grouped layers are listed under their group, which is placed where the first of its
+        // layers is in the draw order
+        let mut listed_groups: Vec<&str> = Vec::new();
+
         for layer_id in &map.draw_order {
             let layer = map.layers.get(layer_id).unwrap();
 
-            let is_selected = if let Some(selected_id) = &ctx.selected_layer {
-                layer_id == selected_id
-            } else {
-                false
-            };
+            if let Some(group) = &layer.group {
+                if listed_groups.contains(&group.as_str()) {
+                    continue;
+                }
 
-            if is_selected {
-                let gui_theme = get_gui_theme();
-                ui.push_skin(&gui_theme.list_box_selected);
-            }
+                listed_groups.push(group);
 
-            let layer_btn = widgets::Button::new("")
-                .size(entry_size)
-                .position(position)
-                .ui(ui);
+                if let Some(action) = self.draw_group_entry(ui, position, entry_size, group, map) {
+                    res = Some(action);
+                }
 
-            let label = if layer.kind == MapLayerKind::ObjectLayer {
-                format!("(O) {}", layer_id)
-            } else {
-                format!("(T) {}", layer_id)
-            };
+                position.y += entry_size.y;
 
-            ui.label(position, &label);
+                if !self.collapsed_groups.contains(group) {
+                    for layer_id in map.get_group_layer_ids(group) {
+                        let layer = map.layers.get(&layer_id).unwrap();
 
-            if layer_btn {
-                res = Some(EditorAction::SelectLayer(layer_id.clone()));
-            }
+                        let indent = Self::GROUP_ENTRY_INDENT;
+                        if let Some(action) =
+                            draw_layer_entry(ui, position, entry_size, indent, layer, ctx)
+                        {
+                            res = Some(action);
+                        }
 
-            if is_selected {
-                ui.pop_skin();
-            }
-
-            {
-                let gui_theme = get_gui_theme();
-                ui.push_skin(&gui_theme.list_box_no_bg);
-            }
-
-            {
-                let texture = {
-                    if layer.is_visible {
-                        get_texture("visibility_icon")
-                    } else {
-                        get_texture("visibility_off_icon")
+                        position.y += entry_size.y;
                     }
-                };
-
-                let btn_size = vec2(entry_size.y, entry_size.y);
-
-                let btn_position = vec2(position.x + entry_size.x - btn_size.x, position.y);
-
-                let visibility_btn = widgets::Button::new("")
-                    .size(btn_size)
-                    .position(btn_position)
-                    .ui(ui);
-
-                {
-                    let texture_size = {
-                        let height = entry_size.y - ELEMENT_MARGIN;
-                        vec2(height, height)
-                    };
-
-                    let half_margin = ELEMENT_MARGIN / 2.0;
-
-                    let texture_position =
-                        vec2(btn_position.x + half_margin, btn_position.y + half_margin);
-
-                    widgets::Texture::new(texture.deref().into())
-                        .size(texture_size.x, texture_size.y)
-                        .position(texture_position)
-                        .ui(ui);
                 }
-
-                if visibility_btn {
-                    let action = EditorAction::UpdateLayer {
-                        id: layer_id.clone(),
-                        is_visible: !layer.is_visible,
-                    };
-
+            } else {
+                if let Some(action) = draw_layer_entry(ui, position, entry_size, 0.0, layer, ctx) {
                     res = Some(action);
                 }
-            }
 
-            ui.pop_skin();
-
-            position.y += entry_size.y;
+                position.y += entry_size.y;
+            }
         }
 
         ui.pop_skin();
@@ -147,6 +311,7 @@ impl ToolbarElement for LayerListElement {
         let mut delete_action = None;
         let mut move_up_action = None;
         let mut move_down_action = None;
+        let mut merge_down_action = None;
 
         if let Some(layer_id) = &ctx.selected_layer {
             let mut index = None;
@@ -162,19 +327,32 @@ impl ToolbarElement for LayerListElement {
 
             delete_action = Some(EditorAction::DeleteLayer(layer_id.clone()));
 
-            if let Some(index) = index {
-                if index > 0 {
-                    move_up_action = Some(EditorAction::SetLayerDrawOrderIndex {
-                        id: layer_id.clone(),
-                        index: index - 1,
-                    });
+            // Layers are moved past whole groups, and only within their own group, so that the
+            // layers of a group stay together
+            move_up_action = map.get_layer_move_index(layer_id, true).map(|index| {
+                EditorAction::SetLayerDrawOrderIndex {
+                    id: layer_id.clone(),
+                    index,
+                }
+            });
+
+            move_down_action = map.get_layer_move_index(layer_id, false).map(|index| {
+                EditorAction::SetLayerDrawOrderIndex {
+                    id: layer_id.clone(),
+                    index,
                 }
+            });
 
+            if let Some(index) = index {
                 if index + 1 < map.draw_order.len() {
-                    move_down_action = Some(EditorAction::SetLayerDrawOrderIndex {
-                        id: layer_id.clone(),
-                        index: index + 1,
-                    });
+                    // Layers are merged into the layer that is drawn beneath them, which is the
+                    // next one in the list
+                    let is_tile_layer =
+                        |id: &str| map.get_layer_kind(id) == Some(MapLayerKind::TileLayer);
+
+                    if is_tile_layer(layer_id) && is_tile_layer(&map.draw_order[index + 1]) {
+                        merge_down_action = Some(EditorAction::MergeLayerDown(layer_id.clone()));
+                    }
                 }
             }
         }
@@ -203,6 +381,12 @@ impl ToolbarElement for LayerListElement {
             ..Default::default()
         });
 
+        res.push(ButtonParams {
+            label: "Merge",
+            action: merge_down_action,
+            ..Default::default()
+        });
+
         res
     }
 }
@@ -60,6 +60,16 @@ pub trait ToolbarElement {
     fn is_drawn(&self, _map: &Map, _ctx: &EditorContext) -> bool {
         true
     }
+
+    // This is called before `draw`, with the rect of the content area of the element, in screen
+    // space. Implement this if the element needs to hit-test the cursor against its content.
+    fn set_content_rect(&mut self, _rect: Rect) {}
+
+    // Implement this to show a tooltip when the cursor, in screen space, is hovering something in
+    // the element.
+    fn get_tooltip(&self, _cursor_position: Vec2) -> Option<Vec<String>> {
+        None
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -127,6 +137,23 @@ impl Toolbar {
         rect.contains(point)
     }
 
+    /// This returns the lines of the tooltip of the element under the cursor, if any
+    pub fn get_tooltip(&self, map: &Map, ctx: &EditorContext) -> Option<Vec<String>> {
+        if !self.contains(ctx.cursor_position) {
+            return None;
+        }
+
+        self.draw_order.iter().find_map(|id| {
+            let (_, element) = self.elements.get(id).unwrap();
+
+            if element.is_drawn(map, ctx) {
+                element.get_tooltip(ctx.cursor_position)
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn draw(&mut self, ui: &mut Ui, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
         let mut res = None;
 
@@ -144,9 +171,10 @@ impl Toolbar {
 
         let toolbar_id = hash!(self.position);
         let toolbar_size = vec2(self.width, viewport_size.height);
+        let toolbar_position = position;
 
         widgets::Group::new(toolbar_id, toolbar_size)
-            .position(position)
+            .position(to_window_position(position))
             .ui(ui, |ui| {
                 let mut position = Vec2::ZERO;
 
@@ -213,6 +241,16 @@ impl Toolbar {
                             content_position += margins;
                         }
 
+                        {
+                            let position = toolbar_position + content_position;
+                            element.set_content_rect(Rect::new(
+                                position.x,
+                                position.y,
+                                content_size.x - margins.x,
+                                content_size.y,
+                            ));
+                        }
+
                         widgets::Group::new(hash!(element_id, "content"), content_size)
                             .position(content_position)
                             .ui(ui, |ui| {
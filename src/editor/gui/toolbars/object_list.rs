@@ -4,9 +4,8 @@ use super::{EditorAction, EditorContext, Map, Toolbar, ToolbarElement, ToolbarEl
 
 use ff_core::map::MapLayerKind;
 
-use crate::editor::{gui::ButtonParams, EditorCamera};
+use crate::editor::{get_view_rect, gui::ButtonParams};
 use ff_core::gui::get_gui_theme;
-use ff_core::macroquad::prelude::scene;
 use ff_core::macroquad::ui::{widgets, Ui};
 
 pub struct ObjectListElement {
@@ -51,8 +50,8 @@ impl ToolbarElement for ObjectListElement {
         let layer = map.layers.get(layer_id).unwrap();
 
         for (i, object) in layer.objects.iter().enumerate() {
-            let is_selected = if let Some(selected_index) = ctx.selected_object {
-                selected_index == i
+            let is_selected = if let Some(selected) = &ctx.selected_object {
+                selected.layer_id == *layer_id && selected.index == i
             } else {
                 false
             };
@@ -92,8 +91,7 @@ impl ToolbarElement for ObjectListElement {
         let layer_id = ctx.selected_layer.clone().unwrap();
 
         let position = {
-            let camera = scene::find_node_by_type::<EditorCamera>().unwrap();
-            let view_rect = camera.get_view_rect();
+            let view_rect = get_view_rect();
             let offset = vec2(view_rect.width, view_rect.height) / 2.0;
             (view_rect.point() + offset) - map.world_offset
         };
@@ -106,7 +104,12 @@ impl ToolbarElement for ObjectListElement {
         let mut delete_action = None;
         let mut properties_action = None;
 
-        if let Some(index) = ctx.selected_object {
+        if let Some(index) = ctx
+            .selected_object
+            .as_ref()
+            .filter(|selected| selected.layer_id == layer_id)
+            .map(|selected| selected.index)
+        {
             delete_action = Some(EditorAction::DeleteObject {
                 index,
                 layer_id: layer_id.clone(),
@@ -120,16 +123,19 @@ impl ToolbarElement for ObjectListElement {
                 label: "+",
                 width_override: Some(0.25),
                 action: create_action,
+                ..Default::default()
             },
             ButtonParams {
                 label: "-",
                 width_override: Some(0.25),
                 action: delete_action,
+                ..Default::default()
             },
             ButtonParams {
                 label: "Edit",
                 width_override: Some(0.5),
                 action: properties_action,
+                ..Default::default()
             },
         ]
     }
@@ -9,9 +9,28 @@ use ff_core::macroquad::ui::{widgets, Ui};
 
 pub struct TilesetDetailsElement {
     params: ToolbarElementParams,
+    // This is the zoom factor of the tileset, where `1.0` fits the tileset to the element width
+    zoom: f32,
+    // This is the scroll offset of the tileset, in scaled pixels
+    scroll: Vec2,
+    // This holds the cursor position of the previous frame, while the tileset is being panned by
+    // dragging it with the middle mouse button
+    pan_cursor_position: Option<Vec2>,
+    content_rect: Rect,
+    // This is used to reset zoom and scroll when the selected tileset changes
+    tileset_id: Option<String>,
 }
 
 impl TilesetDetailsElement {
+    const ZOOM_MIN: f32 = 1.0;
+    const ZOOM_MAX: f32 = 8.0;
+    const ZOOM_STEP: f32 = 0.25;
+
+    const SCROLL_SPEED: f32 = 32.0;
+
+    const FIT_BUTTON_WIDTH: f32 = 32.0;
+    const FIT_BUTTON_HEIGHT: f32 = 20.0;
+
     pub fn new() -> Self {
         let params = ToolbarElementParams {
             header: None,
@@ -19,7 +38,61 @@ impl TilesetDetailsElement {
             ..Default::default()
         };
 
-        TilesetDetailsElement { params }
+        TilesetDetailsElement {
+            params,
+            zoom: Self::ZOOM_MIN,
+            scroll: Vec2::ZERO,
+            pan_cursor_position: None,
+            content_rect: Rect::default(),
+            tileset_id: None,
+        }
+    }
+
+    fn reset_view(&mut self) {
+        self.zoom = Self::ZOOM_MIN;
+        self.scroll = Vec2::ZERO;
+    }
+
+    // Dragging with the middle mouse button pans the tileset. A drag has to start inside the
+    // element, but it will continue if the cursor leaves it, until the button is released.
+    fn handle_pan(&mut self, cursor_position: Vec2) {
+        if !is_mouse_button_down(MouseButton::Middle) {
+            self.pan_cursor_position = None;
+            return;
+        }
+
+        if let Some(previous_position) = self.pan_cursor_position {
+            self.scroll -= cursor_position - previous_position;
+            self.pan_cursor_position = Some(cursor_position);
+        } else if self.content_rect.contains(cursor_position) {
+            self.pan_cursor_position = Some(cursor_position);
+        }
+    }
+
+    // Ctrl + wheel zooms, anchored at the cursor, shift + wheel scrolls horizontally and wheel
+    // scrolls vertically.
+    fn handle_input(&mut self, cursor_position: Vec2) {
+        let mouse_wheel = mouse_wheel().y;
+
+        if mouse_wheel == 0.0 {
+            return;
+        }
+
+        let direction = mouse_wheel.signum();
+
+        if is_key_down(KeyCode::LeftControl) {
+            let local_position = cursor_position - self.content_rect.point();
+            let unscaled_position = (local_position + self.scroll) / self.zoom;
+
+            self.zoom = (self.zoom + direction * Self::ZOOM_STEP * self.zoom)
+                .clamp(Self::ZOOM_MIN, Self::ZOOM_MAX);
+
+            self.scroll = unscaled_position * self.zoom - local_position;
+        } else if is_key_down(KeyCode::LeftShift) {
+            self.scroll.x -= direction * Self::SCROLL_SPEED;
+        } else {
+            self.scroll.y -= direction * Self::SCROLL_SPEED;
+        }
     }
 }
 
@@ -40,6 +113,17 @@ impl ToolbarElement for TilesetDetailsElement {
         let mut position = Vec2::ZERO;
 
         if let Some(tileset_id) = &ctx.selected_tileset {
+            if self.tileset_id.as_ref() != Some(tileset_id) {
+                self.tileset_id = Some(tileset_id.clone());
+                self.reset_view();
+            }
+
+            if self.content_rect.contains(ctx.cursor_position) {
+                self.handle_input(ctx.cursor_position);
+            }
+
+            self.handle_pan(ctx.cursor_position);
+
             let tileset = map.tilesets.get(tileset_id).unwrap();
 
             let texture = get_texture(&tileset.texture_id);
@@ -49,7 +133,7 @@ impl ToolbarElement for TilesetDetailsElement {
                 tileset.grid_size.height as f32,
             );
 
-            let scaled_width = size.x;
+            let scaled_width = size.x * self.zoom;
             let texture_size = texture.size();
 
             let scaled_height = (scaled_width / texture_size.width) * texture_size.height;
@@ -59,8 +143,13 @@ impl ToolbarElement for TilesetDetailsElement {
                 scaled_height / grid_size.height,
             );
 
+            {
+                let max_scroll = (vec2(scaled_width, scaled_height) - size).max(Vec2::ZERO);
+                self.scroll = self.scroll.clamp(Vec2::ZERO, max_scroll);
+            }
+
             widgets::Texture::new(texture.deref().into())
-                .position(position)
+                .position(position - self.scroll)
                 .size(scaled_width, scaled_height)
                 .ui(ui);
 
@@ -69,8 +158,20 @@ impl ToolbarElement for TilesetDetailsElement {
                 ui.push_skin(&gui_theme.tileset_grid);
             }
 
+            let visible_rect = Rect::new(self.scroll.x, self.scroll.y, size.x, size.y);
+
             for y in 0..tileset.grid_size.height {
                 for x in 0..tileset.grid_size.width {
+                    let position: Vec2 = vec2(x as f32, y as f32) * Vec2::from(scaled_tile_size);
+
+                    let tile_rect = Rect::from_point_size(position, scaled_tile_size);
+
+                    // Tiles that are scrolled out of view are not drawn, so that they can not be
+                    // clicked
+                    if !visible_rect.overlaps(&tile_rect) {
+                        continue;
+                    }
+
                     let tile_id = y * tileset.grid_size.width + x;
 
                     let is_selected = if let Some(selected) = ctx.selected_tile {
@@ -84,18 +185,21 @@ impl ToolbarElement for TilesetDetailsElement {
                         ui.push_skin(&gui_theme.tileset_grid_selected);
                     }
 
-                    let position: Vec2 = vec2(x as f32, y as f32) * Vec2::from(scaled_tile_size);
-
                     let button = widgets::Button::new("")
                         .size(scaled_tile_size.into())
-                        .position(position)
+                        .position(position - self.scroll)
                         .ui(ui);
 
                     if button {
-                        res = Some(EditorAction::SelectTile {
+                        // Tiles selected from the tileset have no attributes, so any attributes
+                        // picked with the tile picker tool are cleared
+                        let action = EditorAction::SelectTile {
                             id: tile_id,
                             tileset_id: tileset.id.clone(),
-                        });
+                        }
+                        .then(EditorAction::ClearPickedTileAttributes);
+
+                        res = Some(action);
                     }
 
                     if is_selected {
@@ -106,7 +210,19 @@ impl ToolbarElement for TilesetDetailsElement {
 
             ui.pop_skin();
 
-            position.y += scaled_height + ELEMENT_MARGIN;
+            if self.zoom != Self::ZOOM_MIN || self.scroll != Vec2::ZERO {
+                let fit_btn = widgets::Button::new("Fit")
+                    .size(vec2(Self::FIT_BUTTON_WIDTH, Self::FIT_BUTTON_HEIGHT))
+                    .position(vec2(size.x - Self::FIT_BUTTON_WIDTH, 0.0))
+                    .ui(ui);
+
+                if fit_btn {
+                    self.reset_view();
+                    res = None;
+                }
+            }
+
+            position.y += scaled_height.min(size.y) + ELEMENT_MARGIN;
         }
 
         res
@@ -115,6 +231,10 @@ impl ToolbarElement for TilesetDetailsElement {
     fn is_drawn(&self, _map: &Map, ctx: &EditorContext) -> bool {
         ctx.selected_tileset.is_some()
     }
+
+    fn set_content_rect(&mut self, rect: Rect) {
+        self.content_rect = rect;
+    }
 }
 
 impl Default for TilesetDetailsElement {
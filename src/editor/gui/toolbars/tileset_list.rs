@@ -48,17 +48,20 @@ impl ToolbarElement for TilesetListElement {
                 label: "+",
                 width_override: Some(0.25),
                 action: Some(EditorAction::OpenCreateTilesetWindow),
+                ..Default::default()
             },
             ButtonParams {
                 label: "-",
                 width_override: Some(0.25),
                 action: delete_action,
+                ..Default::default()
             },
             ButtonParams {
                 label: "Edit",
                 width_override: Some(0.5),
                 //action: properties_action,
                 action: None,
+                ..Default::default()
             },
         ]
     }
@@ -78,7 +81,11 @@ impl ToolbarElement for TilesetListElement {
         let gui_theme = get_gui_theme();
         ui.push_skin(&gui_theme.list_box);
 
-        for tileset_id in map.tilesets.keys() {
+        // This is sorted to match the order used by the tileset hotkeys
+        let mut tileset_ids = map.tilesets.keys().collect::<Vec<_>>();
+        tileset_ids.sort_unstable();
+
+        for tileset_id in tileset_ids {
             let is_selected = if let Some(selected_id) = &ctx.selected_tileset {
                 tileset_id == selected_id
             } else {
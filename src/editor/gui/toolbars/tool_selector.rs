@@ -7,15 +7,18 @@ use super::{
     EditorAction, EditorContext, Map, ToolbarElement, ToolbarElementParams, ELEMENT_MARGIN,
 };
 
-use crate::editor::tools::EditorTool;
-use crate::editor::tools::{get_tool_instance_of_id, EditorToolParams};
+use crate::editor::input::get_tool_keybind;
+use crate::editor::tools::{iter_tools, EditorToolParams};
 
 use ff_core::gui::get_gui_theme;
 use ff_core::macroquad::ui::{widgets, Ui};
 
 pub struct ToolSelectorElement {
     params: ToolbarElementParams,
-    tools: Vec<TypeId>,
+    content_rect: Rect,
+    // This holds the rect of each tool button, relative to the content rect, along with the lines
+    // of its tooltip. It is updated every draw.
+    tooltips: Vec<(Rect, Vec<String>)>,
 }
 
 impl ToolSelectorElement {
@@ -28,23 +31,10 @@ impl ToolSelectorElement {
 
         ToolSelectorElement {
             params,
-            tools: Vec::new(),
+            content_rect: Rect::default(),
+            tooltips: Vec::new(),
         }
     }
-
-    #[must_use]
-    pub fn with_tool<T: EditorTool + 'static>(self) -> Self {
-        let id = TypeId::of::<T>();
-        let mut tools = self.tools;
-        tools.push(id);
-
-        ToolSelectorElement { tools, ..self }
-    }
-
-    pub fn add_tool<T: EditorTool + 'static>(&mut self) {
-        let id = TypeId::of::<T>();
-        self.tools.push(id);
-    }
 }
 
 impl ToolbarElement for ToolSelectorElement {
@@ -70,17 +60,9 @@ impl ToolbarElement for ToolSelectorElement {
         let mut position = Vec2::ZERO;
 
         // TODO: Grey out inactive tools, in stead of removing them altogether
-        let mut available_tools = self
-            .tools
-            .iter()
-            .filter_map(|id| {
-                let tool = get_tool_instance_of_id(id);
-                if tool.is_available(map, ctx) {
-                    return Some((Some(*id), tool.get_params().clone()));
-                }
-
-                None
-            })
+        let mut available_tools = iter_tools()
+            .filter(|(_, tool)| tool.is_available(map, ctx))
+            .map(|(id, tool)| (Some(id), tool.get_params().clone()))
             .collect::<Vec<(Option<TypeId>, EditorToolParams)>>();
 
         available_tools.insert(
@@ -89,13 +71,32 @@ impl ToolbarElement for ToolSelectorElement {
                 None,
                 EditorToolParams {
                     name: "Cursor".to_string(),
-                    icon_texture_id: "cursor_tool_icon".to_string(),
+                    description: "Select, move and edit objects, spawn points and tiles"
+                        .to_string(),
+                    icon_texture_id: Some("cursor_tool_icon".to_string()),
                     ..Default::default()
                 },
             ),
         );
 
+        self.tooltips.clear();
+
         for (id, params) in available_tools {
+            {
+                let title = match get_tool_keybind(id) {
+                    Some(keybind) => format!("{} ({})", params.name, keybind),
+                    None => params.name.clone(),
+                };
+
+                let mut lines = vec![title];
+                if !params.description.is_empty() {
+                    lines.push(params.description.clone());
+                }
+
+                let rect = Rect::new(position.x, position.y, size.x, size.y);
+                self.tooltips.push((rect, lines));
+            }
+
             let mut is_selected = false;
             if let Some(id) = id {
                 if let Some(selected_id) = ctx.selected_tool {
@@ -115,7 +116,7 @@ impl ToolbarElement for ToolSelectorElement {
                 .size(size)
                 .ui(ui);
 
-            let texture = get_texture(&params.icon_texture_id);
+            let texture = params.get_icon_texture();
 
             widgets::Texture::new(texture.deref().into())
                 .position(position)
@@ -160,6 +161,19 @@ impl ToolbarElement for ToolSelectorElement {
 
         res
     }
+
+    fn set_content_rect(&mut self, rect: Rect) {
+        self.content_rect = rect;
+    }
+
+    fn get_tooltip(&self, cursor_position: Vec2) -> Option<Vec<String>> {
+        let local_position = cursor_position - self.content_rect.point();
+
+        self.tooltips
+            .iter()
+            .find(|(rect, _)| rect.contains(local_position))
+            .map(|(_, lines)| lines.clone())
+    }
 }
 
 impl Default for ToolSelectorElement {
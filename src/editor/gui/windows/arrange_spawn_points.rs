@@ -0,0 +1,118 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::NumberInput;
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+/// This lays out all the spawn points of the map at once, either by spreading them evenly along
+/// the width of the map, or by mirroring the left half of the map onto the right half.
+pub struct ArrangeSpawnPointsWindow {
+    params: WindowParams,
+    y: Option<f32>,
+}
+
+impl ArrangeSpawnPointsWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Arrange Spawn Points".to_string()),
+            size: vec2(350.0, 200.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        ArrangeSpawnPointsWindow { params, y: None }
+    }
+}
+
+impl Window for ArrangeSpawnPointsWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("arrange_spawn_points_window");
+
+        // This defaults to the average height of the existing spawn points
+        let y = self.y.get_or_insert_with(|| {
+            let cnt = map.spawn_points.len().max(1) as f32;
+            let sum = map
+                .spawn_points
+                .iter()
+                .map(|spawn_point| spawn_point.position.y)
+                .sum::<f32>();
+
+            (sum / cnt).round()
+        });
+
+        {
+            let cnt_label = format!("Spawn points: {}", map.spawn_points.len());
+            widgets::Label::new(cnt_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        if NumberInput::new(hash!(id, "y_input"))
+            .with_size(vec2(75.0, 25.0))
+            .with_ratio(1.0)
+            .with_label("Distribute at Y")
+            .ui(ui, y)
+        {
+            *y = (*y * 100.0).round() / 100.0;
+        }
+
+        None
+    }
+
+    fn get_buttons(&self, map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut distribute_action = None;
+        let mut mirror_action = None;
+
+        if !map.spawn_points.is_empty() {
+            if let Some(y) = self.y {
+                let action = EditorAction::DistributeSpawnPoints { y };
+                distribute_action = Some(self.get_close_action().then(action));
+            }
+
+            let action = EditorAction::MirrorSpawnPoints;
+            mirror_action = Some(self.get_close_action().then(action));
+        }
+
+        res.push(ButtonParams {
+            label: "Distribute",
+            action: distribute_action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Mirror",
+            action: mirror_action,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
+
+impl Default for ArrangeSpawnPointsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,8 @@
 use ff_core::prelude::*;
 
-use ff_core::gui::{get_gui_theme, theme::LIST_BOX_ENTRY_HEIGHT, ELEMENT_MARGIN};
+use ff_core::gui::{
+    get_gui_theme, number_input, theme::LIST_BOX_ENTRY_HEIGHT, ColorInput, ELEMENT_MARGIN,
+};
 use ff_core::map::{Map, MapBackgroundLayer};
 
 use ff_core::macroquad::hash;
@@ -22,6 +24,7 @@ impl BackgroundPropertiesWindow {
         let params = WindowParams {
             title: Some("Background Properties".to_string()),
             size: vec2(360.0, 500.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -54,6 +57,7 @@ impl Window for BackgroundPropertiesWindow {
         res.push(ButtonParams {
             label: "Save",
             action: Some(action),
+            is_default: true,
             ..Default::default()
         });
 
@@ -78,46 +82,7 @@ impl Window for BackgroundPropertiesWindow {
         widgets::Group::new(hash!(id, "color_group"), vec2(size.x * 0.4, size.y * 0.5))
             .position(vec2(0.0, 0.0))
             .ui(ui, |ui| {
-                let mut r_str = format!("{:.1}", self.color.red);
-                let mut g_str = format!("{:.1}", self.color.green);
-                let mut b_str = format!("{:.1}", self.color.blue);
-                let mut a_str = format!("{:.1}", self.color.alpha);
-
-                widgets::InputText::new(hash!(id, "color_r_input"))
-                    .ratio(1.0)
-                    .label("r")
-                    .ui(ui, &mut r_str);
-
-                widgets::InputText::new(hash!(id, "color_g_input"))
-                    .ratio(1.0)
-                    .label("g")
-                    .ui(ui, &mut g_str);
-
-                widgets::InputText::new(hash!(id, "color_b_input"))
-                    .ratio(1.0)
-                    .label("b")
-                    .ui(ui, &mut b_str);
-
-                widgets::InputText::new(hash!(id, "color_a_input"))
-                    .ratio(1.0)
-                    .label("a")
-                    .ui(ui, &mut a_str);
-
-                if let Ok(r) = r_str.parse::<f32>() {
-                    self.color.red = r;
-                }
-
-                if let Ok(g) = g_str.parse::<f32>() {
-                    self.color.green = g;
-                }
-
-                if let Ok(b) = b_str.parse::<f32>() {
-                    self.color.blue = b;
-                }
-
-                if let Ok(a) = a_str.parse::<f32>() {
-                    self.color.alpha = a;
-                }
+                ColorInput::new(hash!(id, "color_input")).ui(ui, &mut self.color);
             });
 
         let layer_list_size = vec2((size.x * 0.6) - ELEMENT_MARGIN, size.y * 0.5);
@@ -207,16 +172,12 @@ impl Window for BackgroundPropertiesWindow {
 
             self.layer_texture_id = texture_ids.get(texture_index).map(|str| str.to_string());
 
-            let mut depth_str = format!("{:.1}", self.layer_depth);
-
-            widgets::InputText::new(hash!(id, "layer_depth_input"))
-                .ratio(0.4)
-                .label("Depth")
-                .ui(ui, &mut depth_str);
-
-            if let Ok(depth) = depth_str.parse::<f32>() {
-                self.layer_depth = depth;
-            }
+            number_input(
+                ui,
+                hash!(id, "layer_depth_input"),
+                "Depth",
+                &mut self.layer_depth,
+            );
 
             ui.same_line(0.0);
 
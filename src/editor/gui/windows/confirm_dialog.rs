@@ -46,6 +46,7 @@ impl Window for ConfirmDialog {
         res.push(ButtonParams {
             label: Self::CONFIRM_LABEL,
             action: Some(action),
+            is_default: true,
             ..Default::default()
         });
 
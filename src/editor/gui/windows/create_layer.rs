@@ -20,6 +20,7 @@ impl CreateLayerWindow {
         let params = WindowParams {
             title: Some("Create Layer".to_string()),
             size: vec2(275.0, 275.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -93,6 +94,7 @@ impl Window for CreateLayerWindow {
         res.push(ButtonParams {
             label: "Create",
             action,
+            is_default: true,
             ..Default::default()
         });
 
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use ff_core::gui::NumberInput;
 use ff_core::macroquad::hash;
 use ff_core::macroquad::ui::{widgets, Ui};
 use ff_core::prelude::*;
@@ -22,6 +23,7 @@ impl CreateMapWindow {
         let params = WindowParams {
             title: Some("Create Map".to_string()),
             size: vec2(350.0, 425.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -90,51 +92,35 @@ impl Window for CreateMapWindow {
         ui.separator();
 
         {
-            let mut grid_width = self.grid_size.x.to_string();
-            let mut grid_height = self.grid_size.y.to_string();
-
-            let mut tile_width = self.tile_size.x.to_string();
-            let mut tile_height = self.tile_size.y.to_string();
-
             let size = vec2(75.0, 25.0);
 
-            widgets::InputText::new(hash!(id, "tile_width_input"))
-                .size(size)
-                .ratio(1.0)
-                .label("x")
-                .ui(ui, &mut tile_width);
+            NumberInput::new(hash!(id, "tile_width_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("x")
+                .ui(ui, &mut self.tile_size.x);
 
             ui.same_line(size.x + 25.0);
 
-            widgets::InputText::new(hash!(id, "tile_height_input"))
-                .size(size)
-                .ratio(1.0)
-                .label("Tile size")
-                .ui(ui, &mut tile_height);
+            NumberInput::new(hash!(id, "tile_height_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("Tile size")
+                .ui(ui, &mut self.tile_size.y);
 
-            widgets::InputText::new(hash!(id, "grid_width_input"))
-                .size(size)
-                .ratio(1.0)
-                .label("x")
-                .ui(ui, &mut grid_width);
+            NumberInput::new(hash!(id, "grid_width_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("x")
+                .ui(ui, &mut self.grid_size.x);
 
             ui.same_line(size.x + 25.0);
 
-            widgets::InputText::new(hash!(id, "grid_height_input"))
-                .size(size)
-                .ratio(1.0)
-                .label("Grid size")
-                .ui(ui, &mut grid_height);
-
-            self.grid_size = uvec2(
-                grid_width.parse::<u32>().unwrap(),
-                grid_height.parse::<u32>().unwrap(),
-            );
-
-            self.tile_size = vec2(
-                tile_width.parse::<f32>().unwrap(),
-                tile_height.parse::<f32>().unwrap(),
-            );
+            NumberInput::new(hash!(id, "grid_height_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("Grid size")
+                .ui(ui, &mut self.grid_size.y);
         }
 
         ui.separator();
@@ -166,6 +152,7 @@ impl Window for CreateMapWindow {
         res.push(ButtonParams {
             label: "Create",
             action,
+            is_default: true,
             ..Default::default()
         });
 
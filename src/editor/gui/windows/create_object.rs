@@ -1,14 +1,13 @@
 use ff_core::prelude::*;
 
 use ff_core::gui::combobox::ComboBoxVec;
-use ff_core::gui::{ComboBoxBuilder, ComboBoxValue};
+use ff_core::gui::{ComboBoxBuilder, ComboBoxValue, NumberInput};
 
 use ff_core::macroquad::hash;
-use ff_core::macroquad::ui::{widgets, Ui};
-use ff_core::map::{iter_decoration, Map, MapObjectKind};
+use ff_core::macroquad::ui::Ui;
+use ff_core::map::{Map, MapObjectKind};
 
-use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
-use crate::items::iter_items;
+use super::{get_object_ids, ButtonParams, EditorAction, EditorContext, Window, WindowParams};
 
 pub struct CreateObjectWindow {
     params: WindowParams,
@@ -23,6 +22,7 @@ impl CreateObjectWindow {
         let params = WindowParams {
             title: Some("Create Object".to_string()),
             size: vec2(300.0, 300.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -55,6 +55,7 @@ impl Window for CreateObjectWindow {
             res.push(ButtonParams {
                 label: "Create",
                 action: Some(action),
+                is_default: true,
                 ..Default::default()
             });
         }
@@ -80,12 +81,13 @@ impl Window for CreateObjectWindow {
         {
             let size = vec2(72.0, 28.0);
 
-            let mut x_str = format!("{:.1}", self.position.x);
-            let mut y_str = format!("{:.1}", self.position.y);
+            let x_input = NumberInput::new(hash!(id, "position_x_input"))
+                .with_size(size)
+                .with_ratio(1.0);
 
-            widgets::InputText::new(hash!(id, "position_x_input"))
-                .size(size)
-                .ui(ui, &mut x_str);
+            if x_input.ui(ui, &mut self.position.x) {
+                self.position.x = (self.position.x * 100.0).round() / 100.0;
+            }
 
             ui.same_line(0.0);
 
@@ -93,28 +95,18 @@ impl Window for CreateObjectWindow {
 
             ui.same_line(0.0);
 
-            widgets::InputText::new(hash!(id, "position_y_input"))
-                .size(size)
-                .ui(ui, &mut y_str);
+            let y_input = NumberInput::new(hash!(id, "position_y_input"))
+                .with_size(size)
+                .with_ratio(1.0);
+
+            if y_input.ui(ui, &mut self.position.y) {
+                self.position.y = (self.position.y * 100.0).round() / 100.0;
+            }
 
             ui.separator();
             ui.separator();
             ui.separator();
             ui.separator();
-
-            let x = if let Ok(x) = x_str.parse::<f32>() {
-                (x * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
-
-            let y = if let Ok(y) = y_str.parse::<f32>() {
-                (y * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
-
-            self.position = vec2(x, y);
         }
 
         ComboBoxBuilder::new(hash!(id, "type_input"))
@@ -122,13 +114,7 @@ impl Window for CreateObjectWindow {
             .with_label("Type")
             .build(ui, &mut self.kind);
 
-        let item_ids = match self.kind {
-            MapObjectKind::Item => iter_items().map(|(k, _)| k.as_str()).collect::<Vec<&str>>(),
-            MapObjectKind::Environment => vec!["sproinger"],
-            MapObjectKind::Decoration => iter_decoration()
-                .map(|(k, _)| k.as_str())
-                .collect::<Vec<&str>>(),
-        };
+        let item_ids = get_object_ids(self.kind);
 
         let mut item_id_value = if let Some(current_id) = &self.id {
             let index = item_ids
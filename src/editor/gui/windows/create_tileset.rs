@@ -18,6 +18,7 @@ impl CreateTilesetWindow {
         let params = WindowParams {
             title: Some("Create Tileset".to_string()),
             size: vec2(320.0, 250.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -56,6 +57,7 @@ impl Window for CreateTilesetWindow {
         res.push(ButtonParams {
             label: "Create",
             action,
+            is_default: true,
             ..Default::default()
         });
 
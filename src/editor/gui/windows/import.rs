@@ -9,7 +9,7 @@ use ff_core::macroquad::ui::{widgets, Ui};
 
 use ff_core::map::{get_map, Map, MapBackgroundLayer, MapTileset};
 
-use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use super::{draw_map_preview, ButtonParams, EditorAction, EditorContext, Window, WindowParams};
 
 pub struct ImportWindow {
     params: WindowParams,
@@ -23,10 +23,13 @@ pub struct ImportWindow {
 }
 
 impl ImportWindow {
+    const PREVIEW_MAX_HEIGHT: f32 = 120.0;
+    const BACKGROUND_LIST_HEIGHT: f32 = 80.0;
+
     pub fn new(map_index: usize) -> Self {
         let params = WindowParams {
             title: Some("Import".to_string()),
-            size: vec2(350.0, 350.0),
+            size: vec2(350.0, 550.0),
             ..Default::default()
         };
 
@@ -57,8 +60,9 @@ impl Window for ImportWindow {
     ) -> Option<EditorAction> {
         let id = hash!("import_window");
 
+        let map_resource = get_map(self.map_index);
+
         if !self.is_loaded {
-            let map_resource = get_map(self.map_index);
             self.tilesets = map_resource.map.tilesets.values().cloned().collect();
 
             self.background_color = Some(map_resource.map.background_color);
@@ -67,8 +71,28 @@ impl Window for ImportWindow {
             self.is_loaded = true;
         }
 
-        widgets::Group::new(hash!(id, "list_box"), vec2(size.x, size.y * 0.8))
-            .position(vec2(0.0, 0.0))
+        let mut position = Vec2::ZERO;
+
+        ui.label(position, &map_resource.meta.name);
+        position.y += LIST_BOX_ENTRY_HEIGHT;
+
+        {
+            let max_size = vec2(size.x, Self::PREVIEW_MAX_HEIGHT);
+            let preview_size = draw_map_preview(ui, map_resource, position, max_size);
+            position.y += preview_size.y + ELEMENT_MARGIN;
+        }
+
+        ui.label(position, "Tilesets");
+        position.y += LIST_BOX_ENTRY_HEIGHT;
+
+        // This is the height of the background layer label, list and checkbox, below the tilesets
+        let background_area_height =
+            (LIST_BOX_ENTRY_HEIGHT * 2.0) + Self::BACKGROUND_LIST_HEIGHT + (ELEMENT_MARGIN * 2.0);
+
+        let list_size = vec2(size.x, size.y - position.y - background_area_height);
+
+        widgets::Group::new(hash!(id, "list_box"), list_size)
+            .position(position)
             .ui(ui, |ui| {
                 {
                     let gui_theme = get_gui_theme();
@@ -109,9 +133,38 @@ impl Window for ImportWindow {
                 ui.pop_skin();
             });
 
-        {
-            let position = vec2(0.0, (size.y * 0.8) + ELEMENT_MARGIN);
+        position.y += list_size.y + ELEMENT_MARGIN;
+
+        ui.label(position, "Background Layers");
+        position.y += LIST_BOX_ENTRY_HEIGHT;
 
+        let background_list_size = vec2(size.x, Self::BACKGROUND_LIST_HEIGHT);
+
+        widgets::Group::new(hash!(id, "background_list_box"), background_list_size)
+            .position(position)
+            .ui(ui, |ui| {
+                {
+                    let gui_theme = get_gui_theme();
+                    ui.push_skin(&gui_theme.list_box_no_bg);
+                }
+
+                if self.background_layers.is_empty() {
+                    ui.label(Vec2::ZERO, "None");
+                }
+
+                for (i, layer) in self.background_layers.iter().enumerate() {
+                    let entry_position = vec2(0.0, i as f32 * LIST_BOX_ENTRY_HEIGHT);
+                    let label = format!("{} (depth: {})", layer.texture_id, layer.depth);
+
+                    ui.label(entry_position, &label);
+                }
+
+                ui.pop_skin();
+            });
+
+        position.y += background_list_size.y + ELEMENT_MARGIN;
+
+        {
             let checkbox = Checkbox::new(
                 hash!(id, "background_checkbox"),
                 position,
@@ -159,6 +212,7 @@ impl Window for ImportWindow {
         res.push(ButtonParams {
             label: "Import",
             action: Some(batch),
+            is_default: true,
             ..Default::default()
         });
 
@@ -0,0 +1,138 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::{get_gui_theme, theme::LIST_BOX_ENTRY_HEIGHT, ELEMENT_MARGIN};
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::input::editor_keybinds;
+
+pub struct KeybindsWindow {
+    params: WindowParams,
+    // This holds the name of the action that is being rebound, while waiting for a key press
+    rebinding: Option<&'static str>,
+}
+
+impl KeybindsWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Keybinds".to_string()),
+            size: vec2(400.0, 450.0),
+            // This suppresses the editor bindings without ctrl, while waiting for a key press
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        KeybindsWindow {
+            params,
+            rebinding: None,
+        }
+    }
+}
+
+impl Window for KeybindsWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("keybinds_window");
+
+        let mut res = None;
+
+        if let Some(name) = self.rebinding {
+            if let Some(key) = get_last_key_pressed().filter(|key| !key.is_modifier()) {
+                res = Some(EditorAction::SetKeybind {
+                    name: name.to_string(),
+                    keybind: Keybind::with_held_modifiers(key),
+                });
+
+                self.rebinding = None;
+            }
+        }
+
+        {
+            let gui_theme = get_gui_theme();
+            ui.push_skin(&gui_theme.list_box_no_bg);
+        }
+
+        let size = vec2(size.x, size.y - ELEMENT_MARGIN);
+        widgets::Group::new(hash!(id, "list_box"), size)
+            .position(Vec2::ZERO)
+            .ui(ui, |ui| {
+                let entry_size = vec2(size.x, LIST_BOX_ENTRY_HEIGHT);
+
+                for (i, (name, keybind)) in editor_keybinds().iter().into_iter().enumerate() {
+                    let is_selected = self.rebinding == Some(name);
+
+                    if is_selected {
+                        let gui_theme = get_gui_theme();
+                        ui.push_skin(&gui_theme.list_box_selected);
+                    }
+
+                    let entry_position = vec2(0.0, i as f32 * entry_size.y);
+
+                    let entry_btn = widgets::Button::new("")
+                        .size(entry_size)
+                        .position(entry_position);
+
+                    // Clicking the entry that is being rebound cancels the rebinding
+                    if entry_btn.ui(ui) {
+                        self.rebinding = if is_selected { None } else { Some(name) };
+                    }
+
+                    let keybind_label = if is_selected {
+                        "Press a key...".to_string()
+                    } else {
+                        keybind.to_string()
+                    };
+
+                    ui.label(entry_position, name);
+
+                    let label_size = ui.calc_size(&keybind_label);
+                    let label_position = vec2(
+                        entry_size.x - label_size.x - ELEMENT_MARGIN,
+                        entry_position.y,
+                    );
+
+                    ui.label(label_position, &keybind_label);
+
+                    if is_selected {
+                        ui.pop_skin();
+                    }
+                }
+            });
+
+        ui.pop_skin();
+
+        res
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        vec![
+            ButtonParams {
+                label: "Reset",
+                action: Some(EditorAction::ResetKeybinds),
+                ..Default::default()
+            },
+            ButtonParams {
+                label: "Close",
+                action: Some(self.get_close_action()),
+                ..Default::default()
+            },
+        ]
+    }
+}
+
+impl Default for KeybindsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
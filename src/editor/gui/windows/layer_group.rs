@@ -0,0 +1,123 @@
+use ff_core::prelude::*;
+
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+pub struct LayerGroupWindow {
+    params: WindowParams,
+    layer_id: String,
+    group: Option<String>,
+}
+
+impl LayerGroupWindow {
+    pub fn new(layer_id: String) -> Self {
+        let params = WindowParams {
+            title: Some("Layer Group".to_string()),
+            size: vec2(350.0, 200.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        LayerGroupWindow {
+            params,
+            layer_id,
+            group: None,
+        }
+    }
+
+    // An empty group name removes the layer from its group
+    fn get_group(&self) -> Option<String> {
+        self.group
+            .as_ref()
+            .map(|group| group.trim().to_string())
+            .filter(|group| !group.is_empty())
+    }
+}
+
+impl Window for LayerGroupWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("layer_group_window");
+
+        let layer = match map.layers.get(&self.layer_id) {
+            Some(layer) => layer,
+            None => return Some(self.get_close_action()),
+        };
+
+        let group = self
+            .group
+            .get_or_insert_with(|| layer.group.clone().unwrap_or_default());
+
+        {
+            let layer_label = format!("Layer: {}", self.layer_id);
+            widgets::Label::new(layer_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        widgets::InputText::new(hash!(id, "group_input"))
+            .size(vec2(173.0, 25.0))
+            .ratio(1.0)
+            .label("Group")
+            .ui(ui, group);
+
+        let mut groups = map
+            .layers
+            .values()
+            .filter_map(|layer| layer.group.as_deref())
+            .collect::<Vec<_>>();
+
+        groups.sort_unstable();
+        groups.dedup();
+
+        if !groups.is_empty() {
+            ui.separator();
+
+            let groups_label = format!("Existing groups: {}", groups.join(", "));
+            widgets::Label::new(groups_label.as_str()).ui(ui);
+        }
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if self.group.is_some() {
+            let group_action = EditorAction::SetLayerGroup {
+                id: self.layer_id.clone(),
+                group: self.get_group(),
+            };
+
+            action = Some(self.get_close_action().then(group_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
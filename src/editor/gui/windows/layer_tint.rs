@@ -0,0 +1,109 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::ColorInput;
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::{Map, MapLayer};
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+/// This sets the tint of a layer, which is multiplied with the color of all its tiles
+pub struct LayerTintWindow {
+    params: WindowParams,
+    layer_id: String,
+    tint: Option<Color>,
+}
+
+impl LayerTintWindow {
+    pub fn new(layer_id: String) -> Self {
+        let params = WindowParams {
+            title: Some("Layer Tint".to_string()),
+            size: vec2(300.0, 300.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        LayerTintWindow {
+            params,
+            layer_id,
+            tint: None,
+        }
+    }
+}
+
+impl Window for LayerTintWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("layer_tint_window");
+
+        let layer = match map.layers.get(&self.layer_id) {
+            Some(layer) => layer,
+            None => return Some(self.get_close_action()),
+        };
+
+        let tint = self.tint.get_or_insert(layer.tint);
+
+        {
+            let layer_label = format!("Layer: {}", self.layer_id);
+            widgets::Label::new(layer_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        ColorInput::new(hash!(id, "tint_input"))
+            .with_component_size(vec2(75.0, 25.0))
+            .with_hex_size(vec2(173.0, 25.0))
+            .ui(ui, tint);
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if let Some(tint) = self.tint {
+            let tint_action = EditorAction::SetLayerTint {
+                id: self.layer_id.clone(),
+                tint,
+            };
+
+            action = Some(self.get_close_action().then(tint_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        let reset_action = EditorAction::SetLayerTint {
+            id: self.layer_id.clone(),
+            tint: MapLayer::default_tint(),
+        };
+
+        res.push(ButtonParams {
+            label: "Reset",
+            action: Some(self.get_close_action().then(reset_action)),
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
@@ -1,12 +1,17 @@
 use ff_core::prelude::*;
-use std::ops::Deref;
 
 use ff_core::gui::{get_gui_theme, theme::LIST_BOX_ENTRY_HEIGHT, ELEMENT_MARGIN};
 use ff_core::macroquad::hash;
 use ff_core::macroquad::ui::{widgets, Ui};
-use ff_core::map::{get_map, iter_maps, Map};
+use ff_core::map::{get_map, iter_maps, Map, MapRequirements};
 
-use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use super::{draw_map_preview, ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+const USER_MAPS_HEADER: &str = "User Maps";
+const BUILTIN_MAPS_HEADER: &str = "Built-in Maps";
+
+// This is appended to the entries of maps that were imported from Tiled
+const TILED_MAP_BADGE: &str = " [Tiled]";
 
 pub struct LoadMapWindow {
     params: WindowParams,
@@ -17,7 +22,7 @@ impl LoadMapWindow {
     pub fn new() -> Self {
         let params = WindowParams {
             title: Some("Open Map".to_string()),
-            size: vec2(350.0, 350.0),
+            size: vec2(400.0, 350.0),
             ..Default::default()
         };
 
@@ -66,25 +71,18 @@ impl Window for LoadMapWindow {
             {
                 let map_resource = get_map(index);
 
-                let preview_size = map_resource.preview.size();
-
-                let mut width = size.x;
-                let mut height = (width / preview_size.width) * preview_size.height;
-
-                let max_height = size.y - LIST_BOX_ENTRY_HEIGHT - (ELEMENT_MARGIN * 2.0);
-
-                if height > max_height {
-                    let preview_size = map_resource.preview.size();
-                    height = max_height;
-                    width = (height / preview_size.height) * preview_size.width;
-                }
+                let position = vec2(0.0, btn_size.y + ELEMENT_MARGIN);
+                // This leaves room for the requirements label, below the preview
+                let max_size = vec2(
+                    size.x,
+                    size.y - (LIST_BOX_ENTRY_HEIGHT * 2.0) - (ELEMENT_MARGIN * 3.0),
+                );
 
-                let preview_position = vec2((size.x - width) / 2.0, btn_size.y + ELEMENT_MARGIN);
+                let preview_size = draw_map_preview(ui, map_resource, position, max_size);
 
-                widgets::Texture::new(map_resource.preview.deref().into())
-                    .size(width, height)
-                    .position(preview_position)
-                    .ui(ui);
+                let position = vec2(0.0, position.y + preview_size.y + ELEMENT_MARGIN);
+                let requirements_label = get_requirements_label(&map_resource.meta.requirements);
+                ui.label(position, &requirements_label);
             }
         } else {
             let size = vec2(size.x, size.y - ELEMENT_MARGIN);
@@ -93,31 +91,53 @@ impl Window for LoadMapWindow {
                 .ui(ui, |ui| {
                     let entry_size = vec2(size.x, LIST_BOX_ENTRY_HEIGHT);
 
-                    for (i, map_resource) in iter_maps().enumerate() {
-                        let mut is_selected = false;
-                        if let Some(index) = self.index {
-                            is_selected = index == i;
-                        }
+                    // User maps are listed first, in their own section, so that they are not
+                    // confused with the built-in maps, which can not be overwritten
+                    let (user_maps, builtin_maps): (Vec<_>, Vec<_>) = iter_maps()
+                        .enumerate()
+                        .partition(|(_, map_resource)| map_resource.meta.is_user_map);
+
+                    let mut entry_position = Vec2::ZERO;
 
-                        if is_selected {
-                            let gui_theme = get_gui_theme();
-                            ui.push_skin(&gui_theme.list_box_selected);
+                    for (header, maps) in [
+                        (USER_MAPS_HEADER, user_maps),
+                        (BUILTIN_MAPS_HEADER, builtin_maps),
+                    ] {
+                        if maps.is_empty() {
+                            continue;
                         }
 
-                        let entry_position = vec2(0.0, i as f32 * entry_size.y);
+                        ui.label(entry_position, header);
+                        entry_position.y += entry_size.y;
 
-                        let entry_btn = widgets::Button::new("")
-                            .size(entry_size)
-                            .position(entry_position);
+                        for (i, map_resource) in maps {
+                            let is_selected = self.index == Some(i);
 
-                        if entry_btn.ui(ui) {
-                            self.index = Some(i);
-                        }
+                            if is_selected {
+                                let gui_theme = get_gui_theme();
+                                ui.push_skin(&gui_theme.list_box_selected);
+                            }
+
+                            let entry_btn = widgets::Button::new("")
+                                .size(entry_size)
+                                .position(entry_position);
+
+                            if entry_btn.ui(ui) {
+                                self.index = Some(i);
+                            }
 
-                        ui.label(entry_position, &map_resource.meta.path);
+                            let mut label = format!("  {}", map_resource.meta.path);
+                            if map_resource.meta.is_tiled_map {
+                                label.push_str(TILED_MAP_BADGE);
+                            }
 
-                        if is_selected {
-                            ui.pop_skin();
+                            ui.label(entry_position, &label);
+
+                            if is_selected {
+                                ui.pop_skin();
+                            }
+
+                            entry_position.y += entry_size.y;
                         }
                     }
                 });
@@ -133,8 +153,16 @@ impl Window for LoadMapWindow {
 
         let mut open_action = None;
         let mut import_action = None;
+        let mut delete_action = None;
 
         if let Some(index) = self.index {
+            if get_map(index).meta.is_user_map {
+                let delete_batch = self
+                    .get_close_action()
+                    .then(EditorAction::OpenDeleteMapDialog(index));
+                delete_action = Some(delete_batch);
+            }
+
             let open_batch = self.get_close_action().then(EditorAction::OpenMap(index));
             open_action = Some(open_batch);
 
@@ -147,6 +175,7 @@ impl Window for LoadMapWindow {
         res.push(ButtonParams {
             label: "Open",
             action: open_action,
+            is_default: true,
             ..Default::default()
         });
 
@@ -156,6 +185,12 @@ impl Window for LoadMapWindow {
             ..Default::default()
         });
 
+        res.push(ButtonParams {
+            label: "Delete",
+            action: delete_action,
+            ..Default::default()
+        });
+
         res.push(ButtonParams {
             label: "Cancel",
             action: Some(self.get_close_action()),
@@ -166,6 +201,25 @@ impl Window for LoadMapWindow {
     }
 }
 
+// This describes the game modes and player counts that a map is made for, on a single line
+fn get_requirements_label(requirements: &MapRequirements) -> String {
+    let game_modes = if requirements.game_modes.is_empty() {
+        "any mode".to_string()
+    } else {
+        requirements.game_modes.join(", ")
+    };
+
+    let players = match (requirements.min_players, requirements.max_players) {
+        (None, None) => "any number of players".to_string(),
+        (Some(min), None) => format!("{}+ players", min),
+        (None, Some(max)) => format!("up to {} players", max),
+        (Some(min), Some(max)) if min == max => format!("{} players", min),
+        (Some(min), Some(max)) => format!("{}-{} players", min, max),
+    };
+
+    format!("{}, {}", game_modes, players)
+}
+
 impl Default for LoadMapWindow {
     fn default() -> Self {
         Self::new()
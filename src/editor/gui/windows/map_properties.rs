@@ -0,0 +1,283 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::{ColorInput, NumberInput};
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::{Map, MapRequirements};
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+const NO_MUSIC_LABEL: &str = "(default)";
+
+pub struct MapPropertiesWindow {
+    params: WindowParams,
+    world_offset: Option<Vec2>,
+    ambient_color: Option<Color>,
+    music_id: Option<Option<String>>,
+    // The game modes are edited as a comma separated list
+    game_modes: Option<String>,
+    // A player count of zero means that there is no limit
+    min_players: Option<f32>,
+    max_players: Option<f32>,
+}
+
+impl MapPropertiesWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Map Properties".to_string()),
+            size: vec2(300.0, 520.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        MapPropertiesWindow {
+            params,
+            world_offset: None,
+            ambient_color: None,
+            music_id: None,
+            game_modes: None,
+            min_players: None,
+            max_players: None,
+        }
+    }
+
+    fn get_requirements(&self, current: &MapRequirements) -> MapRequirements {
+        let game_modes = match &self.game_modes {
+            Some(game_modes) => game_modes
+                .split(',')
+                .map(|mode| mode.trim().to_string())
+                .filter(|mode| !mode.is_empty())
+                .collect(),
+            None => current.game_modes.clone(),
+        };
+
+        let get_player_cnt = |value: Option<f32>, current: Option<u32>| match value {
+            Some(value) if value >= 1.0 => Some(value.round() as u32),
+            Some(_) => None,
+            None => current,
+        };
+
+        let mut min_players = get_player_cnt(self.min_players, current.min_players);
+        let mut max_players = get_player_cnt(self.max_players, current.max_players);
+
+        if let (Some(min), Some(max)) = (min_players, max_players) {
+            if min > max {
+                std::mem::swap(&mut min_players, &mut max_players);
+            }
+        }
+
+        MapRequirements {
+            game_modes,
+            min_players,
+            max_players,
+        }
+    }
+}
+
+impl Window for MapPropertiesWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("map_properties_window");
+
+        {
+            let size_label = format!(
+                "Grid size: {}x{}",
+                map.grid_size.width, map.grid_size.height
+            );
+            widgets::Label::new(size_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        let world_offset = self.world_offset.get_or_insert(map.world_offset);
+
+        {
+            let size = vec2(75.0, 25.0);
+
+            if NumberInput::new(hash!(id, "world_offset_x_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("x")
+                .ui(ui, &mut world_offset.x)
+            {
+                world_offset.x = (world_offset.x * 100.0).round() / 100.0;
+            }
+
+            ui.same_line(size.x + 25.0);
+
+            if NumberInput::new(hash!(id, "world_offset_y_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("World Offset")
+                .ui(ui, &mut world_offset.y)
+            {
+                world_offset.y = (world_offset.y * 100.0).round() / 100.0;
+            }
+        }
+
+        ui.separator();
+
+        let ambient_color = self.ambient_color.get_or_insert(map.ambient_color);
+
+        widgets::Label::new("Ambient Color").ui(ui);
+
+        ColorInput::new(hash!(id, "ambient_color_input"))
+            .with_component_size(vec2(75.0, 25.0))
+            .with_hex_size(vec2(173.0, 25.0))
+            .ui(ui, ambient_color);
+
+        ui.separator();
+
+        let music_id = self.music_id.get_or_insert_with(|| map.music_id.clone());
+
+        // The first entry is used to play the default music
+        let mut music_ids = vec![NO_MUSIC_LABEL.to_string()];
+        music_ids.extend(iter_sound_ids_of_kind(AudioKind::Music));
+
+        let mut music_index = music_id
+            .as_ref()
+            .and_then(|music_id| music_ids.iter().position(|id| id == music_id))
+            .unwrap_or(0);
+
+        let music_labels = music_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>();
+
+        let old_music_index = music_index;
+
+        widgets::ComboBox::new(hash!(id, "music_input"), &music_labels)
+            .ratio(0.8)
+            .label("Music")
+            .ui(ui, &mut music_index);
+
+        // Music that is not loaded is kept, unless another entry is explicitly selected
+        if music_index != old_music_index {
+            *music_id = if music_index > 0 {
+                music_ids.get(music_index).cloned()
+            } else {
+                None
+            };
+        }
+
+        ui.separator();
+
+        let requirements = &ctx.map_requirements;
+
+        let game_modes = self
+            .game_modes
+            .get_or_insert_with(|| requirements.game_modes.join(", "));
+
+        widgets::InputText::new(hash!(id, "game_modes_input"))
+            .size(vec2(173.0, 25.0))
+            .ratio(1.0)
+            .label("Game Modes")
+            .ui(ui, game_modes);
+
+        {
+            let size = vec2(75.0, 25.0);
+
+            let min_players = self
+                .min_players
+                .get_or_insert(requirements.min_players.unwrap_or(0) as f32);
+
+            if NumberInput::new(hash!(id, "min_players_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("min")
+                .ui(ui, min_players)
+            {
+                *min_players = min_players.max(0.0).round();
+            }
+
+            ui.same_line(size.x + 25.0);
+
+            let max_players = self
+                .max_players
+                .get_or_insert(requirements.max_players.unwrap_or(0) as f32);
+
+            if NumberInput::new(hash!(id, "max_players_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("Players")
+                .ui(ui, max_players)
+            {
+                *max_players = max_players.max(0.0).round();
+            }
+        }
+
+        widgets::Label::new("Leave empty, or at 0, for no restriction").ui(ui);
+
+        None
+    }
+
+    fn get_buttons(&self, map: &Map, ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut actions = Vec::new();
+
+        if let Some(world_offset) = self.world_offset {
+            if world_offset != map.world_offset {
+                actions.push(EditorAction::SetWorldOffset(world_offset));
+            }
+        }
+
+        if let Some(ambient_color) = self.ambient_color {
+            if ambient_color != map.ambient_color {
+                actions.push(EditorAction::SetAmbientColor(ambient_color));
+            }
+        }
+
+        if let Some(music_id) = &self.music_id {
+            if *music_id != map.music_id {
+                actions.push(EditorAction::SetMapMusic(music_id.clone()));
+            }
+        }
+
+        let requirements = self.get_requirements(&ctx.map_requirements);
+        if requirements != ctx.map_requirements {
+            actions.push(EditorAction::SetMapRequirements(requirements));
+        }
+
+        let mut action = None;
+        if !actions.is_empty() {
+            actions.insert(0, self.get_close_action());
+            action = Some(EditorAction::batch(&actions));
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        let reset_action = EditorAction::SetAmbientColor(Map::default_ambient_color());
+
+        res.push(ButtonParams {
+            label: "Reset Ambient",
+            action: Some(self.get_close_action().then(reset_action)),
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
+
+impl Default for MapPropertiesWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
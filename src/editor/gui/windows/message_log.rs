@@ -0,0 +1,100 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::{get_gui_theme, theme::LIST_BOX_ENTRY_HEIGHT, ELEMENT_MARGIN};
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use crate::editor::message_log::MessageLog;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams, WindowPosition};
+
+pub struct MessageLogWindow {
+    params: WindowParams,
+}
+
+impl MessageLogWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Message Log".to_string()),
+            size: vec2(450.0, 300.0),
+            position: WindowPosition::Absolute(vec2(16.0, 64.0)),
+            ..Default::default()
+        };
+
+        MessageLogWindow { params }
+    }
+}
+
+impl Window for MessageLogWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("message_log_window");
+        let message_log = storage::get::<MessageLog>();
+
+        {
+            let gui_theme = get_gui_theme();
+            ui.push_skin(&gui_theme.list_box_no_bg);
+        }
+
+        let size = vec2(size.x, size.y - ELEMENT_MARGIN);
+        widgets::Group::new(hash!(id, "list_box"), size)
+            .position(Vec2::ZERO)
+            .ui(ui, |ui| {
+                if message_log.is_empty() {
+                    ui.label(Vec2::ZERO, "No messages");
+                }
+
+                // Newest messages are listed first
+                for (i, entry) in message_log.entries().rev().enumerate() {
+                    let entry_position = vec2(0.0, i as f32 * LIST_BOX_ENTRY_HEIGHT);
+                    let label = format!("[{}] {}", entry.timestamp_label(), entry.message);
+
+                    ui.label(entry_position, &label);
+                }
+            });
+
+        ui.pop_skin();
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if !storage::get::<MessageLog>().is_empty() {
+            action = Some(EditorAction::ClearMessageLog);
+        }
+
+        res.push(ButtonParams {
+            label: "Clear",
+            action,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Close",
+            action: Some(self.get_close_action()),
+            is_default: true,
+            ..Default::default()
+        });
+
+        res
+    }
+}
+
+impl Default for MessageLogWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,32 +1,66 @@
 use std::any::TypeId;
+use std::ops::Deref;
 
 use ff_core::prelude::*;
 
+use crate::items::iter_items;
+
 mod confirm_dialog;
 mod create_tileset;
 
+mod arrange_spawn_points;
 mod background_properties;
 mod create_layer;
 mod create_map;
 mod create_object;
 mod import;
+mod keybinds;
+mod layer_group;
+mod layer_tint;
 mod load_map;
+mod map_properties;
+mod message_log;
 mod object_properties;
+mod prefab_list;
+mod replace_tile;
+mod resize_map;
 mod save_map;
+mod save_prefab;
+mod save_stamp;
+mod shift_layer;
+mod spawn_point_properties;
+mod stamp_palette;
+mod tile_attributes;
 mod tile_properties;
 mod tileset_properties;
 
+pub use arrange_spawn_points::ArrangeSpawnPointsWindow;
 pub use background_properties::BackgroundPropertiesWindow;
 pub use confirm_dialog::ConfirmDialog;
 pub use create_layer::CreateLayerWindow;
 pub use create_map::CreateMapWindow;
 pub use create_object::CreateObjectWindow;
 pub use create_tileset::CreateTilesetWindow;
-use ff_core::macroquad::ui::Ui;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::{iter_decorations, MapObjectKind, MapResource};
 pub use import::ImportWindow;
+pub use keybinds::KeybindsWindow;
+pub use layer_group::LayerGroupWindow;
+pub use layer_tint::LayerTintWindow;
 pub use load_map::LoadMapWindow;
+pub use map_properties::MapPropertiesWindow;
+pub use message_log::MessageLogWindow;
 pub use object_properties::ObjectPropertiesWindow;
+pub use prefab_list::PrefabListWindow;
+pub use replace_tile::ReplaceTileWindow;
+pub use resize_map::ResizeMapWindow;
 pub use save_map::SaveMapWindow;
+pub use save_prefab::SavePrefabWindow;
+pub use save_stamp::SaveStampWindow;
+pub use shift_layer::ShiftLayerWindow;
+pub use spawn_point_properties::SpawnPointPropertiesWindow;
+pub use stamp_palette::StampPaletteWindow;
+pub use tile_attributes::TileAttributesWindow;
 pub use tile_properties::TilePropertiesWindow;
 pub use tileset_properties::TilesetPropertiesWindow;
 
@@ -61,6 +95,9 @@ pub struct WindowParams {
     pub position: WindowPosition,
     pub has_buttons: bool,
     pub is_static: bool,
+    /// Set this to `true` if the window has text fields, so that editor key bindings are
+    /// suppressed while it is open
+    pub has_text_input: bool,
 }
 
 impl WindowParams {
@@ -77,6 +114,7 @@ impl Default for WindowParams {
             position: WindowPosition::Centered,
             has_buttons: true,
             is_static: false,
+            has_text_input: false,
         }
     }
 }
@@ -131,4 +169,158 @@ pub trait Window {
         let id = TypeId::of::<Self>();
         EditorAction::CloseWindow(id)
     }
+
+    // This is applied when enter is pressed while the window is the topmost window. By default,
+    // it is the action of the button that has `is_default` set, if that button is enabled.
+    fn get_confirm_action(&self, map: &Map, ctx: &EditorContext) -> Option<EditorAction>
+    where
+        Self: 'static,
+    {
+        if !self.get_params().has_buttons {
+            return None;
+        }
+
+        self.get_buttons(map, ctx)
+            .into_iter()
+            .find(|button| button.is_default)
+            .and_then(|button| button.action)
+    }
+
+    // This is applied when the `toggle_menu` key binding, escape by default, is pressed while the
+    // window is the topmost window. By default, this closes the window.
+    fn get_cancel_action(&self, _map: &Map, _ctx: &EditorContext) -> Option<EditorAction>
+    where
+        Self: 'static,
+    {
+        Some(self.get_close_action())
+    }
+}
+
+/// This draws the preview of `map_resource` at `position`, scaled to fit inside `max_size` while
+/// keeping its aspect ratio, and centered horizontally. It returns the size of the drawn preview.
+pub fn draw_map_preview(
+    ui: &mut Ui,
+    map_resource: &MapResource,
+    position: Vec2,
+    max_size: Vec2,
+) -> Vec2 {
+    let preview_size = map_resource.preview.size();
+
+    let mut width = max_size.x;
+    let mut height = (width / preview_size.width) * preview_size.height;
+
+    if height > max_size.y {
+        height = max_size.y;
+        width = (height / preview_size.height) * preview_size.width;
+    }
+
+    let preview_position = vec2(position.x + (max_size.x - width) / 2.0, position.y);
+
+    widgets::Texture::new(map_resource.preview.deref().into())
+        .size(width, height)
+        .position(preview_position)
+        .ui(ui);
+
+    vec2(width, height)
+}
+
+/// This returns the ids that can be selected as the variant of an object of `kind`.
+/// Resources are stored in hash maps, so the ids are sorted to keep the order stable.
+pub fn get_object_ids(kind: MapObjectKind) -> Vec<&'static str> {
+    let mut ids = match kind {
+        MapObjectKind::Item => iter_items().map(|(k, _)| k.as_str()).collect::<Vec<&str>>(),
+        MapObjectKind::Environment => vec!["sproinger"],
+        MapObjectKind::Decoration => iter_decorations()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<&str>>(),
+    };
+
+    ids.sort_unstable();
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // This has a default button, which is disabled until `is_ready` is set, and ignores escape
+    struct TestWindow {
+        params: WindowParams,
+        is_ready: bool,
+    }
+
+    impl Window for TestWindow {
+        fn get_params(&self) -> &WindowParams {
+            &self.params
+        }
+
+        fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+            let action = if self.is_ready {
+                Some(EditorAction::ConfirmQuit)
+            } else {
+                None
+            };
+
+            vec![ButtonParams {
+                label: "Ok",
+                action,
+                is_default: true,
+                ..Default::default()
+            }]
+        }
+
+        fn draw(
+            &mut self,
+            _ui: &mut Ui,
+            _size: Vec2,
+            _map: &Map,
+            _ctx: &EditorContext,
+        ) -> Option<EditorAction> {
+            None
+        }
+
+        fn get_cancel_action(&self, _map: &Map, _ctx: &EditorContext) -> Option<EditorAction> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_window_key_actions() {
+        let map = Map::new(vec2(16.0, 16.0), uvec2(4, 4));
+        let ctx = EditorContext::default();
+
+        let dialog: Box<dyn Window> = Box::new(ConfirmDialog::new(
+            vec2(100.0, 100.0),
+            &["Body"],
+            EditorAction::ConfirmQuit,
+        ));
+
+        let confirm_action = dialog.get_confirm_action(&map, &ctx);
+        assert!(matches!(
+            confirm_action,
+            Some(EditorAction::Batch(actions))
+                if matches!(actions.last(), Some(EditorAction::ConfirmQuit))
+        ));
+
+        let cancel_action = dialog.get_cancel_action(&map, &ctx);
+        assert!(matches!(
+            cancel_action,
+            Some(EditorAction::CloseWindow(id)) if id == TypeId::of::<ConfirmDialog>()
+        ));
+
+        let mut window = TestWindow {
+            params: WindowParams::default(),
+            is_ready: false,
+        };
+
+        assert!(window.get_confirm_action(&map, &ctx).is_none());
+        assert!(window.get_cancel_action(&map, &ctx).is_none());
+
+        window.is_ready = true;
+        assert!(window.get_confirm_action(&map, &ctx).is_some());
+
+        window.params.has_buttons = false;
+        assert!(window.get_confirm_action(&map, &ctx).is_none());
+    }
 }
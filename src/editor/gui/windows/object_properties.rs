@@ -2,29 +2,36 @@ use ff_core::prelude::*;
 
 use ff_core::gui::combobox::ComboBoxVec;
 use ff_core::macroquad::hash;
-use ff_core::macroquad::ui::{widgets, Ui};
-use ff_core::map::{iter_decoration, MapObject};
+use ff_core::macroquad::ui::Ui;
+use ff_core::map::{try_get_decoration, MapObject};
 use ff_core::{
-    gui::{ComboBoxBuilder, ComboBoxValue},
+    gui::{Checkbox, ComboBoxBuilder, ComboBoxValue, NumberInput},
     map::{Map, MapObjectKind},
 };
 
-use crate::items::iter_items;
+use crate::items::try_get_item;
 
-use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use super::{get_object_ids, ButtonParams, EditorAction, EditorContext, Window, WindowParams};
 
 pub struct ObjectPropertiesWindow {
     params: WindowParams,
     layer_id: String,
     index: usize,
     object: Option<MapObject>,
+    // If this is `true`, changing the scale along one axis will scale the other axis by the same
+    // factor, so that the aspect ratio of the decoration is kept
+    is_scale_locked: bool,
 }
 
 impl ObjectPropertiesWindow {
+    const POSITION_INPUT_WIDTH: f32 = 120.0;
+    const POSITION_INPUT_RATIO: f32 = 0.8;
+
     pub fn new(layer_id: String, index: usize) -> Self {
         let params = WindowParams {
             title: Some("Object Properties".to_string()),
-            size: vec2(300.0, 300.0),
+            size: vec2(300.0, 420.0),
+            has_text_input: true,
             ..Default::default()
         };
 
@@ -33,6 +40,7 @@ impl ObjectPropertiesWindow {
             layer_id,
             index,
             object: None,
+            is_scale_locked: true,
         }
     }
 }
@@ -48,13 +56,39 @@ impl Window for ObjectPropertiesWindow {
         let mut action = None;
 
         if let Some(object) = &self.object {
-            let batch = self.get_close_action().then(EditorAction::UpdateObject {
-                layer_id: self.layer_id.clone(),
-                index: self.index,
-                id: object.id.clone(),
-                kind: object.kind,
-                position: object.position,
-            });
+            // Collider size overrides only apply to items, so they are removed from other objects
+            let collider_size = if object.kind == MapObjectKind::Item {
+                object.collider_size
+            } else {
+                None
+            };
+
+            let scale = if object.kind == MapObjectKind::Decoration {
+                object.scale
+            } else {
+                None
+            };
+
+            let batch = EditorAction::batch(&[
+                self.get_close_action(),
+                EditorAction::UpdateObject {
+                    layer_id: self.layer_id.clone(),
+                    index: self.index,
+                    id: object.id.clone(),
+                    kind: object.kind,
+                    position: object.position,
+                },
+                EditorAction::SetObjectColliderSize {
+                    layer_id: self.layer_id.clone(),
+                    index: self.index,
+                    collider_size,
+                },
+                EditorAction::SetObjectScale {
+                    layer_id: self.layer_id.clone(),
+                    index: self.index,
+                    scale,
+                },
+            ]);
 
             action = Some(batch);
         }
@@ -62,6 +96,7 @@ impl Window for ObjectPropertiesWindow {
         res.push(ButtonParams {
             label: "Save",
             action,
+            is_default: true,
             ..Default::default()
         });
 
@@ -94,43 +129,36 @@ impl Window for ObjectPropertiesWindow {
         });
 
         {
-            let size = vec2(72.0, 28.0);
+            // The position is relative to the world offset of the map, which is what is stored in
+            // the map file, so that exact coordinates can be entered for precise placement
+            ui.label(None, "Position");
 
-            let mut x_str = format!("{:.1}", object.position.x);
-            let mut y_str = format!("{:.1}", object.position.y);
+            let size = vec2(Self::POSITION_INPUT_WIDTH, 28.0);
 
-            widgets::InputText::new(hash!(id, "position_x_input"))
-                .size(size)
-                .ui(ui, &mut x_str);
+            let x_input = NumberInput::new(hash!(id, "position_x_input"))
+                .with_label("X")
+                .with_size(size)
+                .with_ratio(Self::POSITION_INPUT_RATIO);
 
-            ui.same_line(0.0);
-
-            ui.label(None, "x");
+            if x_input.ui(ui, &mut object.position.x) {
+                object.position.x = (object.position.x * 100.0).round() / 100.0;
+            }
 
             ui.same_line(0.0);
 
-            widgets::InputText::new(hash!(id, "position_y_input"))
-                .size(size)
-                .ui(ui, &mut y_str);
+            let y_input = NumberInput::new(hash!(id, "position_y_input"))
+                .with_label("Y")
+                .with_size(size)
+                .with_ratio(Self::POSITION_INPUT_RATIO);
+
+            if y_input.ui(ui, &mut object.position.y) {
+                object.position.y = (object.position.y * 100.0).round() / 100.0;
+            }
 
             ui.separator();
             ui.separator();
             ui.separator();
             ui.separator();
-
-            let x = if let Ok(x) = x_str.parse::<f32>() {
-                (x * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
-
-            let y = if let Ok(y) = y_str.parse::<f32>() {
-                (y * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
-
-            object.position = vec2(x, y);
         }
 
         ComboBoxBuilder::new(hash!(id, "type_input"))
@@ -138,13 +166,7 @@ impl Window for ObjectPropertiesWindow {
             .with_label("Type")
             .build(ui, &mut object.kind);
 
-        let item_ids = match object.kind {
-            MapObjectKind::Item => iter_items().map(|(k, _)| k.as_str()).collect::<Vec<&str>>(),
-            MapObjectKind::Environment => vec!["sproinger"],
-            MapObjectKind::Decoration => iter_decoration()
-                .map(|(k, _)| k.as_str())
-                .collect::<Vec<&str>>(),
-        };
+        let item_ids = get_object_ids(object.kind);
 
         let mut item_id_value = {
             let index = item_ids
@@ -163,8 +185,124 @@ impl Window for ObjectPropertiesWindow {
 
         object.id = item_id_value.get_value();
 
+        if object.kind == MapObjectKind::Item {
+            ui.separator();
+
+            let mut is_overridden = object.collider_size.is_some();
+
+            Checkbox::new(
+                hash!(id, "collider_override_input"),
+                None,
+                "Override Collider",
+            )
+            .ui(ui, &mut is_overridden);
+
+            if !is_overridden {
+                object.collider_size = None;
+            } else if object.collider_size.is_none() {
+                // The override starts out as the collider size from the item metadata
+                let collider_size = try_get_item(&object.id)
+                    .map(|meta| meta.collider_size)
+                    .unwrap_or_else(|| Size::new(0.0, 0.0));
+
+                object.collider_size = Some(collider_size);
+            }
+
+            if let Some(collider_size) = &mut object.collider_size {
+                let size = vec2(Self::POSITION_INPUT_WIDTH, 28.0);
+
+                NumberInput::new(hash!(id, "collider_width_input"))
+                    .with_label("W")
+                    .with_size(size)
+                    .with_ratio(Self::POSITION_INPUT_RATIO)
+                    .ui(ui, &mut collider_size.width);
+
+                ui.same_line(0.0);
+
+                NumberInput::new(hash!(id, "collider_height_input"))
+                    .with_label("H")
+                    .with_size(size)
+                    .with_ratio(Self::POSITION_INPUT_RATIO)
+                    .ui(ui, &mut collider_size.height);
+
+                collider_size.width = collider_size.width.max(0.0);
+                collider_size.height = collider_size.height.max(0.0);
+            }
+        }
+
+        if object.kind == MapObjectKind::Decoration {
+            ui.separator();
+
+            let default_scale = try_get_decoration(&object.id)
+                .map(|meta| meta.get_scale())
+                .unwrap_or(Vec2::ONE);
+
+            let old_scale = object
+                .scale
+                .unwrap_or(default_scale)
+                .max(Vec2::splat(MIN_SCALE));
+            let mut scale = old_scale;
+
+            ui.label(None, "Scale");
+
+            let size = vec2(Self::POSITION_INPUT_WIDTH, 28.0);
+
+            NumberInput::new(hash!(id, "scale_x_input"))
+                .with_label("X")
+                .with_size(size)
+                .with_ratio(Self::POSITION_INPUT_RATIO)
+                .ui(ui, &mut scale.x);
+
+            ui.same_line(0.0);
+
+            NumberInput::new(hash!(id, "scale_y_input"))
+                .with_label("Y")
+                .with_size(size)
+                .with_ratio(Self::POSITION_INPUT_RATIO)
+                .ui(ui, &mut scale.y);
+
+            Checkbox::new(hash!(id, "scale_lock_input"), None, "Lock Aspect Ratio")
+                .ui(ui, &mut self.is_scale_locked);
+
+            scale = scale.max(Vec2::splat(MIN_SCALE));
+
+            if self.is_scale_locked {
+                scale = get_locked_scale(old_scale, scale);
+            }
+
+            // The override is removed when it matches the metadata, so that changes to the
+            // metadata still apply to the decoration
+            object.scale = if scale == default_scale {
+                None
+            } else {
+                Some(scale)
+            };
+        }
+
         self.object = Some(object);
 
         None
     }
 }
+
+// Scales are clamped to this, as a decoration with a scale of zero, or less, can not be seen or
+// selected, and the aspect ratio could not be kept when scaling it back up
+const MIN_SCALE: f32 = 0.01;
+
+// This returns `scale` with the axis that was not changed from `old_scale` scaled by the same
+// factor as the axis that was, so that the aspect ratio is kept. Both scales are clamped to
+// `MIN_SCALE`.
+fn get_locked_scale(old_scale: Vec2, scale: Vec2) -> Vec2 {
+    let old_scale = old_scale.max(Vec2::splat(MIN_SCALE));
+    let scale = scale.max(Vec2::splat(MIN_SCALE));
+
+    let res = if scale.x != old_scale.x {
+        vec2(scale.x, old_scale.y * (scale.x / old_scale.x))
+    } else if scale.y != old_scale.y {
+        vec2(old_scale.x * (scale.y / old_scale.y), scale.y)
+    } else {
+        scale
+    };
+
+    res.max(Vec2::splat(MIN_SCALE))
+}
@@ -0,0 +1,188 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::number_input;
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::{Map, MapLayerKind};
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+const ALL_LAYERS_LABEL: &str = "All Layers";
+
+pub struct ReplaceTileWindow {
+    params: WindowParams,
+    // `None` means that tiles will be replaced in all tile layers
+    layer_id: Option<String>,
+    from_tileset_id: Option<String>,
+    from_tile_id: u32,
+    to_tileset_id: Option<String>,
+    to_tile_id: u32,
+}
+
+impl ReplaceTileWindow {
+    pub fn new(layer_id: Option<String>, from: Option<(String, u32)>) -> Self {
+        let params = WindowParams {
+            title: Some("Replace Tiles".to_string()),
+            size: vec2(350.0, 350.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        let (from_tileset_id, from_tile_id) = match from {
+            Some((tileset_id, tile_id)) => (Some(tileset_id), tile_id),
+            None => (None, 0),
+        };
+
+        ReplaceTileWindow {
+            params,
+            layer_id,
+            to_tileset_id: from_tileset_id.clone(),
+            to_tile_id: from_tile_id,
+            from_tileset_id,
+            from_tile_id,
+        }
+    }
+
+    fn draw_tile_input(
+        ui: &mut Ui,
+        id: u64,
+        label: &str,
+        map: &Map,
+        tileset_id: &mut Option<String>,
+        tile_id: &mut u32,
+    ) {
+        let mut tileset_ids = map
+            .tilesets
+            .keys()
+            .map(|id| id.as_str())
+            .collect::<Vec<_>>();
+        tileset_ids.sort_unstable();
+
+        let mut tileset_index = tileset_ids
+            .iter()
+            .position(|id| Some(*id) == tileset_id.as_deref())
+            .unwrap_or(0);
+
+        let tileset_label = format!("{} Tileset", label);
+
+        widgets::ComboBox::new(hash!(id, label, "tileset_input"), &tileset_ids)
+            .ratio(0.8)
+            .label(tileset_label.as_str())
+            .ui(ui, &mut tileset_index);
+
+        *tileset_id = tileset_ids.get(tileset_index).map(|id| id.to_string());
+
+        let tile_label = format!("{} Tile", label);
+
+        number_input(ui, hash!(id, label, "tile_input"), &tile_label, tile_id);
+    }
+
+    fn is_valid_tile(map: &Map, tileset_id: &Option<String>, tile_id: u32) -> bool {
+        if let Some(tileset) = tileset_id.as_ref().and_then(|id| map.tilesets.get(id)) {
+            return tile_id < tileset.tile_cnt;
+        }
+
+        false
+    }
+}
+
+impl Window for ReplaceTileWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("replace_tile_window");
+
+        {
+            let mut layer_ids = vec![ALL_LAYERS_LABEL];
+            layer_ids.extend(
+                map.draw_order
+                    .iter()
+                    .filter(|id| map.get_layer_kind(id) == Some(MapLayerKind::TileLayer))
+                    .map(|id| id.as_str()),
+            );
+
+            let mut layer_index = layer_ids
+                .iter()
+                .skip(1)
+                .position(|id| Some(*id) == self.layer_id.as_deref())
+                .map(|i| i + 1)
+                .unwrap_or(0);
+
+            widgets::ComboBox::new(hash!(id, "layer_input"), &layer_ids)
+                .ratio(0.8)
+                .label("Layer")
+                .ui(ui, &mut layer_index);
+
+            self.layer_id = if layer_index == 0 {
+                None
+            } else {
+                layer_ids.get(layer_index).map(|id| id.to_string())
+            };
+        }
+
+        ui.separator();
+
+        Self::draw_tile_input(
+            ui,
+            id,
+            "From",
+            map,
+            &mut self.from_tileset_id,
+            &mut self.from_tile_id,
+        );
+
+        ui.separator();
+
+        Self::draw_tile_input(
+            ui,
+            id,
+            "To",
+            map,
+            &mut self.to_tileset_id,
+            &mut self.to_tile_id,
+        );
+
+        None
+    }
+
+    fn get_buttons(&self, map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let is_valid = Self::is_valid_tile(map, &self.from_tileset_id, self.from_tile_id)
+            && Self::is_valid_tile(map, &self.to_tileset_id, self.to_tile_id);
+
+        let mut action = None;
+        if is_valid {
+            let replace_action = EditorAction::ReplaceTile {
+                layer_id: self.layer_id.clone(),
+                from: (self.from_tileset_id.clone().unwrap(), self.from_tile_id),
+                to: (self.to_tileset_id.clone().unwrap(), self.to_tile_id),
+            };
+
+            action = Some(self.get_close_action().then(replace_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Replace",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
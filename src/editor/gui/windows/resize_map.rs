@@ -0,0 +1,142 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::NumberInput;
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::{Anchor, Map};
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::gui::ComboBoxBuilder;
+
+pub struct ResizeMapWindow {
+    params: WindowParams,
+    grid_size: Option<UVec2>,
+    anchor: Anchor,
+}
+
+impl ResizeMapWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Resize Map".to_string()),
+            size: vec2(300.0, 250.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        ResizeMapWindow {
+            params,
+            grid_size: None,
+            anchor: Anchor::default(),
+        }
+    }
+}
+
+impl Window for ResizeMapWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("resize_map_window");
+
+        {
+            let size_label = format!(
+                "Current size: {}x{}",
+                map.grid_size.width, map.grid_size.height
+            );
+            widgets::Label::new(size_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        let grid_size = self
+            .grid_size
+            .get_or_insert_with(|| map.grid_size.as_uvec2());
+
+        {
+            let size = vec2(75.0, 25.0);
+
+            NumberInput::new(hash!(id, "width_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("x")
+                .ui(ui, &mut grid_size.x);
+
+            ui.same_line(size.x + 25.0);
+
+            NumberInput::new(hash!(id, "height_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("Size (tiles)")
+                .ui(ui, &mut grid_size.y);
+        }
+
+        ui.separator();
+
+        ComboBoxBuilder::new(hash!(id, "anchor_input"))
+            .with_label("Anchor")
+            .with_ratio(1.0)
+            .build(ui, &mut self.anchor);
+
+        let grid_size = Size::from(*grid_size);
+        if grid_size.width > 0 && grid_size.height > 0 {
+            let clamped_cnt = map.get_resize_clamped_cnt(grid_size, self.anchor);
+            if clamped_cnt > 0 {
+                ui.separator();
+
+                let warning_label = format!(
+                    "{} object(s) and spawn point(s) will be moved to the map edges",
+                    clamped_cnt
+                );
+                widgets::Label::new(warning_label.as_str()).ui(ui);
+            }
+        }
+
+        None
+    }
+
+    fn get_buttons(&self, map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if let Some(grid_size) = self.grid_size {
+            let grid_size = Size::from(grid_size);
+
+            if grid_size.width > 0 && grid_size.height > 0 && grid_size != map.grid_size {
+                let resize_action = EditorAction::ResizeMap {
+                    grid_size,
+                    anchor: self.anchor,
+                };
+
+                action = Some(self.get_close_action().then(resize_action));
+            }
+        }
+
+        res.push(ButtonParams {
+            label: "Resize",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
+
+impl Default for ResizeMapWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
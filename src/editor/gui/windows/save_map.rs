@@ -5,28 +5,52 @@ use ff_core::macroquad::ui::{widgets, Ui};
 use ff_core::prelude::*;
 
 use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
-use ff_core::map::{map_name_to_filename, Map, MAP_EXPORTS_DEFAULT_DIR, MAP_EXPORTS_EXTENSION};
+use ff_core::map::{
+    default_map_format, iter_maps, map_format_extensions, map_name_to_filename, Map,
+    MAP_EXPORTS_DEFAULT_DIR,
+};
 
 pub struct SaveMapWindow {
     params: WindowParams,
     name: String,
     should_overwrite: bool,
+    format_extensions: Vec<String>,
+    format_index: usize,
+    // This holds the reason the map can not be saved, if `validate_map_for_save` failed
+    validation_error: Option<String>,
 }
 
 impl SaveMapWindow {
-    pub fn new(current_name: &str) -> Self {
+    pub fn new(current_name: &str, extension: &str, validation_error: Option<String>) -> Self {
         let params = WindowParams {
             title: Some("Save Map".to_string()),
             size: vec2(350.0, 350.0),
+            has_text_input: true,
             ..Default::default()
         };
 
+        let format_extensions = map_format_extensions();
+        let format_index = format_extensions
+            .iter()
+            .position(|other| other == extension)
+            .unwrap_or(0);
+
         SaveMapWindow {
             params,
             name: current_name.to_string(),
             should_overwrite: false,
+            format_extensions,
+            format_index,
+            validation_error,
         }
     }
+
+    fn get_extension(&self) -> String {
+        self.format_extensions
+            .get(self.format_index)
+            .cloned()
+            .unwrap_or_else(|| default_map_format().extension().to_string())
+    }
 }
 
 impl Window for SaveMapWindow {
@@ -57,7 +81,7 @@ impl Window for SaveMapWindow {
                 let path = Path::new(&assets_dir)
                     .join(MAP_EXPORTS_DEFAULT_DIR)
                     .join(map_name_to_filename(&self.name))
-                    .with_extension(MAP_EXPORTS_EXTENSION);
+                    .with_extension(&self.get_extension());
 
                 widgets::Label::new(path.to_string_lossy().as_ref()).ui(ui);
             }
@@ -72,6 +96,43 @@ impl Window for SaveMapWindow {
             .label("Overwrite Existing")
             .ui(ui, &mut self.should_overwrite);
 
+        {
+            let format_labels = self
+                .format_extensions
+                .iter()
+                .map(|extension| extension.as_str())
+                .collect::<Vec<_>>();
+
+            widgets::ComboBox::new(hash!(id, "format_input"), &format_labels)
+                .ratio(0.4)
+                .label("Format")
+                .ui(ui, &mut self.format_index);
+        }
+
+        {
+            let path = Path::new(MAP_EXPORTS_DEFAULT_DIR)
+                .join(map_name_to_filename(&self.name))
+                .with_extension(&self.get_extension());
+
+            let is_builtin_map = iter_maps().any(|map_resource| {
+                !map_resource.meta.is_user_map && Path::new(&map_resource.meta.path) == path
+            });
+
+            if is_builtin_map {
+                ui.separator();
+
+                widgets::Label::new("This is the name of a built-in map").ui(ui);
+            }
+        }
+
+        if let Some(validation_error) = &self.validation_error {
+            ui.separator();
+            ui.separator();
+
+            widgets::Label::new("The map can not be saved:").ui(ui);
+            widgets::Label::new(validation_error.as_str()).ui(ui);
+        }
+
         None
     }
 
@@ -80,11 +141,14 @@ impl Window for SaveMapWindow {
 
         let path = Path::new(MAP_EXPORTS_DEFAULT_DIR)
             .join(map_name_to_filename(&self.name))
-            .with_extension(MAP_EXPORTS_EXTENSION);
+            .with_extension(&self.get_extension());
 
         let mut action = None;
-        if ff_core::map::is_valid_map_export_path(&path, self.should_overwrite) {
-            let save_action = EditorAction::SaveMap(Some(self.name.clone()));
+        if self.validation_error.is_none()
+            && ff_core::map::is_valid_map_export_path(&path, self.should_overwrite)
+        {
+            let save_action =
+                EditorAction::SaveMap(Some((self.name.clone(), self.get_extension())));
             let batch = self.get_close_action().then(save_action);
 
             action = Some(batch);
@@ -93,6 +157,7 @@ impl Window for SaveMapWindow {
         res.push(ButtonParams {
             label: "Save",
             action,
+            is_default: true,
             ..Default::default()
         });
 
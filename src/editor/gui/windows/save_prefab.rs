@@ -0,0 +1,113 @@
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::prelude::*;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::library::{get_library_asset_path, try_get_library_asset};
+use crate::editor::prefab::Prefab;
+use ff_core::map::Map;
+
+pub struct SavePrefabWindow {
+    params: WindowParams,
+    region: URect,
+    name: String,
+    should_overwrite: bool,
+}
+
+impl SavePrefabWindow {
+    pub fn new(region: URect) -> Self {
+        let params = WindowParams {
+            title: Some("Save Prefab".to_string()),
+            size: vec2(350.0, 250.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        SavePrefabWindow {
+            params,
+            region,
+            name: "Unnamed Prefab".to_string(),
+            should_overwrite: false,
+        }
+    }
+}
+
+impl Window for SavePrefabWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("save_prefab_window");
+
+        {
+            let size = vec2(173.0, 25.0);
+
+            widgets::InputText::new(hash!(id, "name_input"))
+                .size(size)
+                .ratio(1.0)
+                .label("Name")
+                .ui(ui, &mut self.name);
+
+            {
+                let path = get_library_asset_path::<Prefab>(&self.name);
+
+                widgets::Label::new(path.to_string_lossy().as_ref()).ui(ui);
+            }
+        }
+
+        ui.separator();
+
+        {
+            let size_label = format!("Size: {}x{} tiles", self.region.width, self.region.height);
+            widgets::Label::new(size_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+        ui.separator();
+
+        widgets::Checkbox::new(hash!(id, "overwrite_input"))
+            .label("Overwrite Existing")
+            .ui(ui, &mut self.should_overwrite);
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let is_valid_name = !self.name.is_empty()
+            && (self.should_overwrite || try_get_library_asset::<Prefab>(&self.name).is_none());
+
+        let mut action = None;
+        if is_valid_name {
+            let save_action = EditorAction::SavePrefab {
+                id: self.name.clone(),
+                region: self.region,
+            };
+
+            action = Some(self.get_close_action().then(save_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
@@ -0,0 +1,119 @@
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::prelude::*;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::library::{get_library_asset_path, try_get_library_asset};
+use crate::editor::stamp::Stamp;
+use ff_core::map::Map;
+
+pub struct SaveStampWindow {
+    params: WindowParams,
+    layer_id: String,
+    region: URect,
+    name: String,
+    should_overwrite: bool,
+}
+
+impl SaveStampWindow {
+    pub fn new(layer_id: &str, region: URect) -> Self {
+        let params = WindowParams {
+            title: Some("Save Stamp".to_string()),
+            size: vec2(350.0, 250.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        SaveStampWindow {
+            params,
+            layer_id: layer_id.to_string(),
+            region,
+            name: "Unnamed Stamp".to_string(),
+            should_overwrite: false,
+        }
+    }
+}
+
+impl Window for SaveStampWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("save_stamp_window");
+
+        {
+            let size = vec2(173.0, 25.0);
+
+            widgets::InputText::new(hash!(id, "name_input"))
+                .size(size)
+                .ratio(1.0)
+                .label("Name")
+                .ui(ui, &mut self.name);
+
+            {
+                let path = get_library_asset_path::<Stamp>(&self.name);
+
+                widgets::Label::new(path.to_string_lossy().as_ref()).ui(ui);
+            }
+        }
+
+        ui.separator();
+
+        {
+            let size_label = format!("Size: {}x{} tiles", self.region.width, self.region.height);
+            widgets::Label::new(size_label.as_str()).ui(ui);
+
+            let layer_label = format!("Layer: {}", self.layer_id);
+            widgets::Label::new(layer_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+        ui.separator();
+
+        widgets::Checkbox::new(hash!(id, "overwrite_input"))
+            .label("Overwrite Existing")
+            .ui(ui, &mut self.should_overwrite);
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let is_valid_name = !self.name.is_empty()
+            && (self.should_overwrite || try_get_library_asset::<Stamp>(&self.name).is_none());
+
+        let mut action = None;
+        if is_valid_name {
+            let save_action = EditorAction::SaveStamp {
+                id: self.name.clone(),
+                layer_id: self.layer_id.clone(),
+                region: self.region,
+            };
+
+            action = Some(self.get_close_action().then(save_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
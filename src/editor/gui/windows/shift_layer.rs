@@ -0,0 +1,111 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::{Checkbox, NumberInput};
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+
+pub struct ShiftLayerWindow {
+    params: WindowParams,
+    layer_id: String,
+    offset: IVec2,
+    should_wrap: bool,
+}
+
+impl ShiftLayerWindow {
+    pub fn new(layer_id: &str) -> Self {
+        let params = WindowParams {
+            title: Some("Shift Layer".to_string()),
+            size: vec2(275.0, 225.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        ShiftLayerWindow {
+            params,
+            layer_id: layer_id.to_string(),
+            offset: IVec2::ZERO,
+            should_wrap: false,
+        }
+    }
+}
+
+impl Window for ShiftLayerWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("shift_layer_window");
+
+        {
+            let layer_label = format!("Layer: {}", self.layer_id);
+            widgets::Label::new(layer_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        {
+            let size = vec2(75.0, 25.0);
+
+            NumberInput::new(hash!(id, "offset_x_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("x")
+                .ui(ui, &mut self.offset.x);
+
+            ui.same_line(size.x + 25.0);
+
+            NumberInput::new(hash!(id, "offset_y_input"))
+                .with_size(size)
+                .with_ratio(1.0)
+                .with_label("Offset (tiles)")
+                .ui(ui, &mut self.offset.y);
+        }
+
+        ui.separator();
+
+        Checkbox::new(hash!(id, "wrap_input"), None, "Wrap Around Edges")
+            .ui(ui, &mut self.should_wrap);
+
+        None
+    }
+
+    fn get_buttons(&self, map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if self.offset != IVec2::ZERO && map.layers.contains_key(&self.layer_id) {
+            let shift_action = EditorAction::ShiftLayer {
+                id: self.layer_id.clone(),
+                offset: self.offset,
+                wrap: self.should_wrap,
+            };
+
+            action = Some(self.get_close_action().then(shift_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Shift",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
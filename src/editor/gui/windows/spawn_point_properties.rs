@@ -0,0 +1,94 @@
+use ff_core::prelude::*;
+
+use ff_core::map::{Map, MapSpawnPointTag};
+
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::Ui;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::gui::ComboBoxBuilder;
+
+pub struct SpawnPointPropertiesWindow {
+    params: WindowParams,
+    index: usize,
+    tag: Option<MapSpawnPointTag>,
+}
+
+impl SpawnPointPropertiesWindow {
+    pub fn new(index: usize) -> Self {
+        let params = WindowParams {
+            title: Some("Spawn Point Properties".to_string()),
+            size: vec2(300.0, 200.0),
+            ..Default::default()
+        };
+
+        SpawnPointPropertiesWindow {
+            params,
+            index,
+            tag: None,
+        }
+    }
+}
+
+impl Window for SpawnPointPropertiesWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+
+        if let Some(tag) = self.tag {
+            let batch = self
+                .get_close_action()
+                .then(EditorAction::SetSpawnPointTag {
+                    index: self.index,
+                    tag,
+                });
+
+            action = Some(batch);
+        }
+
+        res.push(ButtonParams {
+            label: "Save",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("spawn_point_properties_window");
+
+        if self.tag.is_none() {
+            if let Some(spawn_point) = map.spawn_points.get(self.index) {
+                self.tag = Some(spawn_point.tag);
+            }
+        }
+
+        if let Some(tag) = &mut self.tag {
+            ComboBoxBuilder::new(hash!(id, "tag_input"))
+                .with_label("Tag")
+                .with_ratio(1.0)
+                .build(ui, tag);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,118 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::{get_gui_theme, theme::LIST_BOX_ENTRY_HEIGHT, ELEMENT_MARGIN};
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::library::get_library_asset_ids;
+use crate::editor::stamp::Stamp;
+
+pub struct StampPaletteWindow {
+    params: WindowParams,
+    selected: Option<String>,
+}
+
+impl StampPaletteWindow {
+    pub fn new() -> Self {
+        let params = WindowParams {
+            title: Some("Stamps".to_string()),
+            size: vec2(350.0, 350.0),
+            ..Default::default()
+        };
+
+        StampPaletteWindow {
+            params,
+            selected: None,
+        }
+    }
+}
+
+impl Window for StampPaletteWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        size: Vec2,
+        _map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("stamp_palette_window");
+
+        {
+            let gui_theme = get_gui_theme();
+            ui.push_skin(&gui_theme.list_box_no_bg);
+        }
+
+        let size = vec2(size.x, size.y - ELEMENT_MARGIN);
+        widgets::Group::new(hash!(id, "list_box"), size)
+            .position(Vec2::ZERO)
+            .ui(ui, |ui| {
+                let entry_size = vec2(size.x, LIST_BOX_ENTRY_HEIGHT);
+
+                for (i, stamp_id) in get_library_asset_ids::<Stamp>().into_iter().enumerate() {
+                    let is_selected = self.selected.as_ref() == Some(&stamp_id);
+
+                    if is_selected {
+                        let gui_theme = get_gui_theme();
+                        ui.push_skin(&gui_theme.list_box_selected);
+                    }
+
+                    let entry_position = vec2(0.0, i as f32 * entry_size.y);
+
+                    let entry_btn = widgets::Button::new("")
+                        .size(entry_size)
+                        .position(entry_position);
+
+                    if entry_btn.ui(ui) {
+                        self.selected = Some(stamp_id.clone());
+                    }
+
+                    ui.label(entry_position, &stamp_id);
+
+                    if is_selected {
+                        ui.pop_skin();
+                    }
+                }
+            });
+
+        ui.pop_skin();
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+        if let Some(stamp_id) = &self.selected {
+            let select_action = EditorAction::SelectStamp(stamp_id.clone());
+            action = Some(self.get_close_action().then(select_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Select",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
+
+impl Default for StampPaletteWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
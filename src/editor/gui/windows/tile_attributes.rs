@@ -0,0 +1,206 @@
+use ff_core::prelude::*;
+
+use ff_core::gui::Checkbox;
+use ff_core::macroquad::hash;
+use ff_core::macroquad::ui::{widgets, Ui};
+use ff_core::map::Map;
+
+use super::{ButtonParams, EditorAction, EditorContext, Window, WindowParams};
+use crate::editor::tile_attributes::get_attribute_states;
+
+const JUMPTHROUGH_ATTRIBUTE: &str = "jumpthrough";
+
+struct AttributeEntry {
+    name: String,
+    // `None` means that the attribute is left as it is on each tile
+    value: Option<bool>,
+    // Only attributes that are mixed across the region, when the window is opened, can be set
+    // back to the indeterminate state
+    is_mixed: bool,
+}
+
+/// This edits the attributes of all the tiles in a region of a tile layer at once. Attributes
+/// that only some of the tiles have are shown as indeterminate, and are left unchanged unless
+/// they are explicitly checked or unchecked.
+pub struct TileAttributesWindow {
+    params: WindowParams,
+    layer_id: String,
+    region: URect,
+    tile_cnt: usize,
+    entries: Option<Vec<AttributeEntry>>,
+    new_attribute: String,
+}
+
+impl TileAttributesWindow {
+    pub fn new(layer_id: String, region: URect) -> Self {
+        let params = WindowParams {
+            title: Some("Tile Attributes".to_string()),
+            size: vec2(350.0, 350.0),
+            has_text_input: true,
+            ..Default::default()
+        };
+
+        TileAttributesWindow {
+            params,
+            layer_id,
+            region,
+            tile_cnt: 0,
+            entries: None,
+            new_attribute: String::new(),
+        }
+    }
+}
+
+impl Window for TileAttributesWindow {
+    fn get_params(&self) -> &WindowParams {
+        &self.params
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _size: Vec2,
+        map: &Map,
+        _ctx: &EditorContext,
+    ) -> Option<EditorAction> {
+        let id = hash!("tile_attributes_window");
+
+        if !map.layers.contains_key(&self.layer_id) {
+            return Some(self.get_close_action());
+        }
+
+        if self.entries.is_none() {
+            let tiles = map
+                .get_tiles(&self.layer_id, Some(self.region))
+                .filter_map(|(_, _, tile)| tile.as_ref())
+                .collect::<Vec<_>>();
+
+            self.tile_cnt = tiles.len();
+
+            let attributes = tiles.iter().map(|tile| tile.attributes.as_slice());
+
+            let mut entries = get_attribute_states(attributes)
+                .into_iter()
+                .map(|(name, value)| AttributeEntry {
+                    name,
+                    value,
+                    is_mixed: value.is_none(),
+                })
+                .collect::<Vec<_>>();
+
+            if !entries
+                .iter()
+                .any(|entry| entry.name == JUMPTHROUGH_ATTRIBUTE)
+            {
+                entries.insert(
+                    0,
+                    AttributeEntry {
+                        name: JUMPTHROUGH_ATTRIBUTE.to_string(),
+                        value: Some(false),
+                        is_mixed: false,
+                    },
+                );
+            }
+
+            self.entries = Some(entries);
+        }
+
+        {
+            let layer_label = format!("Layer: {}", self.layer_id);
+            widgets::Label::new(layer_label.as_str()).ui(ui);
+
+            let size_label = format!(
+                "Region: {}x{} ({} tiles)",
+                self.region.width, self.region.height, self.tile_cnt
+            );
+            widgets::Label::new(size_label.as_str()).ui(ui);
+        }
+
+        ui.separator();
+
+        if let Some(entries) = &mut self.entries {
+            for (i, entry) in entries.iter_mut().enumerate() {
+                let checkbox = Checkbox::new(hash!(id, "attribute_input", i), None, &entry.name);
+
+                if entry.is_mixed {
+                    checkbox.ui_tri_state(ui, &mut entry.value);
+                } else {
+                    let mut value = entry.value.unwrap_or_default();
+                    checkbox.ui(ui, &mut value);
+                    entry.value = Some(value);
+                }
+            }
+
+            ui.separator();
+
+            widgets::InputText::new(hash!(id, "new_attribute_input"))
+                .size(vec2(173.0, 25.0))
+                .ratio(1.0)
+                .label("New Attribute")
+                .ui(ui, &mut self.new_attribute);
+
+            if widgets::Button::new("Add").ui(ui) {
+                let name = self.new_attribute.trim().to_string();
+
+                if !name.is_empty() {
+                    if let Some(entry) = entries.iter_mut().find(|entry| entry.name == name) {
+                        entry.value = Some(true);
+                    } else {
+                        entries.push(AttributeEntry {
+                            name,
+                            value: Some(true),
+                            is_mixed: false,
+                        });
+                    }
+                }
+
+                self.new_attribute.clear();
+            }
+        }
+
+        None
+    }
+
+    fn get_buttons(&self, _map: &Map, _ctx: &EditorContext) -> Vec<ButtonParams> {
+        let mut res = Vec::new();
+
+        let mut action = None;
+
+        if let Some(entries) = &self.entries {
+            let mut set = Vec::new();
+            let mut unset = Vec::new();
+
+            for entry in entries {
+                match entry.value {
+                    Some(true) => set.push(entry.name.clone()),
+                    Some(false) => unset.push(entry.name.clone()),
+                    None => {}
+                }
+            }
+
+            let update_action = EditorAction::UpdateTileAttributesInRegion {
+                layer_id: self.layer_id.clone(),
+                region: self.region,
+                set,
+                unset,
+            };
+
+            action = Some(self.get_close_action().then(update_action));
+        }
+
+        res.push(ButtonParams {
+            label: "Apply",
+            action,
+            is_default: true,
+            ..Default::default()
+        });
+
+        res.push(ButtonParams {
+            label: "Cancel",
+            action: Some(self.get_close_action()),
+            ..Default::default()
+        });
+
+        res
+    }
+}
@@ -59,6 +59,7 @@ impl Window for TilePropertiesWindow {
         res.push(ButtonParams {
             label: "Save",
             action,
+            is_default: true,
             ..Default::default()
         });
 
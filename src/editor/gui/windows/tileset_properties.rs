@@ -186,6 +186,7 @@ impl Window for TilesetPropertiesWindow {
         res.push(ButtonParams {
             label: "Save",
             action: Some(action),
+            is_default: true,
             ..Default::default()
         });
 
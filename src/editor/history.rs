@@ -1,12 +1,33 @@
+use ff_core::math::Rect;
 use ff_core::result::Result;
 
 use crate::Map;
 
-use super::UndoableAction;
+use super::actions::BatchedAction;
+use super::{EditorSelection, UndoableAction};
+
+/// This identifies a state of the map in the history. Every applied action results in a new id,
+/// so an id will never refer to a different state, even if actions are undone and others are
+/// applied in their place.
+pub type SavePoint = u64;
+
+// An action in the history, along with the id of the state that applying it results in
+struct HistoryEntry {
+    action: Box<dyn UndoableAction>,
+    state_id: SavePoint,
+}
 
 pub struct EditorHistory {
-    undo_stack: Vec<Box<dyn UndoableAction>>,
-    redo_stack: Vec<Box<dyn UndoableAction>>,
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+    // While a stroke is in progress, this holds the number of actions, at the top of the undo
+    // stack, that has been applied as a part of it
+    stroke_len: Option<usize>,
+    // This is the id of the state when the undo stack is empty
+    base_state_id: SavePoint,
+    next_state_id: SavePoint,
+    // This holds the id of the state the map was in when it was last saved
+    save_point: SavePoint,
 }
 
 impl EditorHistory {
@@ -14,39 +35,507 @@ impl EditorHistory {
         EditorHistory {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
+            stroke_len: None,
+            base_state_id: 0,
+            next_state_id: 1,
+            save_point: 0,
         }
     }
 
+    fn get_state_id(&self) -> SavePoint {
+        self.undo_stack
+            .last()
+            .map(|entry| entry.state_id)
+            .unwrap_or(self.base_state_id)
+    }
+
+    fn get_next_state_id(&mut self) -> SavePoint {
+        let res = self.next_state_id;
+        self.next_state_id += 1;
+        res
+    }
+
     pub fn apply(&mut self, mut action: Box<dyn UndoableAction>, map: &mut Map) -> Result<()> {
         if !action.is_redundant(map) {
             action.apply(map)?;
-            self.undo_stack.push(action);
+
+            let state_id = self.get_next_state_id();
+            self.undo_stack.push(HistoryEntry { action, state_id });
             self.redo_stack.clear();
+
+            if let Some(stroke_len) = &mut self.stroke_len {
+                *stroke_len += 1;
+            }
         }
 
         Ok(())
     }
 
-    pub fn undo(&mut self, map: &mut Map) -> Result<()> {
-        if let Some(mut action) = self.undo_stack.pop() {
-            action.undo(map)?;
-            self.redo_stack.push(action);
+    /// This records an action that changes the editor selection, in stead of the map. Unlike
+    /// `apply`, this keeps the redo stack and the state id, so that changing the selection after
+    /// an undo does not discard the undone actions, or make the map count as changed.
+    pub fn apply_selection(
+        &mut self,
+        mut action: Box<dyn UndoableAction>,
+        map: &mut Map,
+    ) -> Result<()> {
+        if !action.is_redundant(map) {
+            action.apply(map)?;
+
+            let state_id = self.get_state_id();
+            self.undo_stack.push(HistoryEntry { action, state_id });
+
+            if let Some(stroke_len) = &mut self.stroke_len {
+                *stroke_len += 1;
+            }
         }
 
         Ok(())
     }
 
-    pub fn redo(&mut self, map: &mut Map) -> Result<()> {
-        if let Some(mut action) = self.redo_stack.pop() {
-            action.redo(map)?;
-            self.undo_stack.push(action);
+    /// This starts a stroke, like a drag with a continuous tool. All actions applied until
+    /// `end_stroke` is called will be merged into one, so that they are undone in one step.
+    pub fn begin_stroke(&mut self) {
+        self.end_stroke();
+        self.stroke_len = Some(0);
+    }
+
+    /// This ends the current stroke, if any, merging the actions applied during it. The merged
+    /// action keeps the state id of the last action of the stroke, so a save point in the middle
+    /// of the stroke can no longer be reached.
+    pub fn end_stroke(&mut self) {
+        if let Some(stroke_len) = self.stroke_len.take() {
+            if stroke_len > 1 {
+                let state_id = self.get_state_id();
+                let stroke_start = self.undo_stack.len() - stroke_len;
+
+                let actions = self
+                    .undo_stack
+                    .split_off(stroke_start)
+                    .into_iter()
+                    .map(|entry| entry.action)
+                    .collect();
+
+                self.undo_stack.push(HistoryEntry {
+                    action: Box::new(BatchedAction::new(actions)),
+                    state_id,
+                });
+            }
         }
+    }
 
-        Ok(())
+    // This returns the selection that should be restored, if the undone action was a change of
+    // selection
+    pub fn undo(&mut self, map: &mut Map) -> Result<Option<EditorSelection>> {
+        self.end_stroke();
+
+        let mut res = None;
+
+        if let Some(mut entry) = self.undo_stack.pop() {
+            entry.action.undo(map)?;
+            res = entry.action.get_selection(true);
+            self.redo_stack.push(entry);
+        }
+
+        Ok(res)
+    }
+
+    // This returns the selection that should be restored, if the redone action was a change of
+    // selection
+    pub fn redo(&mut self, map: &mut Map) -> Result<Option<EditorSelection>> {
+        self.end_stroke();
+
+        let mut res = None;
+
+        if let Some(mut entry) = self.redo_stack.pop() {
+            entry.action.redo(map)?;
+            res = entry.action.get_selection(false);
+            self.undo_stack.push(entry);
+        }
+
+        Ok(res)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// This returns the area of the map, in world space, that was changed by the action that was
+    /// last undone, if it is known. This should be called directly after `undo`.
+    pub fn get_undone_rect(&self, map: &Map) -> Option<Rect> {
+        self.redo_stack
+            .last()
+            .and_then(|entry| entry.action.get_affected_rect(map))
+    }
+
+    /// This returns the area of the map, in world space, that was changed by the action that was
+    /// last redone, if it is known. This should be called directly after `redo`.
+    pub fn get_redone_rect(&self, map: &Map) -> Option<Rect> {
+        self.undo_stack
+            .last()
+            .and_then(|entry| entry.action.get_affected_rect(map))
+    }
+
+    /// This marks the current state as saved, for `has_unsaved_changes`
+    pub fn mark_saved(&mut self) {
+        self.save_point = self.get_state_id();
+    }
+
+    /// This returns a save point for the current state. When a map is saved in the background,
+    /// this is taken when the save starts and passed to `mark_saved_at` when it completes, so
+    /// that changes made while the map was being written are still considered unsaved.
+    pub fn get_save_point(&self) -> SavePoint {
+        self.get_state_id()
+    }
+
+    pub fn mark_saved_at(&mut self, save_point: SavePoint) {
+        self.save_point = save_point;
+    }
+
+    /// This returns `true` if actions have been applied, undone or redone since the map was last
+    /// saved, or since the history was cleared
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.save_point != self.get_state_id()
     }
 
     pub fn clear(&mut self) {
         self.undo_stack.clear();
         self.redo_stack.clear();
+        self.stroke_len = None;
+        self.base_state_id = self.get_next_state_id();
+        self.save_point = self.base_state_id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff_core::map::test_utils::{map_with_layers, TEST_TILESET_ID};
+    use ff_core::map::{MapLayerKind, MapObjectKind};
+    use ff_core::prelude::*;
+
+    use super::*;
+    use crate::editor::actions::{
+        BatchedAction, CreateLayerAction, CreateObjectAction, PlaceTileAction, SelectionAction,
+        UpdateTileAttributesAction,
+    };
+    use crate::editor::{EditorContext, SelectedObject};
+
+    fn map_with_tile_layer() -> Map {
+        let layers = [("tiles", MapLayerKind::TileLayer)];
+        map_with_layers(vec2(16.0, 16.0), uvec2(16, 4), &layers)
+    }
+
+    fn place_tile(history: &mut EditorHistory, map: &mut Map, x: u32) {
+        let action = PlaceTileAction::new(
+            0,
+            "tiles".to_string(),
+            TEST_TILESET_ID.to_string(),
+            uvec2(x, 0),
+        );
+        history.apply(Box::new(action), map).unwrap();
+    }
+
+    fn tile_cnt(map: &Map) -> usize {
+        map.layers["tiles"].tiles.iter().flatten().count()
+    }
+
+    #[test]
+    fn test_stroke_undoes_in_one_step() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        history.begin_stroke();
+        for x in 0..10 {
+            place_tile(&mut history, &mut map, x);
+        }
+        history.end_stroke();
+
+        assert_eq!(tile_cnt(&map), 10);
+
+        history.undo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 0);
+
+        history.redo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 10);
+    }
+
+    #[test]
+    fn test_actions_outside_stroke_undo_separately() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        place_tile(&mut history, &mut map, 0);
+
+        history.begin_stroke();
+        for x in 1..10 {
+            place_tile(&mut history, &mut map, x);
+        }
+        history.end_stroke();
+
+        place_tile(&mut history, &mut map, 10);
+
+        history.undo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 10);
+
+        history.undo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 1);
+
+        history.undo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 0);
+    }
+
+    #[test]
+    fn test_undo_invalidates_selected_object() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        let action = CreateLayerAction::new(
+            "objects".to_string(),
+            MapLayerKind::ObjectLayer,
+            false,
+            None,
+        );
+        history.apply(Box::new(action), &mut map).unwrap();
+
+        let action = CreateObjectAction::new(
+            "object".to_string(),
+            MapObjectKind::Item,
+            Vec2::ZERO,
+            "objects".to_string(),
+        );
+        history.apply(Box::new(action), &mut map).unwrap();
+
+        // The editor holds the selection as a layer id and an index, across edits
+        let mut ctx = EditorContext {
+            selected_layer: Some("objects".to_string()),
+            selected_object: Some(SelectedObject {
+                layer_id: "objects".to_string(),
+                index: 0,
+            }),
+            ..Default::default()
+        };
+
+        ctx.validate_selection(&map);
+        assert_eq!(
+            ctx.selected_object.as_ref().map(|object| object.index),
+            Some(0)
+        );
+
+        // Undoing the object leaves the layer, but the selected index is no longer valid
+        history.undo(&mut map).unwrap();
+        assert!(map.layers["objects"].objects.is_empty());
+        assert_eq!(map.draw_order, vec!["tiles", "objects"]);
+
+        ctx.validate_selection(&map);
+        assert_eq!(ctx.selected_layer.as_deref(), Some("objects"));
+        assert_eq!(ctx.selected_object, None);
+
+        // Undoing the layer removes it, so the selected layer is no longer valid either
+        history.undo(&mut map).unwrap();
+        assert!(!map.layers.contains_key("objects"));
+        assert_eq!(map.draw_order, vec!["tiles"]);
+
+        ctx.validate_selection(&map);
+        assert_eq!(ctx.selected_layer, None);
+
+        history.redo(&mut map).unwrap();
+        assert_eq!(map.draw_order, vec!["tiles", "objects"]);
+        assert_eq!(map.layers["objects"].kind, MapLayerKind::ObjectLayer);
+        assert!(map.layers["objects"].objects.is_empty());
+
+        history.redo(&mut map).unwrap();
+        let object = map.get_object("objects", 0).unwrap();
+        assert_eq!(object.id, "object");
+        assert_eq!(object.kind, MapObjectKind::Item);
+        assert_eq!(object.position, Vec2::ZERO);
+        assert_eq!(map.layers["objects"].objects.len(), 1);
+    }
+
+    #[test]
+    fn test_batched_tile_attributes_undo_in_one_step() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        for x in 0..4 {
+            place_tile(&mut history, &mut map, x);
+        }
+
+        let actions = (0..4)
+            .map(|x| {
+                let action = UpdateTileAttributesAction::new(
+                    x,
+                    "tiles".to_string(),
+                    vec!["slippery".to_string()],
+                );
+
+                Box::new(action) as Box<dyn UndoableAction>
+            })
+            .collect();
+
+        history
+            .apply(Box::new(BatchedAction::new(actions)), &mut map)
+            .unwrap();
+
+        let slippery_cnt = |map: &Map| {
+            map.iter_tiles("tiles")
+                .filter(|(_, _, tile)| tile.attributes.contains(&"slippery".to_string()))
+                .count()
+        };
+
+        assert_eq!(slippery_cnt(&map), 4);
+
+        history.undo(&mut map).unwrap();
+        assert_eq!(slippery_cnt(&map), 0);
+        assert_eq!(tile_cnt(&map), 4);
+    }
+
+    #[test]
+    fn test_undone_rect() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        assert!(!history.can_undo());
+
+        history.begin_stroke();
+        place_tile(&mut history, &mut map, 1);
+        place_tile(&mut history, &mut map, 3);
+        history.end_stroke();
+
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        history.undo(&mut map).unwrap();
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+        assert_eq!(
+            history.get_undone_rect(&map),
+            Some(Rect::new(16.0, 0.0, 48.0, 16.0))
+        );
+
+        history.redo(&mut map).unwrap();
+        assert_eq!(
+            history.get_redone_rect(&map),
+            Some(Rect::new(16.0, 0.0, 48.0, 16.0))
+        );
+    }
+
+    #[test]
+    fn test_background_save_point() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        place_tile(&mut history, &mut map, 0);
+        let save_point = history.get_save_point();
+
+        // Changes made while the map is being written remain unsaved when the save completes
+        place_tile(&mut history, &mut map, 1);
+        history.mark_saved_at(save_point);
+        assert!(history.has_unsaved_changes());
+
+        history.undo(&mut map).unwrap();
+        assert!(!history.has_unsaved_changes());
+
+        // A state that was undone, and replaced by another, while the map was being written is
+        // not considered saved when the save completes
+        let save_point = history.get_save_point();
+
+        history.undo(&mut map).unwrap();
+        place_tile(&mut history, &mut map, 2);
+        history.mark_saved_at(save_point);
+        assert!(history.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_unsaved_changes() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        assert!(!history.has_unsaved_changes());
+
+        place_tile(&mut history, &mut map, 0);
+        assert!(history.has_unsaved_changes());
+
+        history.mark_saved();
+        assert!(!history.has_unsaved_changes());
+
+        history.undo(&mut map).unwrap();
+        assert!(history.has_unsaved_changes());
+
+        history.redo(&mut map).unwrap();
+        assert!(!history.has_unsaved_changes());
+
+        // Applying an action after undoing past the save point makes it unreachable
+        history.undo(&mut map).unwrap();
+        place_tile(&mut history, &mut map, 1);
+        assert!(history.has_unsaved_changes());
+
+        history.undo(&mut map).unwrap();
+        assert!(history.has_unsaved_changes());
+
+        history.clear();
+        assert!(!history.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_selection_keeps_redo_and_save_point() {
+        let mut map = map_with_tile_layer();
+        let mut history = EditorHistory::new();
+
+        let select_layer = |layer: &str| {
+            let selection = EditorSelection {
+                layer: Some(layer.to_string()),
+                ..Default::default()
+            };
+
+            Box::new(SelectionAction::new(EditorSelection::default(), selection))
+        };
+
+        place_tile(&mut history, &mut map, 0);
+        history.mark_saved();
+
+        // A change of selection does not make the map count as changed
+        history
+            .apply_selection(select_layer("tiles"), &mut map)
+            .unwrap();
+        assert!(!history.has_unsaved_changes());
+
+        let selection = history.undo(&mut map).unwrap();
+        assert_eq!(selection, Some(EditorSelection::default()));
+
+        // Changing the selection after an undo keeps the undone action
+        history.undo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 0);
+
+        history
+            .apply_selection(select_layer("tiles"), &mut map)
+            .unwrap();
+        assert!(history.can_redo());
+
+        history.redo(&mut map).unwrap();
+        assert_eq!(tile_cnt(&map), 1);
+        assert!(!history.has_unsaved_changes());
+
+        // Selections merged into a stroke are still restored
+        history.begin_stroke();
+        history
+            .apply_selection(select_layer("a"), &mut map)
+            .unwrap();
+        history
+            .apply_selection(select_layer("b"), &mut map)
+            .unwrap();
+        history.end_stroke();
+
+        let selection = history.undo(&mut map).unwrap();
+        assert_eq!(selection, Some(EditorSelection::default()));
+
+        let selection = history.redo(&mut map).unwrap();
+        assert_eq!(
+            selection.and_then(|selection| selection.layer).as_deref(),
+            Some("b")
+        );
     }
 }
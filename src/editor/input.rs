@@ -1,5 +1,14 @@
+use std::any::TypeId;
+
+use ff_core::config::EditorKeybinds;
 use ff_core::prelude::*;
 
+use super::tools::{
+    iter_tools, EraserTool, ObjectPlacementTool, PrefabPlacementTool, RegionSelectionTool,
+    SpawnPointPlacementTool, StampTool, TilePickerTool, TilePlacementTool,
+};
+use super::EditorAction;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct EditorInput {
     pub action: bool,
@@ -14,14 +23,116 @@ pub struct EditorInput {
     pub toggle_menu: bool,
     pub toggle_draw_grid: bool,
     pub toggle_snap_to_grid: bool,
+    pub cycle_snap_increment: bool,
     pub toggle_disable_parallax: bool,
+    pub toggle_parallax_preview: bool,
+    pub toggle_empty_tiles: bool,
     pub save: bool,
     pub save_as: bool,
     pub load: bool,
+    pub test_play: bool,
     pub delete: bool,
+    pub copy: bool,
+    pub paste: bool,
+    pub move_layer_up: bool,
+    pub move_layer_down: bool,
+    pub toggle_solo_layer: bool,
+    /// Index of the tileset to select, in alphabetical order, from the number keys
+    pub select_tileset: Option<usize>,
+    pub cycle_tileset: bool,
+    pub toggle_message_log: bool,
+    /// Tool to select, from its key binding. `Some(None)` selects the cursor.
+    pub select_tool: Option<Option<TypeId>>,
+}
+
+const TILESET_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+/// This returns the key bindings of the editor, from the config. The config is read every time, so
+/// that any changes to the bindings take effect immediately.
+pub fn editor_keybinds() -> &'static EditorKeybinds {
+    &config().editor.keybinds
+}
+
+/// This returns the key binding of the tool with the specified id, or of the cursor if `id` is
+/// `None`. Tools that are not built into the editor have no key bindings.
+pub fn get_tool_keybind(id: Option<TypeId>) -> Option<Keybind> {
+    let keybinds = editor_keybinds();
+
+    let id = match id {
+        Some(id) => id,
+        None => return Some(keybinds.cursor_tool),
+    };
+
+    let tools = [
+        (
+            TypeId::of::<TilePlacementTool>(),
+            keybinds.tile_placement_tool,
+        ),
+        (
+            TypeId::of::<ObjectPlacementTool>(),
+            keybinds.object_placement_tool,
+        ),
+        (
+            TypeId::of::<SpawnPointPlacementTool>(),
+            keybinds.spawn_point_placement_tool,
+        ),
+        (TypeId::of::<EraserTool>(), keybinds.eraser_tool),
+        (
+            TypeId::of::<RegionSelectionTool>(),
+            keybinds.region_selection_tool,
+        ),
+        (
+            TypeId::of::<PrefabPlacementTool>(),
+            keybinds.prefab_placement_tool,
+        ),
+        (TypeId::of::<StampTool>(), keybinds.stamp_tool),
+        (TypeId::of::<TilePickerTool>(), keybinds.tile_picker_tool),
+    ];
+
+    tools
+        .iter()
+        .find(|(tool_id, _)| *tool_id == id)
+        .map(|(_, keybind)| *keybind)
+}
+
+/// This returns the key binding that will trigger `action`, if any. This is used to show the
+/// shortcuts of actions in the GUI.
+pub fn get_action_keybind(action: &EditorAction) -> Option<Keybind> {
+    let keybinds = editor_keybinds();
+
+    match action {
+        EditorAction::Undo => Some(keybinds.undo),
+        EditorAction::Redo => Some(keybinds.redo),
+        EditorAction::SelectTool(id) => get_tool_keybind(*id),
+        EditorAction::SaveMap(None) => Some(keybinds.save),
+        EditorAction::OpenSaveMapWindow => Some(keybinds.save_as),
+        EditorAction::OpenLoadMapWindow => Some(keybinds.load),
+        EditorAction::TestPlay => Some(keybinds.test_play),
+        EditorAction::ToggleSoloLayer => Some(keybinds.toggle_solo_layer),
+        EditorAction::ToggleMessageLogWindow => Some(keybinds.toggle_message_log),
+        EditorAction::CopyObjects { .. } => Some(keybinds.copy),
+        EditorAction::PasteObjects(_) => Some(keybinds.paste),
+        EditorAction::DeleteObject { .. }
+        | EditorAction::RemoveTile { .. }
+        | EditorAction::DeleteSpawnPoint(_) => Some(keybinds.delete),
+        _ => None,
+    }
 }
 
-pub fn collect_editor_input() -> EditorInput {
+/// If `is_text_input_active` is `true`, key bindings that would interfere with typing in a text
+/// field are not read. This includes all bindings without ctrl, except for the menu and test play
+/// bindings, as well as undo, redo, copy, paste and grid snapping.
+pub fn collect_editor_input(is_text_input_active: bool) -> EditorInput {
     let mut input = EditorInput {
         action: is_mouse_button_down(MouseButton::Left),
         camera_mouse_move: is_mouse_button_down(MouseButton::Middle),
@@ -36,34 +147,50 @@ pub fn collect_editor_input() -> EditorInput {
         input.camera_zoom = 1.0;
     }
 
-    if is_key_down(KeyCode::LeftControl) {
-        if is_key_pressed(KeyCode::Z) {
-            if is_key_down(KeyCode::LeftShift) {
-                input.redo = true;
-            } else {
-                input.undo = true;
-            }
-        }
-
-        input.toggle_snap_to_grid = is_key_pressed(KeyCode::G);
-
-        if is_key_pressed(KeyCode::S) {
-            if is_key_down(KeyCode::LeftShift) {
-                input.save_as = true;
-            } else {
-                input.save = true;
-            }
-        }
-
-        if is_key_pressed(KeyCode::L) {
-            input.load = true;
-        }
-    } else {
-        if is_key_pressed(KeyCode::Escape) {
-            input.toggle_menu = true;
-            input.back = true;
-        }
+    let keybinds = editor_keybinds();
+
+    // Bindings without ctrl would be triggered by typing
+    let read = |keybind: &Keybind| (!is_text_input_active || keybind.ctrl) && keybind.is_pressed();
+    // These bindings shadow the text editing shortcuts, so they are never read while typing
+    let read_outside_text = |keybind: &Keybind| !is_text_input_active && keybind.is_pressed();
+
+    input.toggle_menu = keybinds.toggle_menu.is_pressed();
+    input.back = input.toggle_menu;
+    input.test_play = keybinds.test_play.is_pressed();
+
+    input.save = read(&keybinds.save);
+    input.save_as = read(&keybinds.save_as);
+    input.load = read(&keybinds.load);
+    input.toggle_parallax_preview = read(&keybinds.toggle_parallax_preview);
+    input.toggle_draw_grid = read(&keybinds.toggle_draw_grid);
+    input.toggle_disable_parallax = read(&keybinds.toggle_disable_parallax);
+    input.toggle_empty_tiles = read(&keybinds.toggle_empty_tiles);
+    input.delete = read(&keybinds.delete);
+    input.move_layer_up = read(&keybinds.move_layer_up);
+    input.move_layer_down = read(&keybinds.move_layer_down);
+    input.toggle_solo_layer = read(&keybinds.toggle_solo_layer);
+    input.cycle_tileset = read(&keybinds.cycle_tileset);
+    input.toggle_message_log = read(&keybinds.toggle_message_log);
+
+    input.undo = read_outside_text(&keybinds.undo);
+    input.redo = read_outside_text(&keybinds.redo);
+    input.copy = read_outside_text(&keybinds.copy);
+    input.paste = read_outside_text(&keybinds.paste);
+    input.toggle_snap_to_grid = read_outside_text(&keybinds.toggle_snap_to_grid);
+    input.cycle_snap_increment = read_outside_text(&keybinds.cycle_snap_increment);
+
+    input.select_tool = {
+        let cursor = std::iter::once(None);
+        let tools = iter_tools().map(|(id, _)| Some(id));
+
+        cursor.chain(tools).find(|id| {
+            get_tool_keybind(*id)
+                .map(|keybind| read(&keybind))
+                .unwrap_or(false)
+        })
+    };
 
+    if !is_text_input_active && !is_key_down(KeyCode::LeftControl) {
         if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
             input.camera_move_direction.x = -1.0;
         } else if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
@@ -76,11 +203,7 @@ pub fn collect_editor_input() -> EditorInput {
             input.camera_move_direction.y = 1.0;
         }
 
-        input.toggle_draw_grid = is_key_pressed(KeyCode::G);
-
-        input.toggle_disable_parallax = is_key_pressed(KeyCode::P);
-
-        input.delete = is_key_pressed(KeyCode::Delete);
+        input.select_tileset = TILESET_KEYS.iter().position(|key| is_key_pressed(*key));
     }
 
     /*
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use ff_core::parsing::{deserialize_json_bytes, serialize_json_string};
+use ff_core::prelude::*;
+use ff_core::serde_json;
+
+pub const LIBRARY_ASSET_EXTENSION: &str = "json";
+
+/// This is implemented by the assets that the user can save to a library in the assets dir, like
+/// prefabs and stamps. Each kind of asset is stored as json files in its own directory, and the
+/// loaded assets are kept in a registry, by id.
+pub trait LibraryAsset: Serialize + DeserializeOwned + 'static {
+    /// The name of the asset kind, used in warnings
+    const KIND: &'static str;
+    /// The directory, in the assets dir, that assets of this kind are stored in
+    const DEFAULT_DIR: &'static str;
+    /// Assets with a different format version than this will be skipped when they are loaded.
+    /// The version is read from the `version` field of the json before the asset is deserialized,
+    /// so that assets in an older format are not reported as malformed.
+    const FORMAT_VERSION: u32;
+
+    fn id(&self) -> &str;
+
+    /// This returns the registry that the loaded assets of this kind are kept in
+    fn registry() -> &'static mut HashMap<String, Self>;
+
+    /// This should return a description of the problem, if the asset is malformed, in which case
+    /// it will be skipped when it is loaded
+    fn validate(&self) -> Option<String> {
+        None
+    }
+}
+
+pub fn try_get_library_asset<T: LibraryAsset>(id: &str) -> Option<&'static T> {
+    T::registry().get(id)
+}
+
+/// This returns the ids of all loaded assets of the specified kind, in alphabetical order.
+pub fn get_library_asset_ids<T: LibraryAsset>() -> Vec<String> {
+    let mut res = T::registry().keys().cloned().collect::<Vec<_>>();
+    res.sort();
+    res
+}
+
+pub fn library_asset_name_to_filename(name: &str) -> String {
+    name.replace(' ', "_").replace('.', "_").to_lowercase()
+}
+
+/// This returns the path that an asset of the specified kind, with the specified name, is saved to
+pub fn get_library_asset_path<T: LibraryAsset>(name: &str) -> PathBuf {
+    Path::new(&assets_dir())
+        .join(T::DEFAULT_DIR)
+        .join(library_asset_name_to_filename(name))
+        .with_extension(LIBRARY_ASSET_EXTENSION)
+}
+
+/// This will load all the assets in the directory of the specified kind, replacing any that are
+/// already loaded. Assets of a different format version than `LibraryAsset::FORMAT_VERSION`, as
+/// well as files that can not be read or parsed and malformed assets, are skipped with a warning.
+pub fn load_library_assets<T: LibraryAsset>() -> Result<()> {
+    let registry = T::registry();
+    registry.clear();
+
+    let dir = Path::new(&assets_dir()).join(T::DEFAULT_DIR);
+
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.extension().map(|ext| ext == LIBRARY_ASSET_EXTENSION) != Some(true) {
+            continue;
+        }
+
+        let value: serde_json::Value = match fs::read(&path)
+            .map_err(Error::from)
+            .and_then(|bytes| deserialize_json_bytes(&bytes).map_err(Error::from))
+        {
+            Ok(value) => value,
+            Err(err) => {
+                println!(
+                    "WARNING: Skipping {} file '{}': {}",
+                    T::KIND,
+                    path.display(),
+                    err
+                );
+                continue;
+            }
+        };
+
+        let version = value.get("version").and_then(|version| version.as_u64());
+        if version != Some(T::FORMAT_VERSION as u64) {
+            println!(
+                "WARNING: Skipping {} file '{}' with unsupported format version {:?}",
+                T::KIND,
+                path.display(),
+                version,
+            );
+            continue;
+        }
+
+        let asset: T = match serde_json::from_value(value) {
+            Ok(asset) => asset,
+            Err(err) => {
+                println!(
+                    "WARNING: Skipping {} file '{}': {}",
+                    T::KIND,
+                    path.display(),
+                    err
+                );
+                continue;
+            }
+        };
+
+        if let Some(err) = asset.validate() {
+            println!("WARNING: Skipping {} '{}': {}", T::KIND, asset.id(), err);
+        } else {
+            registry.insert(asset.id().to_string(), asset);
+        }
+    }
+
+    Ok(())
+}
+
+/// This will write the asset to the directory of its kind, overwriting any existing asset with
+/// the same id, and add it to the loaded assets.
+pub fn save_library_asset<T: LibraryAsset>(asset: T) -> Result<()> {
+    let path = get_library_asset_path::<T>(asset.id());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let json = serialize_json_string(&asset)?;
+    fs::write(path, json)?;
+
+    T::registry().insert(asset.id().to_string(), asset);
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct MessageLogEntry {
+    /// The time, in seconds since the log was created, that the message was added
+    pub timestamp: f32,
+    pub message: String,
+}
+
+impl MessageLogEntry {
+    /// This returns the timestamp formatted as minutes and seconds, like `03:07`
+    pub fn timestamp_label(&self) -> String {
+        let seconds = self.timestamp as u32;
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// This holds the most recent info messages shown in the editor, so that they can be reviewed
+/// after they have timed out. When the log is full, the oldest message is dropped.
+#[derive(Debug, Clone)]
+pub struct MessageLog {
+    entries: VecDeque<MessageLogEntry>,
+    capacity: usize,
+    time: f32,
+}
+
+impl MessageLog {
+    pub fn new(capacity: usize) -> Self {
+        MessageLog {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            time: 0.0,
+        }
+    }
+
+    /// This advances the clock used to timestamp new messages
+    pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+    }
+
+    pub fn push(&mut self, message: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(MessageLogEntry {
+            timestamp: self.time,
+            message,
+        });
+    }
+
+    /// This returns the logged messages, from oldest to newest
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &MessageLogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_log_drops_oldest() {
+        let mut log = MessageLog::new(2);
+
+        log.push("first".to_string());
+        log.update(61.5);
+        log.push("second".to_string());
+        log.update(2.0);
+        log.push("third".to_string());
+
+        let entries = log.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "second");
+        assert_eq!(entries[0].timestamp_label(), "01:01");
+        assert_eq!(entries[1].message, "third");
+        assert_eq!(entries[1].timestamp_label(), "01:03");
+    }
+
+    #[test]
+    fn test_message_log_zero_capacity() {
+        let mut log = MessageLog::new(0);
+        log.push("ignored".to_string());
+
+        assert!(log.is_empty());
+    }
+}
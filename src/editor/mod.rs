@@ -1,9 +1,13 @@
 use std::any::TypeId;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 mod camera;
 
-pub use camera::EditorCamera;
+pub use camera::{
+    get_editor_camera, get_view_rect, to_screen_space, to_world_space, try_get_editor_camera,
+    EditorCamera,
+};
 
 pub mod gui;
 
@@ -22,50 +26,92 @@ use gui::{
 mod actions;
 
 use actions::{
-    CreateLayerAction, CreateObjectAction, CreateTilesetAction, DeleteLayerAction,
+    BatchedAction, CreateLayerAction, CreateObjectAction, CreateTilesetAction, DeleteLayerAction,
     DeleteObjectAction, DeleteTilesetAction, EditorAction, PlaceTileAction, RemoveTileAction,
-    SetLayerDrawOrderIndexAction, UndoableAction, UpdateTilesetAction,
+    SelectionAction, SetLayerDrawOrderIndexAction, UndoableAction, UpdateTilesetAction,
 };
 
 mod input;
 
+mod clipboard;
 mod history;
+mod library;
+mod message_log;
+mod object_batch;
+mod prefab;
+mod preview;
+mod region_text;
+mod smart_guides;
+mod stamp;
+mod state;
+mod test_play;
+mod tile_attributes;
 mod tools;
 
 pub use tools::{
-    add_tool_instance, get_tool_instance, get_tool_instance_of_id, EraserTool, ObjectPlacementTool,
-    TilePlacementTool, DEFAULT_TOOL_ICON_TEXTURE_ID,
+    add_default_tool_instances, add_tool_instance, get_tool_ids, get_tool_instance,
+    get_tool_instance_of_id, iter_tools, EditorTool, EditorToolParams, EraserTool,
+    ObjectPlacementTool, TilePlacementTool, DEFAULT_TOOL_ICON_TEXTURE_ID,
 };
 
-use history::EditorHistory;
+pub use state::EditorState;
+
+use history::{EditorHistory, SavePoint};
+use message_log::MessageLog;
+use object_batch::ObjectDrawBatch;
+use preview::{read_map_preview, render_map_preview, PendingMapPreview};
+use smart_guides::{snap_to_rects, SmartGuide};
 
 use crate::editor::actions::{
-    CreateSpawnPointAction, DeleteSpawnPointAction, ImportAction, MoveSpawnPointAction,
-    UpdateBackgroundAction, UpdateLayerAction, UpdateObjectAction, UpdateTileAttributesAction,
+    ConvertLayerAction, CreateSpawnPointAction, DeleteSpawnPointAction, ImportAction,
+    MergeLayerDownAction, MoveObjectToLayerAction, MoveSpawnPointAction, ReorderObjectAction,
+    ResizeMapAction, SetAmbientColorAction, SetLayerGroupAction, SetLayerSnapToGridAction,
+    SetLayerTintAction, SetMapMusicAction, SetObjectColliderSizeAction, SetObjectGroupAction,
+    SetObjectScaleAction, SetSpawnPointFacingAction, SetSpawnPointTagAction, SetWorldOffsetAction,
+    ShiftLayerAction, UpdateBackgroundAction, UpdateLayerAction, UpdateObjectAction,
+    UpdateTileAttributesAction,
 };
 use crate::editor::gui::windows::{
-    BackgroundPropertiesWindow, CreateMapWindow, ImportWindow, LoadMapWindow,
-    ObjectPropertiesWindow, SaveMapWindow, TilePropertiesWindow,
+    ArrangeSpawnPointsWindow, BackgroundPropertiesWindow, ConfirmDialog, CreateMapWindow,
+    ImportWindow, KeybindsWindow, LayerGroupWindow, LayerTintWindow, LoadMapWindow,
+    MapPropertiesWindow, MessageLogWindow, ObjectPropertiesWindow, PrefabListWindow,
+    ReplaceTileWindow, ResizeMapWindow, SaveMapWindow, SavePrefabWindow, SaveStampWindow,
+    ShiftLayerWindow, SpawnPointPropertiesWindow, StampPaletteWindow, TileAttributesWindow,
+    TilePropertiesWindow,
 };
 use ff_core::gui::SELECTION_HIGHLIGHT_COLOR;
-use ff_core::map::{try_get_decoration, Map, MapLayerKind, MapObject, MapObjectKind};
+use ff_core::image::Image;
+use ff_core::map::{
+    try_get_decoration, Map, MapLayer, MapLayerKind, MapObject, MapObjectKind, MapSpawnPointTag,
+    MapTile,
+};
 
-use crate::editor::input::{collect_editor_input, EditorInput};
-use crate::editor::tools::SpawnPointPlacementTool;
+use crate::camera::get_single_player_camera_position;
+use crate::editor::clipboard::EditorClipboard;
+use crate::editor::input::{collect_editor_input, editor_keybinds, EditorInput};
+use crate::editor::library::{load_library_assets, save_library_asset, try_get_library_asset};
+use crate::editor::prefab::Prefab;
+use crate::editor::region_text::RegionText;
+use crate::editor::stamp::Stamp;
+use crate::editor::test_play::{is_test_playing, TestPlayState};
+use crate::editor::tile_attributes::apply_attribute_changes;
+use crate::editor::tools::{PrefabPlacementTool, StampTool};
 use crate::items::try_get_item;
+use crate::player::character::{iter_characters, CharacterMetadata};
 use crate::player::IDLE_ANIMATION_ID;
 
 use ff_core::text::{draw_text, HorizontalAlignment, TextParams, VerticalAlignment};
 
 use ff_core::macroquad::camera::{pop_camera_state, push_camera_state, set_default_camera};
-use ff_core::macroquad::experimental::scene;
-use ff_core::macroquad::experimental::scene::RefMut;
+use ff_core::macroquad::experimental::coroutines::start_coroutine;
+use ff_core::macroquad::experimental::scene::{self, RefMut};
 use ff_core::macroquad::prelude::scene::Node;
 
 use crate::gui::MainMenuState;
 use ff_core::map::{
-    create_map, delete_map, map_name_to_filename, save_map, MapResource, MAP_EXPORTS_DEFAULT_DIR,
-    MAP_EXPORTS_EXTENSION,
+    create_map, default_map_format, delete_map, get_map_format_of_path, map_name_to_filename,
+    save_map_async, validate_map_for_save, MapRequirements, MapResource, MAP_EXPORTS_DEFAULT_DIR,
+    MAP_PREVIEW_EXTENSION,
 };
 
 #[derive(Debug, Clone)]
@@ -74,11 +120,141 @@ pub struct EditorContext {
     pub selected_layer: Option<String>,
     pub selected_tileset: Option<String>,
     pub selected_tile: Option<u32>,
-    pub selected_object: Option<usize>,
+    pub picked_tile_attributes: Vec<String>,
+    pub selected_object: Option<SelectedObject>,
+    pub selected_map_tile_index: Option<usize>,
+    pub selected_region: Option<URect>,
+    pub selected_prefab: Option<String>,
+    pub selected_stamp: Option<String>,
+    pub soloed_layer: Option<String>,
     pub cursor_position: Vec2,
     pub is_user_map: bool,
     pub is_tiled_map: bool,
+    pub map_requirements: MapRequirements,
     pub should_snap_to_grid: bool,
+    pub snap_increment: SnapIncrement,
+    pub grid_origin_offset: Vec2,
+}
+
+/// The fraction of a tile that positions are snapped to, when snap to grid is enabled. This only
+/// applies to positions, like those of objects and spawn points, as tiles are always placed on
+/// whole cells.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SnapIncrement {
+    Tile,
+    HalfTile,
+    QuarterTile,
+}
+
+impl SnapIncrement {
+    pub fn as_f32(&self) -> f32 {
+        match self {
+            SnapIncrement::Tile => 1.0,
+            SnapIncrement::HalfTile => 0.5,
+            SnapIncrement::QuarterTile => 0.25,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SnapIncrement::Tile => SnapIncrement::HalfTile,
+            SnapIncrement::HalfTile => SnapIncrement::QuarterTile,
+            SnapIncrement::QuarterTile => SnapIncrement::Tile,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapIncrement::Tile => "1 tile",
+            SnapIncrement::HalfTile => "1/2 tile",
+            SnapIncrement::QuarterTile => "1/4 tile",
+        }
+    }
+}
+
+impl Default for SnapIncrement {
+    fn default() -> Self {
+        SnapIncrement::Tile
+    }
+}
+
+/// An object that is selected in the editor. Objects are referenced by their index in a layer, so
+/// the layer is kept with the index, to tell selections in different object layers apart.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SelectedObject {
+    pub layer_id: String,
+    pub index: usize,
+}
+
+/// This is the part of the editor state that is recorded in the history, as `SelectionAction`s,
+/// when `record-selection-history` is enabled in the editor config.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EditorSelection {
+    pub tool: Option<TypeId>,
+    pub layer: Option<String>,
+    pub tileset: Option<String>,
+    pub tile: Option<u32>,
+}
+
+impl EditorContext {
+    /// This clears the parts of the selection that are no longer valid in `map`, for example after
+    /// an undo or a change of layer. Selections are only cleared when they no longer refer to
+    /// something in the selected layer, so that switching between tools that work on the same kind
+    /// of layer keeps the selected object or tile. The tileset and tile used as a brush are kept
+    /// across layers, as long as they exist in the map.
+    pub fn validate_selection(&mut self, map: &Map) {
+        if let Some(layer_id) = &self.selected_layer {
+            if !map.draw_order.contains(layer_id) {
+                self.selected_layer = None;
+            }
+        } else if let Some(layer_id) = map.draw_order.first() {
+            self.selected_layer = Some(layer_id.clone());
+        }
+
+        if let Some(layer_id) = &self.soloed_layer {
+            if !map.draw_order.contains(layer_id) {
+                self.soloed_layer = None;
+            }
+        }
+
+        let layer_kind = self
+            .selected_layer
+            .as_ref()
+            .and_then(|layer_id| map.get_layer_kind(layer_id));
+
+        // Indices may be left stale by undo and redo, so they are validated against the map. An
+        // object is also deselected when another layer is selected, even an object layer where
+        // the same index would be valid.
+        if let Some(object) = &self.selected_object {
+            let is_valid = self.selected_layer.as_ref() == Some(&object.layer_id)
+                && map.get_object(&object.layer_id, object.index).is_some();
+
+            if !is_valid {
+                self.selected_object = None;
+            }
+        }
+
+        if let Some(index) = self.selected_map_tile_index {
+            if layer_kind != Some(MapLayerKind::TileLayer)
+                || map.try_to_tile_coords(index).is_none()
+            {
+                self.selected_map_tile_index = None;
+            }
+        }
+
+        if let Some(tileset_id) = &self.selected_tileset {
+            if let Some(tileset) = map.tilesets.get(tileset_id) {
+                if let Some(tile_id) = self.selected_tile {
+                    if tile_id >= tileset.tile_cnt {
+                        self.selected_tile = None;
+                    }
+                }
+            } else {
+                self.selected_tileset = None;
+                self.selected_tile = None;
+            }
+        }
+    }
 }
 
 impl Default for EditorContext {
@@ -88,15 +264,33 @@ impl Default for EditorContext {
             selected_layer: None,
             selected_tileset: None,
             selected_tile: None,
+            picked_tile_attributes: Vec::new(),
             selected_object: None,
+            selected_map_tile_index: None,
+            selected_region: None,
+            selected_prefab: None,
+            selected_stamp: None,
+            soloed_layer: None,
             cursor_position: Vec2::ZERO,
             is_user_map: false,
             is_tiled_map: false,
+            map_requirements: MapRequirements::default(),
             should_snap_to_grid: false,
+            snap_increment: SnapIncrement::default(),
+            grid_origin_offset: Vec2::ZERO,
         }
     }
 }
 
+/// This is what is under the cursor, resolved by hit-testing the map, when the context menu is
+/// opened. It determines which entries the context menu will offer.
+#[derive(Debug, Clone)]
+pub enum HoveredItem {
+    Object { layer_id: String, index: usize },
+    Tile { layer_id: String, index: usize },
+    SpawnPoint(usize),
+}
+
 #[derive(Debug, Clone)]
 enum DraggedObject {
     MapObject {
@@ -110,10 +304,147 @@ enum DraggedObject {
         index: usize,
         click_offset: Vec2,
     },
+    // The facing handle of a spawn point is being dragged. The facing is set from what side of
+    // the spawn point the handle is released on.
+    SpawnPointFacing {
+        index: usize,
+    },
+}
+
+const EDITOR_QUIT_HANDLER_ID: &str = "editor";
+
+// This is registered as a quit handler while the editor is active
+fn handle_editor_quit_request() -> QuitResponse {
+    match scene::find_node_by_type::<Editor>() {
+        Some(mut editor) => editor.handle_quit_request(),
+        None => QuitResponse::Proceed,
+    }
+}
+
+const SPAWN_POINT_FACING_HANDLE_SIZE: f32 = 16.0;
+const SPAWN_POINT_FACING_HANDLE_OFFSET: f32 = 4.0;
+
+const SPAWN_POINT_BOUNDS_COLOR: Color = Color {
+    red: 1.0,
+    green: 1.0,
+    blue: 1.0,
+    alpha: 0.5,
+};
+const SPAWN_POINT_EMBEDDED_BOUNDS_COLOR: Color = colors::RED;
+
+// This draws a checkerboard pattern over the cells in `rect` that have no tile on the layer with
+// the specified id. Every other square is left out, so that the map shows through.
+fn draw_empty_tiles(map: &Map, layer_id: &str, rect: URect) {
+    let divisions = Editor::EMPTY_TILE_CHECKER_DIVISIONS;
+    let square_size = map.tile_size / divisions as f32;
+
+    for y in rect.y..rect.y + rect.height {
+        for x in rect.x..rect.x + rect.width {
+            if map.get_tile(layer_id, x, y).is_some() {
+                continue;
+            }
+
+            let position = map.to_position(uvec2(x, y));
+
+            for i in 0..divisions {
+                for j in 0..divisions {
+                    if (i + j) % 2 == 0 {
+                        draw_rectangle(
+                            position.x + j as f32 * square_size.width,
+                            position.y + i as f32 * square_size.height,
+                            square_size.width,
+                            square_size.height,
+                            Editor::EMPTY_TILE_CHECKER_COLOR,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+// This returns the largest player collider of all the characters, so that spawn points are shown
+// with the bounds of the biggest player that can spawn at them
+fn get_player_collider_size() -> Size<f32> {
+    iter_characters()
+        .map(|character| character.collider_size)
+        .reduce(|a, b| Size::new(a.width.max(b.width), a.height.max(b.height)))
+        .unwrap_or_else(CharacterMetadata::default_collider_size)
+}
+
+// This returns the bounds of a player spawned at `position`. This must match the physics body
+// that is created in `spawn_player`, which is centered horizontally on the spawn position.
+fn get_spawn_point_rect(position: Vec2, collider_size: Size<f32>) -> Rect {
+    Rect::new(
+        position.x - collider_size.width / 2.0,
+        position.y,
+        collider_size.width,
+        collider_size.height,
+    )
+}
+
+// This returns `true` if a player spawned within `rect` would be stuck in solid tiles. Touching
+// tiles, like when standing on the ground, does not count.
+fn is_spawn_point_embedded(map: &Map, rect: &Rect) -> bool {
+    map.get_collisions(rect, true).into_iter().any(|tile_rect| {
+        tile_rect
+            .intersect(*rect)
+            .map(|overlap| overlap.width > 0.0 && overlap.height > 0.0)
+            .unwrap_or(false)
+    })
+}
+
+// This returns the rect of the facing handle of a spawn point with the bounds `rect`. The handle
+// is placed on the side of the spawn point that it is facing.
+fn get_spawn_point_facing_handle_rect(rect: Rect, is_facing_left: bool) -> Rect {
+    let x = if is_facing_left {
+        rect.x - SPAWN_POINT_FACING_HANDLE_OFFSET - SPAWN_POINT_FACING_HANDLE_SIZE
+    } else {
+        rect.x + rect.width + SPAWN_POINT_FACING_HANDLE_OFFSET
+    };
+
+    let y = rect.y + (rect.height - SPAWN_POINT_FACING_HANDLE_SIZE) / 2.0;
+
+    Rect::new(
+        x,
+        y,
+        SPAWN_POINT_FACING_HANDLE_SIZE,
+        SPAWN_POINT_FACING_HANDLE_SIZE,
+    )
+}
+
+// This returns the color that the icon of a spawn point with `tag` is tinted with
+fn get_spawn_point_tag_color(tag: MapSpawnPointTag) -> Color {
+    match tag {
+        MapSpawnPointTag::Free => colors::WHITE,
+        MapSpawnPointTag::TeamA => colors::RED,
+        MapSpawnPointTag::TeamB => colors::SKY_BLUE,
+    }
 }
 
-const SPAWN_POINT_COLLIDER_WIDTH: f32 = 38.0;
-const SPAWN_POINT_COLLIDER_HEIGHT: f32 = 49.0;
+// This formats tile attributes as a comma separated list, for display in the editor
+fn format_tile_attributes(attributes: &[String]) -> String {
+    if attributes.is_empty() {
+        "no attributes".to_string()
+    } else {
+        attributes.join(", ")
+    }
+}
+
+// A save of the map that is being written in the background. `res` is set by the save coroutine
+// when it completes.
+struct PendingSave {
+    map_resource: MapResource,
+    // The path of the map that was open in the editor when the save was started
+    original_path: String,
+    save_point: SavePoint,
+    res: Arc<Mutex<Option<Result<()>>>>,
+}
+
+// This returns `true` if `point` is left of the center of a spawn point at `position`
+fn is_left_of_spawn_point(point: Vec2, position: Vec2) -> bool {
+    point.x < position.x
+}
 
 pub struct Editor {
     map_resource: MapResource,
@@ -123,12 +454,28 @@ pub struct Editor {
     selected_tileset: Option<String>,
     // Selected tile in tileset
     selected_tile: Option<u32>,
-    selected_object: Option<usize>,
+    // Attributes picked along with the selected tile, with the tile picker tool, that will be
+    // given to placed tiles
+    picked_tile_attributes: Vec<String>,
+    selected_object: Option<SelectedObject>,
     selected_spawn_point: Option<usize>,
+    // The size of the player collider, which is used for the bounds of spawn points
+    player_collider_size: Size<f32>,
 
     // Selected tile in map
     selected_map_tile_index: Option<usize>,
 
+    // Selected region of the map, in map coords, used when capturing prefabs and stamps
+    selected_region: Option<URect>,
+    selected_prefab: Option<String>,
+    selected_stamp: Option<String>,
+
+    // This is a transient override of layer visibility. When set, only this layer is drawn, while
+    // the `is_visible` values stored in the map are left untouched.
+    soloed_layer: Option<String>,
+
+    clipboard: EditorClipboard,
+
     previous_cursor_position: Vec2,
     cursor_position: Vec2,
     history: EditorHistory,
@@ -141,28 +488,78 @@ pub struct Editor {
 
     dragged_object: Option<DraggedObject>,
 
+    // This holds a preview that has been regenerated since the map was last saved
+    preview_image: Option<Image>,
+    // This holds a preview that has been rendered but can not be read back until the frame it was
+    // rendered in has been presented
+    pending_preview: Option<PendingMapPreview>,
+    // Only one save can be in progress at a time
+    pending_save: Option<PendingSave>,
+
     info_message_timer: f32,
+    // The area of the map, in world space, that was changed by the last undo or redo
+    change_highlight: Option<Rect>,
+    change_highlight_timer: f32,
     double_click_timer: f32,
 
     should_draw_grid: bool,
     should_snap_to_grid: bool,
+    snap_increment: SnapIncrement,
     is_parallax_disabled: bool,
+    // If this is `true`, background parallax will be relative to the position the game camera
+    // would have, if following a player at the center of the editor camera
+    is_parallax_preview_enabled: bool,
+    // If this is `true`, the empty cells of the selected tile layer are drawn with a checkerboard
+    // pattern, so that they can be told apart from tiles that are fully transparent
+    should_draw_empty_tiles: bool,
+    // This is set when the user has confirmed that unsaved changes should be discarded on quit
+    is_quit_confirmed: bool,
+    // This is set when exiting to the main menu, after which the editor nodes will be removed
+    is_exiting: bool,
+    // Changes to the map metadata are not tracked by the history, so this is set when the metadata
+    // has been changed since the map was last saved
+    has_unsaved_meta_changes: bool,
+    // This is set when the editor has changed the config, so that it is only written to disk on
+    // quit if there is something to save
+    has_unsaved_config_changes: bool,
 }
 
 impl Editor {
-    const CAMERA_PAN_THRESHOLD: f32 = 0.025;
-
-    const CAMERA_PAN_SPEED: f32 = 5.0;
-    const CAMERA_ZOOM_STEP: f32 = 0.1;
-    const CAMERA_ZOOM_MIN: f32 = 0.1;
-    const CAMERA_ZOOM_MAX: f32 = 2.5;
+    // When zoomed all the way out, the whole map will fit inside this fraction of the viewport
+    const CAMERA_ZOOM_OUT_MAP_FRACTION: f32 = 0.9;
+    // When zoomed all the way in, this many tiles will be visible along the shortest axis
+    const CAMERA_ZOOM_IN_VISIBLE_TILES: f32 = 4.0;
 
     #[allow(dead_code)]
     const CURSOR_MOVE_SPEED: f32 = 5.0;
 
     const OBJECT_SELECTION_RECT_SIZE: f32 = 75.0;
+    // Colliders smaller than this, like empty collider overrides, are still selectable
+    const OBJECT_SELECTION_RECT_MIN_SIZE: f32 = 16.0;
     const OBJECT_SELECTION_RECT_PADDING: f32 = 8.0;
 
+    const COLLIDER_GIZMO_LINE_WIDTH: f32 = 2.0;
+    const COLLIDER_GIZMO_COLOR: Color = Color {
+        red: 0.0,
+        green: 1.0,
+        blue: 0.5,
+        alpha: 0.75,
+    };
+    const COLLIDER_OVERRIDE_GIZMO_COLOR: Color = Color {
+        red: 0.0,
+        green: 0.75,
+        blue: 1.0,
+        alpha: 0.75,
+    };
+
+    const SMART_GUIDE_LINE_WIDTH: f32 = 1.0;
+    const SMART_GUIDE_COLOR: Color = Color {
+        red: 1.0,
+        green: 0.0,
+        blue: 1.0,
+        alpha: 0.75,
+    };
+
     const GRID_LINE_WIDTH: f32 = 1.0;
     const GRID_COLOR: Color = Color {
         red: 1.0,
@@ -171,28 +568,63 @@ impl Editor {
         alpha: 0.25,
     };
 
+    // The number of checkerboard squares along each side of an empty tile
+    const EMPTY_TILE_CHECKER_DIVISIONS: u32 = 2;
+    const EMPTY_TILE_CHECKER_COLOR: Color = Color {
+        red: 1.0,
+        green: 1.0,
+        blue: 1.0,
+        alpha: 0.1,
+    };
+
+    const LABEL_OUTLINE_COLOR: Color = Color {
+        red: 0.0,
+        green: 0.0,
+        blue: 0.0,
+        alpha: 0.75,
+    };
+    const LABEL_OUTLINE_OFFSET: f32 = 1.0;
+    // This is the distance from the top of a dragged object to its position readout
+    const DRAGGED_OBJECT_POSITION_MARGIN: f32 = 4.0;
+    // This is the distance from the bottom of the viewport to the selected tile's info strip
+    const TILE_INFO_STRIP_MARGIN: f32 = 16.0;
+
     const DOUBLE_CLICK_THRESHOLD: f32 = 0.25;
 
+    const CHANGE_HIGHLIGHT_DURATION: f32 = 0.5;
+    const CHANGE_HIGHLIGHT_COLOR: Color = Color {
+        red: 1.0,
+        green: 0.85,
+        blue: 0.2,
+        alpha: 0.5,
+    };
+
+    const QUIT_DIALOG_WIDTH: f32 = 350.0;
+    const QUIT_DIALOG_HEIGHT: f32 = 150.0;
+
+    const DELETE_MAP_DIALOG_WIDTH: f32 = 350.0;
+    const DELETE_MAP_DIALOG_HEIGHT: f32 = 150.0;
+
     const MESSAGE_TIMEOUT: f32 = 2.5;
 
+    const CURSOR_ICON_SIZE: f32 = 24.0;
+    const CURSOR_ICON_OFFSET: f32 = 12.0;
+
     pub fn new(map_resource: MapResource) -> Self {
-        add_tool_instance(TilePlacementTool::new());
-        add_tool_instance(ObjectPlacementTool::new());
-        add_tool_instance(SpawnPointPlacementTool::new());
-        add_tool_instance(EraserTool::new());
+        add_default_tool_instances();
 
-        let selected_tool = None;
+        if let Err(err) = load_library_assets::<Prefab>() {
+            println!("WARNING: Unable to load prefabs: {}", err);
+        }
 
-        let selected_layer = map_resource.map.draw_order.first().cloned();
+        if let Err(err) = load_library_assets::<Stamp>() {
+            println!("WARNING: Unable to load stamps: {}", err);
+        }
 
         let viewport_size = viewport_size();
         let cursor_position = vec2(viewport_size.width / 2.0, viewport_size.height / 2.0);
 
-        let tool_selector_element = ToolSelectorElement::new()
-            .with_tool::<TilePlacementTool>()
-            .with_tool::<ObjectPlacementTool>()
-            .with_tool::<SpawnPointPlacementTool>()
-            .with_tool::<EraserTool>();
+        let tool_selector_element = ToolSelectorElement::new();
 
         let left_toolbar = Toolbar::new(ToolbarPosition::Left, EditorGui::LEFT_TOOLBAR_WIDTH)
             .with_element(
@@ -221,17 +653,41 @@ impl Editor {
 
         storage::store(gui);
 
+        // The message log is kept in storage, rather than in the editor context, so that it does
+        // not have to be copied every time the context is created
+        storage::store(MessageLog::new(config().editor.message_log_size));
+
+        set_quit_handler(EDITOR_QUIT_HANDLER_ID, handle_editor_quit_request);
+
+        Self::from_map_resource(map_resource, cursor_position)
+    }
+
+    // This creates the editor state for `map_resource`, without setting up the GUI, or anything
+    // else that is global, which is done by `Editor::new`
+    fn from_map_resource(map_resource: MapResource, cursor_position: Vec2) -> Self {
+        let selected_layer = map_resource.map.draw_order.first().cloned();
+
         Editor {
             map_resource,
-            selected_tool,
+            selected_tool: None,
             selected_layer,
             selected_tileset: None,
             selected_tile: None,
+            picked_tile_attributes: Vec::new(),
             selected_object: None,
             selected_spawn_point: None,
+            player_collider_size: get_player_collider_size(),
 
             selected_map_tile_index: None,
 
+            selected_region: None,
+            selected_prefab: None,
+            selected_stamp: None,
+
+            soloed_layer: None,
+
+            clipboard: EditorClipboard::default(),
+
             previous_cursor_position: cursor_position,
             cursor_position,
             history: EditorHistory::new(),
@@ -244,15 +700,117 @@ impl Editor {
 
             dragged_object: None,
 
+            preview_image: None,
+            pending_preview: None,
+
             info_message_timer: 0.0,
             double_click_timer: Self::DOUBLE_CLICK_THRESHOLD,
 
             should_draw_grid: true,
             should_snap_to_grid: false,
+            snap_increment: SnapIncrement::default(),
             is_parallax_disabled: false,
+            is_parallax_preview_enabled: false,
+            should_draw_empty_tiles: false,
+            pending_save: None,
+            change_highlight: None,
+            change_highlight_timer: 0.0,
+            is_quit_confirmed: false,
+            is_exiting: false,
+            has_unsaved_meta_changes: false,
+            has_unsaved_config_changes: false,
+        }
+    }
+
+    // This highlights `rect`, in world space, fading it out over `CHANGE_HIGHLIGHT_DURATION`.
+    // Passing `None` clears any current highlight.
+    fn highlight_change(&mut self, rect: Option<Rect>) {
+        self.change_highlight = rect;
+        self.change_highlight_timer = 0.0;
+    }
+
+    // This completes the pending save, if its map has been written. If another map has been
+    // opened since the save was started, only the loaded maps are affected by the result.
+    fn update_pending_save(&mut self) {
+        let res = match &self.pending_save {
+            Some(pending_save) => pending_save.res.lock().unwrap().take(),
+            None => return,
+        };
+
+        if let Some(res) = res {
+            let pending_save = self.pending_save.take().unwrap();
+
+            match res {
+                Err(err) => {
+                    self.show_info_message(format!("Unable to save map: {}", err));
+                }
+                Ok(()) => {
+                    let meta = pending_save.map_resource.meta;
+                    let path = Path::new(&assets_dir()).join(&meta.path);
+
+                    if self.map_resource.meta.path == pending_save.original_path {
+                        // Requirements that were changed while saving are kept, to be saved later
+                        let requirements = self.map_resource.meta.requirements.clone();
+                        self.has_unsaved_meta_changes = requirements != meta.requirements;
+
+                        self.map_resource.meta = meta;
+                        self.map_resource.meta.requirements = requirements;
+                        self.history.mark_saved_at(pending_save.save_point);
+                    }
+
+                    dispatch_event(Event::MapSaved(path));
+                }
+            }
         }
     }
 
+    // This reads back the regenerated preview, once the frame it was rendered in has been presented
+    fn update_pending_preview(&mut self) {
+        if let Some(preview) = self.pending_preview.take() {
+            let image = read_map_preview(&preview);
+
+            match Texture2D::from_image(image.clone(), None, TextureFilterMode::Nearest, None) {
+                Err(err) => println!("Regenerate Preview: {}", err),
+                Ok(texture) => {
+                    self.map_resource.preview = texture;
+                    self.preview_image = Some(image);
+
+                    self.show_info_message("Preview regenerated".to_string());
+                }
+            }
+        }
+    }
+
+    // This saves editor preferences, like rebound keys, and defers quitting, while asking the
+    // user to confirm, if there are unsaved changes to the map
+    fn handle_quit_request(&mut self) -> QuitResponse {
+        // The config can not be written on Android and wasm, so changes only last for the session
+        #[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+        if self.has_unsaved_config_changes {
+            match save_config(crate::config_path(), config()) {
+                Err(err) => println!("WARNING: Unable to save config: {}", err),
+                Ok(()) => self.has_unsaved_config_changes = false,
+            }
+        }
+
+        if self.is_quit_confirmed
+            || (!self.history.has_unsaved_changes()
+                && !self.has_unsaved_meta_changes
+                && self.pending_save.is_none())
+        {
+            return QuitResponse::Proceed;
+        }
+
+        let mut gui = storage::get_mut::<EditorGui>();
+        gui.add_window(ConfirmDialog::new(
+            vec2(Self::QUIT_DIALOG_WIDTH, Self::QUIT_DIALOG_HEIGHT),
+            &["There are unsaved changes to the map.", "Quit anyway?"],
+            EditorAction::ConfirmQuit,
+        ));
+
+        QuitResponse::Defer
+    }
+
     #[allow(dead_code)]
     fn get_selected_tile(&self) -> Option<(String, u32)> {
         if let Some(tileset_id) = self.selected_tileset.clone() {
@@ -280,47 +838,387 @@ impl Editor {
             selected_layer: self.selected_layer.clone(),
             selected_tileset: self.selected_tileset.clone(),
             selected_tile: self.selected_tile,
-            selected_object: self.selected_object,
+            picked_tile_attributes: self.picked_tile_attributes.clone(),
+            selected_object: self.selected_object.clone(),
+            selected_map_tile_index: self.selected_map_tile_index,
+            selected_region: self.selected_region,
+            selected_prefab: self.selected_prefab.clone(),
+            selected_stamp: self.selected_stamp.clone(),
+            soloed_layer: self.soloed_layer.clone(),
             cursor_position: self.cursor_position,
             is_user_map: self.map_resource.meta.is_user_map,
             is_tiled_map: self.map_resource.meta.is_tiled_map,
-            should_snap_to_grid: self.should_snap_to_grid,
+            map_requirements: self.map_resource.meta.requirements.clone(),
+            should_snap_to_grid: self.get_snap_to_grid(self.selected_layer.as_deref()),
+            snap_increment: self.snap_increment,
+            grid_origin_offset: config().editor.grid_origin_offset,
         }
     }
 
-    fn update_context(&mut self) {
-        if let Some(layer_id) = &self.selected_layer {
-            if !self.get_map().draw_order.contains(layer_id) {
-                self.selected_layer = None;
+    // This snaps `position` to the grid, with the current snap increment, offset by the grid origin
+    // offset from the editor config, so that it lines up with the grid that is drawn
+    fn snap_position(&self, position: Vec2) -> Vec2 {
+        self.get_map().snap_position_with_origin(
+            position,
+            self.snap_increment.as_f32(),
+            config().editor.grid_origin_offset,
+        )
+    }
+
+    // This returns the position, in world space, that the dragged object at `index`, in
+    // `layer_id`, would be dropped at, if released at the current cursor position, along with the
+    // smart guides of any objects it has been aligned to. Grid snapping takes precedence over
+    // object snapping, so objects are only aligned to each other when the layer does not snap.
+    // The position is clamped to the map bounds after it has been snapped, so that snapping can
+    // not move objects outside of the map.
+    fn get_dragged_object_position(
+        &self,
+        layer_id: &str,
+        index: usize,
+        click_offset: Vec2,
+    ) -> (Vec2, Vec<SmartGuide>) {
+        let map = self.get_map();
+
+        let mut position = to_world_space(self.cursor_position - click_offset);
+
+        let mut guides = Vec::new();
+
+        if self.get_snap_to_grid(Some(layer_id)) {
+            position = self.snap_position(position);
+        } else if config().editor.should_snap_to_objects {
+            let get_rect = |position: Vec2, object: &MapObject| get_object_rect(object, position);
+
+            let object = map.get_object(layer_id, index);
+            let group_indices = &map.get_object_group_indices(layer_id, index);
+
+            let others = map
+                .draw_order
+                .iter()
+                .filter_map(|id| map.layers.get(id))
+                .filter(|layer| {
+                    layer.kind == MapLayerKind::ObjectLayer && self.is_layer_drawn(layer)
+                })
+                .flat_map(|layer| {
+                    layer
+                        .objects
+                        .iter()
+                        .enumerate()
+                        .filter(move |(i, _)| layer.id != layer_id || !group_indices.contains(i))
+                        .map(|(_, object)| get_rect(map.world_offset + object.position, object))
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(object) = object {
+                let (offset, res) = snap_to_rects(
+                    get_rect(position, object),
+                    &others,
+                    config().editor.object_snap_threshold,
+                );
+
+                position += offset;
+                guides = res;
             }
-        } else if let Some(layer_id) = self.get_map().draw_order.first() {
-            self.selected_layer = Some(layer_id.clone());
         }
 
-        if let Some(layer_id) = &self.selected_layer {
-            let layer = self.get_map().layers.get(layer_id).unwrap();
+        let clamped = position.clamp(
+            map.world_offset,
+            map.world_offset + (UVec2::from(map.grid_size).as_f32() * Vec2::from(map.tile_size)),
+        );
+
+        // Guides along an axis that the clamping moved the object on no longer line up with it
+        guides.retain(|guide| {
+            if guide.start.x == guide.end.x {
+                clamped.x == position.x
+            } else {
+                clamped.y == position.y
+            }
+        });
+
+        (clamped, guides)
+    }
+
+    // This returns the selected tile in the map, if any, which is in the selected layer
+    fn get_selected_map_tile(&self) -> Option<&MapTile> {
+        let index = self.selected_map_tile_index?;
+        let layer_id = self.selected_layer.as_ref()?;
 
-            match layer.kind {
-                MapLayerKind::TileLayer => {
-                    self.selected_object = None;
+        self.get_map()
+            .layers
+            .get(layer_id)
+            .and_then(|layer| layer.tiles.get(index))
+            .and_then(|tile| tile.as_ref())
+    }
+
+    // This moves the spawn points to `positions`, which should have one position for every
+    // spawn point, in the same order, as a single undoable action
+    fn move_spawn_points(&mut self, positions: Vec<Vec2>) -> Result<()> {
+        let actions = positions
+            .into_iter()
+            .enumerate()
+            .filter(|(i, position)| self.get_map().spawn_points[*i].position != *position)
+            .map(|(i, position)| {
+                Box::new(MoveSpawnPointAction::new(i, position)) as Box<dyn UndoableAction>
+            })
+            .collect::<Vec<_>>();
+
+        let cnt = actions.len();
+
+        if cnt == 0 {
+            self.show_info_message("The spawn points are already arranged".to_string());
+            return Ok(());
+        }
+
+        let action = BatchedAction::new(actions);
+        let res = self
+            .history
+            .apply(Box::new(action), &mut self.map_resource.map);
+
+        self.show_info_message(format!("Moved {} spawn points", cnt));
+
+        res
+    }
+
+    // This shows `message` in the info message overlay and adds it to the message log
+    fn show_info_message(&mut self, message: String) {
+        // The log is not set up when the editor is created without its GUI, like in tests
+        if let Some(mut message_log) = storage::try_get_mut::<MessageLog>() {
+            message_log.push(message.clone());
+        }
+
+        self.info_message = Some(message);
+    }
+
+    fn get_selection(&self) -> EditorSelection {
+        EditorSelection {
+            tool: self.selected_tool,
+            layer: self.selected_layer.clone(),
+            tileset: self.selected_tileset.clone(),
+            tile: self.selected_tile,
+        }
+    }
+
+    fn set_selection(&mut self, selection: EditorSelection) {
+        self.selected_tool = selection.tool;
+        self.selected_layer = selection.layer;
+        self.selected_tileset = selection.tileset;
+        self.selected_tile = selection.tile;
+    }
+
+    // This will record a change of selection in history, if this is enabled in config.
+    // Call this after applying the change, with the selection from before it was applied.
+    fn record_selection(&mut self, old_selection: EditorSelection) -> Result<()> {
+        let selection = self.get_selection();
+
+        if config().editor.should_record_selection_history && selection != old_selection {
+            let action = SelectionAction::new(old_selection, selection);
+            return self
+                .history
+                .apply_selection(Box::new(action), &mut self.map_resource.map);
+        }
+
+        Ok(())
+    }
+
+    // This returns the min and max camera scale for the map, in that order. At min scale, the
+    // whole map will be visible and at max scale, a fixed number of tiles will be visible.
+    fn get_camera_zoom_bounds(map: &Map, viewport_size: Size<f32>) -> (f32, f32) {
+        let map_size = map.get_size();
+
+        let min = (viewport_size.width / map_size.width)
+            .min(viewport_size.height / map_size.height)
+            * Self::CAMERA_ZOOM_OUT_MAP_FRACTION;
+
+        let max = (viewport_size.width / map.tile_size.width)
+            .min(viewport_size.height / map.tile_size.height)
+            / Self::CAMERA_ZOOM_IN_VISIBLE_TILES;
+
+        (min, max.max(min))
+    }
+
+    // This returns the snap to grid override of the specified layer, falling back to the global
+    // setting if the layer has no override, or if no layer is specified
+    fn get_snap_to_grid(&self, layer_id: Option<&str>) -> bool {
+        layer_id
+            .and_then(|layer_id| self.get_map().layers.get(layer_id))
+            .and_then(|layer| layer.should_snap_to_grid)
+            .unwrap_or(self.should_snap_to_grid)
+    }
+
+    fn is_layer_locked(&self, layer_id: &str) -> bool {
+        self.get_map()
+            .layers
+            .get(layer_id)
+            .map(|layer| layer.is_locked)
+            .unwrap_or(false)
+    }
+
+    fn is_layer_drawn(&self, layer: &MapLayer) -> bool {
+        match &self.soloed_layer {
+            Some(soloed_layer) => layer.id == *soloed_layer,
+            None => layer.is_visible,
+        }
+    }
+
+    // This returns the index of the selected spawn point, if the cursor is over its facing handle
+    fn get_hovered_facing_handle(&self) -> Option<usize> {
+        let index = self.selected_spawn_point?;
+        let map = self.get_map();
+        let spawn_point = map.spawn_points.get(index)?;
+
+        let rect = get_spawn_point_rect(
+            map.world_offset + spawn_point.position,
+            self.player_collider_size,
+        );
+        let rect = get_spawn_point_facing_handle_rect(rect, spawn_point.is_facing_left);
+
+        if rect.contains(to_world_space(self.cursor_position)) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    // This returns what is under the cursor, checking objects first, spawn points second and
+    // tiles last. The selected layer is checked before any other layers. Locked layers are
+    // skipped.
+    fn get_hovered_item(&self, cursor_position: Vec2) -> Option<HoveredItem> {
+        let map = self.get_map();
+        let cursor_world_position = to_world_space(cursor_position);
+
+        let mut layer_ids = map
+            .draw_order
+            .iter()
+            .rev()
+            .filter(|id| !self.is_layer_locked(id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if let Some(selected_layer_id) = &self.selected_layer {
+            if let Some(i) = layer_ids.iter().position(|id| id == selected_layer_id) {
+                let id = layer_ids.remove(i);
+                layer_ids.insert(0, id);
+            }
+        }
+
+        for layer_id in &layer_ids {
+            let layer = map.layers.get(layer_id).unwrap();
+            if layer.kind == MapLayerKind::ObjectLayer {
+                for (index, object) in layer.objects.iter().enumerate() {
+                    let position = object.position + map.world_offset;
+
+                    let rect = get_object_rect(object, position);
+
+                    if rect.contains(cursor_world_position) {
+                        return Some(HoveredItem::Object {
+                            layer_id: layer_id.clone(),
+                            index,
+                        });
+                    }
                 }
-                MapLayerKind::ObjectLayer => {
-                    self.selected_tileset = None;
-                    self.selected_tile = None;
+            }
+        }
+
+        for (i, spawn_point) in map.spawn_points.iter().enumerate() {
+            let rect = get_spawn_point_rect(
+                map.world_offset + spawn_point.position,
+                self.player_collider_size,
+            );
+
+            if rect.contains(cursor_world_position) {
+                return Some(HoveredItem::SpawnPoint(i));
+            }
+        }
+
+        if map.contains(cursor_world_position) {
+            let coords = map.to_coords(cursor_world_position);
+
+            for layer_id in &layer_ids {
+                let layer = map.layers.get(layer_id).unwrap();
+                if layer.kind == MapLayerKind::TileLayer
+                    && map.get_tile(layer_id, coords.x, coords.y).is_some()
+                {
+                    return Some(HoveredItem::Tile {
+                        layer_id: layer_id.clone(),
+                        index: map.to_index(coords),
+                    });
                 }
             }
         }
 
-        if let Some(tileset_id) = &self.selected_tileset {
-            if let Some(tileset) = self.get_map().tilesets.get(tileset_id) {
-                if let Some(tile_id) = self.selected_tile {
-                    if tile_id >= tileset.tile_cnt {
-                        self.selected_tile = None;
-                    }
+        None
+    }
+
+    // This returns the layer that objects should be pasted into. This is the selected layer, if
+    // it is an object layer, or the first object layer in the draw order, if it is not.
+    fn get_paste_layer_id(&self) -> Option<String> {
+        let map = self.get_map();
+
+        if let Some(layer_id) = &self.selected_layer {
+            if map.get_layer_kind(layer_id) == Some(MapLayerKind::ObjectLayer) {
+                return Some(layer_id.clone());
+            }
+        }
+
+        map.draw_order
+            .iter()
+            .find(|id| map.get_layer_kind(id) == Some(MapLayerKind::ObjectLayer))
+            .cloned()
+    }
+
+    // This returns the id of the layer that the specified layer would be merged into. The first
+    // layer in the draw order is drawn on top, so the layer beneath is the next one.
+    fn get_merge_down_target_id(&self, layer_id: &str) -> Option<String> {
+        let map = self.get_map();
+
+        map.draw_order
+            .iter()
+            .position(|id| id == layer_id)
+            .and_then(|i| map.draw_order.get(i + 1))
+            .cloned()
+    }
+
+    // This returns the id of a locked layer that `action` would change, if any. Layers that an
+    // action only finds when it is applied are found here the same way.
+    fn get_locked_layer_id(&self, action: &EditorAction) -> Option<String> {
+        let mut layer_ids = action
+            .get_layer_ids()
+            .into_iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>();
+
+        match action {
+            EditorAction::MergeLayerDown(id) => {
+                layer_ids.extend(self.get_merge_down_target_id(id));
+            }
+            EditorAction::PasteObjects(_) if !self.clipboard.is_empty() => {
+                layer_ids.extend(self.get_paste_layer_id());
+            }
+            // A prefab can span several layers, so it is not stamped if any of them is locked
+            EditorAction::StampPrefab { id, .. } => {
+                if let Some(prefab) = try_get_library_asset::<Prefab>(id) {
+                    layer_ids.extend(prefab.get_layer_ids());
                 }
-            } else {
-                self.selected_tileset = None;
-                self.selected_tile = None;
+            }
+            _ => {}
+        }
+
+        layer_ids.into_iter().find(|id| self.is_layer_locked(id))
+    }
+
+    fn update_context(&mut self) {
+        let mut ctx = self.get_context();
+        ctx.validate_selection(self.get_map());
+
+        self.selected_layer = ctx.selected_layer;
+        self.selected_object = ctx.selected_object;
+        self.selected_map_tile_index = ctx.selected_map_tile_index;
+        self.selected_tileset = ctx.selected_tileset;
+        self.selected_tile = ctx.selected_tile;
+        self.soloed_layer = ctx.soloed_layer;
+
+        if let Some(index) = self.selected_spawn_point {
+            if index >= self.get_map().spawn_points.len() {
+                self.selected_spawn_point = None;
             }
         }
 
@@ -339,6 +1237,8 @@ impl Editor {
         self.selected_tileset = None;
         self.selected_tile = None;
         self.selected_object = None;
+        self.selected_region = None;
+        self.soloed_layer = None;
     }
 
     fn select_tileset(&mut self, tileset_id: &str, tile_id: Option<u32>) {
@@ -361,6 +1261,13 @@ impl Editor {
     fn apply_action(&mut self, action: EditorAction) {
         //println!("Action: {:?}", action);
 
+        // Actions that would change a locked layer are discarded here, so that this applies to
+        // actions from tools, windows and keyboard shortcuts alike
+        if let Some(layer_id) = self.get_locked_layer_id(&action) {
+            self.show_info_message(format!("Layer '{}' is locked", layer_id));
+            return;
+        }
+
         let mut res = Ok(());
 
         match action {
@@ -369,14 +1276,42 @@ impl Editor {
                     self.apply_action(action)
                 }
             }
+            // Changes are only highlighted if something was actually undone or redone, as the
+            // top of the other stack is otherwise unrelated to the current action
             EditorAction::Undo => {
-                res = self.history.undo(&mut self.map_resource.map);
+                if self.history.can_undo() {
+                    res = self
+                        .history
+                        .undo(&mut self.map_resource.map)
+                        .map(|selection| {
+                            if let Some(selection) = selection {
+                                self.set_selection(selection);
+                            }
+                        });
+
+                    let rect = self.history.get_undone_rect(self.get_map());
+                    self.highlight_change(rect);
+                }
             }
             EditorAction::Redo => {
-                res = self.history.redo(&mut self.map_resource.map);
+                if self.history.can_redo() {
+                    res = self
+                        .history
+                        .redo(&mut self.map_resource.map)
+                        .map(|selection| {
+                            if let Some(selection) = selection {
+                                self.set_selection(selection);
+                            }
+                        });
+
+                    let rect = self.history.get_redone_rect(self.get_map());
+                    self.highlight_change(rect);
+                }
             }
             EditorAction::SelectTool(id) => {
+                let old_selection = self.get_selection();
                 self.selected_tool = id;
+                res = self.record_selection(old_selection);
             }
             EditorAction::UpdateBackground { color, layers } => {
                 let action = UpdateBackgroundAction::new(color, layers);
@@ -417,12 +1352,87 @@ impl Editor {
                 let mut gui = storage::get_mut::<EditorGui>();
                 gui.add_window(TilePropertiesWindow::new(layer_id, index))
             }
+            EditorAction::OpenSpawnPointPropertiesWindow(index) => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(SpawnPointPropertiesWindow::new(index))
+            }
+            EditorAction::ToggleMessageLogWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                if gui.has_window::<MessageLogWindow>() {
+                    gui.remove_window::<MessageLogWindow>();
+                } else {
+                    gui.add_window(MessageLogWindow::new());
+                }
+            }
+            EditorAction::ClearMessageLog => {
+                storage::get_mut::<MessageLog>().clear();
+            }
+            EditorAction::OpenKeybindsWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(KeybindsWindow::new());
+            }
+            EditorAction::SetKeybind { name, keybind } => {
+                let keybinds = &mut config_mut().editor.keybinds;
+
+                let conflicts = keybinds.find_conflicts(&name, keybind);
+
+                if keybinds.set(&name, keybind) {
+                    self.has_unsaved_config_changes = true;
+
+                    let message = if conflicts.is_empty() {
+                        format!("Bound '{}' to {}", name, keybind)
+                    } else {
+                        format!(
+                            "Bound '{}' to {} (also bound to: {})",
+                            name,
+                            keybind,
+                            conflicts.join(", ")
+                        )
+                    };
+
+                    self.show_info_message(message);
+                }
+            }
+            EditorAction::ResetKeybinds => {
+                config_mut().editor.keybinds = EditorKeybinds::default();
+                self.has_unsaved_config_changes = true;
+                self.show_info_message("Keybinds reset to defaults".to_string());
+            }
             EditorAction::CloseWindow(id) => {
                 let mut gui = storage::get_mut::<EditorGui>();
                 gui.remove_window_id(id);
             }
             EditorAction::SelectTile { id, tileset_id } => {
+                let old_selection = self.get_selection();
+                self.select_tileset(&tileset_id, Some(id));
+                res = self.record_selection(old_selection);
+            }
+            EditorAction::PickTile {
+                id,
+                tileset_id,
+                attributes,
+            } => {
+                let old_selection = self.get_selection();
                 self.select_tileset(&tileset_id, Some(id));
+                res = self.record_selection(old_selection);
+
+                if config().editor.should_pick_tile_attributes {
+                    self.show_info_message(format!(
+                        "Picked tile {} ({}): {}",
+                        id,
+                        tileset_id,
+                        format_tile_attributes(&attributes),
+                    ));
+
+                    self.picked_tile_attributes = attributes;
+                } else {
+                    self.show_info_message(format!("Picked tile {} ({})", id, tileset_id));
+
+                    self.picked_tile_attributes.clear();
+                }
+            }
+            EditorAction::ClearPickedTileAttributes => {
+                self.picked_tile_attributes.clear();
             }
             EditorAction::UpdateTileAttributes {
                 index,
@@ -434,9 +1444,58 @@ impl Editor {
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
+            EditorAction::OpenTileAttributesWindow { layer_id, region } => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(TileAttributesWindow::new(layer_id, region));
+            }
+            EditorAction::UpdateTileAttributesInRegion {
+                layer_id,
+                region,
+                set,
+                unset,
+            } => {
+                let map = self.get_map();
+
+                let mut actions: Vec<Box<dyn UndoableAction>> = Vec::new();
+
+                if map.get_layer_kind(&layer_id) == Some(MapLayerKind::TileLayer) {
+                    for (x, y, tile) in map.get_tiles(&layer_id, Some(region)) {
+                        if let Some(tile) = tile {
+                            let attributes =
+                                apply_attribute_changes(&tile.attributes, &set, &unset);
+
+                            if attributes != tile.attributes {
+                                let index = map.to_index(uvec2(x, y));
+                                let action = UpdateTileAttributesAction::new(
+                                    index,
+                                    layer_id.clone(),
+                                    attributes,
+                                );
+
+                                actions.push(Box::new(action));
+                            }
+                        }
+                    }
+                }
+
+                let cnt = actions.len();
+
+                if cnt == 0 {
+                    self.show_info_message("No tile attributes were changed".to_string());
+                } else {
+                    let action = BatchedAction::new(actions);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    self.show_info_message(format!("Updated the attributes of {} tiles", cnt));
+                }
+            }
             EditorAction::SelectLayer(id) => {
                 if self.get_map().layers.contains_key(&id) {
+                    let old_selection = self.get_selection();
                     self.selected_layer = Some(id);
+                    res = self.record_selection(old_selection);
                 }
             }
             EditorAction::SetLayerDrawOrderIndex { id, index } => {
@@ -457,19 +1516,215 @@ impl Editor {
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
             EditorAction::DeleteLayer(id) => {
+                if self.soloed_layer.as_ref() == Some(&id) {
+                    self.soloed_layer = None;
+                }
+
                 let action = DeleteLayerAction::new(id);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
-            EditorAction::UpdateLayer { id, is_visible } => {
-                let action = UpdateLayerAction::new(id, is_visible);
+            EditorAction::MergeLayerDown(id) => {
+                let map = self.get_map();
+
+                if let Some(target_id) = self.get_merge_down_target_id(&id) {
+                    // Overlapping tiles are replaced by the tiles of the upper layer, so the
+                    // number of them is reported, in case that was not intended
+                    let overlapping_cnt = map
+                        .layers
+                        .get(&id)
+                        .zip(map.layers.get(&target_id))
+                        .map(|(layer, target)| target.get_overlapping_tile_cnt(layer))
+                        .unwrap_or(0);
+
+                    if self.soloed_layer.as_ref() == Some(&id) {
+                        self.soloed_layer = None;
+                    }
+
+                    let action = MergeLayerDownAction::new(id.clone(), target_id.clone());
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    if res.is_ok() {
+                        self.selected_layer = Some(target_id.clone());
+
+                        if overlapping_cnt > 0 {
+                            self.show_info_message(format!(
+                                "Merged {} into {}, replacing {} overlapping tiles",
+                                id, target_id, overlapping_cnt
+                            ));
+                        } else {
+                            self.show_info_message(format!("Merged {} into {}", id, target_id));
+                        }
+                    }
+                }
+            }
+            EditorAction::ConvertLayer {
+                id,
+                kind,
+                has_collision,
+            } => {
+                let err = self.get_map().layers.get(&id).and_then(|layer| {
+                    ConvertLayerAction::get_conversion_error(layer, kind, has_collision)
+                });
+
+                if let Some(err) = err {
+                    self.show_info_message(format!("Unable to convert layer: {}", err));
+                } else {
+                    let action = ConvertLayerAction::new(id.clone(), kind, has_collision);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    if res.is_ok() {
+                        let kind_label = match kind {
+                            MapLayerKind::TileLayer if has_collision => {
+                                "a tile layer with collision"
+                            }
+                            MapLayerKind::TileLayer => "a tile layer without collision",
+                            MapLayerKind::ObjectLayer => "an object layer",
+                        };
+
+                        self.show_info_message(format!("Converted {} to {}", id, kind_label));
+                    }
+                }
+            }
+            EditorAction::UpdateLayer {
+                id,
+                is_visible,
+                is_locked,
+            } => {
+                let action = UpdateLayerAction::new(id, is_visible, is_locked);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::OpenLayerGroupWindow(id) => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(LayerGroupWindow::new(id));
+            }
+            EditorAction::SetLayerGroup { id, group } => {
+                let action = SetLayerGroupAction::new(id, group);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::OpenLayerTintWindow(id) => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(LayerTintWindow::new(id));
+            }
+            EditorAction::SetLayerTint { id, tint } => {
+                let action = SetLayerTintAction::new(id, tint);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetLayerGroupVisibility { group, is_visible } => {
+                let map = self.get_map();
+
+                let actions = map
+                    .get_group_layer_ids(&group)
+                    .into_iter()
+                    .filter_map(|id| {
+                        let layer = map.layers.get(&id)?;
+
+                        if layer.is_visible == is_visible {
+                            return None;
+                        }
+
+                        let action = UpdateLayerAction::new(id, is_visible, layer.is_locked);
+                        Some(Box::new(action) as Box<dyn UndoableAction>)
+                    })
+                    .collect::<Vec<_>>();
+
+                if !actions.is_empty() {
+                    let action = BatchedAction::new(actions);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+                }
+            }
+            EditorAction::SetLayerSnapToGrid {
+                id,
+                should_snap_to_grid,
+            } => {
+                let action = SetLayerSnapToGridAction::new(id, should_snap_to_grid);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
+            EditorAction::ToggleSoloLayer => {
+                if self.soloed_layer.is_some() {
+                    self.soloed_layer = None;
+                    self.show_info_message("Showing all layers".to_string());
+                } else if let Some(layer_id) = self.selected_layer.clone() {
+                    self.show_info_message(format!("Soloing layer '{}'", layer_id));
+                    self.soloed_layer = Some(layer_id);
+                }
+            }
+            EditorAction::OpenShiftLayerWindow(id) => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(ShiftLayerWindow::new(&id));
+            }
+            EditorAction::ShiftLayer { id, offset, wrap } => {
+                let action = ShiftLayerAction::new(id, offset, wrap);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::OpenResizeMapWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(ResizeMapWindow::new());
+            }
+            EditorAction::OpenMapPropertiesWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(MapPropertiesWindow::new());
+            }
+            EditorAction::SetWorldOffset(world_offset) => {
+                let action = SetWorldOffsetAction::new(world_offset);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetAmbientColor(color) => {
+                let action = SetAmbientColorAction::new(color);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetMapMusic(music_id) => {
+                let action = SetMapMusicAction::new(music_id);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetMapRequirements(requirements) => {
+                if requirements != self.map_resource.meta.requirements {
+                    self.map_resource.meta.requirements = requirements;
+                    self.has_unsaved_meta_changes = true;
+                }
+            }
+            EditorAction::ResizeMap { grid_size, anchor } => {
+                let clamped_cnt = self.get_map().get_resize_clamped_cnt(grid_size, anchor);
+
+                let action = ResizeMapAction::new(grid_size, anchor);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+
+                if res.is_ok() && clamped_cnt > 0 {
+                    self.show_info_message(format!(
+                        "{} object(s) and spawn point(s) outside of the new bounds were moved to the map edges",
+                        clamped_cnt
+                    ));
+                }
+            }
             EditorAction::SelectTileset(id) => {
+                let old_selection = self.get_selection();
                 self.select_tileset(&id, None);
+                res = self.record_selection(old_selection);
             }
             EditorAction::CreateTileset { id, texture_id } => {
                 let action = CreateTilesetAction::new(id, texture_id);
@@ -494,8 +1749,8 @@ impl Editor {
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
             EditorAction::SelectObject { index, layer_id } => {
-                self.selected_layer = Some(layer_id);
-                self.selected_object = Some(index);
+                self.selected_layer = Some(layer_id.clone());
+                self.selected_object = Some(SelectedObject { layer_id, index });
             }
             EditorAction::CreateObject {
                 id,
@@ -503,29 +1758,202 @@ impl Editor {
                 position,
                 layer_id,
             } => {
-                let action = CreateObjectAction::new(id, kind, position, layer_id);
+                let action = CreateObjectAction::new(id, kind, position, layer_id);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::DeleteObject { index, layer_id } => {
+                let action = DeleteObjectAction::new(index, layer_id);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::MoveObjectToLayer {
+                from_layer,
+                index,
+                to_layer,
+            } => {
+                if self.get_map().get_layer_kind(&to_layer) == Some(MapLayerKind::ObjectLayer) {
+                    let action = MoveObjectToLayerAction::new(from_layer, index, to_layer);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    if res.is_ok() {
+                        self.selected_object = None;
+                    }
+                } else {
+                    self.show_info_message(format!(
+                        "Unable to move object: '{}' is not an object layer",
+                        to_layer
+                    ));
+                }
+            }
+            EditorAction::ReorderObject {
+                layer_id,
+                index,
+                new_index,
+            } => {
+                let selected_object = SelectedObject {
+                    layer_id: layer_id.clone(),
+                    index,
+                };
+                let is_selected = self.selected_object.as_ref() == Some(&selected_object);
+
+                let action = ReorderObjectAction::new(layer_id, index, new_index);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+
+                // The selection follows the object, as it is referenced by index
+                if res.is_ok() && is_selected {
+                    self.selected_object = Some(SelectedObject {
+                        index: new_index,
+                        ..selected_object
+                    });
+                }
+            }
+            EditorAction::UpdateObject {
+                layer_id,
+                index,
+                id,
+                kind,
+                position,
+            } => {
+                let action = UpdateObjectAction::new(layer_id, index, id, kind, position);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetObjectColliderSize {
+                layer_id,
+                index,
+                collider_size,
+            } => {
+                let action = SetObjectColliderSizeAction::new(layer_id, index, collider_size);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
-            EditorAction::DeleteObject { index, layer_id } => {
-                let action = DeleteObjectAction::new(index, layer_id);
+            EditorAction::SetObjectScale {
+                layer_id,
+                index,
+                scale,
+            } => {
+                let action = SetObjectScaleAction::new(layer_id, index, scale);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
-            EditorAction::UpdateObject {
+            EditorAction::MoveObjects {
                 layer_id,
-                index,
-                id,
-                kind,
-                position,
+                indices,
+                offset,
             } => {
-                let action = UpdateObjectAction::new(layer_id, index, id, kind, position);
+                let actions = indices
+                    .into_iter()
+                    .filter_map(|index| {
+                        self.get_map()
+                            .get_object(&layer_id, index)
+                            .map(|object| (index, object))
+                    })
+                    .map(|(index, object)| {
+                        Box::new(UpdateObjectAction::new(
+                            layer_id.clone(),
+                            index,
+                            object.id.clone(),
+                            object.kind,
+                            object.position + offset,
+                        )) as Box<dyn UndoableAction>
+                    })
+                    .collect::<Vec<_>>();
+
+                let action = BatchedAction::new(actions);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::GroupObjects { layer_id, indices } => {
+                if indices.len() < 2 {
+                    self.show_info_message(
+                        "Unable to group objects: Select at least two objects".to_string(),
+                    );
+                } else {
+                    let cnt = indices.len();
+                    let group_id = self.get_map().get_unused_object_group_id();
+
+                    let action = SetObjectGroupAction::new(layer_id, indices, Some(group_id));
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    if res.is_ok() {
+                        self.show_info_message(format!("Grouped {} objects", cnt));
+                    }
+                }
+            }
+            EditorAction::UngroupObjects { layer_id, group_id } => {
+                let indices = self
+                    .get_map()
+                    .layers
+                    .get(&layer_id)
+                    .map(|layer| {
+                        layer
+                            .objects
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, object)| object.group_id.as_ref() == Some(&group_id))
+                            .map(|(i, _)| i)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+
+                let action = SetObjectGroupAction::new(layer_id, indices, None);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
+            EditorAction::CopyObjects { layer_id, indices } => {
+                self.clipboard
+                    .copy_objects(&self.map_resource.map, &layer_id, &indices);
+
+                let cnt = self.clipboard.objects.len();
+                self.show_info_message(format!("Copied {} object(s)", cnt));
+            }
+            EditorAction::PasteObjects(position) => {
+                if !self.clipboard.is_empty() {
+                    match self.get_paste_layer_id() {
+                        None => {
+                            self.show_info_message(
+                                "Paste: There is no object layer to paste into".to_string(),
+                            );
+                        }
+                        Some(layer_id) => {
+                            let actions = self
+                                .clipboard
+                                .get_paste_objects(self.get_map(), position)
+                                .into_iter()
+                                .map(|object| {
+                                    let action =
+                                        CreateObjectAction::from_object(object, layer_id.clone());
+
+                                    Box::new(action) as Box<dyn UndoableAction>
+                                })
+                                .collect();
+
+                            let action = BatchedAction::new(actions);
+                            res = self
+                                .history
+                                .apply(Box::new(action), &mut self.map_resource.map);
+
+                            if self.selected_layer.as_ref() != Some(&layer_id) {
+                                self.show_info_message(format!("Pasted into layer '{}'", layer_id));
+                            }
+                        }
+                    }
+                }
+            }
             EditorAction::CreateSpawnPoint(position) => {
                 let action = CreateSpawnPointAction::new(position);
                 res = self
@@ -544,13 +1972,46 @@ impl Editor {
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
             }
+            EditorAction::OpenArrangeSpawnPointsWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(ArrangeSpawnPointsWindow::new());
+            }
+            EditorAction::DistributeSpawnPoints { y } => {
+                // Spawn points are centered horizontally on their position, so they have no
+                // width to account for here
+                let positions = self.get_map().get_distributed_spawn_positions(y, 0.0);
+
+                res = self.move_spawn_points(positions);
+            }
+            EditorAction::MirrorSpawnPoints => {
+                let positions = self.get_map().get_mirrored_spawn_positions(0.0);
+
+                res = self.move_spawn_points(positions);
+            }
+            EditorAction::SetSpawnPointFacing {
+                index,
+                is_facing_left,
+            } => {
+                let action = SetSpawnPointFacingAction::new(index, is_facing_left);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
+            EditorAction::SetSpawnPointTag { index, tag } => {
+                let action = SetSpawnPointTagAction::new(index, tag);
+                res = self
+                    .history
+                    .apply(Box::new(action), &mut self.map_resource.map);
+            }
             EditorAction::PlaceTile {
                 id,
                 layer_id,
                 tileset_id,
                 coords,
+                attributes,
             } => {
-                let action = PlaceTileAction::new(id, layer_id, tileset_id, coords);
+                let action = PlaceTileAction::new(id, layer_id, tileset_id, coords)
+                    .with_attributes(attributes);
                 res = self
                     .history
                     .apply(Box::new(action), &mut self.map_resource.map);
@@ -586,7 +2047,10 @@ impl Editor {
                     Err(err) => println!("Create Map: {}", err),
                     Ok(map_resource) => {
                         self.map_resource = map_resource;
+                        self.preview_image = None;
+                        self.pending_preview = None;
                         self.history.clear();
+                        self.has_unsaved_meta_changes = false;
                         self.clear_context();
                     }
                 }
@@ -597,20 +2061,29 @@ impl Editor {
             }
             EditorAction::OpenMap(index) => {
                 self.map_resource = get_map(index).clone();
+                self.preview_image = None;
+                self.pending_preview = None;
                 self.history.clear();
+                self.has_unsaved_meta_changes = false;
                 self.clear_context();
+
+                let path = Path::new(&assets_dir()).join(&self.map_resource.meta.path);
+                dispatch_event(Event::MapOpened(path));
             }
             EditorAction::OpenLoadMapWindow => {
                 let mut gui = storage::get_mut::<EditorGui>();
                 gui.add_window(LoadMapWindow::new());
             }
-            EditorAction::SaveMap(name) => {
+            EditorAction::SaveMap(_) if self.pending_save.is_some() => {
+                self.show_info_message("The map is already being saved".to_string());
+            }
+            EditorAction::SaveMap(export) => {
                 let mut map_resource = self.map_resource.clone();
 
-                if let Some(name) = name {
+                if let Some((name, extension)) = export {
                     let path = Path::new(MAP_EXPORTS_DEFAULT_DIR)
                         .join(map_name_to_filename(&name))
-                        .with_extension(MAP_EXPORTS_EXTENSION);
+                        .with_extension(extension);
 
                     map_resource.meta.name = name;
                     map_resource.meta.path = path.to_string_lossy().to_string();
@@ -619,23 +2092,342 @@ impl Editor {
                 map_resource.meta.is_user_map = true;
                 map_resource.meta.is_tiled_map = false;
 
-                if save_map(&map_resource).is_ok() {
-                    self.map_resource = map_resource;
+                let mut preview = None;
+
+                if let Some(image) = &self.preview_image {
+                    let preview_path = Path::new(&map_resource.meta.path)
+                        .with_extension(MAP_PREVIEW_EXTENSION)
+                        .to_string_lossy()
+                        .to_string();
+
+                    let export_path = Path::new(&assets_dir()).join(&preview_path);
+                    preview = Some((image.clone(), export_path.to_string_lossy().to_string()));
+
+                    map_resource.meta.preview_path = preview_path;
+                    map_resource.meta.preview_format = Some(TextureFormat::Png);
+                }
+
+                let save_res = Arc::new(Mutex::new(None));
+
+                {
+                    let map_resource = map_resource.clone();
+                    let save_res = save_res.clone();
+
+                    start_coroutine(async move {
+                        let res = save_map_async(map_resource).await;
+
+                        // The preview is only written once the map has been saved, so that no
+                        // preview is left behind for a map that does not exist
+                        if res.is_ok() {
+                            if let Some((image, export_path)) = preview {
+                                image.export_png_async(&export_path).await;
+                            }
+                        }
+                        *save_res.lock().unwrap() = Some(res);
+                    });
                 }
+
+                self.pending_save = Some(PendingSave {
+                    original_path: self.map_resource.meta.path.clone(),
+                    save_point: self.history.get_save_point(),
+                    map_resource,
+                    res: save_res,
+                });
             }
             EditorAction::OpenSaveMapWindow => {
                 let mut gui = storage::get_mut::<EditorGui>();
-                gui.add_window(SaveMapWindow::new(&self.map_resource.meta.name));
+                let validation_error = validate_map_for_save(&self.map_resource)
+                    .err()
+                    .map(|err| err.to_string());
+
+                let format = get_map_format_of_path(&self.map_resource.meta.path)
+                    .unwrap_or_else(default_map_format);
+
+                gui.add_window(SaveMapWindow::new(
+                    &self.map_resource.meta.name,
+                    format.extension(),
+                    validation_error,
+                ));
+            }
+            EditorAction::RegeneratePreview => {
+                self.pending_preview = Some(render_map_preview(self.get_map()));
+            }
+            EditorAction::SelectRegion(region) => {
+                self.selected_region = region;
+            }
+            EditorAction::OpenSavePrefabWindow(region) => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(SavePrefabWindow::new(region));
+            }
+            EditorAction::SavePrefab { id, region } => {
+                let prefab = Prefab::from_region(&id, self.get_map(), region);
+
+                match save_library_asset(prefab) {
+                    Err(err) => println!("Save Prefab: {}", err),
+                    Ok(()) => {
+                        self.show_info_message(format!("Saved prefab '{}'", id));
+                    }
+                }
+            }
+            EditorAction::OpenPrefabListWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(PrefabListWindow::new());
+            }
+            EditorAction::SelectPrefab(id) => {
+                self.selected_prefab = Some(id);
+
+                let tool_id = TypeId::of::<PrefabPlacementTool>();
+                self.apply_action(EditorAction::SelectTool(Some(tool_id)));
+            }
+            EditorAction::StampPrefab { id, coords } => {
+                if let Some(prefab) = try_get_library_asset::<Prefab>(&id) {
+                    let action = prefab.get_stamp_action(self.get_map(), coords);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+                } else {
+                    println!("Stamp Prefab: No prefab with id '{}'", id);
+                }
+            }
+            EditorAction::CopyRegionToClipboard(region) => {
+                let region_text = RegionText::from_region(self.get_map(), region);
+
+                match region_text.to_text() {
+                    Err(err) => println!("Copy Region: {}", err),
+                    Ok(text) => {
+                        set_clipboard_text(&text);
+
+                        self.show_info_message(format!(
+                            "Copied {}x{} region to clipboard",
+                            region.width, region.height
+                        ));
+                    }
+                }
+            }
+            EditorAction::PasteRegionFromClipboard(coords) => {
+                let text = get_clipboard_text().unwrap_or_default();
+
+                match RegionText::from_text(&text) {
+                    Err(err) => {
+                        self.show_info_message(format!("Unable to paste region: {}", err));
+                    }
+                    Ok(region_text) => {
+                        let map = self.get_map();
+
+                        // The region is only known once the clipboard has been read, so it is
+                        // checked for locked layers here. Like a prefab, it is not pasted if any
+                        // of its layers is locked.
+                        let locked_layer_id = region_text
+                            .prefab
+                            .get_layer_ids()
+                            .into_iter()
+                            .find(|layer_id| self.is_layer_locked(layer_id));
+
+                        if let Some(layer_id) = locked_layer_id {
+                            self.show_info_message(format!("Layer '{}' is locked", layer_id));
+                            return;
+                        }
+
+                        let action = region_text.prefab.get_stamp_action(map, coords);
+
+                        let warnings = region_text
+                            .get_layer_warnings(map)
+                            .into_iter()
+                            .chain(region_text.get_tileset_warnings(map))
+                            .collect::<Vec<_>>();
+
+                        // Nothing is added to the history if all of the region was skipped
+                        if action.is_empty() {
+                            self.show_info_message(
+                                "Nothing in the region could be pasted into this map".to_string(),
+                            );
+                        } else {
+                            res = self
+                                .history
+                                .apply(Box::new(action), &mut self.map_resource.map);
+                        }
+
+                        for warning in warnings {
+                            self.show_info_message(format!("WARNING: {}", warning));
+                        }
+                    }
+                }
+            }
+            EditorAction::OpenSaveStampWindow { layer_id, region } => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(SaveStampWindow::new(&layer_id, region));
+            }
+            EditorAction::SaveStamp {
+                id,
+                layer_id,
+                region,
+            } => {
+                let stamp = Stamp::from_region(&id, self.get_map(), &layer_id, region);
+
+                match save_library_asset(stamp) {
+                    Err(err) => println!("Save Stamp: {}", err),
+                    Ok(()) => {
+                        self.show_info_message(format!("Saved stamp '{}'", id));
+                    }
+                }
+            }
+            EditorAction::OpenStampPaletteWindow => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(StampPaletteWindow::new());
+            }
+            EditorAction::SelectStamp(id) => {
+                self.selected_stamp = Some(id);
+
+                let tool_id = TypeId::of::<StampTool>();
+                self.apply_action(EditorAction::SelectTool(Some(tool_id)));
+            }
+            EditorAction::PaintStamp {
+                id,
+                layer_id,
+                coords,
+            } => {
+                if let Some(stamp) = try_get_library_asset::<Stamp>(&id) {
+                    let action = stamp.get_paint_action(self.get_map(), &layer_id, coords);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+                } else {
+                    println!("Paint Stamp: No stamp with id '{}'", id);
+                }
+            }
+            EditorAction::OpenReplaceTileWindow { layer_id, from } => {
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(ReplaceTileWindow::new(layer_id, from));
+            }
+            EditorAction::ReplaceTile { layer_id, from, to } => {
+                let map = self.get_map();
+
+                // Locked layers are skipped when replacing in all layers
+                let layer_ids = match layer_id {
+                    Some(layer_id) => vec![layer_id],
+                    None => map
+                        .draw_order
+                        .iter()
+                        .filter(|id| !self.is_layer_locked(id))
+                        .cloned()
+                        .collect(),
+                };
+
+                let mut actions: Vec<Box<dyn UndoableAction>> = Vec::new();
+
+                for layer_id in layer_ids {
+                    if map.get_layer_kind(&layer_id) != Some(MapLayerKind::TileLayer) {
+                        continue;
+                    }
+
+                    for (x, y, tile) in map.iter_tiles(&layer_id) {
+                        if tile.tileset_id == from.0 && tile.tile_id == from.1 {
+                            let action = PlaceTileAction::new(
+                                to.1,
+                                layer_id.clone(),
+                                to.0.clone(),
+                                uvec2(x, y),
+                            );
+
+                            actions.push(Box::new(action));
+                        }
+                    }
+                }
+
+                let cnt = actions.len();
+
+                if cnt == 0 {
+                    self.show_info_message("No matching tiles to replace".to_string());
+                } else {
+                    let action = BatchedAction::new(actions);
+                    res = self
+                        .history
+                        .apply(Box::new(action), &mut self.map_resource.map);
+
+                    self.show_info_message(format!("Replaced {} tiles", cnt));
+                }
+            }
+            EditorAction::OpenDeleteMapDialog(index) => {
+                // The map is referred to by its path, as indices change if maps are added or
+                // removed while the dialog is open
+                let path = get_map(index).meta.path.clone();
+                let body = format!("Delete the map '{}'?", path);
+
+                let mut gui = storage::get_mut::<EditorGui>();
+                gui.add_window(ConfirmDialog::new(
+                    vec2(
+                        Self::DELETE_MAP_DIALOG_WIDTH,
+                        Self::DELETE_MAP_DIALOG_HEIGHT,
+                    ),
+                    &[&body, "This can not be undone."],
+                    EditorAction::DeleteMap(path),
+                ));
+            }
+            EditorAction::DeleteMap(path) => match delete_map(&path) {
+                Err(err) => {
+                    self.show_info_message(format!("Unable to delete map: {}", err));
+                }
+                Ok(()) => {
+                    self.show_info_message(format!("Deleted {}", path));
+                }
+            },
+            EditorAction::TestPlay => {
+                if self.map_resource.map.spawn_points.is_empty() {
+                    self.show_info_message(
+                        "The map needs at least one spawn point to test play".to_string(),
+                    );
+                } else {
+                    let map = self.map_resource.map.clone();
+
+                    match TestPlayState::new(map) {
+                        Ok(state) => {
+                            self.show_info_message(format!(
+                                "Press {} to return to the editor",
+                                editor_keybinds().test_play
+                            ));
+
+                            dispatch_event(Event::state_transition(state));
+                        }
+                        Err(err) => {
+                            self.show_info_message(format!("Unable to start test play: {}", err));
+                        }
+                    }
+                }
             }
-            EditorAction::DeleteMap(index) => {
-                delete_map(index).unwrap();
+            EditorAction::RevealMapInFolder => {
+                let path = Path::new(&assets_dir()).join(&self.map_resource.meta.path);
+
+                if !self.map_resource.meta.is_user_map || !path.exists() {
+                    self.show_info_message("The map has not been saved yet".to_string());
+                } else {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Err(err) = ff_core::file::reveal_in_file_browser(&path) {
+                        self.show_info_message(format!("Unable to open the map folder: {}", err));
+                    }
+
+                    #[cfg(target_arch = "wasm32")]
+                    self.show_info_message(
+                        "Opening the map folder is not supported on this platform".to_string(),
+                    );
+                }
             }
             EditorAction::ExitToMainMenu => {
+                remove_quit_handler(EDITOR_QUIT_HANDLER_ID);
+
+                self.is_exiting = true;
+
                 let state = MainMenuState::new();
-                dispatch_event(Event::state_transition(state));
+                dispatch_event(Event::state_transition_with(
+                    state,
+                    Transition::fade(Transition::DEFAULT_FADE_DURATION),
+                ));
             }
             EditorAction::QuitToDesktop => {
-                dispatch_event(Event::Quit);
+                dispatch_event(Event::QuitRequested);
+            }
+            EditorAction::ConfirmQuit => {
+                self.is_quit_confirmed = true;
+                dispatch_event(Event::QuitRequested);
             }
         }
 
@@ -649,6 +2441,19 @@ impl Editor {
 
 impl Node for Editor {
     fn update(mut node: RefMut<Self>) {
+        if is_test_playing() {
+            return;
+        }
+
+        if node.is_exiting {
+            if let Some(camera) = try_get_editor_camera() {
+                camera.delete();
+            }
+
+            node.delete();
+            return;
+        }
+
         node.update_context();
 
         node.previous_cursor_position = node.cursor_position;
@@ -657,13 +2462,29 @@ impl Node for Editor {
         let dt = ff_core::macroquad::prelude::get_frame_time();
 
         node.previous_input = node.input;
-        node.input = collect_editor_input();
+        node.input = {
+            let gui = storage::get::<EditorGui>();
+            collect_editor_input(gui.has_text_input())
+        };
 
         {
             let movement = node.cursor_position - node.previous_cursor_position;
             node.mouse_movement += movement;
         }
 
+        storage::get_mut::<MessageLog>().update(dt);
+
+        node.update_pending_save();
+        node.update_pending_preview();
+
+        if node.change_highlight.is_some() {
+            node.change_highlight_timer += dt;
+
+            if node.change_highlight_timer >= Self::CHANGE_HIGHLIGHT_DURATION {
+                node.highlight_change(None);
+            }
+        }
+
         if node.info_message.is_some() {
             node.info_message_timer += dt;
 
@@ -693,51 +2514,104 @@ impl Node for Editor {
             node.apply_action(action);
         }
 
+        if node.input.test_play {
+            node.apply_action(EditorAction::TestPlay);
+        }
+
+        if let Some(id) = node.input.select_tool {
+            let is_available = match id {
+                Some(id) => {
+                    let ctx = node.get_context();
+                    get_tool_instance_of_id(&id).is_available(node.get_map(), &ctx)
+                }
+                None => true,
+            };
+
+            if is_available {
+                node.apply_action(EditorAction::SelectTool(id));
+            }
+        }
+
         if !node.input.action && node.double_click_timer < Self::DOUBLE_CLICK_THRESHOLD {
             node.double_click_timer =
                 (node.double_click_timer + dt).clamp(0.0, Self::DOUBLE_CLICK_THRESHOLD);
         }
 
         if node.input.toggle_menu {
-            toggle_editor_menu(&node.get_context());
+            // If any windows are open, only the topmost one is dismissed
+            let top_window_id = storage::get::<EditorGui>().get_top_window_id();
+
+            if top_window_id.is_some() {
+                let ctx = node.get_context();
+                let cancel_action =
+                    storage::get::<EditorGui>().get_top_window_cancel_action(node.get_map(), &ctx);
+
+                if let Some(action) = cancel_action {
+                    node.apply_action(action);
+                }
+            } else {
+                toggle_editor_menu(&node.get_context());
+            }
         }
 
         if node.input.toggle_draw_grid {
             node.should_draw_grid = !node.should_draw_grid;
 
-            node.info_message = {
-                let state = if node.should_draw_grid { "ON" } else { "OFF" };
-
-                Some(format!("Draw grid: {}", state))
-            }
+            let state = if node.should_draw_grid { "ON" } else { "OFF" };
+            node.show_info_message(format!("Draw grid: {}", state));
         }
 
-        if node.input.toggle_snap_to_grid {
-            node.should_snap_to_grid = !node.should_snap_to_grid;
+        if node.input.toggle_snap_to_grid || node.input.cycle_snap_increment {
+            if node.input.cycle_snap_increment {
+                node.snap_increment = node.snap_increment.next();
+            } else {
+                node.should_snap_to_grid = !node.should_snap_to_grid;
+            }
 
-            node.info_message = {
-                let state = if node.should_snap_to_grid {
-                    "ON"
-                } else {
-                    "OFF"
-                };
+            let state = if node.should_snap_to_grid {
+                "ON"
+            } else {
+                "OFF"
+            };
 
-                Some(format!("Snap to grid: {}", state))
-            }
+            let message = format!("Snap to grid: {} ({})", state, node.snap_increment.label());
+            node.show_info_message(message);
         }
 
         if node.input.toggle_disable_parallax {
             node.is_parallax_disabled = !node.is_parallax_disabled;
 
-            node.info_message = {
-                let state = if node.is_parallax_disabled {
-                    "OFF"
-                } else {
-                    "ON"
-                };
+            let state = if node.is_parallax_disabled {
+                "OFF"
+            } else {
+                "ON"
+            };
 
-                Some(format!("Parallax: {}", state))
-            }
+            node.show_info_message(format!("Parallax: {}", state));
+        }
+
+        if node.input.toggle_parallax_preview {
+            node.is_parallax_preview_enabled = !node.is_parallax_preview_enabled;
+
+            let state = if node.is_parallax_preview_enabled {
+                "ON"
+            } else {
+                "OFF"
+            };
+
+            node.show_info_message(format!("Game parallax preview: {}", state));
+        }
+
+        if node.input.toggle_empty_tiles {
+            node.should_draw_empty_tiles = !node.should_draw_empty_tiles;
+
+            let state = if node.should_draw_empty_tiles {
+                "ON"
+            } else {
+                "OFF"
+            };
+
+            node.show_info_message(format!("Show empty tiles: {}", state));
         }
 
         if node.input.undo {
@@ -746,9 +2620,7 @@ impl Node for Editor {
             node.apply_action(EditorAction::Redo);
         }
 
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(node.cursor_position);
+        let cursor_world_position = to_world_space(node.cursor_position);
 
         let (is_cursor_over_gui, is_cursor_over_context_menu) = {
             let gui = storage::get::<EditorGui>();
@@ -775,6 +2647,10 @@ impl Node for Editor {
             }
         }
 
+        if !node.input.action {
+            node.history.end_stroke();
+        }
+
         if node.input.action {
             if !is_cursor_over_context_menu {
                 let mut gui = storage::get_mut::<EditorGui>();
@@ -786,6 +2662,12 @@ impl Node for Editor {
                     let ctx = node.get_context();
                     let tool = get_tool_instance_of_id(id);
                     let params = tool.get_params();
+
+                    // All actions from a drag with a continuous tool are undone in one step
+                    if !node.previous_input.action && params.is_continuous {
+                        node.history.begin_stroke();
+                    }
+
                     if !node.previous_input.action || params.is_continuous {
                         if let Some(action) = tool.get_action(node.get_map(), &ctx) {
                             node.apply_action(action);
@@ -795,53 +2677,57 @@ impl Node for Editor {
                     if node.cursor_position == node.previous_cursor_position
                         && node.dragged_object.is_none()
                     {
-                        if let Some(index) = node.selected_object {
-                            let layer_id = node.selected_layer.clone().unwrap();
-                            let layer = node.get_map().layers.get(&layer_id).unwrap();
+                        if let Some(SelectedObject { layer_id, index }) =
+                            node.selected_object.clone()
+                        {
+                            let object = node.get_map().get_object(&layer_id, index).cloned();
 
-                            let object = layer.objects.get(index).unwrap();
-                            let position = scene::find_node_by_type::<EditorCamera>()
-                                .unwrap()
-                                .to_screen_space(object.position);
+                            if let Some(object) = object {
+                                let position = to_screen_space(object.position);
 
-                            let size = get_object_size(object);
-                            let rect = Rect::new(position.x, position.y, size.width, size.height);
+                                let rect = get_object_rect(&object, position);
 
-                            if rect.contains(node.cursor_position) {
-                                let click_offset = node.cursor_position - position;
+                                if rect.contains(node.cursor_position)
+                                    && !node.is_layer_locked(&layer_id)
+                                {
+                                    let click_offset = node.cursor_position - position;
 
-                                node.dragged_object = Some(DraggedObject::MapObject {
-                                    id: object.id.clone(),
-                                    kind: object.kind,
-                                    index,
-                                    layer_id,
-                                    click_offset,
-                                })
+                                    node.dragged_object = Some(DraggedObject::MapObject {
+                                        id: object.id,
+                                        kind: object.kind,
+                                        index,
+                                        layer_id,
+                                        click_offset,
+                                    })
+                                }
+                            } else {
+                                node.selected_object = None;
                             }
                         } else if let Some(index) = node.selected_spawn_point {
-                            let spawn_point = node.get_map().spawn_points[index];
+                            let world_offset = node.get_map().world_offset;
+                            let spawn_point = node.get_map().spawn_points.get(index).copied();
 
-                            let position = scene::find_node_by_type::<EditorCamera>()
-                                .unwrap()
-                                .to_screen_space(spawn_point);
-
-                            let rect = Rect::new(
-                                position.x,
-                                position.y,
-                                SPAWN_POINT_COLLIDER_WIDTH,
-                                SPAWN_POINT_COLLIDER_HEIGHT,
-                            );
+                            if let Some(spawn_point) = spawn_point {
+                                let position = world_offset + spawn_point.position;
+                                let rect =
+                                    get_spawn_point_rect(position, node.player_collider_size);
 
-                            if rect.contains(node.cursor_position) {
-                                let click_offset = node.cursor_position - position;
+                                if rect.contains(to_world_space(node.cursor_position)) {
+                                    let click_offset =
+                                        node.cursor_position - to_screen_space(position);
 
-                                node.dragged_object = Some(DraggedObject::SpawnPoint {
-                                    index,
-                                    click_offset,
-                                })
+                                    node.dragged_object = Some(DraggedObject::SpawnPoint {
+                                        index,
+                                        click_offset,
+                                    })
+                                }
+                            } else {
+                                node.selected_spawn_point = None;
                             }
                         }
                     }
+                } else if let Some(index) = node.get_hovered_facing_handle() {
+                    node.dragged_object = Some(DraggedObject::SpawnPointFacing { index });
                 } else {
                     let mut is_double_click = false;
                     let mut is_selecting_object = false;
@@ -859,8 +2745,9 @@ impl Node for Editor {
                         .map_resource
                         .map
                         .layers
-                        .keys()
-                        .cloned()
+                        .values()
+                        .filter(|layer| !layer.is_locked)
+                        .map(|layer| layer.id.clone())
                         .collect::<Vec<String>>();
 
                     if let Some(selected_layer_id) = &node.selected_layer {
@@ -882,14 +2769,14 @@ impl Node for Editor {
                     let mut layer_id = None;
 
                     'layers: for id in &layer_ids {
-                        let layer = node.map_resource.map.layers.get(id).unwrap();
-                        if layer.kind == MapLayerKind::ObjectLayer {
+                        let layer = node.map_resource.map.layers.get(id);
+                        if let Some(layer) =
+                            layer.filter(|layer| layer.kind == MapLayerKind::ObjectLayer)
+                        {
                             for (i, object) in layer.objects.iter().enumerate() {
-                                let size = get_object_size(object);
                                 let position = object.position + node.map_resource.map.world_offset;
 
-                                let rect =
-                                    Rect::new(position.x, position.y, size.width, size.height);
+                                let rect = get_object_rect(object, position);
 
                                 if rect.contains(cursor_world_position) {
                                     object_index = Some(i);
@@ -901,18 +2788,16 @@ impl Node for Editor {
                         }
                     }
 
-                    if let Some(i) = object_index {
+                    if let (Some(i), Some(layer_id)) = (object_index, layer_id.clone()) {
                         let mut should_select = true;
 
-                        if let Some(current_index) = node.selected_object {
-                            if current_index == i {
+                        if let Some(current) = node.selected_object.clone() {
+                            if current.layer_id == layer_id && current.index == i {
                                 should_select = false;
 
                                 if is_double_click {
-                                    let layer_id = layer_id.clone().unwrap();
-
                                     let action = EditorAction::OpenObjectPropertiesWindow {
-                                        layer_id,
+                                        layer_id: layer_id.clone(),
                                         index: i,
                                     };
 
@@ -926,34 +2811,36 @@ impl Node for Editor {
                         if should_select {
                             is_selecting_object = true;
 
-                            let layer_id = layer_id.unwrap();
-
                             let action = EditorAction::SelectObject { index: i, layer_id };
 
                             node.apply_action(action);
                         }
                     } else {
-                        for (i, spawn_point) in node.get_map().spawn_points.iter().enumerate() {
-                            let position = scene::find_node_by_type::<EditorCamera>()
-                                .unwrap()
-                                .to_screen_space(*spawn_point);
-
-                            let rect = Rect::new(
-                                position.x,
-                                position.y,
-                                SPAWN_POINT_COLLIDER_WIDTH,
-                                SPAWN_POINT_COLLIDER_HEIGHT,
+                        let map = node.get_map();
+
+                        for (i, spawn_point) in map.spawn_points.iter().enumerate() {
+                            let rect = get_spawn_point_rect(
+                                map.world_offset + spawn_point.position,
+                                node.player_collider_size,
                             );
 
-                            if rect.contains(node.cursor_position) {
+                            if rect.contains(to_world_space(node.cursor_position)) {
                                 is_selecting_spawn_point = true;
 
                                 let mut should_select = true;
 
                                 if let Some(index) = node.selected_spawn_point {
                                     if index == i {
-                                        node.selected_spawn_point = None;
                                         should_select = false;
+
+                                        if is_double_click {
+                                            let action =
+                                                EditorAction::OpenSpawnPointPropertiesWindow(i);
+
+                                            node.apply_action(action);
+                                        } else {
+                                            node.selected_spawn_point = None;
+                                        }
                                     }
                                 }
 
@@ -969,46 +2856,44 @@ impl Node for Editor {
                             let mut tile_index = None;
 
                             'tile_layers: for id in &layer_ids {
-                                let layer = node.get_map().layers.get(id).unwrap();
-                                if layer.kind == MapLayerKind::TileLayer {
+                                if node.get_map().get_layer_kind(id)
+                                    == Some(MapLayerKind::TileLayer)
+                                {
                                     let world_offset = node.get_map().world_offset;
                                     let tile_size = node.get_map().tile_size;
 
-                                    for (x, y, tile) in node.map_resource.map.get_tiles(id, None) {
-                                        if tile.is_some() {
-                                            let rect = Rect::new(
-                                                world_offset.x + (x as f32 * tile_size.width),
-                                                world_offset.y + (y as f32 * tile_size.height),
-                                                tile_size.width,
-                                                tile_size.height,
-                                            );
-                                            if rect.contains(cursor_world_position) {
-                                                let i = node.get_map().to_index(uvec2(x, y));
-                                                tile_index = Some(i);
-                                                layer_id = Some(id.clone());
+                                    for (x, y, _) in node.map_resource.map.iter_tiles(id) {
+                                        let rect = Rect::new(
+                                            world_offset.x + (x as f32 * tile_size.width),
+                                            world_offset.y + (y as f32 * tile_size.height),
+                                            tile_size.width,
+                                            tile_size.height,
+                                        );
+                                        if rect.contains(cursor_world_position) {
+                                            let i = node.get_map().to_index(uvec2(x, y));
+                                            tile_index = Some(i);
+                                            layer_id = Some(id.clone());
 
-                                                break 'tile_layers;
-                                            }
+                                            break 'tile_layers;
                                         }
                                     }
                                 }
                             }
 
-                            if let Some(tile_index) = tile_index {
+                            if let (Some(tile_index), Some(layer_id)) =
+                                (tile_index, layer_id.clone())
+                            {
                                 let mut should_select = true;
 
                                 if let Some(selected_tile_index) = node.selected_map_tile_index {
                                     if selected_tile_index == tile_index
-                                        && layer_id.as_ref().unwrap()
-                                            == node.selected_layer.as_ref().unwrap()
+                                        && node.selected_layer.as_ref() == Some(&layer_id)
                                     {
                                         should_select = false;
 
                                         if is_double_click {
-                                            let layer_id = layer_id.clone().unwrap();
-
                                             let action = EditorAction::OpenTilePropertiesWindow {
-                                                layer_id,
+                                                layer_id: layer_id.clone(),
                                                 index: tile_index,
                                             };
 
@@ -1022,7 +2907,7 @@ impl Node for Editor {
                                 if should_select {
                                     is_selecting_tile = true;
                                     node.selected_map_tile_index = Some(tile_index);
-                                    node.selected_layer = layer_id;
+                                    node.selected_layer = Some(layer_id);
                                 }
                             }
                         }
@@ -1037,10 +2922,9 @@ impl Node for Editor {
             }
         } else if let Some(dragged_object) = node.dragged_object.take() {
             let map = node.get_map();
+            let world_offset = map.world_offset;
 
-            let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-                .unwrap()
-                .to_world_space(node.cursor_position);
+            let cursor_world_position = to_world_space(node.cursor_position);
 
             let mut position = (cursor_world_position).clamp(
                 map.world_offset,
@@ -1048,9 +2932,10 @@ impl Node for Editor {
                     + (UVec2::from(map.grid_size).as_f32() * Vec2::from(map.tile_size)),
             );
 
-            if node.should_snap_to_grid {
-                let coords = map.to_coords(position);
-                position = map.to_position(coords);
+            if let DraggedObject::SpawnPoint { .. } = &dragged_object {
+                if node.should_snap_to_grid {
+                    position = node.snap_position(position);
+                }
             }
 
             match dragged_object {
@@ -1061,14 +2946,33 @@ impl Node for Editor {
                     layer_id,
                     click_offset,
                 } => {
-                    let position = position - click_offset;
+                    let (position, _) =
+                        node.get_dragged_object_position(&layer_id, index, click_offset);
+
+                    let position = position - world_offset;
 
-                    let action = EditorAction::UpdateObject {
-                        id,
-                        kind,
-                        index,
-                        layer_id,
-                        position,
+                    let map = node.get_map();
+                    let indices = map.get_object_group_indices(&layer_id, index);
+
+                    let action = if indices.len() > 1 {
+                        let offset = map
+                            .get_object(&layer_id, index)
+                            .map(|object| position - object.position)
+                            .unwrap_or_default();
+
+                        EditorAction::MoveObjects {
+                            layer_id,
+                            indices,
+                            offset,
+                        }
+                    } else {
+                        EditorAction::UpdateObject {
+                            id,
+                            kind,
+                            index,
+                            layer_id,
+                            position,
+                        }
                     };
 
                     node.apply_action(action);
@@ -1077,47 +2981,148 @@ impl Node for Editor {
                     index,
                     click_offset,
                 } => {
-                    let position = position - click_offset;
+                    let position = position - click_offset - world_offset;
 
                     let action = EditorAction::MoveSpawnPoint { index, position };
 
                     node.apply_action(action);
                 }
+                DraggedObject::SpawnPointFacing { index } => {
+                    let spawn_point = node.get_map().spawn_points.get(index).copied();
+
+                    if let Some(spawn_point) = spawn_point {
+                        let is_facing_left = is_left_of_spawn_point(
+                            cursor_world_position,
+                            world_offset + spawn_point.position,
+                        );
+
+                        if is_facing_left != spawn_point.is_facing_left {
+                            let action = EditorAction::SetSpawnPointFacing {
+                                index,
+                                is_facing_left,
+                            };
+
+                            node.apply_action(action);
+                        }
+                    }
+                }
             }
         }
 
         if node.input.delete {
-            if let Some(index) = node.selected_object.take() {
-                let layer_id = node.selected_layer.clone().unwrap();
-
+            if let Some(SelectedObject { layer_id, index }) = node.selected_object.take() {
                 let action = EditorAction::DeleteObject { index, layer_id };
 
                 node.apply_action(action);
             } else if let Some(index) = node.selected_map_tile_index.take() {
-                let layer_id = node.selected_layer.clone().unwrap();
-                let coords = {
-                    let grid_size = node.get_map().grid_size;
-                    uvec2(
-                        index as u32 % grid_size.width,
-                        index as u32 / grid_size.width,
-                    )
-                };
+                let layer_id = node.selected_layer.clone();
 
-                let action = EditorAction::RemoveTile { coords, layer_id };
+                // A stale index is simply deselected
+                let coords = node.get_map().try_to_tile_coords(index);
 
-                node.apply_action(action);
+                if let (Some(coords), Some(layer_id)) = (coords, layer_id) {
+                    let action = EditorAction::RemoveTile { coords, layer_id };
+
+                    node.apply_action(action);
+                }
             } else if let Some(index) = node.selected_spawn_point.take() {
                 let action = EditorAction::DeleteSpawnPoint(index);
                 node.apply_action(action);
             }
         }
 
+        if node.input.select_tileset.is_some() || node.input.cycle_tileset {
+            let is_tile_layer = node
+                .selected_layer
+                .as_ref()
+                .and_then(|layer_id| node.get_map().get_layer_kind(layer_id))
+                == Some(MapLayerKind::TileLayer);
+
+            if is_tile_layer {
+                let mut tileset_ids = node.get_map().tilesets.keys().cloned().collect::<Vec<_>>();
+                tileset_ids.sort_unstable();
+
+                let index = if let Some(index) = node.input.select_tileset {
+                    Some(index)
+                } else if tileset_ids.is_empty() {
+                    None
+                } else {
+                    let current_index = node
+                        .selected_tileset
+                        .as_ref()
+                        .and_then(|id| tileset_ids.iter().position(|other| other == id));
+
+                    match current_index {
+                        Some(i) => Some((i + 1) % tileset_ids.len()),
+                        None => Some(0),
+                    }
+                };
+
+                if let Some(tileset_id) = index.and_then(|i| tileset_ids.get(i)) {
+                    node.apply_action(EditorAction::SelectTileset(tileset_id.clone()));
+                    node.show_info_message(format!("Tileset: {}", tileset_id));
+                }
+            }
+        }
+
+        if node.input.toggle_solo_layer {
+            node.apply_action(EditorAction::ToggleSoloLayer);
+        }
+
+        if node.input.toggle_message_log {
+            node.apply_action(EditorAction::ToggleMessageLogWindow);
+        }
+
+        if node.input.move_layer_up || node.input.move_layer_down {
+            if let Some(layer_id) = node.selected_layer.clone() {
+                // This moves layers the same way as the buttons in the layer list, so that the
+                // layers of a group stay together
+                let new_index = node
+                    .get_map()
+                    .get_layer_move_index(&layer_id, node.input.move_layer_up);
+
+                if let Some(index) = new_index {
+                    let action = EditorAction::SetLayerDrawOrderIndex {
+                        id: layer_id,
+                        index,
+                    };
+
+                    node.apply_action(action);
+                }
+            }
+        }
+
+        if node.input.copy {
+            if let Some(SelectedObject { layer_id, index }) = node.selected_object.clone() {
+                let indices = node.get_map().get_object_group_indices(&layer_id, index);
+
+                let action = EditorAction::CopyObjects { layer_id, indices };
+
+                node.apply_action(action);
+            }
+        }
+
+        if node.input.paste && !is_cursor_over_gui {
+            let map = node.get_map();
+
+            let mut position = to_world_space(node.cursor_position);
+
+            if node.get_snap_to_grid(node.get_paste_layer_id().as_deref()) {
+                position = node.snap_position(position);
+            }
+
+            let action = EditorAction::PasteObjects(position - map.world_offset);
+            node.apply_action(action);
+        }
+
         if node.input.context_menu {
             let mut gui = storage::get_mut::<EditorGui>();
+            let hovered_item = node.get_hovered_item(node.cursor_position);
             gui.open_context_menu(
                 node.cursor_position,
                 &node.map_resource.map,
                 node.get_context(),
+                hovered_item,
             );
         }
     }
@@ -1138,7 +3143,7 @@ impl Node for Editor {
 
         let viewport_size = viewport_size();
 
-        let threshold = viewport_size.as_vec2() * Self::CAMERA_PAN_THRESHOLD;
+        let threshold = viewport_size.as_vec2() * config().editor.get_camera_pan_threshold();
 
         let mut pan_direction = node.input.camera_move_direction;
 
@@ -1154,11 +3159,13 @@ impl Node for Editor {
             pan_direction.y = 1.0;
         }
 
-        let mut movement = pan_direction * Self::CAMERA_PAN_SPEED;
+        let mut movement = pan_direction * config().editor.get_camera_pan_speed();
 
-        let mut camera = scene::find_node_by_type::<EditorCamera>().unwrap();
+        let mut camera = get_editor_camera();
 
-        if movement == Vec2::ZERO && node.input.camera_mouse_move {
+        // Dragging with the middle mouse button over the GUI is left to the GUI, as it is used to
+        // pan the tileset, for example
+        if movement == Vec2::ZERO && node.input.camera_mouse_move && is_cursor_over_map {
             movement = -node.mouse_movement / camera.scale;
         }
 
@@ -1168,18 +3175,47 @@ impl Node for Editor {
             (camera.position + movement).clamp(Vec2::ZERO, node.get_map().get_size().into());
 
         if is_cursor_over_map {
-            camera.scale = (camera.scale + node.input.camera_zoom * Self::CAMERA_ZOOM_STEP)
-                .clamp(Self::CAMERA_ZOOM_MIN, Self::CAMERA_ZOOM_MAX);
+            let (zoom_min, zoom_max) = Self::get_camera_zoom_bounds(node.get_map(), viewport_size);
+
+            camera.scale = (camera.scale
+                + node.input.camera_zoom * config().editor.get_camera_zoom_step())
+            .clamp(zoom_min, zoom_max);
         }
     }
 
     fn draw(mut node: RefMut<Self>) {
+        if is_test_playing() {
+            return;
+        }
+
         {
-            let camera = scene::find_node_by_type::<EditorCamera>().unwrap();
+            let camera = get_editor_camera();
+
+            let camera_position = if node.is_parallax_preview_enabled {
+                let bounds = window_size();
+                let aspect_ratio = bounds.width / bounds.height;
+
+                get_single_player_camera_position(camera.position, aspect_ratio, bounds)
+            } else {
+                camera.position
+            };
 
             let map = node.get_map();
-            map.draw_background(None, camera.position, node.is_parallax_disabled);
-            map.draw(None, None);
+            map.draw_background(None, camera_position, node.is_parallax_disabled);
+
+            // Only the tiles that are inside the view are drawn
+            let (min, max) = map.world_to_tile_rect(camera.get_view_rect());
+            let rect = URect::new(min.x, min.y, max.x - min.x + 1, max.y - min.y + 1);
+
+            map.draw_filtered(Some(rect), None, |layer| node.is_layer_drawn(layer));
+
+            if node.should_draw_empty_tiles {
+                if let Some(layer_id) = &node.selected_layer {
+                    if map.get_layer_kind(layer_id) == Some(MapLayerKind::TileLayer) {
+                        draw_empty_tiles(map, layer_id, rect);
+                    }
+                }
+            }
         }
 
         if node.should_draw_grid {
@@ -1196,16 +3232,14 @@ impl Node for Editor {
                 Self::GRID_COLOR,
             );
 
-            for x in 0..map.grid_size.width {
-                let begin = vec2(
-                    map.world_offset.x + (x as f32 * map.tile_size.width),
-                    map.world_offset.y,
-                );
+            // The grid lines are moved by the grid origin offset, while the outline always
+            // follows the edges of the map, as the tiles are not affected by the offset
+            let origin_offset = config().editor.grid_origin_offset;
 
-                let end = vec2(
-                    begin.x,
-                    begin.y + (map.grid_size.height as f32 * map.tile_size.height),
-                );
+            let mut x = origin_offset.x.rem_euclid(map.tile_size.width);
+            while x < map_size.width {
+                let begin = vec2(map.world_offset.x + x, map.world_offset.y);
+                let end = vec2(begin.x, begin.y + map_size.height);
 
                 draw_line(
                     begin.x,
@@ -1214,19 +3248,15 @@ impl Node for Editor {
                     end.y,
                     Self::GRID_LINE_WIDTH,
                     Self::GRID_COLOR,
-                )
-            }
-
-            for y in 0..map.grid_size.height {
-                let begin = vec2(
-                    map.world_offset.x,
-                    map.world_offset.y + (y as f32 * map.tile_size.height),
                 );
 
-                let end = vec2(
-                    begin.x + (map.grid_size.width as f32 * map.tile_size.width),
-                    begin.y,
-                );
+                x += map.tile_size.width;
+            }
+
+            let mut y = origin_offset.y.rem_euclid(map.tile_size.height);
+            while y < map_size.height {
+                let begin = vec2(map.world_offset.x, map.world_offset.y + y);
+                let end = vec2(begin.x + map_size.width, begin.y);
 
                 draw_line(
                     begin.x,
@@ -1235,15 +3265,27 @@ impl Node for Editor {
                     end.y,
                     Self::GRID_LINE_WIDTH,
                     Self::GRID_COLOR,
-                )
+                );
+
+                y += map.tile_size.height;
             }
         }
 
         {
+            let world_offset = node.get_map().world_offset;
+
             for (i, spawn_point) in node.get_map().spawn_points.iter().enumerate() {
                 let mut is_selected = false;
 
-                let mut position = *spawn_point;
+                let mut position = world_offset + spawn_point.position;
+                let mut is_facing_left = spawn_point.is_facing_left;
+
+                if let Some(DraggedObject::SpawnPointFacing { index }) = node.dragged_object {
+                    if index == i {
+                        let cursor_world_position = to_world_space(node.cursor_position);
+                        is_facing_left = is_left_of_spawn_point(cursor_world_position, position);
+                    }
+                }
 
                 if let Some(DraggedObject::SpawnPoint {
                     index,
@@ -1253,9 +3295,8 @@ impl Node for Editor {
                     if index == i {
                         let map = node.get_map();
 
-                        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-                            .unwrap()
-                            .to_world_space(node.cursor_position - click_offset);
+                        let cursor_world_position =
+                            to_world_space(node.cursor_position - click_offset);
 
                         position = (cursor_world_position).clamp(
                             map.world_offset,
@@ -1265,8 +3306,7 @@ impl Node for Editor {
                         );
 
                         if node.should_snap_to_grid {
-                            let coords = map.to_coords(position);
-                            position = map.to_position(coords);
+                            position = node.snap_position(position);
                         }
                     }
                 }
@@ -1275,35 +3315,128 @@ impl Node for Editor {
                     is_selected = index == i;
                 }
 
+                let rect = get_spawn_point_rect(position, node.player_collider_size);
+
                 let texture = get_texture("spawn_point_icon");
 
                 let frame_size = texture.frame_size();
 
                 let source_rect = Rect::new(0.0, 0.0, frame_size.width, frame_size.height);
 
+                // The icon is centered on the spawn position and aligned with the bottom of the
+                // player bounds
                 draw_texture(
-                    position.x,
-                    position.y,
+                    position.x - frame_size.width / 2.0,
+                    rect.y + rect.height - frame_size.height,
                     texture,
                     DrawTextureParams {
+                        tint: Some(get_spawn_point_tag_color(spawn_point.tag)),
                         dest_size: Some(frame_size),
                         source: Some(source_rect),
                         ..Default::default()
                     },
                 );
 
+                // The bounds of the player are shown, so that it is possible to see whether the
+                // spawn point is embedded in solid tiles
+                if !is_selected {
+                    let color = if is_spawn_point_embedded(node.get_map(), &rect) {
+                        SPAWN_POINT_EMBEDDED_BOUNDS_COLOR
+                    } else {
+                        SPAWN_POINT_BOUNDS_COLOR
+                    };
+
+                    draw_rectangle_outline(rect.x, rect.y, rect.width, rect.height, 1.0, color);
+                }
+
+                {
+                    let rect = get_spawn_point_facing_handle_rect(rect, is_facing_left);
+
+                    let (tail_x, head_x) = if is_facing_left {
+                        (rect.x + rect.width, rect.x)
+                    } else {
+                        (rect.x, rect.x + rect.width)
+                    };
+
+                    let center_y = rect.y + rect.height / 2.0;
+                    let head_length = rect.width / 2.0;
+                    let back_x = if is_facing_left {
+                        head_x + head_length
+                    } else {
+                        head_x - head_length
+                    };
+
+                    draw_line(tail_x, center_y, head_x, center_y, 2.0, colors::WHITE);
+                    draw_line(back_x, rect.y, head_x, center_y, 2.0, colors::WHITE);
+                    draw_line(
+                        back_x,
+                        rect.y + rect.height,
+                        head_x,
+                        center_y,
+                        2.0,
+                        colors::WHITE,
+                    );
+
+                    if is_selected {
+                        draw_rectangle_outline(
+                            rect.x,
+                            rect.y,
+                            rect.width,
+                            rect.height,
+                            2.0,
+                            SELECTION_HIGHLIGHT_COLOR,
+                        );
+                    }
+                }
+
                 if is_selected {
-                    draw_rectangle_outline(
-                        position.x,
-                        position.y,
-                        SPAWN_POINT_COLLIDER_WIDTH,
-                        SPAWN_POINT_COLLIDER_HEIGHT,
-                        4.0,
-                        SELECTION_HIGHLIGHT_COLOR,
-                    )
+                    let color = if is_spawn_point_embedded(node.get_map(), &rect) {
+                        SPAWN_POINT_EMBEDDED_BOUNDS_COLOR
+                    } else {
+                        SELECTION_HIGHLIGHT_COLOR
+                    };
+
+                    draw_rectangle_outline(rect.x, rect.y, rect.width, rect.height, 4.0, color)
+                }
+            }
+
+            let mut smart_guides = Vec::new();
+            let mut dragged_object_position = None;
+
+            // The other members of the group of a dragged object are moved along with it, by the
+            // same offset
+            let mut dragged_group = None;
+
+            if let Some(DraggedObject::MapObject {
+                layer_id,
+                index,
+                click_offset,
+                ..
+            }) = node.dragged_object.clone()
+            {
+                let map = node.get_map();
+
+                if let Some(object) = map.get_object(&layer_id, index) {
+                    let (position, guides) =
+                        node.get_dragged_object_position(&layer_id, index, click_offset);
+
+                    let offset = position - (map.world_offset + object.position);
+                    let indices = map.get_object_group_indices(&layer_id, index);
+
+                    smart_guides = guides;
+                    dragged_object_position = Some(position);
+                    dragged_group = Some((layer_id, indices, offset));
                 }
             }
 
+            // All the members of the group of the selected object are highlighted
+            let selected_group = node.selected_object.clone().map(|object| {
+                let indices = node
+                    .get_map()
+                    .get_object_group_indices(&object.layer_id, object.index);
+                (object.layer_id, indices)
+            });
+
             let len = node.get_map().draw_order.len();
             for i in 0..len {
                 let i = len as i32 - i as i32 - 1;
@@ -1311,47 +3444,30 @@ impl Node for Editor {
                     let layer_id = node.get_map().draw_order.get(i as usize).unwrap();
                     let layer = node.get_map().layers.get(layer_id).unwrap();
 
-                    if layer.is_visible && layer.kind == MapLayerKind::ObjectLayer {
+                    if node.is_layer_drawn(layer) && layer.kind == MapLayerKind::ObjectLayer {
+                        // Sprites are batched by texture and labels and selection highlights are
+                        // drawn on top, after the sprites of the layer, so that they do not break
+                        // up the batches
+                        let mut batch = ObjectDrawBatch::new();
+                        let mut overlays = Vec::new();
+                        let mut colliders = Vec::new();
+
                         for (i, object) in layer.objects.iter().enumerate() {
                             let mut label = None;
 
-                            let mut is_selected = false;
-                            if let Some(layer_id) = &node.selected_layer {
-                                if let Some(index) = node.selected_object {
-                                    is_selected = *layer_id == layer.id && index == i;
-                                }
-                            }
+                            let is_selected = selected_group
+                                .as_ref()
+                                .map(|(layer_id, indices)| {
+                                    *layer_id == layer.id && indices.contains(&i)
+                                })
+                                .unwrap_or(false);
 
                             let mut object_position =
                                 node.map_resource.map.world_offset + object.position;
 
-                            if let Some(DraggedObject::MapObject {
-                                layer_id,
-                                index,
-                                click_offset,
-                                ..
-                            }) = node.dragged_object.clone()
-                            {
-                                if layer.id == layer_id && index == i {
-                                    let map = node.get_map();
-
-                                    let cursor_world_position =
-                                        scene::find_node_by_type::<EditorCamera>()
-                                            .unwrap()
-                                            .to_world_space(node.cursor_position - click_offset);
-
-                                    object_position = (cursor_world_position).clamp(
-                                        map.world_offset,
-                                        map.world_offset
-                                            + (Size::from(UVec2::from(map.grid_size).as_f32())
-                                                * map.tile_size)
-                                                .into(),
-                                    );
-
-                                    if node.should_snap_to_grid {
-                                        let coords = map.to_coords(object_position);
-                                        object_position = map.to_position(coords);
-                                    }
+                            if let Some((layer_id, indices, offset)) = &dragged_group {
+                                if *layer_id == layer.id && indices.contains(&i) {
+                                    object_position += *offset;
                                 }
                             }
 
@@ -1387,10 +3503,9 @@ impl Node for Editor {
                                                 frame_size.height,
                                             ));
 
-                                            draw_texture(
-                                                position.x,
-                                                position.y,
+                                            batch.push(
                                                 texture,
+                                                position,
                                                 DrawTextureParams {
                                                     dest_size,
                                                     source,
@@ -1416,10 +3531,10 @@ impl Node for Editor {
 
                                             let frame_size = texture.frame_size();
 
-                                            let dest_size = params
-                                                .sprite
-                                                .scale
-                                                .map(|s| Size::new(s, s) * frame_size);
+                                            let scale =
+                                                object.scale.unwrap_or_else(|| params.get_scale());
+
+                                            let dest_size = Some(frame_size.to_scaled_xy(scale));
 
                                             let source =
                                                 params.sprite.animations.first().map(|a| {
@@ -1431,10 +3546,9 @@ impl Node for Editor {
                                                     )
                                                 });
 
-                                            draw_texture(
-                                                position.x,
-                                                position.y,
+                                            batch.push(
                                                 texture,
+                                                position,
                                                 DrawTextureParams {
                                                     dest_size,
                                                     source,
@@ -1462,10 +3576,9 @@ impl Node for Editor {
                                             frame_size.height,
                                         );
 
-                                        draw_texture(
-                                            object_position.x,
-                                            object_position.y,
+                                        batch.push(
                                             texture,
+                                            object_position,
                                             DrawTextureParams {
                                                 dest_size: Some(frame_size),
                                                 source: Some(source_rect),
@@ -1478,15 +3591,54 @@ impl Node for Editor {
                                 }
                             }
 
-                            let size = get_object_size(object);
+                            if label.is_some() || is_selected {
+                                let rect = get_object_rect(object, object_position);
+                                overlays.push((object_position, rect, label, is_selected));
+                            }
+
+                            // The colliders of items are shown when they are selected, or when
+                            // they have been overridden, so that overrides stand out
+                            if is_selected || object.collider_size.is_some() {
+                                if let Some(size) = get_object_collider_size(object) {
+                                    let offset = get_object_collider_offset(object);
+
+                                    let rect =
+                                        Rect::from_point_size(object_position + offset, size);
+                                    colliders.push((rect, object.collider_size.is_some()));
+                                }
+                            }
+                        }
+
+                        batch.flush();
+
+                        for (rect, is_override) in colliders {
+                            let color = if is_override {
+                                Self::COLLIDER_OVERRIDE_GIZMO_COLOR
+                            } else {
+                                Self::COLLIDER_GIZMO_COLOR
+                            };
+
+                            draw_rectangle_outline(
+                                rect.x,
+                                rect.y,
+                                rect.width,
+                                rect.height,
+                                Self::COLLIDER_GIZMO_LINE_WIDTH,
+                                color,
+                            );
+                        }
 
+                        for (object_position, rect, label, is_selected) in overlays {
                             if let Some(label) = &label {
-                                let params = TextParams::default();
+                                let params = TextParams::default().with_outline(
+                                    Self::LABEL_OUTLINE_COLOR,
+                                    vec2(Self::LABEL_OUTLINE_OFFSET, Self::LABEL_OUTLINE_OFFSET),
+                                );
 
                                 draw_text(
                                     label,
                                     object_position.x,
-                                    object_position.y + (size.height / 2.0)
+                                    object_position.y + (rect.height / 2.0)
                                         - Self::OBJECT_SELECTION_RECT_PADDING,
                                     params,
                                 );
@@ -1494,10 +3646,10 @@ impl Node for Editor {
 
                             if is_selected {
                                 draw_rectangle_outline(
-                                    object_position.x - Self::OBJECT_SELECTION_RECT_PADDING,
-                                    object_position.y - Self::OBJECT_SELECTION_RECT_PADDING,
-                                    size.width,
-                                    size.height,
+                                    rect.x,
+                                    rect.y,
+                                    rect.width,
+                                    rect.height,
                                     4.0,
                                     SELECTION_HIGHLIGHT_COLOR,
                                 );
@@ -1506,16 +3658,69 @@ impl Node for Editor {
                     }
                 }
             }
+
+            for guide in smart_guides {
+                draw_line(
+                    guide.start.x,
+                    guide.start.y,
+                    guide.end.x,
+                    guide.end.y,
+                    Self::SMART_GUIDE_LINE_WIDTH,
+                    Self::SMART_GUIDE_COLOR,
+                );
+            }
+
+            // The position of a dragged object is shown above it, relative to the world offset,
+            // like it is in the object properties window, so that it can be placed precisely
+            if let Some(position) = dragged_object_position {
+                let local_position = position - node.get_map().world_offset;
+
+                let params = TextParams::default().with_outline(
+                    Self::LABEL_OUTLINE_COLOR,
+                    vec2(Self::LABEL_OUTLINE_OFFSET, Self::LABEL_OUTLINE_OFFSET),
+                );
+
+                draw_text(
+                    &format!("{:.2}, {:.2}", local_position.x, local_position.y),
+                    position.x - Self::OBJECT_SELECTION_RECT_PADDING,
+                    position.y
+                        - Self::OBJECT_SELECTION_RECT_PADDING
+                        - Self::DRAGGED_OBJECT_POSITION_MARGIN,
+                    params,
+                );
+            }
         }
 
-        if let Some(tile_index) = node.selected_map_tile_index {
-            let grid_size = node.get_map().grid_size;
-            let tile_size = node.get_map().tile_size;
+        if let Some(rect) = node.change_highlight {
+            let t = node.change_highlight_timer / Self::CHANGE_HIGHLIGHT_DURATION;
+
+            let mut color = Self::CHANGE_HIGHLIGHT_COLOR;
+            color.alpha *= 1.0 - t.clamp(0.0, 1.0);
+
+            draw_rectangle(rect.x, rect.y, rect.width, rect.height, color);
+        }
+
+        if let Some(region) = node.selected_region {
+            let map = node.get_map();
+            let position = map.to_position(region.point());
 
-            let coords = uvec2(
-                tile_index as u32 % grid_size.width,
-                tile_index as u32 / grid_size.width,
+            draw_rectangle_outline(
+                position.x,
+                position.y,
+                region.width as f32 * map.tile_size.width,
+                region.height as f32 * map.tile_size.height,
+                2.0,
+                SELECTION_HIGHLIGHT_COLOR,
             );
+        }
+
+        let selected_tile_coords = node
+            .selected_map_tile_index
+            .and_then(|index| node.get_map().try_to_tile_coords(index));
+
+        if let Some(coords) = selected_tile_coords {
+            let tile_size = node.get_map().tile_size;
+
             let position = node.get_map().to_position(coords);
 
             draw_rectangle_outline(
@@ -1528,22 +3733,68 @@ impl Node for Editor {
             )
         }
 
-        if let Some(label) = &node.info_message {
+        if let Some(tile) = node.get_selected_map_tile() {
+            push_camera_state();
+            set_default_camera();
+
+            let label = format!(
+                "Tile {} ({}): {}",
+                tile.tile_id,
+                tile.tileset_id,
+                format_tile_attributes(&tile.attributes),
+            );
+
+            let viewport_size = viewport_size();
+            let label_position = to_window_position(vec2(
+                viewport_size.width / 2.0,
+                viewport_size.height - Self::TILE_INFO_STRIP_MARGIN,
+            ));
+
+            draw_text(
+                &label,
+                label_position.x,
+                label_position.y,
+                TextParams::default()
+                    .with_alignment(HorizontalAlignment::Center, VerticalAlignment::Normal)
+                    .with_outline(
+                        Self::LABEL_OUTLINE_COLOR,
+                        vec2(Self::LABEL_OUTLINE_OFFSET, Self::LABEL_OUTLINE_OFFSET),
+                    ),
+            );
+
+            pop_camera_state();
+        }
+
+        // The saving indicator takes the place of the info message, while a save is in progress
+        let info_message = if node.pending_save.is_some() {
+            Some("Saving...")
+        } else {
+            node.info_message.as_deref()
+        };
+
+        if let Some(label) = info_message {
             push_camera_state();
             set_default_camera();
 
+            // The read-only banner of built-in maps is drawn at the top of the view
+            let mut y = 16.0;
+            if !node.map_resource.meta.is_user_map {
+                y += EditorGui::READ_ONLY_BANNER_HEIGHT;
+            }
+
             let viewport_size = viewport_size();
-            let label_position = vec2(viewport_size.width / 2.0, 16.0);
+            let label_position = to_window_position(vec2(viewport_size.width / 2.0, y));
 
             draw_text(
                 label,
                 label_position.x,
                 label_position.y,
-                TextParams {
-                    horizontal_align: HorizontalAlignment::Center,
-                    vertical_align: VerticalAlignment::Normal,
-                    ..Default::default()
-                },
+                TextParams::default()
+                    .with_alignment(HorizontalAlignment::Center, VerticalAlignment::Normal)
+                    .with_outline(
+                        Self::LABEL_OUTLINE_COLOR,
+                        vec2(Self::LABEL_OUTLINE_OFFSET, Self::LABEL_OUTLINE_OFFSET),
+                    ),
             );
 
             pop_camera_state();
@@ -1555,6 +3806,44 @@ impl Node for Editor {
             let tool = get_tool_instance_of_id(tool_id);
             let ctx = node.get_context();
             res = tool.draw_cursor(node.get_map(), &ctx);
+
+            let is_cursor_over_gui = {
+                let gui = storage::get::<EditorGui>();
+                gui.contains(node.cursor_position)
+            };
+
+            let cursor_icon = tool
+                .get_params()
+                .cursor_icon_texture_id
+                .as_ref()
+                .and_then(|texture_id| try_get_texture(texture_id));
+
+            if let Some(texture) = cursor_icon {
+                if !is_cursor_over_gui {
+                    push_camera_state();
+                    set_default_camera();
+
+                    let position = to_window_position(
+                        node.cursor_position
+                            + vec2(Self::CURSOR_ICON_OFFSET, Self::CURSOR_ICON_OFFSET),
+                    );
+
+                    draw_texture(
+                        position.x,
+                        position.y,
+                        texture,
+                        DrawTextureParams {
+                            dest_size: Some(Size::new(
+                                Self::CURSOR_ICON_SIZE,
+                                Self::CURSOR_ICON_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                    );
+
+                    pop_camera_state();
+                }
+            }
         }
 
         {
@@ -1571,8 +3860,46 @@ impl Node for Editor {
     }
 }
 
-fn get_object_size(_object: &MapObject) -> Size<f32> {
-    let res = None;
+// This returns the size of the collider of `object`, if it is an item, which is its collider size
+// override, if it has one, or the collider size from the item metadata, if it does not
+fn get_object_collider_size(object: &MapObject) -> Option<Size<f32>> {
+    if object.kind != MapObjectKind::Item {
+        return None;
+    }
+
+    object
+        .collider_size
+        .or_else(|| try_get_item(&object.id).map(|meta| meta.collider_size))
+}
+
+// This returns the offset of the collider of an item from its position, so that the selection
+// rect lines up with the collider gizmo
+fn get_object_collider_offset(object: &MapObject) -> Vec2 {
+    if get_object_collider_size(object).is_none() {
+        return Vec2::ZERO;
+    }
+
+    try_get_item(&object.id)
+        .map(|meta| meta.collider_offset)
+        .unwrap_or_default()
+}
+
+// This returns the rect that is used to select the object, at `position`, including padding
+fn get_object_rect(object: &MapObject, position: Vec2) -> Rect {
+    Rect::from_point_size(
+        position + get_object_collider_offset(object)
+            - Vec2::splat(Editor::OBJECT_SELECTION_RECT_PADDING),
+        get_object_size(object),
+    )
+}
+
+fn get_object_size(object: &MapObject) -> Size<f32> {
+    let res = get_object_collider_size(object).map(|size| {
+        Size::new(
+            size.width.max(Editor::OBJECT_SELECTION_RECT_MIN_SIZE),
+            size.height.max(Editor::OBJECT_SELECTION_RECT_MIN_SIZE),
+        )
+    });
 
     /*
     let mut label = None;
@@ -1632,3 +3959,247 @@ fn get_object_size(_object: &MapObject) -> Size<f32> {
         Editor::OBJECT_SELECTION_RECT_PADDING,
     ) * Size::new(2.0, 2.0))
 }
+
+#[cfg(test)]
+mod tests {
+    use ff_core::map::{test_utils, MapLayer, MapMetadata, MAP_PREVIEW_PLACEHOLDER_PATH};
+
+    use super::*;
+
+    fn map_with_layers() -> Map {
+        let layers = [
+            ("tiles", MapLayerKind::TileLayer),
+            ("objects", MapLayerKind::ObjectLayer),
+        ];
+
+        test_utils::map_with_layers(vec2(16.0, 16.0), uvec2(8, 8), &layers)
+    }
+
+    fn editor_with_map(map: Map) -> Editor {
+        let map_resource = MapResource {
+            map,
+            preview: Texture2D::invalid(),
+            meta: MapMetadata {
+                name: "test".to_string(),
+                description: None,
+                path: "maps/test.json".to_string(),
+                preview_path: MAP_PREVIEW_PLACEHOLDER_PATH.to_string(),
+                preview_format: None,
+                is_tiled_map: false,
+                is_user_map: true,
+                requirements: MapRequirements::default(),
+            },
+        };
+
+        Editor::from_map_resource(map_resource, Vec2::ZERO)
+    }
+
+    #[test]
+    fn test_apply_action_rejects_locked_layer() {
+        let mut map = map_with_layers();
+
+        let object = MapObject::new("object", MapObjectKind::Item, vec2(16.0, 16.0));
+        map.layers.get_mut("objects").unwrap().objects.push(object);
+        map.layers.get_mut("objects").unwrap().is_locked = true;
+
+        let mut editor = editor_with_map(map);
+        editor.selected_layer = Some("objects".to_string());
+
+        editor.apply_action(EditorAction::DeleteObject {
+            index: 0,
+            layer_id: "objects".to_string(),
+        });
+        assert_eq!(editor.get_map().layers["objects"].objects.len(), 1);
+        assert_eq!(
+            editor.info_message.as_deref(),
+            Some("Layer 'objects' is locked")
+        );
+
+        // The layer that objects are pasted into is only found when the action is applied
+        editor
+            .clipboard
+            .copy_objects(&editor.map_resource.map, "objects", &[0]);
+        editor.apply_action(EditorAction::PasteObjects(Vec2::ZERO));
+        assert_eq!(editor.get_map().layers["objects"].objects.len(), 1);
+
+        // Actions in a batch are checked one by one
+        editor.apply_action(EditorAction::batch(&[EditorAction::MoveObjects {
+            layer_id: "objects".to_string(),
+            indices: vec![0],
+            offset: vec2(16.0, 0.0),
+        }]));
+        assert_eq!(
+            editor.get_map().layers["objects"].objects[0].position,
+            vec2(16.0, 16.0)
+        );
+        assert!(!editor.history.can_undo());
+
+        // Unlocking the layer is not blocked by the lock
+        editor.apply_action(EditorAction::UpdateLayer {
+            id: "objects".to_string(),
+            is_visible: true,
+            is_locked: false,
+        });
+        editor.apply_action(EditorAction::DeleteObject {
+            index: 0,
+            layer_id: "objects".to_string(),
+        });
+        assert!(editor.get_map().layers["objects"].objects.is_empty());
+    }
+
+    #[test]
+    fn test_copy_and_paste_group() {
+        let mut map = map_with_layers();
+
+        for (i, x) in [16.0, 32.0, 64.0].into_iter().enumerate() {
+            let mut object = MapObject::new("object", MapObjectKind::Item, vec2(x, 16.0));
+            if i < 2 {
+                object.group_id = Some("group_1".to_string());
+            }
+            object.collider_size = Some(Size::new(8.0, 8.0));
+            object.scale = Some(vec2(2.0, 1.0));
+
+            map.layers.get_mut("objects").unwrap().objects.push(object);
+        }
+
+        let mut editor = editor_with_map(map);
+        editor.selected_layer = Some("objects".to_string());
+
+        let indices = editor.get_map().get_object_group_indices("objects", 0);
+        assert_eq!(indices, vec![0, 1]);
+
+        editor.apply_action(EditorAction::CopyObjects {
+            layer_id: "objects".to_string(),
+            indices,
+        });
+        editor.apply_action(EditorAction::PasteObjects(vec2(16.0, 48.0)));
+
+        let objects = &editor.get_map().layers["objects"].objects;
+        assert_eq!(objects.len(), 5);
+
+        // The pasted objects form a new group, separate from the one they were copied from
+        let group_id = objects[0].group_id.clone();
+        assert!(group_id.is_some());
+        assert_ne!(group_id.as_deref(), Some("group_1"));
+        assert_eq!(objects[1].group_id, group_id);
+        assert_eq!(
+            editor.get_map().get_object_group_indices("objects", 0),
+            vec![0, 1]
+        );
+        assert_eq!(
+            editor.get_map().get_object_group_indices("objects", 2),
+            vec![2, 3]
+        );
+
+        for object in &objects[0..2] {
+            assert_eq!(object.collider_size, Some(Size::new(8.0, 8.0)));
+            assert_eq!(object.scale, Some(vec2(2.0, 1.0)));
+        }
+
+        let mut positions = vec![objects[0].position, objects[1].position];
+        positions.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+        assert_eq!(positions, vec![vec2(16.0, 48.0), vec2(32.0, 48.0)]);
+
+        // Objects that would end up outside of the map are clamped to its edges
+        editor.apply_action(EditorAction::PasteObjects(vec2(120.0, 136.0)));
+
+        let objects = &editor.get_map().layers["objects"].objects;
+        let mut positions = vec![objects[0].position, objects[1].position];
+        positions.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+        assert_eq!(positions, vec![vec2(120.0, 128.0), vec2(128.0, 128.0)]);
+    }
+
+    #[test]
+    fn test_tile_selection_kept_across_tool_switch() {
+        add_tool_instance(TilePlacementTool::new());
+        add_tool_instance(EraserTool::new());
+
+        let mut editor = editor_with_map(map_with_layers());
+        editor.selected_tool = Some(TypeId::of::<TilePlacementTool>());
+        editor.selected_layer = Some("tiles".to_string());
+        editor.selected_tileset = Some("tileset".to_string());
+        editor.selected_tile = Some(0);
+        editor.selected_map_tile_index = Some(3);
+
+        editor.apply_action(EditorAction::SelectTool(Some(TypeId::of::<EraserTool>())));
+        assert_eq!(editor.selected_tool, Some(TypeId::of::<EraserTool>()));
+        assert_eq!(editor.selected_map_tile_index, Some(3));
+        assert_eq!(editor.selected_tileset.as_deref(), Some("tileset"));
+        assert_eq!(editor.selected_tile, Some(0));
+
+        editor.apply_action(EditorAction::SelectTool(Some(TypeId::of::<
+            TilePlacementTool,
+        >())));
+        assert_eq!(
+            editor.selected_tool,
+            Some(TypeId::of::<TilePlacementTool>())
+        );
+        assert_eq!(editor.selected_map_tile_index, Some(3));
+
+        // The selected tile is no longer valid on an object layer, but the brush is kept
+        editor.apply_action(EditorAction::SelectLayer("objects".to_string()));
+        assert_eq!(editor.selected_map_tile_index, None);
+        assert_eq!(editor.selected_tileset.as_deref(), Some("tileset"));
+        assert_eq!(editor.selected_tile, Some(0));
+    }
+
+    #[test]
+    fn test_object_selection_cleared_across_object_layers() {
+        let mut map = map_with_layers();
+
+        let mut layer = MapLayer::new("objects_2", MapLayerKind::ObjectLayer, false, map.grid_size);
+        for layer in [map.layers.get_mut("objects").unwrap(), &mut layer] {
+            let object = MapObject::new("object", MapObjectKind::Item, vec2(16.0, 16.0));
+            layer.objects.push(object);
+        }
+        map.layers.insert(layer.id.clone(), layer);
+        map.draw_order.push("objects_2".to_string());
+
+        let mut editor = editor_with_map(map);
+
+        editor.apply_action(EditorAction::SelectObject {
+            index: 0,
+            layer_id: "objects".to_string(),
+        });
+        assert_eq!(
+            editor.selected_object,
+            Some(SelectedObject {
+                layer_id: "objects".to_string(),
+                index: 0,
+            })
+        );
+
+        // The same index is valid in the other layer, but it refers to a different object
+        editor.apply_action(EditorAction::SelectLayer("objects_2".to_string()));
+        assert_eq!(editor.selected_object, None);
+    }
+
+    #[test]
+    fn test_invalid_selection_cleared() {
+        let map = map_with_layers();
+
+        let mut ctx = EditorContext {
+            selected_layer: Some("objects".to_string()),
+            selected_tileset: Some("missing".to_string()),
+            selected_tile: Some(0),
+            selected_object: Some(SelectedObject {
+                layer_id: "objects".to_string(),
+                index: 0,
+            }),
+            selected_map_tile_index: Some(64),
+            soloed_layer: Some("missing".to_string()),
+            ..Default::default()
+        };
+
+        ctx.validate_selection(&map);
+        assert_eq!(ctx.soloed_layer, None);
+        assert_eq!(ctx.selected_object, None);
+        assert_eq!(ctx.selected_map_tile_index, None);
+        assert_eq!(ctx.selected_tileset, None);
+        assert_eq!(ctx.selected_tile, None);
+
+        ctx.selected_layer = Some("missing".to_string());
+        ctx.validate_selection(&map);
+        assert_eq!(ctx.selected_layer, None);
+    }
+}
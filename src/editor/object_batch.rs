@@ -0,0 +1,159 @@
+use ff_core::prelude::*;
+
+struct ObjectSpriteDraw {
+    texture: Texture2D,
+    position: Vec2,
+    params: DrawTextureParams,
+    // This is the area covered by the draw, in world space
+    rect: Rect,
+}
+
+/// This holds the sprite draws of object previews, so that they can be drawn grouped by texture.
+/// The renderer merges consecutive draws that share a texture into a single draw call, so drawing
+/// the objects of a layer in texture order, rather than in the order they are stored in, reduces
+/// the number of draw calls on dense object layers considerably. Each draw keeps its own params,
+/// so tint and scale are preserved per object. Draws are never moved in front of, or behind,
+/// draws they overlap, so the order of the objects in the layer decides which is drawn on top.
+#[derive(Default)]
+pub struct ObjectDrawBatch {
+    draws: Vec<ObjectSpriteDraw>,
+}
+
+impl ObjectDrawBatch {
+    pub fn new() -> Self {
+        ObjectDrawBatch { draws: Vec::new() }
+    }
+
+    pub fn push(&mut self, texture: Texture2D, position: Vec2, params: DrawTextureParams) {
+        let size = params.dest_size.unwrap_or_else(|| {
+            params
+                .source
+                .map(|source| Size::new(source.width, source.height))
+                .unwrap_or_else(|| texture.size())
+        });
+
+        self.draws.push(ObjectSpriteDraw {
+            texture,
+            position,
+            params,
+            rect: Rect::from_point_size(position, size),
+        });
+    }
+
+    /// This draws all the queued sprites, grouped by texture, and clears the batch. The number of
+    /// texture batches drawn, which is the number of draw calls required, is returned.
+    pub fn flush(&mut self) -> usize {
+        let draws = group_by_key(
+            std::mem::take(&mut self.draws),
+            |draw| draw.texture,
+            |a, b| a.rect.overlaps(&b.rect),
+        );
+
+        let batch_cnt = count_batches(draws.iter().map(|draw| draw.texture));
+
+        for draw in draws {
+            draw_texture(draw.position.x, draw.position.y, draw.texture, draw.params);
+        }
+
+        batch_cnt
+    }
+}
+
+// This reorders `items` so that items with equal keys are adjacent, where possible. An item is
+// added to the last group with its key, unless it overlaps an item in a group after that one, in
+// which case it starts a new group, so that items are never reordered relative to items that
+// they overlap. Items keep their relative order within a group.
+fn group_by_key<T, K, F, O>(items: Vec<T>, f: F, overlaps: O) -> Vec<T>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+    O: Fn(&T, &T) -> bool,
+{
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+
+    for item in items {
+        let key = f(&item);
+
+        let mut target = None;
+
+        for (i, (other, group)) in groups.iter().enumerate().rev() {
+            if *other == key {
+                target = Some(i);
+                break;
+            }
+
+            if group.iter().any(|other| overlaps(&item, other)) {
+                break;
+            }
+        }
+
+        match target {
+            Some(i) => groups[i].1.push(item),
+            None => groups.push((key, vec![item])),
+        }
+    }
+
+    groups.into_iter().flat_map(|(_, group)| group).collect()
+}
+
+// This returns the number of runs of equal keys, which is the number of draw calls needed to
+// draw sprites with textures in this order
+fn count_batches<K, I>(keys: I) -> usize
+where
+    K: PartialEq,
+    I: IntoIterator<Item = K>,
+{
+    let mut res = 0;
+    let mut previous = None;
+
+    for key in keys {
+        if previous.as_ref() != Some(&key) {
+            res += 1;
+        }
+
+        previous = Some(key);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_key_keeps_order_within_groups() {
+        let items = vec![(0, 'a'), (1, 'b'), (0, 'c'), (2, 'd'), (1, 'e')];
+
+        let grouped = group_by_key(items, |(key, _)| *key, |_, _| false);
+
+        assert_eq!(
+            grouped,
+            vec![(0, 'a'), (0, 'c'), (1, 'b'), (1, 'e'), (2, 'd')]
+        );
+    }
+
+    #[test]
+    fn test_group_by_key_keeps_overlapping_order() {
+        // Items overlap if they have the same position
+        let items = vec![(0, 1), (1, 2), (0, 2), (0, 3), (1, 1)];
+
+        let grouped = group_by_key(items, |(key, _)| *key, |a, b| a.1 == b.1);
+
+        // `(0, 2)` has to be drawn after `(1, 2)` so it starts a new group, which `(0, 3)` joins.
+        // `(1, 1)` does not overlap anything in that group, so it can join the group of `(1, 2)`
+        assert_eq!(grouped, vec![(0, 1), (1, 2), (1, 1), (0, 2), (0, 3)]);
+    }
+
+    #[test]
+    fn test_dense_layer_batch_reduction() {
+        // This simulates a dense object layer, with 4 different textures interleaved
+        let textures = (0..400).map(|i| i % 4).collect::<Vec<_>>();
+
+        assert_eq!(count_batches(textures.iter()), 400);
+
+        let grouped = group_by_key(textures, |texture| *texture, |_, _| false);
+
+        assert_eq!(count_batches(grouped.iter()), 4);
+    }
+}
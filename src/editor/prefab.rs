@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use ff_core::map::{Map, MapLayerKind, MapObject, MapObjectKind, MapProperty};
+use ff_core::prelude::*;
+
+use super::actions::{BatchedAction, CreateObjectAction, PlaceTileAction, UndoableAction};
+use super::library::LibraryAsset;
+
+/// This should be incremented whenever the prefab format changes. Prefabs with a different
+/// version than this will be skipped when prefabs are loaded.
+pub const PREFAB_FORMAT_VERSION: u32 = 2;
+
+pub const PREFABS_DEFAULT_DIR: &str = "prefabs";
+
+/// A tile in a prefab. `coords` are relative to the top-left corner of the prefab.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabTile {
+    pub layer_id: String,
+    #[serde(with = "ff_core::parsing::uvec2_def")]
+    pub coords: UVec2,
+    pub tileset_id: String,
+    pub tile_id: u32,
+}
+
+/// An object in a prefab. `offset` is relative to the top-left corner of the prefab.
+/// Object groups are not kept, as stamping a prefab more than once would join the copies into
+/// a single group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabObject {
+    pub layer_id: String,
+    pub id: String,
+    pub kind: MapObjectKind,
+    #[serde(with = "ff_core::parsing::vec2_def")]
+    pub offset: Vec2,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, MapProperty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collider_size: Option<Size<f32>>,
+    #[serde(
+        default,
+        with = "ff_core::parsing::vec2_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scale: Option<Vec2>,
+}
+
+/// A prefab is a rectangular region of tiles and objects, captured from a map, that can be
+/// stamped onto a map as a single undoable action.
+/// Tiles and objects are stamped into the layers with the same ids as the ones they were captured
+/// from, so layers that are not present in the target map will be skipped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prefab {
+    pub version: u32,
+    pub id: String,
+    pub size: Size<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiles: Vec<PrefabTile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<PrefabObject>,
+}
+
+impl Prefab {
+    pub fn from_region(id: &str, map: &Map, region: URect) -> Self {
+        let mut tiles = Vec::new();
+        let mut objects = Vec::new();
+
+        let origin = map.to_position(region.point()) - map.world_offset;
+        let rect = Rect::new(
+            origin.x,
+            origin.y,
+            region.width as f32 * map.tile_size.width,
+            region.height as f32 * map.tile_size.height,
+        );
+
+        for layer_id in &map.draw_order {
+            let layer = map.layers.get(layer_id).unwrap();
+
+            match layer.kind {
+                MapLayerKind::TileLayer => {
+                    for (x, y, tile) in map.get_tiles(layer_id, Some(region)) {
+                        if let Some(tile) = tile {
+                            tiles.push(PrefabTile {
+                                layer_id: layer_id.clone(),
+                                coords: uvec2(x - region.x, y - region.y),
+                                tileset_id: tile.tileset_id.clone(),
+                                tile_id: tile.tile_id,
+                            });
+                        }
+                    }
+                }
+                MapLayerKind::ObjectLayer => {
+                    for object in &layer.objects {
+                        if rect.contains(object.position) {
+                            objects.push(PrefabObject {
+                                layer_id: layer_id.clone(),
+                                id: object.id.clone(),
+                                kind: object.kind,
+                                offset: object.position - origin,
+                                properties: object.properties.clone(),
+                                collider_size: object.collider_size,
+                                scale: object.scale,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Prefab {
+            version: PREFAB_FORMAT_VERSION,
+            id: id.to_string(),
+            size: Size::new(region.width, region.height),
+            tiles,
+            objects,
+        }
+    }
+
+    /// This returns the ids of the layers that the prefab has tiles or objects in
+    pub fn get_layer_ids(&self) -> Vec<String> {
+        let mut res = Vec::new();
+
+        let layer_ids = self
+            .tiles
+            .iter()
+            .map(|tile| &tile.layer_id)
+            .chain(self.objects.iter().map(|object| &object.layer_id));
+
+        for layer_id in layer_ids {
+            if !res.contains(layer_id) {
+                res.push(layer_id.clone());
+            }
+        }
+
+        res
+    }
+
+    /// This returns a single action that will stamp the prefab onto the map, with its top-left
+    /// corner at `coords`. Tiles that fall outside of the map, as well as tiles and objects
+    /// belonging to layers or tilesets that are not present in the map, will be skipped.
+    pub fn get_stamp_action(&self, map: &Map, coords: UVec2) -> BatchedAction {
+        let mut actions: Vec<Box<dyn UndoableAction>> = Vec::new();
+
+        for tile in &self.tiles {
+            let tile_coords = coords + tile.coords;
+
+            if tile_coords.x >= map.grid_size.width || tile_coords.y >= map.grid_size.height {
+                continue;
+            }
+
+            if map.get_layer_kind(&tile.layer_id) == Some(MapLayerKind::TileLayer)
+                && map.tilesets.contains_key(&tile.tileset_id)
+            {
+                let action = PlaceTileAction::new(
+                    tile.tile_id,
+                    tile.layer_id.clone(),
+                    tile.tileset_id.clone(),
+                    tile_coords,
+                );
+
+                actions.push(Box::new(action));
+            }
+        }
+
+        let origin = map.to_position(coords) - map.world_offset;
+
+        for object in &self.objects {
+            if map.get_layer_kind(&object.layer_id) == Some(MapLayerKind::ObjectLayer) {
+                let mut map_object =
+                    MapObject::new(&object.id, object.kind, origin + object.offset);
+                map_object.properties = object.properties.clone();
+                map_object.collider_size = object.collider_size;
+                map_object.scale = object.scale;
+
+                let action = CreateObjectAction::from_object(map_object, object.layer_id.clone());
+
+                actions.push(Box::new(action));
+            }
+        }
+
+        BatchedAction::new(actions)
+    }
+}
+
+static mut PREFABS: Option<HashMap<String, Prefab>> = None;
+
+impl LibraryAsset for Prefab {
+    const KIND: &'static str = "prefab";
+    const DEFAULT_DIR: &'static str = PREFABS_DEFAULT_DIR;
+    const FORMAT_VERSION: u32 = PREFAB_FORMAT_VERSION;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn registry() -> &'static mut HashMap<String, Self> {
+        unsafe { PREFABS.get_or_insert_with(HashMap::new) }
+    }
+}
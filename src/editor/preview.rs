@@ -0,0 +1,57 @@
+use ff_core::image::{Image, ImageImpl};
+use ff_core::macroquad::camera::{pop_camera_state, push_camera_state, set_camera, Camera2D};
+use ff_core::macroquad::texture::{render_target, FilterMode, RenderTarget};
+use ff_core::map::Map;
+use ff_core::prelude::*;
+
+/// This is the resolution of the map previews generated by the editor.
+/// The map is scaled to fit inside this, keeping its aspect ratio, and any remaining space is
+/// filled with the background color of the map. `LoadMapWindow` scales previews to fit its
+/// content area, so this only has to be large enough for the thumbnail to look sharp there.
+pub const MAP_PREVIEW_SIZE: Size<u32> = Size::new(320, 180);
+
+/// This is a map preview that has been rendered to an offscreen target. The draws are only
+/// executed when the frame is presented, so it should not be read back, with `read_map_preview`,
+/// before the next frame.
+pub struct PendingMapPreview {
+    target: RenderTarget,
+}
+
+/// Render the map to an offscreen target, with parallax disabled, sized according to
+/// `MAP_PREVIEW_SIZE`.
+pub fn render_map_preview(map: &Map) -> PendingMapPreview {
+    let target = render_target(MAP_PREVIEW_SIZE.width, MAP_PREVIEW_SIZE.height);
+    target.texture.set_filter(FilterMode::Nearest);
+
+    let map_size = map.get_size();
+    let preview_size = MAP_PREVIEW_SIZE.as_f32();
+
+    let scale = (preview_size.width / map_size.width).min(preview_size.height / map_size.height);
+    let view_size = preview_size / scale;
+
+    let center = map.world_offset + Vec2::from(map_size) / 2.0;
+
+    push_camera_state();
+
+    set_camera(&Camera2D {
+        target: center,
+        zoom: vec2(2.0 / view_size.width, 2.0 / view_size.height),
+        render_target: Some(target),
+        ..Camera2D::default()
+    });
+
+    clear_screen(map.background_color);
+
+    map.draw_background(None, center, true);
+    map.draw(None, None);
+
+    pop_camera_state();
+
+    PendingMapPreview { target }
+}
+
+/// Read back a preview rendered by `render_map_preview` as an image
+pub fn read_map_preview(preview: &PendingMapPreview) -> Image {
+    let image_impl = ImageImpl::from(preview.target.texture.get_texture_data());
+    Image::from(image_impl)
+}
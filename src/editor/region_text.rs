@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+
+use ff_core::map::{Map, MapLayerKind};
+use ff_core::parsing::deserialize_json_string;
+use ff_core::prelude::*;
+
+use super::prefab::Prefab;
+
+/// This should be incremented whenever the region text format changes. Region texts with a
+/// different version than this will be rejected when pasted.
+pub const REGION_TEXT_FORMAT_VERSION: u32 = 2;
+
+/// The first line of a region text is this header, followed by the format version
+pub const REGION_TEXT_HEADER: &str = "fishfight-region";
+
+const CLIPBOARD_PREFAB_ID: &str = "clipboard";
+
+/// A reference to a tileset that is used by the tiles of a region text, so that it can be
+/// validated against the tilesets of the map that the region is pasted into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionTilesetRef {
+    pub id: String,
+    pub texture_id: String,
+}
+
+/// A region of tiles and objects, captured from a map, in a text form that can be shared through
+/// the system clipboard. The text is a header line, holding the format version, followed by a
+/// single line of JSON, so that it is compact but still possible to inspect by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionText {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tilesets: Vec<RegionTilesetRef>,
+    pub prefab: Prefab,
+}
+
+impl RegionText {
+    pub fn from_region(map: &Map, region: URect) -> Self {
+        let prefab = Prefab::from_region(CLIPBOARD_PREFAB_ID, map, region);
+
+        let mut tilesets: Vec<RegionTilesetRef> = Vec::new();
+
+        for tile in &prefab.tiles {
+            if tilesets.iter().any(|tileset| tileset.id == tile.tileset_id) {
+                continue;
+            }
+
+            if let Some(tileset) = map.tilesets.get(&tile.tileset_id) {
+                tilesets.push(RegionTilesetRef {
+                    id: tileset.id.clone(),
+                    texture_id: tileset.texture_id.clone(),
+                });
+            }
+        }
+
+        RegionText { tilesets, prefab }
+    }
+
+    pub fn to_text(&self) -> Result<String> {
+        let json = ff_core::serde_json::to_string(self)?;
+        let res = format!(
+            "{} {}\n{}",
+            REGION_TEXT_HEADER, REGION_TEXT_FORMAT_VERSION, json
+        );
+
+        Ok(res)
+    }
+
+    pub fn from_text(text: &str) -> Result<Self> {
+        let text = text.trim();
+
+        let (header, json) = text.split_once('\n').unwrap_or((text, ""));
+
+        let version = header
+            .strip_prefix(REGION_TEXT_HEADER)
+            .and_then(|version| version.trim().parse::<u32>().ok())
+            .ok_or_else(|| Error::new_const(ErrorKind::Parsing, &"The text is not a map region"))?;
+
+        if version != REGION_TEXT_FORMAT_VERSION {
+            return Err(Error::new_message(
+                ErrorKind::Parsing,
+                &format!("Unsupported map region format version {}", version),
+            ));
+        }
+
+        let res = deserialize_json_string(json.trim())?;
+
+        Ok(res)
+    }
+
+    /// This returns a warning for every tileset used by the region that is either missing from
+    /// `map`, in which case its tiles will be skipped when pasted, or that uses a different
+    /// texture in `map`, in which case the pasted tiles might not look as they did when copied.
+    pub fn get_tileset_warnings(&self, map: &Map) -> Vec<String> {
+        let mut res = Vec::new();
+
+        for tileset_ref in &self.tilesets {
+            match map.tilesets.get(&tileset_ref.id) {
+                None => res.push(format!(
+                    "Tileset '{}' is not in this map, so its tiles were skipped",
+                    tileset_ref.id
+                )),
+                Some(tileset) if tileset.texture_id != tileset_ref.texture_id => res.push(format!(
+                    "Tileset '{}' uses texture '{}' in this map, but '{}' in the pasted region",
+                    tileset_ref.id, tileset.texture_id, tileset_ref.texture_id
+                )),
+                _ => {}
+            }
+        }
+
+        res
+    }
+
+    /// This returns a warning for every layer used by the region that is either missing from
+    /// `map`, or of a different kind in `map`, in which case its tiles or objects will be skipped
+    /// when pasted.
+    pub fn get_layer_warnings(&self, map: &Map) -> Vec<String> {
+        let mut layers: Vec<(&str, MapLayerKind)> = Vec::new();
+
+        let tile_layers = self.prefab.tiles.iter().map(|tile| &tile.layer_id);
+        let object_layers = self.prefab.objects.iter().map(|object| &object.layer_id);
+
+        for layer in tile_layers
+            .map(|layer_id| (layer_id.as_str(), MapLayerKind::TileLayer))
+            .chain(object_layers.map(|layer_id| (layer_id.as_str(), MapLayerKind::ObjectLayer)))
+        {
+            if !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+
+        let mut res = Vec::new();
+
+        for (layer_id, kind) in layers {
+            let content = match kind {
+                MapLayerKind::TileLayer => "tiles",
+                MapLayerKind::ObjectLayer => "objects",
+            };
+
+            match map.get_layer_kind(layer_id) {
+                None => res.push(format!(
+                    "Layer '{}' is not in this map, so its {} were skipped",
+                    layer_id, content
+                )),
+                Some(other) if other != kind => res.push(format!(
+                    "Layer '{}' is of a different kind in this map, so its {} were skipped",
+                    layer_id, content
+                )),
+                _ => {}
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff_core::map::{MapLayer, MapObject, MapObjectKind, MapProperty, MapTile, MapTileset};
+
+    use super::*;
+    use crate::editor::actions::UndoableAction;
+
+    fn map_with_tileset(texture_id: &str) -> Map {
+        let mut map = Map::new(vec2(16.0, 16.0), uvec2(8, 8));
+
+        let mut layer = MapLayer::new("tiles", MapLayerKind::TileLayer, false, map.grid_size);
+        layer.tiles[0] = Some(MapTile {
+            tile_id: 0,
+            tileset_id: "tileset".to_string(),
+            texture_id: texture_id.to_string(),
+            texture: None,
+            texture_coords: Vec2::ZERO,
+            attributes: Vec::new(),
+        });
+
+        map.layers.insert(layer.id.clone(), layer);
+        map.draw_order.push("tiles".to_string());
+
+        let tileset = MapTileset::new(
+            "tileset",
+            texture_id,
+            Size::new(32, 32),
+            Size::new(16.0, 16.0),
+            1,
+        );
+        map.tilesets.insert(tileset.id.clone(), tileset);
+
+        map
+    }
+
+    #[test]
+    fn test_region_text_round_trip() {
+        let map = map_with_tileset("texture");
+        let region = RegionText::from_region(&map, URect::new(0, 0, 2, 2));
+
+        let text = region.to_text().unwrap();
+        assert!(text.starts_with(&format!(
+            "{} {}\n",
+            REGION_TEXT_HEADER, REGION_TEXT_FORMAT_VERSION
+        )));
+
+        let res = RegionText::from_text(&text).unwrap();
+        assert_eq!(res.prefab.tiles.len(), 1);
+        assert_eq!(res.tilesets.len(), 1);
+        assert!(res.get_tileset_warnings(&map).is_empty());
+
+        assert!(RegionText::from_text("not a region").is_err());
+
+        let text = text.replacen(
+            &format!("{} {}", REGION_TEXT_HEADER, REGION_TEXT_FORMAT_VERSION),
+            &format!("{} {}", REGION_TEXT_HEADER, REGION_TEXT_FORMAT_VERSION + 1),
+            1,
+        );
+        assert!(RegionText::from_text(&text).is_err());
+    }
+
+    #[test]
+    fn test_region_text_keeps_object_overrides() {
+        let mut map = map_with_tileset("texture");
+
+        let mut layer = MapLayer::new("objects", MapLayerKind::ObjectLayer, false, map.grid_size);
+        let mut object = MapObject::new("object", MapObjectKind::Item, vec2(8.0, 8.0));
+        object
+            .properties
+            .insert("key".to_string(), MapProperty::Bool(true));
+        object.collider_size = Some(Size::new(4.0, 6.0));
+        object.scale = Some(vec2(2.0, 0.5));
+        layer.objects.push(object);
+
+        map.layers.insert(layer.id.clone(), layer);
+        map.draw_order.push("objects".to_string());
+
+        let text = RegionText::from_region(&map, URect::new(0, 0, 2, 2))
+            .to_text()
+            .unwrap();
+        let region = RegionText::from_text(&text).unwrap();
+
+        let mut other = map_with_tileset("texture");
+        let layer = MapLayer::new("objects", MapLayerKind::ObjectLayer, false, other.grid_size);
+        other.layers.insert(layer.id.clone(), layer);
+        other.draw_order.push("objects".to_string());
+
+        let mut action = region.prefab.get_stamp_action(&other, uvec2(1, 1));
+        action.apply(&mut other).unwrap();
+
+        let object = &other.layers["objects"].objects[0];
+        assert_eq!(object.position, vec2(24.0, 24.0));
+        assert!(object.properties.contains_key("key"));
+        assert_eq!(object.collider_size, Some(Size::new(4.0, 6.0)));
+        assert_eq!(object.scale, Some(vec2(2.0, 0.5)));
+    }
+
+    #[test]
+    fn test_region_text_tileset_warnings() {
+        let region = RegionText::from_region(&map_with_tileset("texture"), URect::new(0, 0, 2, 2));
+
+        let other_texture = map_with_tileset("other_texture");
+        assert_eq!(region.get_tileset_warnings(&other_texture).len(), 1);
+
+        let no_tilesets = Map::new(vec2(16.0, 16.0), uvec2(8, 8));
+        assert_eq!(region.get_tileset_warnings(&no_tilesets).len(), 1);
+    }
+
+    #[test]
+    fn test_region_text_layer_warnings() {
+        let map = map_with_tileset("texture");
+        let region = RegionText::from_region(&map, URect::new(0, 0, 2, 2));
+
+        assert!(region.get_layer_warnings(&map).is_empty());
+
+        let mut other_kind = map_with_tileset("texture");
+        other_kind.layers.get_mut("tiles").unwrap().kind = MapLayerKind::ObjectLayer;
+        assert_eq!(region.get_layer_warnings(&other_kind).len(), 1);
+
+        let no_layers = Map::new(vec2(16.0, 16.0), uvec2(8, 8));
+        assert_eq!(region.get_layer_warnings(&no_layers).len(), 1);
+    }
+}
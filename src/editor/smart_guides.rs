@@ -0,0 +1,152 @@
+use ff_core::prelude::*;
+
+// Lines are considered aligned if they are within this distance of each other, relative to their
+// magnitude, as the offset that aligns them will not be exact for fractional or large coordinates
+const ALIGNMENT_TOLERANCE: f32 = 0.00001;
+
+/// A line, in world space, along which a dragged object has been aligned with another object
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SmartGuide {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+fn is_aligned(a: f32, b: f32) -> bool {
+    (a - b).abs() <= ALIGNMENT_TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
+
+// This returns the left edge, center and right edge of `rect`, on the x axis
+fn get_x_lines(rect: &Rect) -> [f32; 3] {
+    [rect.left(), rect.x + rect.width / 2.0, rect.right()]
+}
+
+// This returns the top edge, center and bottom edge of `rect`, on the y axis
+fn get_y_lines(rect: &Rect) -> [f32; 3] {
+    [rect.top(), rect.y + rect.height / 2.0, rect.bottom()]
+}
+
+// This returns the smallest offset, within `threshold`, that will align one of `lines` with one
+// of `other_lines`
+fn get_snap_offset(lines: &[f32; 3], other_lines: &[f32; 3], threshold: f32) -> Option<f32> {
+    let mut res: Option<f32> = None;
+
+    for line in lines {
+        for other in other_lines {
+            let offset = other - line;
+            if offset.abs() <= threshold && res.map(|res| offset.abs() < res.abs()).unwrap_or(true)
+            {
+                res = Some(offset);
+            }
+        }
+    }
+
+    res
+}
+
+/// This returns the offset that will align the edges or center of `rect` with those of the closest
+/// rect in `others`, on each axis independently, as long as they are within `threshold` of each
+/// other. The returned guides are the lines that are aligned, after the offset has been applied,
+/// spanning both of the aligned rects.
+pub fn snap_to_rects(rect: Rect, others: &[Rect], threshold: f32) -> (Vec2, Vec<SmartGuide>) {
+    let mut offset_x: Option<f32> = None;
+    let mut offset_y: Option<f32> = None;
+
+    for other in others {
+        if let Some(offset) = get_snap_offset(&get_x_lines(&rect), &get_x_lines(other), threshold) {
+            if offset_x.map(|res| offset.abs() < res.abs()).unwrap_or(true) {
+                offset_x = Some(offset);
+            }
+        }
+
+        if let Some(offset) = get_snap_offset(&get_y_lines(&rect), &get_y_lines(other), threshold) {
+            if offset_y.map(|res| offset.abs() < res.abs()).unwrap_or(true) {
+                offset_y = Some(offset);
+            }
+        }
+    }
+
+    let offset = vec2(offset_x.unwrap_or(0.0), offset_y.unwrap_or(0.0));
+    let snapped = rect.offset(offset);
+
+    let mut guides = Vec::new();
+
+    for other in others {
+        if offset_x.is_some() {
+            for x in get_x_lines(&snapped) {
+                if get_x_lines(other)
+                    .iter()
+                    .any(|&other_x| is_aligned(other_x, x))
+                {
+                    guides.push(SmartGuide {
+                        start: vec2(x, snapped.top().min(other.top())),
+                        end: vec2(x, snapped.bottom().max(other.bottom())),
+                    });
+                }
+            }
+        }
+
+        if offset_y.is_some() {
+            for y in get_y_lines(&snapped) {
+                if get_y_lines(other)
+                    .iter()
+                    .any(|&other_y| is_aligned(other_y, y))
+                {
+                    guides.push(SmartGuide {
+                        start: vec2(snapped.left().min(other.left()), y),
+                        end: vec2(snapped.right().max(other.right()), y),
+                    });
+                }
+            }
+        }
+    }
+
+    (offset, guides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_rects() {
+        let others = [Rect::new(0.0, 0.0, 16.0, 16.0)];
+
+        let (offset, guides) = snap_to_rects(Rect::new(3.0, 40.0, 16.0, 16.0), &others, 4.0);
+        assert_eq!(offset, vec2(-3.0, 0.0));
+        assert!(guides.contains(&SmartGuide {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, 56.0),
+        }));
+        assert!(guides.iter().all(|guide| guide.start.x == guide.end.x));
+
+        let (offset, guides) = snap_to_rects(Rect::new(40.0, 18.0, 16.0, 16.0), &others, 4.0);
+        assert_eq!(offset, vec2(0.0, -2.0));
+        assert!(guides.contains(&SmartGuide {
+            start: vec2(0.0, 16.0),
+            end: vec2(56.0, 16.0),
+        }));
+
+        let (offset, guides) = snap_to_rects(Rect::new(40.0, 40.0, 16.0, 16.0), &others, 4.0);
+        assert_eq!(offset, Vec2::ZERO);
+        assert!(guides.is_empty());
+    }
+
+    #[test]
+    fn test_snap_to_rects_fractional() {
+        let others = [Rect::new(10000.3, 0.7, 16.1, 16.1)];
+
+        let (offset, guides) = snap_to_rects(Rect::new(10002.9, 40.0, 16.1, 16.1), &others, 4.0);
+        assert!((offset.x - -2.6).abs() < 0.01);
+        assert_eq!(offset.y, 0.0);
+        assert!(!guides.is_empty());
+        assert!(guides.iter().all(|guide| guide.start.x == guide.end.x));
+        assert!(guides
+            .iter()
+            .any(|guide| (guide.start.x - 10000.3).abs() < 0.01));
+
+        let (offset, guides) = snap_to_rects(Rect::new(-40.0, 18.1, 16.1, 16.1), &others, 4.0);
+        assert!((offset.y - -1.3).abs() < 0.01);
+        assert!(!guides.is_empty());
+        assert!(guides.iter().all(|guide| guide.start.y == guide.end.y));
+    }
+}
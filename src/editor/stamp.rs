@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use ff_core::map::{Map, MapLayerKind};
+use ff_core::prelude::*;
+
+use super::actions::{BatchedAction, PlaceTileAction, UndoableAction};
+use super::library::LibraryAsset;
+
+/// This should be incremented whenever the stamp format changes. Stamps with a different version
+/// than this will be skipped when stamps are loaded.
+pub const STAMP_FORMAT_VERSION: u32 = 1;
+
+pub const STAMPS_DEFAULT_DIR: &str = "stamps";
+
+/// A cell in a stamp pattern, holding a tileset id and a tile id. Empty cells leave the tiles
+/// they are painted over untouched.
+pub type StampCell = Option<(String, u32)>;
+
+/// A stamp is a small pattern of tiles, captured from a single tile layer, that can be painted
+/// onto any tile layer as a single undoable action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stamp {
+    pub version: u32,
+    pub id: String,
+    pub size: Size<u32>,
+    /// The cells of the pattern, in rows, from the top-left corner
+    pub pattern: Vec<StampCell>,
+}
+
+impl Stamp {
+    pub fn from_region(id: &str, map: &Map, layer_id: &str, region: URect) -> Self {
+        let mut pattern = vec![None; (region.width * region.height) as usize];
+
+        for (x, y, tile) in map.get_tiles(layer_id, Some(region)) {
+            if let Some(tile) = tile {
+                let i = ((y - region.y) * region.width + (x - region.x)) as usize;
+                pattern[i] = Some((tile.tileset_id.clone(), tile.tile_id));
+            }
+        }
+
+        Stamp {
+            version: STAMP_FORMAT_VERSION,
+            id: id.to_string(),
+            size: Size::new(region.width, region.height),
+            pattern,
+        }
+    }
+
+    /// This returns the non-empty cells of the pattern, along with their coords relative to the
+    /// top-left corner of the stamp.
+    pub fn get_cells(&self) -> impl Iterator<Item = (UVec2, &str, u32)> {
+        let width = self.size.width;
+
+        self.pattern
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, cell)| {
+                cell.as_ref().map(|(tileset_id, tile_id)| {
+                    let i = i as u32;
+                    (uvec2(i % width, i / width), tileset_id.as_str(), *tile_id)
+                })
+            })
+    }
+
+    /// This returns a single action that will paint the stamp onto the specified layer, with its
+    /// top-left corner at `coords`. Cells that fall outside of the map, as well as cells using
+    /// tilesets that are not present in the map, will be skipped.
+    pub fn get_paint_action(&self, map: &Map, layer_id: &str, coords: UVec2) -> BatchedAction {
+        let mut actions: Vec<Box<dyn UndoableAction>> = Vec::new();
+
+        if map.get_layer_kind(layer_id) == Some(MapLayerKind::TileLayer) {
+            for (offset, tileset_id, tile_id) in self.get_cells() {
+                let tile_coords = coords + offset;
+
+                if tile_coords.x >= map.grid_size.width || tile_coords.y >= map.grid_size.height {
+                    continue;
+                }
+
+                if map.tilesets.contains_key(tileset_id) {
+                    let action = PlaceTileAction::new(
+                        tile_id,
+                        layer_id.to_string(),
+                        tileset_id.to_string(),
+                        tile_coords,
+                    );
+
+                    actions.push(Box::new(action));
+                }
+            }
+        }
+
+        BatchedAction::new(actions)
+    }
+}
+
+static mut STAMPS: Option<HashMap<String, Stamp>> = None;
+
+impl LibraryAsset for Stamp {
+    const KIND: &'static str = "stamp";
+    const DEFAULT_DIR: &'static str = STAMPS_DEFAULT_DIR;
+    const FORMAT_VERSION: u32 = STAMP_FORMAT_VERSION;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn registry() -> &'static mut HashMap<String, Self> {
+        unsafe { STAMPS.get_or_insert_with(HashMap::new) }
+    }
+
+    fn validate(&self) -> Option<String> {
+        if self.pattern.len() != (self.size.width * self.size.height) as usize {
+            Some("its pattern does not match its size".to_string())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use ff_core::ecs::World;
+use ff_core::macroquad::experimental::scene;
+use ff_core::map::MapResource;
+use ff_core::prelude::*;
+
+use super::{Editor, EditorCamera};
+
+pub const EDITOR_STATE_ID: &str = "editor";
+
+/// This starts the editor, with the specified map, when it begins. The editor runs as scene nodes,
+/// next to the `Game` node, so that it is kept when the game is handed over to another state, like
+/// test play. Because of this, the state itself does nothing after the editor has been started.
+pub struct EditorState {
+    map_resource: Option<MapResource>,
+}
+
+impl EditorState {
+    pub fn new(map_resource: MapResource) -> Self {
+        EditorState {
+            map_resource: Some(map_resource),
+        }
+    }
+}
+
+impl GameState for EditorState {
+    fn id(&self) -> String {
+        EDITOR_STATE_ID.to_string()
+    }
+
+    fn begin(&mut self, _world: Option<World>) -> Result<()> {
+        if let Some(map_resource) = self.map_resource.take() {
+            let position = Vec2::from(map_resource.map.get_size()) / 2.0;
+
+            scene::add_node(EditorCamera::new(position));
+            scene::add_node(Editor::new(map_resource));
+        }
+
+        Ok(())
+    }
+}
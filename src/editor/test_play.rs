@@ -0,0 +1,119 @@
+use ff_core::map::Map;
+use ff_core::prelude::*;
+
+use crate::editor::input::editor_keybinds;
+use crate::game::{build_state_for_game_mode, GameMode, StatePayload};
+use crate::player::character::get_character;
+use crate::player::{PlayerControllerKind, PlayerParams};
+
+pub const TEST_PLAY_STATE_ID: &str = "editor_test_play";
+
+static mut IS_TEST_PLAYING: bool = false;
+
+/// This returns `true` while a test play session, started from the editor, is running. The editor
+/// nodes are kept in the scene during test play, so that the map and history are preserved, but
+/// they should neither update nor draw while this is set.
+pub fn is_test_playing() -> bool {
+    unsafe { IS_TEST_PLAYING }
+}
+
+fn set_test_playing(is_test_playing: bool) {
+    unsafe { IS_TEST_PLAYING = is_test_playing };
+}
+
+/// This wraps a local game session, played on a copy of the map being edited. When the session is
+/// stopped, the game state is ended and this will idle, handing control back to the editor.
+pub struct TestPlayState {
+    game_state: Option<DefaultGameState<StatePayload>>,
+}
+
+impl TestPlayState {
+    pub fn new(map: Map) -> Result<Self> {
+        let players = [
+            PlayerParams {
+                index: 0,
+                controller: PlayerControllerKind::LocalInput(GameInputScheme::KeyboardLeft),
+                character: get_character(0).clone(),
+            },
+            PlayerParams {
+                index: 1,
+                controller: PlayerControllerKind::LocalInput(GameInputScheme::KeyboardRight),
+                character: get_character(1).clone(),
+            },
+        ];
+
+        let game_state = build_state_for_game_mode(GameMode::Local, map, &players, None)?;
+
+        Ok(TestPlayState {
+            game_state: Some(game_state),
+        })
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        if let Some(mut game_state) = self.game_state.take() {
+            game_state.end()?;
+            stop_music();
+        }
+
+        Ok(())
+    }
+}
+
+impl GameState for TestPlayState {
+    fn id(&self) -> String {
+        TEST_PLAY_STATE_ID.to_string()
+    }
+
+    fn begin(&mut self, world: Option<World>) -> Result<()> {
+        if let Some(game_state) = &mut self.game_state {
+            game_state.begin(world)?;
+            set_test_playing(true);
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, delta_time: f32) -> Result<()> {
+        if let Some(game_state) = &mut self.game_state {
+            // The test play key binding will also end the session and return to the editor
+            if editor_keybinds().test_play.is_pressed() {
+                return self.stop();
+            }
+
+            game_state.update(delta_time)?;
+        } else {
+            // This is cleared on the update following the one where the session was stopped, so
+            // that the stop key is not also read by the editor, as it resumes
+            set_test_playing(false);
+        }
+
+        Ok(())
+    }
+
+    fn fixed_update(&mut self, delta_time: f32, integration_factor: f32) -> Result<()> {
+        if let Some(game_state) = &mut self.game_state {
+            game_state.fixed_update(delta_time, integration_factor)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, delta_time: f32) -> Result<()> {
+        if let Some(game_state) = &mut self.game_state {
+            game_state.draw(delta_time)?;
+        }
+
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<Option<World>> {
+        let res = match &mut self.game_state {
+            Some(game_state) => game_state.end()?,
+            None => None,
+        };
+
+        set_test_playing(false);
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,102 @@
+/// This returns every attribute found on any of the specified tiles, in the order they are first
+/// encountered, along with its state across all the tiles. The state is `Some(true)` if all the
+/// tiles have the attribute, or `None` if only some of them do.
+pub fn get_attribute_states<'a, I>(tiles: I) -> Vec<(String, Option<bool>)>
+where
+    I: IntoIterator<Item = &'a [String]>,
+{
+    let mut res: Vec<(String, usize)> = Vec::new();
+    let mut tile_cnt = 0;
+
+    for attributes in tiles {
+        tile_cnt += 1;
+
+        for (i, attribute) in attributes.iter().enumerate() {
+            // Tiles should not have duplicate attributes, but they are only counted once if they do
+            if attributes[..i].contains(attribute) {
+                continue;
+            }
+
+            if let Some((_, cnt)) = res.iter_mut().find(|(a, _)| a == attribute) {
+                *cnt += 1;
+            } else {
+                res.push((attribute.clone(), 1));
+            }
+        }
+    }
+
+    res.into_iter()
+        .map(|(attribute, cnt)| {
+            let state = if cnt == tile_cnt { Some(true) } else { None };
+            (attribute, state)
+        })
+        .collect()
+}
+
+/// This returns `attributes` with the attributes in `unset` removed and the attributes in `set`
+/// added, if they are not already present. The order of existing attributes is preserved.
+pub fn apply_attribute_changes(
+    attributes: &[String],
+    set: &[String],
+    unset: &[String],
+) -> Vec<String> {
+    let mut res = attributes
+        .iter()
+        .filter(|attribute| !unset.contains(attribute))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for attribute in set {
+        if !res.contains(attribute) {
+            res.push(attribute.clone());
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(attributes: &[&str]) -> Vec<String> {
+        attributes.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_mixed_attribute_states() {
+        let tiles = vec![
+            attributes(&["jumpthrough", "slippery"]),
+            attributes(&["slippery", "damage", "damage"]),
+            attributes(&["slippery"]),
+        ];
+
+        let states = get_attribute_states(tiles.iter().map(|a| a.as_slice()));
+
+        assert_eq!(
+            states,
+            vec![
+                ("jumpthrough".to_string(), None),
+                ("slippery".to_string(), Some(true)),
+                ("damage".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_attribute_changes() {
+        let set = attributes(&["slippery", "damage"]);
+        let unset = attributes(&["jumpthrough"]);
+
+        // Mixed attributes are in neither list, so they are left as they are on each tile
+        assert_eq!(
+            apply_attribute_changes(&attributes(&["jumpthrough", "water"]), &set, &unset),
+            attributes(&["water", "slippery", "damage"]),
+        );
+
+        assert_eq!(
+            apply_attribute_changes(&attributes(&["damage"]), &set, &unset),
+            attributes(&["damage", "slippery"]),
+        );
+    }
+}
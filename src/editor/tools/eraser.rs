@@ -1,8 +1,7 @@
 use ff_core::prelude::*;
 
 use super::{EditorAction, EditorContext, EditorTool, EditorToolParams};
-use crate::editor::EditorCamera;
-use ff_core::macroquad::experimental::scene;
+use crate::editor::to_world_space;
 use ff_core::map::{Map, MapLayerKind};
 
 #[derive(Default)]
@@ -14,7 +13,9 @@ impl EraserTool {
     pub fn new() -> Self {
         let params = EditorToolParams {
             name: "Erase Tiles".to_string(),
-            icon_texture_id: "eraser_tool_icon".to_string(),
+            description: "Remove tiles from the selected tile layer".to_string(),
+            icon_texture_id: Some("eraser_tool_icon".to_string()),
+            cursor_icon_texture_id: Some("eraser_tool_icon".to_string()),
             is_continuous: true,
         };
 
@@ -28,15 +29,12 @@ impl EditorTool for EraserTool {
     }
 
     fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        let cursor_world_position = to_world_space(ctx.cursor_position);
 
         if map.contains(cursor_world_position) {
             if let Some(layer_id) = &ctx.selected_layer {
                 let layer = map.layers.get(layer_id).unwrap();
-                let camera = scene::find_node_by_type::<EditorCamera>().unwrap();
-                let world_position = camera.to_world_space(ctx.cursor_position);
+                let world_position = to_world_space(ctx.cursor_position);
 
                 match layer.kind {
                     MapLayerKind::TileLayer => {
@@ -67,9 +65,7 @@ impl EditorTool for EraserTool {
     }
 
     fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        let cursor_world_position = to_world_space(ctx.cursor_position);
 
         if map.contains(cursor_world_position) {
             if let Some(layer_id) = &ctx.selected_layer {
@@ -79,7 +75,13 @@ impl EditorTool for EraserTool {
                     let coords = map.to_coords(cursor_world_position);
                     let position = map.to_position(coords);
 
-                    let outline_color = if layer.tiles[map.to_index(coords)].is_some() {
+                    let is_occupied = map
+                        .try_to_index(coords)
+                        .and_then(|i| layer.tiles.get(i))
+                        .map(|tile| tile.is_some())
+                        .unwrap_or(false);
+
+                    let outline_color = if is_occupied {
                         colors::YELLOW
                     } else {
                         colors::RED
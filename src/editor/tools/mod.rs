@@ -1,14 +1,25 @@
 use std::{any::TypeId, collections::HashMap};
 
 mod eraser;
+mod picker;
 mod placement;
+mod prefab;
+mod stamp;
 
 pub use eraser::EraserTool;
+pub use picker::TilePickerTool;
 pub use placement::{ObjectPlacementTool, SpawnPointPlacementTool, TilePlacementTool};
+pub use prefab::{PrefabPlacementTool, RegionSelectionTool};
+pub use stamp::StampTool;
+
+use ff_core::texture::{get_texture, try_get_texture, Texture2D};
 
 use super::{EditorAction, EditorContext, Map};
 
 static mut TOOL_INSTANCES: Option<HashMap<TypeId, Box<dyn EditorTool>>> = None;
+// This holds the ids of the registered tools, in the order they were first registered, which is
+// the order they will appear in, in the tool selector
+static mut TOOL_ORDER: Vec<TypeId> = Vec::new();
 
 unsafe fn get_tool_instance_directory() -> &'static mut HashMap<TypeId, Box<dyn EditorTool>> {
     if TOOL_INSTANCES.is_none() {
@@ -18,12 +29,49 @@ unsafe fn get_tool_instance_directory() -> &'static mut HashMap<TypeId, Box<dyn
     TOOL_INSTANCES.as_mut().unwrap()
 }
 
+/// This registers a tool with the editor and returns its id. Registered tools are listed in the
+/// tool selector, in the order they were first registered, so custom tools can be added without
+/// changes to the editor itself. Registering a tool of a type that is already registered will
+/// replace the existing instance but keep its place in the tool selector.
 pub fn add_tool_instance<T: EditorTool + 'static>(tool: T) -> TypeId {
     let id = TypeId::of::<T>();
-    unsafe { get_tool_instance_directory() }.insert(id, Box::new(tool));
+
+    let previous = unsafe { get_tool_instance_directory() }.insert(id, Box::new(tool));
+    if previous.is_none() {
+        unsafe { TOOL_ORDER.push(id) };
+    }
+
     id
 }
 
+/// This registers the tools that are built into the editor.
+pub fn add_default_tool_instances() {
+    add_tool_instance(TilePlacementTool::new());
+    add_tool_instance(ObjectPlacementTool::new());
+    add_tool_instance(SpawnPointPlacementTool::new());
+    add_tool_instance(EraserTool::new());
+    add_tool_instance(RegionSelectionTool::new());
+    add_tool_instance(PrefabPlacementTool::new());
+    add_tool_instance(StampTool::new());
+    add_tool_instance(TilePickerTool::new());
+}
+
+/// This returns the ids of all registered tools, in the order they were first registered.
+pub fn get_tool_ids() -> Vec<TypeId> {
+    unsafe { TOOL_ORDER.clone() }
+}
+
+/// This iterates over all registered tools, along with their ids, in the order they were first
+/// registered. Anything that lists the tools, like the tool selector, should be built from this,
+/// so that custom tools are included.
+pub fn iter_tools() -> impl Iterator<Item = (TypeId, &'static dyn EditorTool)> {
+    let tools = unsafe { get_tool_instance_directory() };
+
+    get_tool_ids()
+        .into_iter()
+        .filter_map(move |id| tools.get(&id).map(|tool| (id, tool.as_ref())))
+}
+
 pub fn get_tool_instance_of_id(id: &TypeId) -> &'static mut dyn EditorTool {
     unsafe { get_tool_instance_directory() }
         .get_mut(id)
@@ -53,35 +101,67 @@ pub const DEFAULT_TOOL_ICON_TEXTURE_ID: &str = "default_tool_icon";
 #[derive(Debug, Clone)]
 pub struct EditorToolParams {
     pub name: String,
-    pub icon_texture_id: String,
+    /// This is shown, along with the name, in the tooltip of the tool selector button
+    pub description: String,
+    /// This is the texture drawn on the tool selector button. If this is not set, or if no texture
+    /// with the id has been loaded, `DEFAULT_TOOL_ICON_TEXTURE_ID` will be used
+    pub icon_texture_id: Option<String>,
+    /// If this is set, and the texture has been loaded, it will be drawn next to the cursor, while
+    /// the tool is selected and the cursor is over the map
+    pub cursor_icon_texture_id: Option<String>,
     /// Set this to `true` if the tool should be activated every update when action button is held
     /// or `false` if it should only activate once per click
     pub is_continuous: bool,
 }
 
+impl EditorToolParams {
+    /// This returns the icon texture of the tool, falling back to the default tool icon
+    pub fn get_icon_texture(&self) -> Texture2D {
+        self.icon_texture_id
+            .as_ref()
+            .and_then(|texture_id| try_get_texture(texture_id))
+            .unwrap_or_else(|| get_texture(DEFAULT_TOOL_ICON_TEXTURE_ID))
+    }
+}
+
 impl Default for EditorToolParams {
     fn default() -> Self {
         EditorToolParams {
             name: "Unnamed Tool".to_string(),
-            icon_texture_id: DEFAULT_TOOL_ICON_TEXTURE_ID.to_string(),
+            description: String::new(),
+            icon_texture_id: None,
+            cursor_icon_texture_id: None,
             is_continuous: false,
         }
     }
 }
 
+/// Tools are registered with `add_tool_instance`, which will make them appear in the tool selector.
+/// Only one instance of each tool type can be registered, and the instance is kept for the lifetime
+/// of the editor, so tools can hold state between calls.
 pub trait EditorTool {
+    /// This should return the same params every time, as they are read by the tool selector, on
+    /// every draw.
     fn get_params(&self) -> &EditorToolParams;
 
+    /// This is called when the action button is pressed over the map, while the tool is selected,
+    /// or every update, while it is held, if the tool is continuous. Actions that target a locked
+    /// layer are rejected by the editor.
     fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction>;
 
+    /// This is called every update, while the tool is selected.
     fn update(&mut self, _map: &Map, _ctx: &EditorContext) -> Option<EditorAction> {
         None
     }
 
+    /// If this returns `false` the tool will not be listed in the tool selector and it will be
+    /// deselected if it is selected.
     fn is_available(&self, _map: &Map, _ctx: &EditorContext) -> bool {
         true
     }
 
+    /// This is called every draw, while the tool is selected, and should draw any cursor overlay,
+    /// in world space.
     fn draw_cursor(&mut self, _map: &Map, _ctx: &EditorContext) -> Option<EditorAction> {
         None
     }
@@ -0,0 +1,84 @@
+use ff_core::prelude::*;
+
+use super::{EditorAction, EditorContext, EditorTool, EditorToolParams};
+use crate::editor::to_world_space;
+use ff_core::map::{Map, MapLayerKind};
+
+#[derive(Default)]
+pub struct TilePickerTool {
+    params: EditorToolParams,
+}
+
+impl TilePickerTool {
+    pub fn new() -> Self {
+        let params = EditorToolParams {
+            name: "Pick Tile".to_string(),
+            description:
+                "Select the tile under the cursor, and optionally its attributes, for placement"
+                    .to_string(),
+            ..Default::default()
+        };
+
+        TilePickerTool { params }
+    }
+}
+
+impl EditorTool for TilePickerTool {
+    fn get_params(&self) -> &EditorToolParams {
+        &self.params
+    }
+
+    fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let Some(layer_id) = &ctx.selected_layer {
+                let coords = map.to_coords(cursor_world_position);
+
+                if let Some(tile) = map.get_tile(layer_id, coords.x, coords.y) {
+                    return Some(EditorAction::PickTile {
+                        id: tile.tile_id,
+                        tileset_id: tile.tileset_id.clone(),
+                        attributes: tile.attributes.clone(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn is_available(&self, map: &Map, ctx: &EditorContext) -> bool {
+        if let Some(layer_id) = &ctx.selected_layer {
+            let layer = map.layers.get(layer_id).unwrap();
+            return layer.kind == MapLayerKind::TileLayer;
+        }
+
+        false
+    }
+
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let Some(layer_id) = &ctx.selected_layer {
+                let coords = map.to_coords(cursor_world_position);
+
+                if map.get_tile(layer_id, coords.x, coords.y).is_some() {
+                    let position = map.to_position(coords);
+
+                    draw_rectangle_outline(
+                        position.x,
+                        position.y,
+                        map.tile_size.width,
+                        map.tile_size.height,
+                        2.0,
+                        colors::YELLOW,
+                    );
+                }
+            }
+        }
+
+        None
+    }
+}
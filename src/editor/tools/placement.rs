@@ -1,12 +1,14 @@
 use super::{EditorAction, EditorContext, EditorTool, EditorToolParams};
 
-use crate::editor::EditorCamera;
+use crate::editor::{get_player_collider_size, get_spawn_point_rect, to_world_space};
 
-use ff_core::macroquad::experimental::scene;
 use ff_core::map::{Map, MapLayerKind};
 use ff_core::prelude::*;
 use ff_core::rand::ChooseRandom;
 
+/// The alpha of the preview that is drawn at the cursor, of what will be placed on click
+const PLACEMENT_PREVIEW_ALPHA: f32 = 0.5;
+
 #[derive(Default)]
 pub struct TilePlacementTool {
     params: EditorToolParams,
@@ -17,7 +19,9 @@ impl TilePlacementTool {
     pub fn new() -> Self {
         let params = EditorToolParams {
             name: "Place Tiles".to_string(),
-            icon_texture_id: "tile_placement_tool_icon".to_string(),
+            description: "Paint the selected tile onto the selected tile layer".to_string(),
+            icon_texture_id: Some("tile_placement_tool_icon".to_string()),
+            cursor_icon_texture_id: Some("tile_placement_tool_icon".to_string()),
             is_continuous: true,
         };
 
@@ -34,14 +38,11 @@ impl EditorTool for TilePlacementTool {
     }
 
     fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        let cursor_world_position = to_world_space(ctx.cursor_position);
 
         if map.contains(cursor_world_position) {
             if let Some(layer_id) = &ctx.selected_layer {
-                let camera = scene::find_node_by_type::<EditorCamera>().unwrap();
-                let world_position = camera.to_world_space(ctx.cursor_position);
+                let world_position = to_world_space(ctx.cursor_position);
 
                 if let Some(tileset_id) = &ctx.selected_tileset {
                     if let Some(tile_id) = ctx.selected_tile {
@@ -52,6 +53,7 @@ impl EditorTool for TilePlacementTool {
                             layer_id: layer_id.clone(),
                             tileset_id: tileset_id.clone(),
                             coords,
+                            attributes: ctx.picked_tile_attributes.clone(),
                         });
                     }
                 }
@@ -67,9 +69,7 @@ impl EditorTool for TilePlacementTool {
 
         if self.is_available(map, ctx) {
             if let Some(tileset_id) = &ctx.selected_tileset {
-                let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-                    .unwrap()
-                    .to_world_space(ctx.cursor_position);
+                let cursor_world_position = to_world_space(ctx.cursor_position);
                 let coords = map.to_coords(cursor_world_position);
 
                 if self.coords != Some(coords) {
@@ -139,9 +139,7 @@ impl EditorTool for TilePlacementTool {
     }
 
     fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        let cursor_world_position = to_world_space(ctx.cursor_position);
 
         if map.contains(cursor_world_position) {
             if let Some(layer_id) = &ctx.selected_layer {
@@ -152,9 +150,7 @@ impl EditorTool for TilePlacementTool {
                         if let Some(tile_id) = ctx.selected_tile {
                             let tileset = map.tilesets.get(tileset_id).unwrap();
 
-                            let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-                                .unwrap()
-                                .to_world_space(ctx.cursor_position);
+                            let cursor_world_position = to_world_space(ctx.cursor_position);
 
                             let coords = map.to_coords(cursor_world_position);
                             let position = map.to_position(coords);
@@ -162,23 +158,33 @@ impl EditorTool for TilePlacementTool {
                             let texture_coords = tileset.get_texture_coords(tile_id);
                             let texture = get_texture(&tileset.texture_id);
 
-                            let source_rect = Rect::new(
-                                texture_coords.x,
-                                texture_coords.y,
-                                map.tile_size.width,
-                                map.tile_size.height,
-                            );
+                            let source_rect =
+                                Rect::from_point_size(texture_coords, tileset.tile_size);
+
+                            // The preview is tinted like the placed tile will be drawn
+                            let mut tint = layer.tint * map.ambient_color;
+                            tint.alpha *= PLACEMENT_PREVIEW_ALPHA;
 
                             draw_texture(
                                 position.x,
                                 position.y,
                                 texture,
                                 DrawTextureParams {
+                                    tint: Some(tint),
                                     dest_size: Some(map.tile_size),
                                     source: Some(source_rect),
                                     ..Default::default()
                                 },
-                            )
+                            );
+
+                            draw_rectangle_outline(
+                                position.x,
+                                position.y,
+                                map.tile_size.width,
+                                map.tile_size.height,
+                                2.0,
+                                colors::YELLOW,
+                            );
                         }
                     }
                 }
@@ -198,12 +204,41 @@ impl ObjectPlacementTool {
     pub fn new() -> Self {
         let params = EditorToolParams {
             name: "Place Objects".to_string(),
-            icon_texture_id: "object_placement_tool_icon".to_string(),
+            description: "Create an object on the selected object layer".to_string(),
+            icon_texture_id: Some("object_placement_tool_icon".to_string()),
+            cursor_icon_texture_id: Some("object_placement_tool_icon".to_string()),
             ..Default::default()
         };
 
         ObjectPlacementTool { params }
     }
+
+    // This returns the world position that an object will be created at, if the cursor is over the
+    // map, snapped to the grid, if snapping is enabled
+    fn get_position(map: &Map, ctx: &EditorContext) -> Option<Vec2> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if !map.contains(cursor_world_position) {
+            return None;
+        }
+
+        let mut position = cursor_world_position;
+
+        if ctx.should_snap_to_grid {
+            position = map.snap_position_with_origin(
+                position,
+                ctx.snap_increment.as_f32(),
+                ctx.grid_origin_offset,
+            );
+        }
+
+        let rect = Rect::from_point_size(map.world_offset, map.get_size());
+        if rect.contains(position) {
+            Some(position)
+        } else {
+            None
+        }
+    }
 }
 
 impl EditorTool for ObjectPlacementTool {
@@ -212,36 +247,17 @@ impl EditorTool for ObjectPlacementTool {
     }
 
     fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        if let Some(layer_id) = ctx.selected_layer.clone() {
+            let layer = map.layers.get(&layer_id).unwrap();
 
-        if map.contains(cursor_world_position) {
-            if let Some(layer_id) = ctx.selected_layer.clone() {
-                let layer = map.layers.get(&layer_id).unwrap();
-
-                if layer.kind == MapLayerKind::ObjectLayer {
-                    let mut position = scene::find_node_by_type::<EditorCamera>()
-                        .unwrap()
-                        .to_world_space(ctx.cursor_position);
-
-                    let rect = Rect::new(
-                        map.world_offset.x,
-                        map.world_offset.y,
-                        map.grid_size.width as f32 * map.tile_size.width,
-                        map.grid_size.height as f32 * map.tile_size.height,
-                    );
-
-                    if ctx.should_snap_to_grid {
-                        let coords = map.to_coords(position);
-                        position = map.to_position(coords);
-                    }
+            if layer.kind == MapLayerKind::ObjectLayer {
+                if let Some(position) = Self::get_position(map, ctx) {
+                    // Object positions are stored in map-local space
+                    let position = position - map.world_offset;
 
-                    if rect.contains(position) {
-                        let action = EditorAction::OpenCreateObjectWindow { position, layer_id };
+                    let action = EditorAction::OpenCreateObjectWindow { position, layer_id };
 
-                        return Some(action);
-                    }
+                    return Some(action);
                 }
             }
         }
@@ -257,6 +273,32 @@ impl EditorTool for ObjectPlacementTool {
 
         false
     }
+
+    // The kind of object is chosen after clicking, so only the spot it will be created at is shown
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        if let Some(position) = Self::get_position(map, ctx) {
+            let color = Color::new(1.0, 1.0, 0.0, PLACEMENT_PREVIEW_ALPHA);
+
+            draw_rectangle(
+                position.x,
+                position.y,
+                map.tile_size.width,
+                map.tile_size.height,
+                color,
+            );
+
+            draw_rectangle_outline(
+                position.x,
+                position.y,
+                map.tile_size.width,
+                map.tile_size.height,
+                2.0,
+                colors::YELLOW,
+            );
+        }
+
+        None
+    }
 }
 
 pub struct SpawnPointPlacementTool {
@@ -267,12 +309,36 @@ impl SpawnPointPlacementTool {
     pub fn new() -> Self {
         let params = EditorToolParams {
             name: "Place Spawn Point".to_string(),
-            icon_texture_id: "spawn_point_placement_tool_icon".to_string(),
+            description: "Add a player spawn point".to_string(),
+            icon_texture_id: Some("spawn_point_placement_tool_icon".to_string()),
+            cursor_icon_texture_id: Some("spawn_point_placement_tool_icon".to_string()),
             ..Default::default()
         };
 
         SpawnPointPlacementTool { params }
     }
+
+    // This returns the world position that a spawn point will be created at, snapped to the grid,
+    // if snapping is enabled
+    fn get_position(map: &Map, ctx: &EditorContext) -> Vec2 {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        // Spawn points are centered horizontally on their position, so only the height of the
+        // icon is offset, to place it above the cursor
+        let frame_size = get_texture("spawn_point_icon").frame_size();
+
+        let mut position = cursor_world_position - vec2(0.0, frame_size.height);
+
+        if ctx.should_snap_to_grid {
+            position = map.snap_position_with_origin(
+                position,
+                ctx.snap_increment.as_f32(),
+                ctx.grid_origin_offset,
+            );
+        }
+
+        position
+    }
 }
 
 impl EditorTool for SpawnPointPlacementTool {
@@ -280,19 +346,43 @@ impl EditorTool for SpawnPointPlacementTool {
         &self.params
     }
 
-    fn get_action(&mut self, _map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
-        // TODO: Snap to grid
+    fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let position = Self::get_position(map, ctx);
+
+        // Spawn point positions are stored in map-local space
+        let action = EditorAction::CreateSpawnPoint(position - map.world_offset);
+
+        Some(action)
+    }
+
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        if !map.contains(to_world_space(ctx.cursor_position)) {
+            return None;
+        }
 
-        let cursor_world_position = scene::find_node_by_type::<EditorCamera>()
-            .unwrap()
-            .to_world_space(ctx.cursor_position);
+        let position = Self::get_position(map, ctx);
+        let rect = get_spawn_point_rect(position, get_player_collider_size());
 
         let texture = get_texture("spawn_point_icon");
-        let texture_size = texture.size();
-        let offset = vec2(texture_size.width / 2.0, texture_size.height);
+        let frame_size = texture.frame_size();
 
-        let action = EditorAction::CreateSpawnPoint(cursor_world_position - offset);
+        let source_rect = Rect::new(0.0, 0.0, frame_size.width, frame_size.height);
 
-        Some(action)
+        // This matches how the editor draws the icons of existing spawn points
+        draw_texture(
+            position.x - frame_size.width / 2.0,
+            rect.y + rect.height - frame_size.height,
+            texture,
+            DrawTextureParams {
+                tint: Some(Color::new(1.0, 1.0, 1.0, PLACEMENT_PREVIEW_ALPHA)),
+                dest_size: Some(frame_size),
+                source: Some(source_rect),
+                ..Default::default()
+            },
+        );
+
+        draw_rectangle_outline(rect.x, rect.y, rect.width, rect.height, 2.0, colors::YELLOW);
+
+        None
     }
 }
@@ -0,0 +1,179 @@
+use ff_core::prelude::*;
+
+use super::{EditorAction, EditorContext, EditorTool, EditorToolParams};
+use crate::editor::library::try_get_library_asset;
+use crate::editor::prefab::Prefab;
+use crate::editor::to_world_space;
+use ff_core::map::Map;
+
+const PREFAB_PREVIEW_ALPHA: f32 = 0.5;
+
+/// This selects a rectangular region of the map, in two clicks, one for each corner.
+/// The selected region is used when capturing prefabs.
+#[derive(Default)]
+pub struct RegionSelectionTool {
+    params: EditorToolParams,
+    start_coords: Option<UVec2>,
+}
+
+impl RegionSelectionTool {
+    pub fn new() -> Self {
+        let params = EditorToolParams {
+            name: "Select Region".to_string(),
+            description: "Select a region of the map, to save as a prefab or stamp".to_string(),
+            ..Default::default()
+        };
+
+        RegionSelectionTool {
+            params,
+            start_coords: None,
+        }
+    }
+
+    fn get_region(start: UVec2, end: UVec2) -> URect {
+        let min = start.min(end);
+        let max = start.max(end);
+        URect::new(min.x, min.y, max.x - min.x + 1, max.y - min.y + 1)
+    }
+}
+
+impl EditorTool for RegionSelectionTool {
+    fn get_params(&self) -> &EditorToolParams {
+        &self.params
+    }
+
+    fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            let coords = map.to_coords(cursor_world_position);
+
+            if let Some(start_coords) = self.start_coords.take() {
+                let region = Self::get_region(start_coords, coords);
+                return Some(EditorAction::SelectRegion(Some(region)));
+            }
+
+            self.start_coords = Some(coords);
+            return Some(EditorAction::SelectRegion(None));
+        }
+
+        None
+    }
+
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            let coords = map.to_coords(cursor_world_position);
+            let region = Self::get_region(self.start_coords.unwrap_or(coords), coords);
+            let position = map.to_position(region.point());
+
+            draw_rectangle_outline(
+                position.x,
+                position.y,
+                region.width as f32 * map.tile_size.width,
+                region.height as f32 * map.tile_size.height,
+                2.0,
+                colors::YELLOW,
+            );
+        }
+
+        None
+    }
+}
+
+/// This stamps the selected prefab onto the map, with its top-left corner at the cursor.
+#[derive(Default)]
+pub struct PrefabPlacementTool {
+    params: EditorToolParams,
+}
+
+impl PrefabPlacementTool {
+    pub fn new() -> Self {
+        let params = EditorToolParams {
+            name: "Place Prefab".to_string(),
+            description: "Place the selected prefab".to_string(),
+            ..Default::default()
+        };
+
+        PrefabPlacementTool { params }
+    }
+}
+
+impl EditorTool for PrefabPlacementTool {
+    fn get_params(&self) -> &EditorToolParams {
+        &self.params
+    }
+
+    fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let Some(prefab_id) = &ctx.selected_prefab {
+                let coords = map.to_coords(cursor_world_position);
+
+                return Some(EditorAction::StampPrefab {
+                    id: prefab_id.clone(),
+                    coords,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn is_available(&self, _map: &Map, ctx: &EditorContext) -> bool {
+        ctx.selected_prefab.is_some()
+    }
+
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let Some(prefab) = ctx
+                .selected_prefab
+                .as_deref()
+                .and_then(try_get_library_asset::<Prefab>)
+            {
+                let coords = map.to_coords(cursor_world_position);
+                let position = map.to_position(coords);
+
+                let tint = Color::new(1.0, 1.0, 1.0, PREFAB_PREVIEW_ALPHA);
+
+                for tile in &prefab.tiles {
+                    if let Some(tileset) = map.tilesets.get(&tile.tileset_id) {
+                        let tile_position = map.to_position(coords + tile.coords);
+
+                        let texture_coords = tileset.get_texture_coords(tile.tile_id);
+                        let texture = get_texture(&tileset.texture_id);
+
+                        let source_rect = Rect::from_point_size(texture_coords, tileset.tile_size);
+
+                        draw_texture(
+                            tile_position.x,
+                            tile_position.y,
+                            texture,
+                            DrawTextureParams {
+                                tint: Some(tint),
+                                dest_size: Some(map.tile_size),
+                                source: Some(source_rect),
+                                ..Default::default()
+                            },
+                        )
+                    }
+                }
+
+                draw_rectangle_outline(
+                    position.x,
+                    position.y,
+                    prefab.size.width as f32 * map.tile_size.width,
+                    prefab.size.height as f32 * map.tile_size.height,
+                    2.0,
+                    colors::YELLOW,
+                );
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,115 @@
+use ff_core::prelude::*;
+
+use super::{EditorAction, EditorContext, EditorTool, EditorToolParams};
+use crate::editor::library::try_get_library_asset;
+use crate::editor::stamp::Stamp;
+use crate::editor::to_world_space;
+use ff_core::map::{Map, MapLayerKind};
+
+const STAMP_PREVIEW_ALPHA: f32 = 0.5;
+
+/// This paints the selected stamp onto the selected tile layer, with its top-left corner at the
+/// cursor.
+#[derive(Default)]
+pub struct StampTool {
+    params: EditorToolParams,
+}
+
+impl StampTool {
+    pub fn new() -> Self {
+        let params = EditorToolParams {
+            name: "Stamp".to_string(),
+            description: "Paint the selected stamp onto the selected tile layer".to_string(),
+            ..Default::default()
+        };
+
+        StampTool { params }
+    }
+}
+
+impl EditorTool for StampTool {
+    fn get_params(&self) -> &EditorToolParams {
+        &self.params
+    }
+
+    fn get_action(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let (Some(stamp_id), Some(layer_id)) = (&ctx.selected_stamp, &ctx.selected_layer) {
+                let coords = map.to_coords(cursor_world_position);
+
+                return Some(EditorAction::PaintStamp {
+                    id: stamp_id.clone(),
+                    layer_id: layer_id.clone(),
+                    coords,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn is_available(&self, map: &Map, ctx: &EditorContext) -> bool {
+        if ctx.selected_stamp.is_none() {
+            return false;
+        }
+
+        if let Some(layer_id) = &ctx.selected_layer {
+            return map.get_layer_kind(layer_id) == Some(MapLayerKind::TileLayer);
+        }
+
+        false
+    }
+
+    fn draw_cursor(&mut self, map: &Map, ctx: &EditorContext) -> Option<EditorAction> {
+        let cursor_world_position = to_world_space(ctx.cursor_position);
+
+        if map.contains(cursor_world_position) {
+            if let Some(stamp) = ctx
+                .selected_stamp
+                .as_deref()
+                .and_then(try_get_library_asset::<Stamp>)
+            {
+                let coords = map.to_coords(cursor_world_position);
+                let position = map.to_position(coords);
+
+                let tint = Color::new(1.0, 1.0, 1.0, STAMP_PREVIEW_ALPHA);
+
+                for (offset, tileset_id, tile_id) in stamp.get_cells() {
+                    if let Some(tileset) = map.tilesets.get(tileset_id) {
+                        let tile_position = map.to_position(coords + offset);
+
+                        let texture_coords = tileset.get_texture_coords(tile_id);
+                        let texture = get_texture(&tileset.texture_id);
+
+                        let source_rect = Rect::from_point_size(texture_coords, tileset.tile_size);
+
+                        draw_texture(
+                            tile_position.x,
+                            tile_position.y,
+                            texture,
+                            DrawTextureParams {
+                                tint: Some(tint),
+                                dest_size: Some(map.tile_size),
+                                source: Some(source_rect),
+                                ..Default::default()
+                            },
+                        )
+                    }
+                }
+
+                draw_rectangle_outline(
+                    position.x,
+                    position.y,
+                    stamp.size.width as f32 * map.tile_size.width,
+                    stamp.size.height as f32 * map.tile_size.height,
+                    2.0,
+                    colors::YELLOW,
+                );
+            }
+        }
+
+        None
+    }
+}
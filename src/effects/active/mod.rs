@@ -161,7 +161,7 @@ pub fn spawn_active_effect(
 
             if spread != 0.0 {
                 let rad = deg_to_rad(spread);
-                let spread = ff_core::rand::gen_range(-rad, rad);
+                let spread = rng::gen_range(-rad, rad);
 
                 velocity = rotate_vector(velocity, spread);
             }
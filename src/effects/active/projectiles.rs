@@ -110,6 +110,9 @@ pub fn spawn_projectile(
 
     let mut transform = Transform::from(origin);
 
+    // Sprites are flipped when moving left, so particle emitters are flipped along with them
+    let is_flipped_x = matches!(kind, ProjectileKind::Sprite { .. }) && velocity.x < 0.0;
+
     let body_params = match kind {
         ProjectileKind::Rect { width, height, .. } => RigidBodyParams {
             offset: vec2(-width, -height) / 2.0,
@@ -131,8 +134,6 @@ pub fn spawn_projectile(
 
             let offset = meta.offset - (vec2(size.width, size.height) / 2.0);
 
-            let is_flipped_x = velocity.x < 0.0;
-
             if can_rotate {
                 let mut direction = Vec2::ZERO;
 
@@ -180,6 +181,7 @@ pub fn spawn_projectile(
     for params in params.particle_effects {
         let mut emitter = ParticleEmitter::from(params);
         emitter.is_active = true;
+        emitter.is_flipped_x = is_flipped_x;
 
         particle_emitters.push(emitter);
     }
@@ -91,7 +91,7 @@ pub fn spawn_triggered_effect(
 
     if meta.spread != 0.0 {
         let rad = deg_to_rad(meta.spread);
-        let spread = rand::gen_range(-rad, rad);
+        let spread = rng::gen_range(-rad, rad);
 
         velocity = rotate_vector(velocity, spread);
     }
@@ -156,9 +156,7 @@ pub fn spawn_triggered_effect(
             .map(ParticleEmitter::new)
             .collect::<Vec<_>>();
 
-        for emitter in &mut particle_emitters {
-            emitter.activate();
-        }
+        activate_all_emitters(&mut particle_emitters);
 
         world.insert_one(entity, particle_emitters)?
     }
@@ -212,12 +210,7 @@ pub fn fixed_update_triggered_effects(
         }
 
         if !effect.is_triggered && effect.activation_timer >= effect.activation_delay {
-            let collider = Rect::new(
-                transform.position.x,
-                transform.position.y,
-                body.size.width,
-                body.size.height,
-            );
+            let collider = Rect::from_point_size(transform.position, body.size);
 
             let can_be_triggered_by_player =
                 effect.trigger.contains(&TriggeredEffectTrigger::Player);
@@ -232,8 +225,7 @@ pub fn fixed_update_triggered_effects(
 
                 'players: for (pe, is_facing_left, position, size) in players.clone() {
                     if !should_exclude_owner || pe != effect.owner {
-                        let player_collider =
-                            Rect::new(position.x, position.y, size.width, size.height);
+                        let player_collider = Rect::from_point_size(position, size);
 
                         if collider.overlaps(&player_collider) {
                             let mut should_trigger = false;
@@ -350,8 +342,7 @@ pub fn update_triggered_effects(world: &mut World) {
                 if *has_weapon || (is_on_left != *player_is_facing_left && opts.must_be_facing) {
                     continue;
                 }
-                let player_collider =
-                    Rect::new(player_pos.x, player_pos.y, size.width, size.height);
+                let player_collider = Rect::from_point_size(*player_pos, *size);
 
                 if collider.overlaps(&player_collider) && controller.should_pickup {
                     if let Some(item_id) = &opts.equips_item {
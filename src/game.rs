@@ -16,7 +16,7 @@ use crate::items::try_get_item;
 use crate::player::{
     draw_weapons_hud, spawn_player, update_player_animations, update_player_controllers,
     update_player_events, update_player_inventory, update_player_passive_effects,
-    update_player_states, PlayerParams,
+    update_player_states, Player, PlayerParams,
 };
 use crate::{Map, MapLayerKind, MapObjectKind};
 
@@ -72,25 +72,48 @@ pub const LOCAL_GAME_STATE_ID: &str = "local_game";
 pub const NETWORK_GAME_CLIENT_STATE_ID: &str = "network_game_client";
 pub const NETWORK_GAME_HOST_STATE_ID: &str = "network_game_host";
 
+/// The id of the game mode of local games, as listed in the supported game modes of a map
+pub const DEATHMATCH_GAME_MODE_ID: &str = "deathmatch";
+
 #[derive(Clone)]
 pub struct StatePayload {
     players: Vec<PlayerParams>,
+    /// The seed of the match-global RNG. This must be the same on all peers in a network game.
+    rng_seed: u64,
 }
 
 #[allow(dead_code)]
 const GAME_MENU_ID: &str = "game_menu";
 
+/// This is played when the map does not specify any music, or if its music is not loaded
+const DEFAULT_MUSIC_ID: &str = "fish_tide";
+
+/// This builds the game state of a match. The match-global RNG is seeded with `rng_seed`, if one is
+/// specified, or with a newly generated seed, if not. In a network game, the host generates the
+/// seed, which it sends to the clients with the start of the match, from `rng::rng_seed`, and the
+/// clients must pass that seed here, so that the match plays out the same on all peers.
 pub fn build_state_for_game_mode(
     game_mode: GameMode,
     map: Map,
     players: &[PlayerParams],
+    rng_seed: Option<u64>,
 ) -> Result<DefaultGameState<StatePayload>> {
+    if game_mode == GameMode::NetworkClient && rng_seed.is_none() {
+        return Err(Error::new_const(
+            ErrorKind::General,
+            &"Game: A network client must use the RNG seed received from the host",
+        ));
+    }
+
+    let rng_seed = rng_seed.unwrap_or_else(rng::generate_rng_seed);
+
     let mut builder = DefaultGameStateBuilder::new(game_mode.into())
         .with_default_systems()
         .with_map(map)
         .with_empty_world()
         .with_payload(StatePayload {
             players: players.to_vec(),
+            rng_seed,
         });
 
     #[cfg(feature = "macroquad-backend")]
@@ -110,7 +133,7 @@ pub fn build_state_for_game_mode(
             MenuEntry {
                 index: GAME_MENU_OPTION_QUIT,
                 title: "Quit".to_string(),
-                action: || dispatch_event(Event::Quit),
+                action: || dispatch_event(Event::QuitRequested),
                 ..Default::default()
             },
         ],
@@ -154,13 +177,30 @@ pub fn build_state_for_game_mode(
         .with_constructor(|world, map, payload| -> Result<()> {
             let payload = payload.unwrap();
 
-            let res = init_game_world(world.unwrap(), map.unwrap().clone(), &payload.players);
+            rng::set_rng_seed(payload.rng_seed);
+
+            let map = map.unwrap();
+
+            let res = init_game_world(world.unwrap(), map.clone(), &payload.players);
             if let Err(err) = res {
                 #[cfg(debug_assertions)]
                 println!("ERROR: init_game_world: {}", err);
             }
 
-            play_sound("fish_tide", true);
+            let music_id = match map.music_id.as_deref() {
+                Some(music_id) if try_get_sound(music_id).is_some() => music_id,
+                Some(music_id) => {
+                    println!(
+                        "WARNING: Map music '{}' is not loaded, playing the default music",
+                        music_id
+                    );
+
+                    DEFAULT_MUSIC_ID
+                }
+                None => DEFAULT_MUSIC_ID,
+            };
+
+            play_sound(music_id, true);
 
             Ok(())
         })
@@ -179,15 +219,19 @@ pub fn init_game_world(world: &mut World, map: Map, players: &[PlayerParams]) ->
     spawn_map_objects(world, &map)?;
 
     for params in players {
-        let position = map.get_random_spawn_point();
+        let spawn_point = map.get_random_spawn_point();
 
-        spawn_player(
+        let player_entity = spawn_player(
             world,
             params.index,
-            position,
+            map.world_offset + spawn_point.position,
             params.controller.clone(),
             params.character.clone(),
         );
+
+        if let Ok(mut player) = world.get_mut::<Player>(player_entity) {
+            player.is_facing_left = spawn_point.is_facing_left;
+        }
     }
 
     world.spawn((Transform::new(Vec2::ZERO, 0.0), CameraController::new()));
@@ -198,15 +242,22 @@ pub fn init_game_world(world: &mut World, map: Map, players: &[PlayerParams]) ->
 pub fn spawn_map_objects(world: &mut World, map: &Map) -> Result<Vec<Entity>> {
     let mut objects = Vec::new();
 
-    for layer in map.layers.values() {
+    // Objects are spawned from the bottom layer up, in the order they are stored in, as drawables
+    // with the same draw order are drawn in the order they were spawned in
+    for layer_id in map.draw_order.iter().rev() {
+        let layer = map.layers.get(layer_id).unwrap();
+
         if layer.is_visible && layer.kind == MapLayerKind::ObjectLayer {
             for map_object in &layer.objects {
+                let position = map.world_offset + map_object.position;
+
                 match map_object.kind {
                     MapObjectKind::Decoration => {
                         let res = try_get_decoration(&map_object.id);
 
                         if let Some(params) = res.cloned() {
-                            let decoration = spawn_decoration(world, map_object.position, params);
+                            let decoration =
+                                spawn_decoration(world, position, params, map_object.scale);
                             objects.push(decoration);
                         } else {
                             #[cfg(debug_assertions)]
@@ -216,8 +267,12 @@ pub fn spawn_map_objects(world: &mut World, map: &Map) -> Result<Vec<Entity>> {
                     MapObjectKind::Item => {
                         let res = try_get_item(&map_object.id);
 
-                        if let Some(params) = res.cloned() {
-                            let item = spawn_item(world, map_object.position, params)?;
+                        if let Some(mut params) = res.cloned() {
+                            if let Some(collider_size) = map_object.collider_size {
+                                params.collider_size = collider_size;
+                            }
+
+                            let item = spawn_item(world, position, params)?;
                             objects.push(item);
                         } else {
                             #[cfg(debug_assertions)]
@@ -226,15 +281,15 @@ pub fn spawn_map_objects(world: &mut World, map: &Map) -> Result<Vec<Entity>> {
                     }
                     MapObjectKind::Environment => match map_object.id.as_str() {
                         "sproinger" => {
-                            let sproinger = spawn_sproinger(world, map_object.position)?;
+                            let sproinger = spawn_sproinger(world, position)?;
                             objects.push(sproinger);
                         }
                         "crab" => {
-                            let crab = spawn_crab(world, map_object.position)?;
+                            let crab = spawn_crab(world, position)?;
                             objects.push(crab);
                         }
                         "fish_school" => {
-                            let fish_school = spawn_fish_school(world, map_object.position)?;
+                            let fish_school = spawn_fish_school(world, position)?;
                             objects.push(fish_school);
                         }
                         _ => {
@@ -12,9 +12,10 @@ use ff_core::gui::{
     WINDOW_MARGIN_V,
 };
 use ff_core::image::Image;
-use ff_core::map::{get_map, iter_maps, MapResource};
+use ff_core::map::{get_map, get_map_indices_for, iter_maps, MapResource};
 
 use crate::player::{PlayerAnimations, PlayerControllerKind, PlayerParams};
+use crate::game::DEATHMATCH_GAME_MODE_ID;
 use crate::{build_state_for_game_mode, gui, GameMode, GuiTheme, Map};
 
 use ff_core::input::{is_gamepad_button_pressed, GameInputScheme};
@@ -402,7 +403,7 @@ impl MainMenuState {
                         let sprite = &mut self.character_select_state.sprites[i];
 
                         // TODO: Calculate scale from a fixed target size, based on ui layout
-                        sprite.scale = 2.0;
+                        sprite.scale = Vec2::splat(2.0);
 
                         let animation_size = sprite.size();
                         let animation_transform = {
@@ -583,7 +584,14 @@ impl MainMenuState {
                 || gamepad.digital_inputs.just_activated(Button::Start.into());
         }
 
-        let map_cnt = iter_maps().len();
+        // Only the maps made for the game mode and player count are listed, when starting a game
+        let map_indices = if self.current_level == MainMenuLevel::GameMapSelect {
+            get_map_indices_for(DEATHMATCH_GAME_MODE_ID, self.player_cnt as u32)
+        } else {
+            (0..iter_maps().len()).collect()
+        };
+
+        let map_cnt = map_indices.len();
 
         let gui_theme = get_gui_theme();
         root_ui().push_skin(&gui_theme.map_selection);
@@ -709,7 +717,7 @@ impl MainMenuState {
                 let end = (begin as usize + entries_per_page).clamp(begin, map_cnt);
 
                 for (pi, i) in (begin..end).enumerate() {
-                    let map_entry = get_map(i);
+                    let map_entry = get_map(map_indices[i]);
                     let is_hovered = self.map_select_state.hovered == i as i32;
 
                     let mut rect = Rect::new(
@@ -742,14 +750,14 @@ impl MainMenuState {
                         map_entry.preview.deref().into();
 
                     if widgets::Button::new(texture)
-                        .size(rect.size())
+                        .size(rect.size().as_vec2())
                         .position(rect.point())
                         .ui(&mut *root_ui())
                         || start
                     {
                         root_ui().pop_skin();
 
-                        let res = get_map(self.map_select_state.selected);
+                        let res = get_map(map_indices[self.map_select_state.selected]);
                         return Some(res.map.clone());
                     }
                 }
@@ -915,7 +923,7 @@ impl GameState for MainMenuState {
                 match res {
                     MainMenuResult::LocalGame { map, players } => {
                         let state =
-                            build_state_for_game_mode(GameMode::Local, map, &players).unwrap();
+                            build_state_for_game_mode(GameMode::Local, map, &players, None).unwrap();
                         dispatch_event(Event::state_transition(state));
                     }
                     MainMenuResult::Editor { map: _ } => {
@@ -926,7 +934,7 @@ impl GameState for MainMenuState {
                         // dispatch_event(Event::ReloadResources);
                     }
                     MainMenuResult::Quit => {
-                        dispatch_event(Event::Quit);
+                        dispatch_event(Event::QuitRequested);
                     }
                 }
             }
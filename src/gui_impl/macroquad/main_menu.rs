@@ -9,8 +9,9 @@ use ff_core::gui::background::draw_main_menu_background;
 use ff_core::gui::{
     get_gui_theme, Menu, MenuEntry, Panel, WINDOW_BG_COLOR, WINDOW_MARGIN_H, WINDOW_MARGIN_V,
 };
-use ff_core::map::{get_map, iter_maps};
+use ff_core::map::{get_map, get_map_indices_for, iter_maps};
 
+use crate::game::DEATHMATCH_GAME_MODE_ID;
 use crate::player::{PlayerControllerKind, PlayerParams};
 use crate::{build_state_for_game_mode, GameMode, Map};
 
@@ -404,7 +405,7 @@ impl MainMenuState {
                         let sprite = &mut self.character_select_state.sprites[i];
 
                         // TODO: Calculate scale from a fixed target size, based on ui layout
-                        sprite.scale = 2.0;
+                        sprite.scale = Vec2::splat(2.0);
 
                         let animation_size = sprite.size();
                         let animation_transform = {
@@ -583,7 +584,14 @@ impl MainMenuState {
                 || gamepad.digital_inputs.just_activated(Button::Start.into());
         }
 
-        let map_cnt = iter_maps().len();
+        // Only the maps made for the game mode and player count are listed, when starting a game
+        let map_indices = if self.current_level == MainMenuLevel::GameMapSelect {
+            get_map_indices_for(DEATHMATCH_GAME_MODE_ID, self.player_cnt as u32)
+        } else {
+            (0..iter_maps().len()).collect()
+        };
+
+        let map_cnt = map_indices.len();
 
         let gui_theme = get_gui_theme();
         root_ui().push_skin(&gui_theme.map_selection);
@@ -700,7 +708,7 @@ impl MainMenuState {
                         - label_size;
 
                     widgets::Label::new(&pagination_label)
-                        .position(label_position)
+                        .position(to_window_position(label_position))
                         .ui(&mut *root_ui());
                 }
 
@@ -709,7 +717,7 @@ impl MainMenuState {
                 let end = (begin as usize + entries_per_page).clamp(begin, map_cnt);
 
                 for (pi, i) in (begin..end).enumerate() {
-                    let map_entry = get_map(i);
+                    let map_entry = get_map(map_indices[i]);
                     let is_hovered = self.map_select_state.hovered == i as i32;
 
                     let mut rect = Rect::new(
@@ -742,14 +750,14 @@ impl MainMenuState {
                         map_entry.preview.deref().into();
 
                     if widgets::Button::new(texture)
-                        .size(rect.size())
-                        .position(rect.point())
+                        .size(rect.size().as_vec2())
+                        .position(to_window_position(rect.point()))
                         .ui(&mut *root_ui())
                         || start
                     {
                         root_ui().pop_skin();
 
-                        let res = get_map(self.map_select_state.selected);
+                        let res = get_map(map_indices[self.map_select_state.selected]);
                         return Some(res.map.clone());
                     }
                 }
@@ -781,7 +789,7 @@ impl MainMenuState {
                 let position = vec2((viewport_size.width - size.width) / 2.0, 35.0);
 
                 widgets::Texture::new(texture.deref().into())
-                    .position(position)
+                    .position(to_window_position(position))
                     .size(size.width, size.height)
                     .ui(&mut *root_ui());
             }
@@ -901,7 +909,8 @@ impl GameState for MainMenuState {
         if let Some(res) = self.draw_current() {
             match res {
                 MainMenuResult::LocalGame { map, players } => {
-                    let state = build_state_for_game_mode(GameMode::Local, map, &players).unwrap();
+                    let state =
+                        build_state_for_game_mode(GameMode::Local, map, &players, None).unwrap();
                     dispatch_event(Event::state_transition(state));
                 }
                 MainMenuResult::Editor { map: _ } => {
@@ -912,7 +921,7 @@ impl GameState for MainMenuState {
                     // dispatch_event(Event::ReloadResources);
                 }
                 MainMenuResult::Quit => {
-                    dispatch_event(Event::Quit);
+                    dispatch_event(Event::QuitRequested);
                 }
             }
         }
@@ -463,11 +463,7 @@ pub fn fire_weapon(world: &mut World, entity: Entity, owner: Entity) -> Result<(
                 }
             }
 
-            if let Ok(mut particle_emitters) = world.get_mut::<Vec<ParticleEmitter>>(entity) {
-                for emitter in particle_emitters.iter_mut() {
-                    emitter.activate();
-                }
-            }
+            set_entity_emitters_active(world, entity, true);
 
             effects = weapon.effects.clone();
         }
@@ -49,6 +49,10 @@ const CONFIG_FILE_ENV_VAR: &str = "FISHFIGHT_CONFIG";
 const ASSETS_DIR_ENV_VAR: &str = "FISHFIGHT_ASSETS";
 const MODS_DIR_ENV_VAR: &str = "FISHFIGHT_MODS";
 
+const ASSETS_DIR_ARG: &str = "--assets";
+#[cfg(feature = "macroquad")]
+const EDIT_MAP_ARG: &str = "--edit";
+
 #[allow(dead_code)]
 const WINDOW_TITLE: &str = "Fish Fight";
 
@@ -65,6 +69,43 @@ pub fn config_path() -> String {
     path.to_string_lossy().to_string()
 }
 
+// This returns the value of the command-line argument `name`, passed either as `name value` or
+// as `name=value`
+fn get_arg_value(name: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+
+        if let Some(value) = arg.strip_prefix(name).and_then(|arg| arg.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
+
+// The assets directory is set with the `--assets` argument or the `FISHFIGHT_ASSETS` environment
+// variable, with the argument taking precedence. If the directory is invalid, the default is used.
+fn get_assets_dir() -> String {
+    let assets_dir = get_arg_value(ASSETS_DIR_ARG).or_else(|| env::var(ASSETS_DIR_ENV_VAR).ok());
+
+    if let Some(assets_dir) = assets_dir {
+        match ff_core::resources::validate_assets_dir(&assets_dir) {
+            Ok(()) => return assets_dir,
+            Err(err) => println!(
+                "ERROR: {}. Falling back to '{}'",
+                err,
+                ff_core::resources::DEFAULT_ASSETS_DIR
+            ),
+        }
+    }
+
+    ff_core::resources::DEFAULT_ASSETS_DIR.to_string()
+}
+
 #[cfg_attr(
     feature = "macroquad",
     ff_core::async_main(
@@ -84,14 +125,14 @@ pub fn config_path() -> String {
     )
 )]
 async fn main() -> Result<()> {
-    let assets_dir = env::var(ASSETS_DIR_ENV_VAR).unwrap_or_else(|_| "assets/".to_string());
+    let assets_dir = get_assets_dir();
     let mods_dir = env::var(MODS_DIR_ENV_VAR).unwrap_or_else(|_| "mods/".to_string());
 
     init_core(0, assets_dir.as_str(), mods_dir.as_str()).await?;
 
     ff_core::cfg_if! {
         if #[cfg(feature = "macroquad")] {
-            macroquad_main().await?;
+            macroquad_main(get_arg_value(EDIT_MAP_ARG)).await?;
         } else if #[cfg(feature = "ultimate")] {
             ultimate_main().await?;
         } else {
@@ -131,7 +172,8 @@ async fn internal_main() -> Result<()> {
         },
     ];
 
-    let initial_state = build_state_for_game_mode(GameMode::Local, map_resource.map, players)?;
+    let initial_state =
+        build_state_for_game_mode(GameMode::Local, map_resource.map, players, None)?;
 
     //let initial_state = MainMenuState::new();
 
@@ -185,7 +227,8 @@ async fn ultimate_main() -> Result<()> {
         },
     ];
 
-    let initial_state = build_state_for_game_mode(GameMode::Local, map_resource.map, players)?;
+    let initial_state =
+        build_state_for_game_mode(GameMode::Local, map_resource.map, players, None)?;
 
     Game::new(initial_state)
         .with_config(config)
@@ -197,8 +240,10 @@ async fn ultimate_main() -> Result<()> {
     Ok(())
 }
 
+// If `edit_map_path` is set, the editor is started with that map, instead of the main menu. If
+// the map can not be loaded, an error is printed and the main menu is shown instead.
 #[cfg(feature = "macroquad")]
-async fn macroquad_main() -> Result<()> {
+async fn macroquad_main(edit_map_path: Option<String>) -> Result<()> {
     load_resources().await?;
 
     rebuild_gui_theme();
@@ -214,7 +259,22 @@ async fn macroquad_main() -> Result<()> {
     {
         let _camera = Camera::default();
 
-        let game = Game::new(MainMenuState::new())?;
+        let mut map_resource = None;
+
+        if let Some(path) = edit_map_path {
+            match ff_core::map::load_map_resource(&path).await {
+                Ok(res) => map_resource = Some(res),
+                Err(err) => println!(
+                    "ERROR: Unable to open '{}' in the editor: {}. Showing the main menu instead",
+                    path, err
+                ),
+            }
+        }
+
+        let game = match map_resource {
+            Some(map_resource) => Game::new(editor::EditorState::new(map_resource))?,
+            None => Game::new(MainMenuState::new())?,
+        };
 
         scene::add_node(game);
     }
@@ -223,9 +283,14 @@ async fn macroquad_main() -> Result<()> {
         #[allow(clippy::never_loop)]
         for event in iter_events() {
             match event {
-                Event::StateTransition(state) => {
+                Event::StateTransition(state, transition) => {
                     let mut game = scene::find_node_by_type::<Game>().unwrap();
-                    game.change_state(state)?;
+                    game.change_state_with_transition(state, transition)?;
+                }
+                Event::QuitRequested => {
+                    if handle_quit_request() {
+                        break 'outer;
+                    }
                 }
                 Event::Quit => break 'outer,
                 _ => {}
@@ -1,3 +1,5 @@
+use ff_core::rng;
+
 use crate::player::GameInput;
 
 pub struct Ai {
@@ -47,7 +49,7 @@ impl Ai {
             && (player.body.position.y - foe.body.position.y).abs() >= 50.
             && !following_horiz
         {
-            self.fix_direction = if rand::gen_range(0, 2) == 0 { 1 } else { -1 };
+            self.fix_direction = if rng::gen_range(0, 2) == 0 { 1 } else { -1 };
             self.keep_direction_until_event = true;
         }
 
@@ -87,17 +89,17 @@ impl Ai {
             }
         }
 
-        if rand::gen_range(0, 200) == 5 {
-            self.fix_direction = if rand::gen_range(0, 2) == 0 { 1 } else { -1 };
+        if rng::gen_range(0, 200) == 5 {
+            self.fix_direction = if rng::gen_range(0, 2) == 0 { 1 } else { -1 };
             self.keep_direction_until_event = true;
         }
 
-        if rand::gen_range(0, 800) == 5 {
+        if rng::gen_range(0, 800) == 5 {
             input.pickup = true;
             self.throw_cooldown = 1.;
         }
 
-        if player.body.position.distance(foe.body.position) <= 100. || rand::gen_range(0, 180) == 5
+        if player.body.position.distance(foe.body.position) <= 100. || rng::gen_range(0, 180) == 5
         {
             //
             if player.state_machine.state() == OldPlayer::ST_NORMAL && player.weapon.is_some() {
@@ -71,7 +71,11 @@ pub fn update_player_states(world: &mut World, delta_time: f32) -> Result<()> {
                 player.respawn_timer = 0.0;
 
                 let mut map = world.query_one::<&Map>(map_entity).unwrap();
-                transform.position = map.get().unwrap().get_random_spawn_point();
+                let map = map.get().unwrap();
+                let spawn_point = map.get_random_spawn_point();
+
+                transform.position = map.world_offset + spawn_point.position;
+                player.is_facing_left = spawn_point.is_facing_left;
             }
         } else if player.state == PlayerState::Incapacitated {
             player.incapacitation_timer += delta_time;